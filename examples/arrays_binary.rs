@@ -0,0 +1,46 @@
+use std::io::{Cursor, Write};
+
+use arrow::array::{Array, BinaryArray, LargeBinaryArray};
+use arrow_guide::builders::{binary_from_slices, BinaryStreamBuilder, LargeBinaryStreamBuilder};
+
+fn main() {
+    // Blobs given as slices, with a null for a missing thumbnail
+    let png_header: &[u8] = &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    let gif_header: &[u8] = b"GIF89a";
+    let thumbnails: BinaryArray =
+        binary_from_slices(vec![Some(png_header), None, Some(gif_header)]).unwrap();
+    println!("{:?}", thumbnails);
+
+    // Messages serialized piece by piece with the Write implementation.
+    // finish_value closes every message
+    let mut builder = BinaryStreamBuilder::new(2, 64);
+    for (id, name) in &[(1u32, "alpha"), (2u32, "beta")] {
+        builder.write_all(&id.to_le_bytes()).unwrap();
+        write!(builder, "{}", name).unwrap();
+        builder.finish_value().unwrap();
+    }
+    builder.append_null().unwrap();
+    let messages = builder.finish().unwrap();
+    for i in 0..messages.len() {
+        println!(
+            "message {}: {:?}",
+            i,
+            messages.is_valid(i).then(|| messages.value(i))
+        );
+    }
+
+    // Large blobs are copied from a reader, for example a file, straight
+    // into the values buffer
+    let mut builder = LargeBinaryStreamBuilder::default();
+    let file = Cursor::new(vec![7u8; 1 << 20]);
+    let read = builder.append_reader(file).unwrap();
+    let blobs: LargeBinaryArray = builder.finish().unwrap();
+    println!("read {} bytes, stored {} bytes", read, blobs.value(0).len());
+
+    // Bytes written without closing the value are reported
+    let mut builder = BinaryStreamBuilder::default();
+    builder.write_all(b"half").unwrap();
+    if let Err(error) = builder.append_value(b"other") {
+        println!("{}", error);
+    }
+}
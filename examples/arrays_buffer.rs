@@ -1,5 +1,8 @@
+use arrow::array::{Array, Int32Array, StringArray};
 use arrow::buffer::Buffer;
-use arrow::datatypes::ToByteSlice;
+use arrow::datatypes::{ToByteSlice, UInt64Type};
+use arrow_guide::buffer::{self, buffer_report};
+use arrow_guide::builders::array_from_vec;
 
 fn main() {
     let buffer_u8 = Buffer::from(&[0u8, 1, 2, 3, 4, 5]);
@@ -22,4 +25,27 @@ fn main() {
             println!("{}", *ptr_32.add(i));
         }
     }
+
+    // Buffer::from copies the bytes. A Vec can be handed over instead, and
+    // the buffer points to the same memory the Vec had
+    let values = vec![10u64, 20, 30, 40];
+    let values_ptr = values.as_ptr() as *const u8;
+    let buffer_u64 = buffer::from_vec(values);
+
+    println!("{:?}", buffer_u64);
+    println!("Same memory: {}", buffer_u64.as_ptr() == values_ptr);
+
+    // The layout of the buffers of an array can be checked without reading
+    // the pointers. The buffers created by arrow are aligned and padded to
+    // 64 bytes, while the one taken from a Vec keeps its own allocation
+    let numbers = Int32Array::from(vec![Some(1), None, Some(3)]);
+    print!("{}", buffer_report(&numbers.data()));
+
+    let strings = StringArray::from(vec!["hello", "from", "arrow"]);
+    print!("{}", buffer_report(&strings.data()));
+
+    let owned = array_from_vec::<UInt64Type>(vec![10, 20, 30, 40]);
+    let report = buffer_report(&owned.data());
+    print!("{}", report);
+    println!("Aligned to 64 bytes: {}", report.buffers[0].is_aligned());
 }
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Float64Builder, Int32Builder, Int64Builder, ListBuilder, StringBuilder,
+        StringDictionaryBuilder, StructBuilder,
+    },
+    datatypes::{DataType, Field, Int8Type, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use arrow_guide::builders::{
+    make_builder, make_column, BoxedBuilder, BuilderPool, MutableColumn, ScalarBuilder,
+};
+use arrow_guide::ScalarValue;
+
+// Parses the text of a field, as read from a CSV line, to the type of the
+// column. It works with any builder behind the trait
+fn append_text(column: &mut dyn MutableColumn, text: &str) -> Result<(), ArrowError> {
+    if text.is_empty() {
+        return column.append_null();
+    }
+    let invalid = || ArrowError::ParseError(format!("Invalid value {}", text));
+    let value = match column.data_type() {
+        DataType::Int64 => ScalarValue::Int64(Some(text.parse().map_err(|_| invalid())?)),
+        DataType::Float64 => ScalarValue::Float64(Some(text.parse().map_err(|_| invalid())?)),
+        DataType::Boolean => ScalarValue::Boolean(Some(text.parse().map_err(|_| invalid())?)),
+        _ => ScalarValue::Utf8(Some(text.to_string())),
+    };
+    column.append_scalar(&value)
+}
+
+fn main() {
+    // A schema that could have been read from a file or received over the
+    // network, so the builders are created from the types
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new(
+            "country",
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+            false,
+        ),
+        Field::new(
+            "scores",
+            DataType::List(Box::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ),
+        Field::new(
+            "owner",
+            DataType::Struct(vec![
+                Field::new("name", DataType::Utf8, true),
+                Field::new("age", DataType::Int32, true),
+            ]),
+            true,
+        ),
+    ]);
+
+    let mut builders = schema
+        .fields()
+        .iter()
+        .map(|field| make_builder(field.data_type(), 3))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let rows = [
+        (1, "Peru", vec![7.5, 9.0], "Ana", 31),
+        (2, "Chile", vec![], "Luis", 45),
+        (3, "Peru", vec![6.25], "Marta", 27),
+    ];
+
+    for (id, country, scores, name, age) in rows.iter() {
+        let id_builder = builders[0].as_any_mut();
+        let id_builder = id_builder.downcast_mut::<Int32Builder>().unwrap();
+        id_builder.append_value(*id).unwrap();
+
+        let country_builder = builders[1].as_any_mut();
+        let country_builder = country_builder
+            .downcast_mut::<StringDictionaryBuilder<Int8Type>>()
+            .unwrap();
+        country_builder.append(country).unwrap();
+
+        // The values of a list are kept in a BoxedBuilder, and the builder
+        // of the values is inside it
+        let scores_builder = builders[2].as_any_mut();
+        let scores_builder = scores_builder
+            .downcast_mut::<ListBuilder<BoxedBuilder>>()
+            .unwrap();
+        for score in scores {
+            let values = scores_builder.values().0.as_any_mut();
+            let values = values.downcast_mut::<Float64Builder>().unwrap();
+            values.append_value(*score).unwrap();
+        }
+        scores_builder.append(true).unwrap();
+
+        let owner_builder = builders[3].as_any_mut();
+        let owner_builder = owner_builder.downcast_mut::<StructBuilder>().unwrap();
+        owner_builder
+            .field_builder::<StringBuilder>(0)
+            .unwrap()
+            .append_value(name)
+            .unwrap();
+        owner_builder
+            .field_builder::<Int32Builder>(1)
+            .unwrap()
+            .append_value(*age)
+            .unwrap();
+        owner_builder.append(true).unwrap();
+    }
+
+    let columns = builders
+        .iter_mut()
+        .map(|builder| builder.finish())
+        .collect::<Vec<_>>();
+    let batch = RecordBatch::try_new(Arc::new(schema), columns).unwrap();
+    println!("{:?}", batch.columns());
+
+    // Rows of ScalarValues, for example parsed from a text format, are
+    // converted to columns with a ScalarBuilder per field
+    let schema = Schema::new(vec![
+        Field::new("city", DataType::Utf8, true),
+        Field::new(
+            "temperatures",
+            DataType::List(Box::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ),
+    ]);
+    let rows = vec![
+        vec![
+            ScalarValue::Utf8(Some("Lima".to_string())),
+            ScalarValue::List(
+                Some(vec![
+                    ScalarValue::Float64(Some(18.5)),
+                    ScalarValue::Float64(None),
+                ]),
+                DataType::Float64,
+            ),
+        ],
+        vec![
+            ScalarValue::Utf8(None),
+            ScalarValue::List(None, DataType::Float64),
+        ],
+    ];
+
+    let mut builders = schema
+        .fields()
+        .iter()
+        .map(|field| ScalarBuilder::new(field.data_type(), rows.len()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    for row in &rows {
+        for (builder, value) in builders.iter_mut().zip(row) {
+            builder.append(value).unwrap();
+        }
+    }
+
+    // Values of another type are rejected
+    if let Err(error) = builders[0].append(&ScalarValue::Int32(Some(4))) {
+        println!("{}", error);
+    }
+
+    let columns = builders
+        .iter_mut()
+        .map(|builder| builder.finish())
+        .collect::<Vec<_>>();
+    let batch = RecordBatch::try_new(Arc::new(schema), columns).unwrap();
+    println!("{:?}", batch.columns());
+
+    // Types without a builder give an error instead of a panic
+    if let Err(error) = make_builder(&DataType::Null, 10) {
+        println!("{}", error);
+    }
+
+    // A producer that emits a batch every tick takes the builders of its
+    // schema from a pool and gives them back after every batch
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("tick", DataType::Int32, false),
+        Field::new("reading", DataType::Float64, true),
+    ]));
+    let mut pool = BuilderPool::new(1024).with_max_idle(2);
+    for tick in 0..3 {
+        let mut builders = pool.take(&schema).unwrap();
+        for i in 0..4 {
+            builders
+                .builder::<Int32Builder>(0)
+                .unwrap()
+                .append_value(tick)
+                .unwrap();
+            builders
+                .builder::<Float64Builder>(1)
+                .unwrap()
+                .append_option(if i == 2 { None } else { Some(i as f64 * 0.5) })
+                .unwrap();
+        }
+        let batch = builders.finish().unwrap();
+        println!(
+            "tick {}: {} rows, {} idle",
+            tick,
+            batch.num_rows(),
+            pool.idle()
+        );
+        pool.put_back(builders).unwrap();
+    }
+
+    // Columns of any type can be filled by the same code through the
+    // MutableColumn trait, using the builders of arrow or a ScalarBuilder
+    let lines = ["7,true,Lima", "12,,Quito", ",false,"];
+    let mut columns: Vec<Box<dyn MutableColumn>> = vec![
+        Box::new(Int64Builder::new(lines.len())),
+        make_column(&DataType::Boolean, lines.len()).unwrap(),
+        Box::new(StringBuilder::new(lines.len())),
+    ];
+    for line in lines.iter() {
+        for (column, text) in columns.iter_mut().zip(line.split(',')) {
+            append_text(column.as_mut(), text).unwrap();
+        }
+    }
+    if let Err(error) = columns[0].append_scalar(&ScalarValue::Utf8(Some("8".to_string()))) {
+        println!("{}", error);
+    }
+
+    let arrays = columns
+        .iter_mut()
+        .map(|column| column.finish())
+        .collect::<Vec<_>>();
+    println!("{:?}", arrays);
+}
@@ -0,0 +1,48 @@
+use arrow::array::Array;
+use arrow_guide::builders::{
+    decimal_array_from_i128, decimal_array_from_strings, format_decimal, parse_decimal,
+};
+
+fn main() {
+    // Prices with 2 decimal digits and up to 7 digits in total
+    let prices = decimal_array_from_strings(
+        vec![
+            Some("19.99"),
+            Some("-0.5"),
+            None,
+            Some("12345.00"),
+            Some("7"),
+        ],
+        7,
+        2,
+    )
+    .unwrap();
+    println!("{:?}", prices.data_type());
+
+    // The values are stored multiplied by 10^scale
+    for i in 0..prices.len() {
+        match prices.is_valid(i) {
+            true => println!(
+                "{} is stored as {}",
+                format_decimal(prices.value(i), prices.scale()),
+                prices.value(i)
+            ),
+            false => println!("null"),
+        }
+    }
+
+    // Values that don't fit are rejected instead of being written to a
+    // file that other readers can't read
+    for value in &["123456.00", "1.999", "1.2.3"] {
+        if let Err(error) = parse_decimal(value, 7, 2) {
+            println!("{}", error);
+        }
+    }
+    if let Err(error) = decimal_array_from_i128(vec![Some(10_000_000)], 7, 2) {
+        println!("{}", error);
+    }
+
+    // Extra zeros don't change the value, so they are accepted
+    assert_eq!(parse_decimal("1.500", 7, 2).unwrap(), 150);
+    assert_eq!(format_decimal(-5, 3), "-0.005");
+}
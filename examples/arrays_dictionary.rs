@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::DictionaryArray,
+    datatypes::{DataType, Field, Int32Type, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::builders::{dictionary_from_strs, dictionary_to_strings, unify_dictionaries};
+use arrow_guide::downcast::downcast_array;
+use arrow_guide::ipc::{IpcStreamReader, IpcStreamWriter};
+
+fn main() {
+    // Every batch is encoded on its own, so their dictionaries are
+    // different and "peru" has a different key in each of them
+    let first = dictionary_from_strs(vec!["mexico", "peru", "mexico", "chile"]).unwrap();
+    let second = dictionary_from_strs(vec![Some("peru"), None, Some("brazil")]).unwrap();
+    println!("first keys: {:?}", first.keys());
+    println!("second keys: {:?}", second.keys());
+
+    // After unifying them both batches share the same dictionary, so the
+    // stream only needs to send it once
+    let unified = unify_dictionaries(&[first, second]).unwrap();
+    println!("dictionary: {:?}", unified[0].values());
+    for array in &unified {
+        println!("keys: {:?}", array.keys());
+    }
+
+    let data_type = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+    let schema = Arc::new(Schema::new(vec![Field::new_dict(
+        "country", data_type, true, 1, false,
+    )]));
+    let batches = unified
+        .into_iter()
+        .map(|array| RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut writer = IpcStreamWriter::try_new(Vec::new(), &schema).unwrap();
+    for batch in &batches {
+        writer.write(batch).unwrap();
+    }
+    writer.finish().unwrap();
+    let bytes = writer.into_inner();
+    println!("Stream size: {} bytes", bytes.len());
+
+    // The strings read back are the ones encoded
+    let reader = IpcStreamReader::try_new(bytes.as_slice()).unwrap();
+    for (original, batch) in batches.iter().zip(reader) {
+        let batch = batch.unwrap();
+        let array = downcast_array::<DictionaryArray<Int32Type>>(batch.column(0).as_ref()).unwrap();
+        let strings = dictionary_to_strings(array).unwrap();
+        println!("{:?}", strings);
+        assert_eq!(original.column(0).data(), batch.column(0).data());
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef, FixedSizeListArray, Float32Array, StringArray},
+    datatypes::{Field, Float32Type, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::builders::{fixed_size_list_from_vecs, fixed_size_list_to_vecs};
+
+fn main() {
+    // Every document has an embedding of 4 values. The embedding of the
+    // last one wasn't calculated, so it's null
+    let documents = StringArray::from(vec!["apples", "pears", "engines", "bikes"]);
+    let embeddings = fixed_size_list_from_vecs::<Float32Type, 4>(vec![
+        Some([0.9, 0.1, 0.0, 0.2]),
+        Some([0.8, 0.2, 0.1, 0.1]),
+        Some([0.0, 0.1, 0.9, 0.7]),
+        None,
+    ])
+    .unwrap();
+    println!("{:?}", embeddings.data_type());
+    println!("{:?}", embeddings);
+
+    let schema = Schema::new(vec![
+        Field::new("document", documents.data_type().clone(), false),
+        Field::new("embedding", embeddings.data_type().clone(), true),
+    ]);
+    let embeddings: ArrayRef = Arc::new(embeddings);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(documents), embeddings.clone()],
+    )
+    .unwrap();
+
+    // The embeddings are read back as arrays to compare them with a query
+    let query = [1.0, 0.0, 0.0, 0.1];
+    let embeddings = embeddings
+        .as_any()
+        .downcast_ref::<FixedSizeListArray>()
+        .unwrap();
+    let similarity = fixed_size_list_to_vecs::<Float32Type, 4>(embeddings)
+        .unwrap()
+        .into_iter()
+        .map(|embedding| embedding.map(|embedding| cosine(&embedding, &query)))
+        .collect::<Float32Array>();
+
+    let documents = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    for i in 0..batch.num_rows() {
+        match similarity.is_valid(i) {
+            true => println!("{}: {:.3}", documents.value(i), similarity.value(i)),
+            false => println!("{}: no embedding", documents.value(i)),
+        }
+    }
+
+    // Reading the lists with another size is an error
+    if let Err(error) = fixed_size_list_to_vecs::<Float32Type, 3>(embeddings) {
+        println!("{}", error);
+    }
+}
+
+fn cosine(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let dot = a.iter().zip(b).map(|(a, b)| a * b).sum::<f32>();
+    let norm = |v: &[f32; 4]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    dot / (norm(a) * norm(b))
+}
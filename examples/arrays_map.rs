@@ -0,0 +1,50 @@
+use arrow::array::{Array, Int64Array, StringArray, StructArray};
+use arrow_guide::builders::{check_map_entries, map_array_from_vecs};
+
+fn main() {
+    // Counters of every request. The second request didn't report any
+    // counters and the value of the retries of the last one is unknown
+    let counters = map_array_from_vecs(vec![
+        Some(vec![("bytes", Some(512i64)), ("retries", Some(0))]),
+        None,
+        Some(vec![]),
+        Some(vec![("bytes", Some(2048)), ("retries", None)]),
+    ])
+    .unwrap();
+    println!("{:?}", counters.data_type());
+    check_map_entries(&counters).unwrap();
+
+    for i in 0..counters.len() {
+        if counters.is_null(i) {
+            println!("request {}: null", i);
+            continue;
+        }
+
+        let entries = counters.value(i);
+        let entries = entries.as_any().downcast_ref::<StructArray>().unwrap();
+        let keys = entries
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let values = entries
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+
+        let pairs = (0..entries.len())
+            .map(|j| {
+                (
+                    keys.value(j),
+                    Some(values.value(j)).filter(|_| values.is_valid(j)),
+                )
+            })
+            .collect::<Vec<_>>();
+        println!("request {}: {:?}", i, pairs);
+    }
+
+    // Keys can't be null
+    let invalid = map_array_from_vecs(vec![Some(vec![(None::<&str>, 1i32)])]);
+    println!("{:?}", invalid.unwrap_err());
+}
@@ -4,37 +4,48 @@ use arrow::array::{
 };
 use arrow::buffer::Buffer;
 use arrow::datatypes::{DataType, Field, ToByteSlice};
+use arrow_guide::builders::OffsetsBuilder;
+use arrow_guide::validate::validate_array_data;
+use arrow_guide::{bitmap, list_array, struct_array};
 
 use std::sync::Arc;
 
 fn main() {
-    // ListArray
-    let value_data = ArrayData::builder(DataType::Int32)
-        .len(10)
-        .add_buffer(Buffer::from(
-            &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10].to_byte_slice(),
-        ))
-        .build();
-
-    let value_offsets = Buffer::from(&[0, 2, 4, 7, 7, 8, 10].to_byte_slice());
-
-    let list_data_type = DataType::List(Box::new(Field::new("item", DataType::Int32, false)));
-    let list_data = ArrayData::builder(list_data_type.clone())
-        .len(6)
-        .add_buffer(value_offsets)
-        .add_child_data(value_data)
-        .null_bit_buffer(Buffer::from([0b00110111]))
-        .build();
-
-    let value_offsets = Buffer::from(&[0, 2, 5, 6].to_byte_slice());
+    // ListArray. The inner lists are built with list_array!, which
+    // calculates the offsets and the null bitmap from the literals
+    let list_array = list_array!(Int32, [[1, 2], [3, 4], [5, 6, 7], None, [8], [9, 10]]);
+    let list_data_type = DataType::List(Box::new(Field::new(
+        "item",
+        list_array.data_type().clone(),
+        true,
+    )));
+    let list_data = list_array.data();
+
+    // The outer list groups the inner lists as [[1, 2], [3, 4]],
+    // [[5, 6, 7], None, [8]] and [[9, 10]]
+    let mut value_offsets = OffsetsBuilder::<i32>::new(3);
+    for len in &[2, 3, 1] {
+        value_offsets.append_length(*len).unwrap();
+    }
     let list_data = ArrayData::builder(list_data_type)
-        .len(3)
-        .add_buffer(value_offsets)
+        .len(value_offsets.len())
+        .add_buffer(value_offsets.finish())
         .add_child_data(list_data)
         .build();
 
+    // Hand-built data is checked before creating the array, since an
+    // offset past the end of the values would be read as if it was valid
+    validate_array_data(&list_data).unwrap();
     println!("{:?}", list_data);
 
+    let broken_offsets = Buffer::from(&[0, 2, 5, 9].to_byte_slice());
+    let broken_data = ArrayData::builder(list_data.data_type().clone())
+        .len(3)
+        .add_buffer(broken_offsets)
+        .add_child_data(list_data.child_data()[0].clone())
+        .build();
+    println!("{}", validate_array_data(&broken_data).unwrap_err());
+
     let list_array = ListArray::from(list_data);
     println!("{:?}", list_array);
 
@@ -63,14 +74,22 @@ fn main() {
         b'h', b'e', b'l', b'l', b'o', b'f', b'r', b'o', b'm', b'A', b'p', b'a', b'c', b'h', b'e',
         b'A', b'r', b'r', b'o', b'w',
     ];
-    let offsets: [i32; 6] = [0, 5, 9, 9, 15, 20];
+    // The offsets are appended where every string ends. An offset before
+    // the previous one is rejected
+    let mut offsets = OffsetsBuilder::<i32>::new(5);
+    for end in &[5, 9, 9, 15, 20] {
+        offsets.append_end(*end).unwrap();
+    }
+    println!("{}", offsets.append_end(12).unwrap_err());
+    assert_eq!(offsets.values_len(), values.len());
 
     let array_data = ArrayData::builder(DataType::Utf8)
-        .len(5)
-        .add_buffer(Buffer::from(offsets.to_byte_slice()))
+        .len(offsets.len())
+        .add_buffer(offsets.finish())
         .add_buffer(Buffer::from(&values[..]))
-        .null_bit_buffer(Buffer::from([0b00011011]))
+        .null_bit_buffer(bitmap::from_bools(&[true, true, false, true, true]))
         .build();
+    validate_array_data(&array_data).unwrap();
     let string_array = StringArray::from(array_data);
     println!("{:?}", string_array);
     println!("Value: {:?}", string_array.value(0));
@@ -88,37 +107,13 @@ fn main() {
     let string_array = builder.finish();
     println!("{:?}", string_array);
 
-    // StructArray ArrayData
-    let boolean_data = ArrayData::builder(DataType::Boolean)
-        .len(5)
-        .add_buffer(Buffer::from([0b00010000]))
-        .null_bit_buffer(Buffer::from([0b00010001]))
-        .build();
-
-    let int_data_b = ArrayData::builder(DataType::Int32)
-        .len(5)
-        .add_buffer(Buffer::from([0, 28, 42, 0, 0].to_byte_slice()))
-        .null_bit_buffer(Buffer::from([0b00000110]))
-        .build();
-
-    let int_data_c = ArrayData::builder(DataType::Int32)
-        .len(5)
-        .add_buffer(Buffer::from([1, 2, 3, 4, 5].to_byte_slice()))
-        .null_bit_buffer(Buffer::from([0b00011111]))
-        .build();
-
-    let mut field_types = vec![];
-    field_types.push(Field::new("a", DataType::Boolean, false));
-    field_types.push(Field::new("b", DataType::Int32, false));
-    field_types.push(Field::new("c", DataType::Int32, false));
-
-    let struct_array_data = ArrayData::builder(DataType::Struct(field_types))
-        .len(5)
-        .add_child_data(boolean_data)
-        .add_child_data(int_data_b)
-        .add_child_data(int_data_c)
-        .build();
-    let struct_array = StructArray::from(struct_array_data);
+    // StructArray from literals. The fields with nulls are nullable
+    let struct_array = struct_array! {
+        "a" => Boolean => [Some(false), None, None, None, Some(true)],
+        "b" => Int32 => [None, Some(28), Some(42), None, None],
+        "c" => Int32 => [1, 2, 3, 4, 5],
+    }
+    .unwrap();
     println!("{:?}", struct_array);
 
     // Constructing StructArray from vector
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::builders::{ListOfStructBuilder, StructArrayBuilder};
+use arrow_guide::ScalarValue;
+
+fn main() {
+    // Struct with a list field: a customer with a list of tags
+    let customer_fields = vec![
+        Field::new("name", DataType::Utf8, false),
+        Field::new(
+            "tags",
+            DataType::List(Box::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+    ];
+    let mut customers = StructArrayBuilder::new(customer_fields, 2).unwrap();
+    customers
+        .append(&[
+            utf8("Ana"),
+            ScalarValue::List(Some(vec![utf8("new"), utf8("vip")]), DataType::Utf8),
+        ])
+        .unwrap();
+    customers
+        .append(&[utf8("Luis"), ScalarValue::List(None, DataType::Utf8)])
+        .unwrap();
+
+    // The name can't be null, so the row is rejected and nothing is added
+    let error = customers
+        .append(&[
+            ScalarValue::Utf8(None),
+            ScalarValue::List(None, DataType::Utf8),
+        ])
+        .unwrap_err();
+    println!("{}", error);
+
+    // List of structs: the lines of every order
+    let line_fields = vec![
+        Field::new("sku", DataType::Utf8, false),
+        Field::new("quantity", DataType::Int32, false),
+    ];
+    let mut lines = ListOfStructBuilder::new(line_fields, 4).unwrap();
+    lines
+        .append(Some(&[
+            vec![utf8("A-1"), ScalarValue::Int32(Some(2))],
+            vec![utf8("B-7"), ScalarValue::Int32(Some(1))],
+        ]))
+        .unwrap();
+    lines.append(None).unwrap();
+
+    let customers = customers.finish();
+    let lines = lines.finish();
+    println!("{:?}", customers);
+    println!("{:?}", lines);
+
+    let schema = Schema::new(vec![
+        Field::new("customer", customers.data_type().clone(), false),
+        Field::new("lines", lines.data_type().clone(), true),
+    ]);
+    let batch =
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(customers), Arc::new(lines)]).unwrap();
+    println!("{:#?}", batch.schema());
+}
+
+fn utf8(value: &str) -> ScalarValue {
+    ScalarValue::Utf8(Some(value.to_string()))
+}
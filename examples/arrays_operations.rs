@@ -1,6 +1,9 @@
-use arrow::array::Array;
-use arrow::array::Int32Array;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, Int32Array, StringArray};
 use arrow::compute::kernels::comparison::eq;
+use arrow::datatypes::Int32Type;
+use arrow_guide::downcast::{as_primitive, downcast_array};
 
 fn main() {
     let a = Int32Array::from(vec![6, 7, 8, 8, 10]);
@@ -9,17 +12,26 @@ fn main() {
     println!("{:?}", a);
     println!("{:?}", b);
 
+    // The slice is an ArrayRef, so it has to be downcast before using it
+    // with the kernels that take a concrete array
     let b_slice = b.slice(5, 5);
     println!("{:?}", b_slice);
 
-    //let c = b_slice.as_any().downcast_ref().unwrap();
-    //println!("{:?}", c);
+    let c = as_primitive::<Int32Type>(&b_slice).unwrap();
+    println!("{:?}", c);
+
+    let d = eq(c, &a).unwrap();
 
-    let d = eq(&b, &a).unwrap();
+    assert!(d.value(0));
+    assert!(d.value(1));
+    assert!(d.value(2));
+    assert!(!d.value(3));
+    assert!(d.value(4));
 
-    assert_eq!(true, d.value(0));
-    assert_eq!(true, d.value(1));
-    assert_eq!(true, d.value(2));
-    assert_eq!(false, d.value(3));
-    assert_eq!(true, d.value(4));
+    // Downcasting to the wrong type says which type the array has
+    let names: ArrayRef = Arc::new(StringArray::from(vec!["a", "b"]));
+    println!(
+        "{}",
+        downcast_array::<Int32Array>(names.as_ref()).unwrap_err()
+    );
 }
@@ -1,6 +1,9 @@
 use arrow::array::{ArrayData, Int32Array, Int32Builder, PrimitiveArray};
 use arrow::buffer::Buffer;
-use arrow::datatypes::{DataType, Date64Type, Int32Type, Time64MicrosecondType, ToByteSlice};
+use arrow::datatypes::{
+    DataType, Date64Type, Float64Type, Int32Type, Time64MicrosecondType, ToByteSlice,
+};
+use arrow_guide::builders::{array_from_iter, array_from_trusted_len_iter};
 
 use std::sync::Arc;
 
@@ -43,4 +46,18 @@ fn main() {
 
     let time_array: PrimitiveArray<Time64MicrosecondType> = (0..100).collect::<Vec<i64>>().into();
     println!("{:?}", time_array);
+
+    // Using iterators. Every third value is null
+    println!("Using iterators to construct primitive array");
+    let squares =
+        array_from_iter::<Int32Type, _>(
+            (0..10).map(|i| if i % 3 == 0 { None } else { Some(i * i) }),
+        );
+    println!("{:?}", squares);
+
+    // A range knows its length, so the buffers are allocated only once
+    let samples =
+        array_from_trusted_len_iter::<Float64Type, _>((0..8).map(|i| Some((i as f64 / 4.0).sin())))
+            .unwrap();
+    println!("{:?}", samples);
 }
@@ -5,6 +5,7 @@ use arrow::{
     datatypes::{DataType, Field, Schema},
     record_batch::RecordBatch,
 };
+use arrow_guide::record_batch;
 
 fn main() {
     let schema = Schema::new(vec![
@@ -20,6 +21,16 @@ fn main() {
 
     println!("{:#?}", record_batch);
 
+    // The same batch built with the record_batch! macro. The fields are
+    // nullable only if their column has nulls
+    let record_batch = record_batch! {
+        "index" => Int32 => [1, 2, 3, 4, 5],
+        "word" => Utf8 => [Some("one"), Some("two"), None, Some("four"), Some("five")],
+    }
+    .unwrap();
+
+    println!("{:#?}", record_batch.schema());
+
     // Creating a schema with metadata
     let field_a = Field::new("a", DataType::Int64, false);
     let field_b = Field::new("b", DataType::Boolean, false);
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef, Float64Array, Int32Array, StringArray},
+    datatypes::{DataType, Field},
+};
+use arrow_guide::builders::{dense_union, sparse_union, UnionArrayBuilder, UnionMode};
+use arrow_guide::ScalarValue;
+
+fn main() {
+    // A column where every row is an integer, a float or a string. The
+    // sparse union has a value in every child for every row
+    let fields = vec![
+        Field::new("int", DataType::Int32, true),
+        Field::new("float", DataType::Float64, true),
+        Field::new("text", DataType::Utf8, true),
+    ];
+    let ints: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, None, Some(4)]));
+    let floats: ArrayRef = Arc::new(Float64Array::from(vec![None, Some(2.5), None, None]));
+    let texts: ArrayRef = Arc::new(StringArray::from(vec![None, None, Some("three"), None]));
+    let sparse = sparse_union(
+        &[0, 1, 2, 0],
+        fields
+            .iter()
+            .cloned()
+            .zip(vec![ints, floats, texts])
+            .collect(),
+    )
+    .unwrap();
+    println!("{:?}", sparse);
+
+    // The dense union only stores the values used, and the offsets point
+    // to the value of every row in its child
+    let ints: ArrayRef = Arc::new(Int32Array::from(vec![1, 4]));
+    let floats: ArrayRef = Arc::new(Float64Array::from(vec![2.5]));
+    let texts: ArrayRef = Arc::new(StringArray::from(vec!["three"]));
+    let dense = dense_union(
+        &[0, 1, 2, 0],
+        &[0, 0, 0, 1],
+        fields
+            .iter()
+            .cloned()
+            .zip(vec![ints, floats, texts])
+            .collect(),
+    )
+    .unwrap();
+    for i in 0..dense.len() {
+        println!(
+            "row {}: type {} value {:?}",
+            i,
+            dense.type_id(i),
+            dense.value(i)
+        );
+    }
+
+    // Offsets out of their child are rejected instead of reading garbage
+    let ints: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+    if let Err(error) = dense_union(&[0, 0], &[0, 1], vec![(fields[0].clone(), ints)]) {
+        println!("{}", error);
+    }
+
+    // The builder takes the values row by row and calculates the type ids
+    // and offsets
+    for mode in &[UnionMode::Sparse, UnionMode::Dense] {
+        let mut builder = UnionArrayBuilder::new(*mode, fields.clone(), 4).unwrap();
+        builder.append(0, &ScalarValue::Int32(Some(1))).unwrap();
+        builder.append(1, &ScalarValue::Float64(Some(2.5))).unwrap();
+        builder
+            .append(2, &ScalarValue::Utf8(Some("three".to_string())))
+            .unwrap();
+        builder.append(0, &ScalarValue::Int32(Some(4))).unwrap();
+
+        let union = builder.finish().unwrap();
+        let children = (0..3)
+            .map(|type_id| union.child(type_id).len())
+            .collect::<Vec<_>>();
+        println!("{} union with children of lengths {:?}", mode, children);
+    }
+}
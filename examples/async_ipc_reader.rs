@@ -0,0 +1,25 @@
+use arrow_guide::ipc::AsyncStreamReader;
+use tokio::net::{TcpListener, TcpStream};
+
+#[tokio::main]
+async fn main() {
+    let listener = TcpListener::bind("127.0.0.1:8000").await.unwrap();
+
+    loop {
+        let (stream, _) = listener.accept().await.unwrap();
+
+        // Each connection is read in its own task
+        tokio::spawn(handle_connection(stream));
+    }
+}
+
+async fn handle_connection(stream: TcpStream) {
+    let mut ipc_reader = AsyncStreamReader::try_new(stream).await.unwrap();
+    println!("{:?}", ipc_reader.schema());
+    println!("{:?}", ipc_reader.schema().metadata());
+
+    while let Some(batch) = ipc_reader.next_batch().await.unwrap() {
+        println!("{:?}", batch);
+        println!("{:?}", batch.schema().metadata());
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+use arrow::{
+    array::{ArrayData, Int64Array},
+    buffer::Buffer,
+    datatypes::{DataType, Int64Type, ToByteSlice},
+};
+use arrow_guide::buffer;
+use arrow_guide::builders::array_from_vec;
+
+// Compares creating an Int64 array from a Vec copying its values with
+// Buffer::from and taking the Vec with buffer::from_vec. The Vec is created
+// in both cases, so the difference is the cost of the copy:
+//
+//   cargo run --release --example bench_buffer -- --values 50000000 --rounds 10
+struct Config {
+    values: usize,
+    rounds: usize,
+}
+
+impl Config {
+    fn from_args() -> Self {
+        let mut config = Config {
+            values: 10_000_000,
+            rounds: 10,
+        };
+
+        let args = std::env::args().skip(1).collect::<Vec<String>>();
+        for pair in args.chunks(2) {
+            let value = pair.get(1).map(String::as_str).unwrap_or_default();
+            match pair[0].as_str() {
+                "--values" => config.values = value.parse().expect("Invalid number of values"),
+                "--rounds" => config.rounds = value.parse().expect("Invalid number of rounds"),
+                other => panic!("Unknown argument {}", other),
+            }
+        }
+
+        config
+    }
+}
+
+fn values(len: usize) -> Vec<i64> {
+    (0..len as i64).collect()
+}
+
+// Runs the closure once per round and returns the fastest round, since the
+// slower ones only add the noise of the machine
+fn bench<F: FnMut() -> Int64Array>(rounds: usize, mut create: F) -> Duration {
+    (0..rounds)
+        .map(|_| {
+            let start = Instant::now();
+            let array = create();
+            let elapsed = start.elapsed();
+            assert_eq!(array.value(array.len() - 1), array.len() as i64 - 1);
+            elapsed
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let config = Config::from_args();
+    let megabytes = (config.values * 8) as f64 / (1024.0 * 1024.0);
+    println!("{} values, {:.1} MB per array", config.values, megabytes);
+
+    let copied = bench(config.rounds, || {
+        let values = values(config.values);
+        let data = ArrayData::builder(DataType::Int64)
+            .len(values.len())
+            .add_buffer(Buffer::from(values.to_byte_slice()))
+            .build();
+        Int64Array::from(data)
+    });
+
+    let owned = bench(config.rounds, || {
+        let values = values(config.values);
+        let data = ArrayData::builder(DataType::Int64)
+            .len(values.len())
+            .add_buffer(buffer::from_vec(values))
+            .build();
+        Int64Array::from(data)
+    });
+
+    let generated = bench(config.rounds, || {
+        array_from_vec::<Int64Type>(values(config.values))
+    });
+
+    println!("Buffer::from:      {:?}", copied);
+    println!("buffer::from_vec:  {:?}", owned);
+    println!("array_from_vec:    {:?}", generated);
+    println!(
+        "copying costs {:.2} ms, {:.0} MB/sec",
+        (copied.as_secs_f64() - owned.as_secs_f64()) * 1000.0,
+        megabytes / (copied.as_secs_f64() - owned.as_secs_f64()).max(f64::EPSILON)
+    );
+}
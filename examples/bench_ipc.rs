@@ -0,0 +1,246 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use arrow::{
+    array::{ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{write_ipc_file, IpcFileReader, IpcStreamReader, IpcStreamWriter};
+
+// Measures how fast batches can be sent through a loopback connection using
+// the stream and the file formats. The size of the batches is configured
+// from the command line:
+//
+//   cargo run --release --example bench_ipc -- --rows 10000 --batches 100 \
+//       --columns 4 --types int32,float64,utf8
+struct Config {
+    rows: usize,
+    batches: usize,
+    columns: usize,
+    types: Vec<DataType>,
+}
+
+impl Config {
+    fn from_args() -> Self {
+        let mut config = Config {
+            rows: 10_000,
+            batches: 100,
+            columns: 4,
+            types: vec![DataType::Int32, DataType::Float64, DataType::Utf8],
+        };
+
+        let args = std::env::args().skip(1).collect::<Vec<String>>();
+        for pair in args.chunks(2) {
+            let value = pair.get(1).map(String::as_str).unwrap_or_default();
+            match pair[0].as_str() {
+                "--rows" => config.rows = value.parse().expect("Invalid number of rows"),
+                "--batches" => config.batches = value.parse().expect("Invalid number of batches"),
+                "--columns" => config.columns = value.parse().expect("Invalid number of columns"),
+                "--types" => config.types = value.split(',').map(parse_type).collect(),
+                other => panic!("Unknown argument {}", other),
+            }
+        }
+
+        config
+    }
+}
+
+fn parse_type(name: &str) -> DataType {
+    match name {
+        "bool" => DataType::Boolean,
+        "int32" => DataType::Int32,
+        "int64" => DataType::Int64,
+        "float64" => DataType::Float64,
+        "utf8" => DataType::Utf8,
+        other => panic!("Type {} isn't supported by the benchmark", other),
+    }
+}
+
+// The columns cycle through the selected types
+fn synthetic_batch(config: &Config) -> RecordBatch {
+    let mut fields = Vec::new();
+    let mut columns: Vec<ArrayRef> = Vec::new();
+
+    for i in 0..config.columns {
+        let data_type = config.types[i % config.types.len()].clone();
+        let rows = 0..config.rows;
+
+        let column: ArrayRef = match data_type {
+            DataType::Boolean => Arc::new(BooleanArray::from(
+                rows.map(|row| row % 2 == 0).collect::<Vec<bool>>(),
+            )),
+            DataType::Int32 => Arc::new(Int32Array::from(
+                rows.map(|row| row as i32).collect::<Vec<i32>>(),
+            )),
+            DataType::Int64 => Arc::new(Int64Array::from(
+                rows.map(|row| row as i64).collect::<Vec<i64>>(),
+            )),
+            DataType::Float64 => Arc::new(Float64Array::from(
+                rows.map(|row| row as f64 * 0.5).collect::<Vec<f64>>(),
+            )),
+            _ => Arc::new(
+                rows.map(|row| Some(format!("value {}", row)))
+                    .collect::<StringArray>(),
+            ),
+        };
+
+        fields.push(Field::new(&format!("column_{}", i), data_type, false));
+        columns.push(column);
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+// Writer that counts the bytes sent through the connection
+struct CountingWriter<W: Write> {
+    writer: W,
+    bytes: usize,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.bytes += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+struct Report {
+    rows: usize,
+    bytes: usize,
+    elapsed: Duration,
+    latencies: Vec<Duration>,
+}
+
+impl Report {
+    fn print(&mut self, format: &str) {
+        let seconds = self.elapsed.as_secs_f64();
+        self.latencies.sort();
+
+        let percentile = |p: f64| {
+            let index = ((self.latencies.len() - 1) as f64 * p).round() as usize;
+            self.latencies[index]
+        };
+
+        println!("{} format", format);
+        println!("  rows/sec:  {:.0}", self.rows as f64 / seconds);
+        println!(
+            "  MB/sec:    {:.2}",
+            self.bytes as f64 / (1024.0 * 1024.0) / seconds
+        );
+        println!(
+            "  latency:   min {:?}, p50 {:?}, p99 {:?}, max {:?}",
+            self.latencies[0],
+            percentile(0.5),
+            percentile(0.99),
+            self.latencies[self.latencies.len() - 1]
+        );
+    }
+}
+
+// The latency of every batch is the time between the writer starting to
+// send it and the reader receiving it
+fn bench_stream(batch: &RecordBatch, batches: usize) -> Report {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (sent_sender, sent_receiver) = channel::<Instant>();
+
+    let writer_batch = batch.clone();
+    let writer = thread::spawn(move || {
+        let stream = TcpStream::connect(addr).unwrap();
+        let counter = CountingWriter {
+            writer: stream,
+            bytes: 0,
+        };
+
+        let mut writer = IpcStreamWriter::try_new(counter, &writer_batch.schema()).unwrap();
+        for _ in 0..batches {
+            sent_sender.send(Instant::now()).unwrap();
+            writer.write(&writer_batch).unwrap();
+        }
+        writer.finish().unwrap();
+        writer.into_inner().bytes
+    });
+
+    let (stream, _) = listener.accept().unwrap();
+    let start = Instant::now();
+
+    let mut rows = 0;
+    let mut latencies = Vec::with_capacity(batches);
+    for batch in IpcStreamReader::try_new(stream).unwrap() {
+        let batch = batch.unwrap();
+        latencies.push(sent_receiver.recv().unwrap().elapsed());
+        rows += batch.num_rows();
+    }
+
+    let elapsed = start.elapsed();
+    Report {
+        rows,
+        bytes: writer.join().unwrap(),
+        elapsed,
+        latencies,
+    }
+}
+
+// The file format needs the complete file before a batch can be read, so
+// the latency of a batch is the time needed to decode it once the file
+// was received
+fn bench_file(batch: &RecordBatch, batches: usize) -> Report {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let writer_batch = batch.clone();
+    let writer = thread::spawn(move || {
+        let stream = TcpStream::connect(addr).unwrap();
+        let batches = vec![writer_batch.clone(); batches];
+        write_ipc_file(stream, &writer_batch.schema(), &batches).unwrap();
+    });
+
+    let (mut stream, _) = listener.accept().unwrap();
+    let start = Instant::now();
+
+    let mut bytes = Vec::new();
+    stream.read_to_end(&mut bytes).unwrap();
+    let total_bytes = bytes.len();
+
+    let mut reader = IpcFileReader::from_bytes(bytes).unwrap();
+    let mut rows = 0;
+    let mut latencies = Vec::with_capacity(batches);
+    for i in 0..reader.num_batches() {
+        let batch_start = Instant::now();
+        rows += reader.read_batch(i).unwrap().num_rows();
+        latencies.push(batch_start.elapsed());
+    }
+
+    let elapsed = start.elapsed();
+    writer.join().unwrap();
+
+    Report {
+        rows,
+        bytes: total_bytes,
+        elapsed,
+        latencies,
+    }
+}
+
+fn main() {
+    let config = Config::from_args();
+    let batch = synthetic_batch(&config);
+
+    println!(
+        "{} batches of {} rows and {} columns",
+        config.batches, config.rows, config.columns
+    );
+
+    bench_stream(&batch, config.batches).print("Stream");
+    bench_file(&batch, config.batches).print("File");
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int8Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{aggregate, TDigest},
+    ScalarValue, Table,
+};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("city", DataType::Utf8, true),
+        Field::new("temperature", DataType::Float64, true),
+        Field::new("floor", DataType::Int8, false),
+    ]);
+
+    let batches = vec![
+        RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(StringArray::from(vec![Some("Oslo"), None, Some("Lima")])),
+                Arc::new(Float64Array::from(vec![Some(-3.5), Some(12.0), None])),
+                Arc::new(Int8Array::from(vec![100, 100, 100])),
+            ],
+        )
+        .unwrap(),
+        RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(StringArray::from(vec![Some("Cairo"), Some("Quito")])),
+                Arc::new(Float64Array::from(vec![Some(31.0), Some(18.5)])),
+                Arc::new(Int8Array::from(vec![100, 100])),
+            ],
+        )
+        .unwrap(),
+    ];
+    let table = Table::new(schema, batches);
+
+    // A single array
+    let chunk = table.column_chunks(1).next().unwrap();
+    println!(
+        "sum of the first chunk: {:?}",
+        aggregate::sum(&chunk).unwrap()
+    );
+
+    // The whole column, combining the results of every chunk
+    let temperature = table.column(1).unwrap();
+    println!("sum:   {:?}", temperature.sum().unwrap());
+    println!("min:   {:?}", temperature.min().unwrap());
+    println!("max:   {:?}", temperature.max().unwrap());
+    println!("mean:  {:?}", temperature.mean().unwrap());
+    println!("count: {:?}", temperature.count());
+    println!("var:    {:?}", temperature.var().unwrap());
+    println!("stddev: {:?}", temperature.stddev().unwrap());
+    println!("skew:   {:?}", temperature.skew().unwrap());
+    println!("median: {:?}", temperature.approx_quantile(0.5).unwrap());
+
+    // A digest can also be fed while the batches are read, and it only
+    // keeps a summary of the values
+    let mut digest = TDigest::default();
+    for chunk in table.column_chunks(1) {
+        digest.add_array(&chunk).unwrap();
+    }
+    println!("p95:    {:?}", digest.quantile(0.95).unwrap());
+    assert_eq!(
+        temperature.mean().unwrap(),
+        ScalarValue::Float64(Some(14.5))
+    );
+
+    // Strings have a minimum and a maximum but no sum
+    let city = table.column(0).unwrap();
+    println!("first city: {:?}", city.min().unwrap());
+    println!("last city:  {:?}", city.max().unwrap());
+    println!("{}", city.sum().unwrap_err());
+
+    // The sum of small integers uses a wider type
+    let floor = table.column(2).unwrap();
+    println!("sum of Int8 values: {:?}", floor.sum().unwrap());
+
+    // Nothing to aggregate gives a null
+    let empty: ArrayRef = Arc::new(Float64Array::from(vec![None, None]));
+    println!("mean of nulls: {:?}", aggregate::mean(&empty).unwrap());
+
+    // The variance of a single value is unknown
+    let single: ArrayRef = Arc::new(Float64Array::from(vec![Some(1.5), None]));
+    println!("var of one value: {:?}", aggregate::var(&single).unwrap());
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int64Array, StringArray},
+    datatypes::{DataType, Float64Type, Int64Type},
+};
+use arrow_guide::{
+    compute::{apply_scalar, apply_unary},
+    ScalarValue,
+};
+
+fn main() {
+    // Celsius to Fahrenheit, keeping the missing readings as nulls
+    let celsius = Float64Array::from(vec![Some(21.5), None, Some(-3.0)]);
+    let fahrenheit = apply_unary::<Float64Type, Float64Type, _>(&celsius, |value| {
+        Some(value * 9.0 / 5.0 + 32.0)
+    });
+    println!("fahrenheit: {:?}", fahrenheit);
+
+    // The function returns None for the values it can't convert
+    let millis = Int64Array::from(vec![Some(1_500), Some(-20), None]);
+    let seconds = apply_unary::<Int64Type, Int64Type, _>(&millis, |value| match value >= 0 {
+        true => Some(value / 1_000),
+        false => None,
+    });
+    println!("seconds: {:?}", seconds);
+
+    // Any column can be mapped through ScalarValues without downcasting it
+    let codes: ArrayRef = Arc::new(StringArray::from(vec![Some("es-MX"), None, Some("fr")]));
+    let countries = apply_scalar(&codes, &DataType::Utf8, |value| {
+        Ok(match value {
+            ScalarValue::Utf8(Some(code)) => {
+                ScalarValue::Utf8(code.split('-').nth(1).map(str::to_string))
+            }
+            other => other,
+        })
+    })
+    .unwrap();
+    println!("countries: {:?}", countries);
+
+    let lengths = apply_scalar(&codes, &DataType::Int32, |value| match value {
+        ScalarValue::Utf8(code) => Ok(ScalarValue::Int32(code.map(|code| code.len() as i32))),
+        other => Ok(other),
+    })
+    .unwrap();
+    println!("lengths: {:?}", lengths);
+
+    let error = apply_scalar(&codes, &DataType::Int32, Ok).unwrap_err();
+    println!("{}", error);
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int32Array, Int8Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{self, ArithmeticMode},
+    Table,
+};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("price", DataType::Float64, true),
+        Field::new("quantity", DataType::Float64, false),
+        Field::new("units", DataType::Int32, false),
+    ]);
+
+    let batches = vec![
+        RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Float64Array::from(vec![Some(2.5), None, Some(4.0)])),
+                Arc::new(Float64Array::from(vec![2.0, 3.0, 1.5])),
+                Arc::new(Int32Array::from(vec![10, 20, 30])),
+            ],
+        )
+        .unwrap(),
+        RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Float64Array::from(vec![Some(1.0), Some(8.0)])),
+                Arc::new(Float64Array::from(vec![4.0, 0.5])),
+                Arc::new(Int32Array::from(vec![40, 50])),
+            ],
+        )
+        .unwrap(),
+    ];
+    let table = Table::new(schema, batches);
+
+    // The columns of the table are combined chunk by chunk without knowing
+    // their types. A null price gives a null total
+    let totals = table
+        .column_chunks(0)
+        .zip(table.column_chunks(1))
+        .map(|(price, quantity)| compute::multiply(&price, &quantity).unwrap())
+        .collect::<Vec<ArrayRef>>();
+
+    for total in &totals {
+        println!("{:?}", total);
+    }
+
+    let per_unit = table
+        .column_chunks(0)
+        .zip(table.column_chunks(1))
+        .map(|(price, quantity)| compute::divide(&price, &quantity).unwrap())
+        .collect::<Vec<ArrayRef>>();
+    println!("{:?}", per_unit[0]);
+
+    // Columns of different types are cast to a type that holds both, so
+    // the Int32 units are multiplied as Float64
+    let price = table.column_chunks(0).next().unwrap();
+    let units = table.column_chunks(2).next().unwrap();
+    println!(
+        "{:?}",
+        compute::coerce_types(price.data_type(), units.data_type()).unwrap()
+    );
+    println!("{:?}", compute::multiply(&price, &units).unwrap());
+
+    let small: ArrayRef = Arc::new(Int8Array::from(vec![1, 2, 3]));
+    println!("{:?}", compute::add(&small, &units).unwrap());
+
+    let names: ArrayRef = Arc::new(StringArray::from(vec!["a", "b", "c"]));
+    println!("{}", compute::add(&names, &units).unwrap_err());
+
+    // Dividing by zero is an error
+    let zeros: ArrayRef = Arc::new(Int32Array::from(vec![0, 1, 2]));
+    println!("{}", compute::divide(&units, &zeros).unwrap_err());
+
+    // Integer sums wrap around by default. The checked kernels fail instead
+    // and the saturating ones stop at the limits of the type
+    let big: ArrayRef = Arc::new(Int32Array::from(vec![Some(i32::MAX), Some(1), None]));
+    let ones: ArrayRef = Arc::new(Int32Array::from(vec![1, 1, 1]));
+    println!("{:?}", compute::add(&big, &ones).unwrap());
+    println!("{}", compute::add_checked(&big, &ones).unwrap_err());
+    println!("{:?}", compute::add_saturating(&big, &ones).unwrap());
+    println!(
+        "{:?}",
+        compute::multiply_with_mode(&big, &big, ArithmeticMode::Saturating).unwrap()
+    );
+}
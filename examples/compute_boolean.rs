@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, BooleanArray, Float64Array, StringArray},
+    compute::filter_record_batch,
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{and, and_kleene, compare_scalar, not, nulls_as_false, or, or_kleene, Operator},
+    ScalarValue, Table,
+};
+
+fn batch(
+    schema: &Arc<Schema>,
+    cities: Vec<Option<&str>>,
+    temperatures: Vec<Option<f64>>,
+) -> RecordBatch {
+    let city: ArrayRef = Arc::new(StringArray::from(cities));
+    let temperature: ArrayRef = Arc::new(Float64Array::from(temperatures));
+    RecordBatch::try_new(schema.clone(), vec![city, temperature]).unwrap()
+}
+
+fn main() {
+    let left = BooleanArray::from(vec![Some(true), Some(false), None, None, Some(true)]);
+    let right = BooleanArray::from(vec![None, None, Some(true), Some(false), Some(false)]);
+
+    // The plain kernels give null when any of the values is null, while the
+    // Kleene versions only do it when the result is unknown
+    println!("and:        {:?}", and(&left, &right).unwrap());
+    println!("and_kleene: {:?}", and_kleene(&left, &right).unwrap());
+    println!("or:         {:?}", or(&left, &right).unwrap());
+    println!("or_kleene:  {:?}", or_kleene(&left, &right).unwrap());
+    println!("not:        {:?}", not(&left).unwrap());
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("city", DataType::Utf8, true),
+        Field::new("temperature", DataType::Float64, true),
+    ]));
+    let table = Table::new(
+        schema.as_ref().clone(),
+        vec![
+            batch(
+                &schema,
+                vec![Some("Oslo"), Some("Lima"), None],
+                vec![Some(-3.5), Some(19.0), Some(25.0)],
+            ),
+            batch(
+                &schema,
+                vec![Some("Cairo"), Some("Oslo"), Some("Quito")],
+                vec![Some(31.0), None, Some(14.5)],
+            ),
+        ],
+    );
+
+    // temperature > 10 and not city = Oslo, composed over every batch.
+    // Rows where the predicate is unknown are not selected
+    for batch in table.data() {
+        let warm = compare_scalar(
+            batch.column(1),
+            Operator::Gt,
+            &ScalarValue::Float64(Some(10.0)),
+        )
+        .unwrap();
+        let oslo = compare_scalar(
+            batch.column(0),
+            Operator::Eq,
+            &ScalarValue::Utf8(Some("Oslo".into())),
+        )
+        .unwrap();
+
+        let mask = and_kleene(&warm, &not(&oslo).unwrap()).unwrap();
+        let selected = filter_record_batch(batch, &nulls_as_false(&mask)).unwrap();
+        println!("{:?}", selected.column(0));
+    }
+
+    // Masks of different lengths can't be combined
+    let short = BooleanArray::from(vec![true]);
+    println!("{}", and_kleene(&left, &short).unwrap_err());
+}
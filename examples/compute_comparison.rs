@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Date32Array, Float64Array, StringArray},
+    compute::filter,
+};
+use arrow_guide::{
+    compute::{compare_scalar, Operator},
+    ScalarValue,
+};
+
+fn main() {
+    let city: ArrayRef = Arc::new(StringArray::from(vec![
+        Some("Oslo"),
+        None,
+        Some("Lima"),
+        Some("Cairo"),
+    ]));
+    let temperature: ArrayRef = Arc::new(Float64Array::from(vec![
+        Some(-3.5),
+        Some(12.0),
+        None,
+        Some(31.0),
+    ]));
+    // Days since 1970-01-01
+    let day: ArrayRef = Arc::new(Date32Array::from(vec![18_000, 18_500, 19_000, 19_500]));
+
+    // Nulls in the column give nulls in the result
+    let warm = compare_scalar(
+        &temperature,
+        Operator::Gt,
+        &ScalarValue::Float64(Some(10.0)),
+    )
+    .unwrap();
+    println!("temperature > 10: {:?}", warm);
+
+    let lima =
+        compare_scalar(&city, Operator::Eq, &ScalarValue::Utf8(Some("Lima".into()))).unwrap();
+    println!("city = Lima: {:?}", lima);
+
+    let recent = compare_scalar(&day, Operator::GtEq, &ScalarValue::Date32(Some(19_000))).unwrap();
+    println!("recent days: {:?}", filter(day.as_ref(), &recent).unwrap());
+
+    // The operator can be parsed, for example from a request
+    let op = "<=".parse::<Operator>().unwrap();
+    println!(
+        "city {} M: {:?}",
+        op,
+        compare_scalar(&city, op, &ScalarValue::Utf8(Some("M".into()))).unwrap()
+    );
+
+    // The scalar has to have the type of the column
+    println!(
+        "{}",
+        compare_scalar(&temperature, Operator::Eq, &ScalarValue::Int32(Some(12))).unwrap_err()
+    );
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Int32Array, Int64Array},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{cumsum, NullTreatment},
+    Table,
+};
+
+fn main() {
+    let amounts: ArrayRef = Arc::new(Int32Array::from(vec![Some(5), None, Some(3), Some(-2)]));
+    println!("skip: {:?}", cumsum(&amounts, NullTreatment::Skip).unwrap());
+    println!(
+        "propagate: {:?}",
+        cumsum(&amounts, NullTreatment::Propagate).unwrap()
+    );
+
+    // Deposits and withdrawals of an account read in two batches. The
+    // balance carries over from the first batch to the second
+    let schema = Schema::new(vec![Field::new("amount", DataType::Int32, true)]);
+    let batch = |amounts: Vec<Option<i32>>| {
+        let amount: ArrayRef = Arc::new(Int32Array::from(amounts));
+        RecordBatch::try_new(Arc::new(schema.clone()), vec![amount]).unwrap()
+    };
+    let table = Table::new(
+        schema.clone(),
+        vec![
+            batch(vec![Some(100), Some(-30)]),
+            batch(vec![None, Some(50), Some(-20)]),
+        ],
+    );
+
+    let nulls = "skip".parse::<NullTreatment>().unwrap();
+    let balance = table.cumsum(0, nulls).unwrap();
+    for chunk in balance.chunks() {
+        println!("balance: {:?}", chunk);
+    }
+
+    // The integers are added as Int64, so only huge sums overflow
+    let big: ArrayRef = Arc::new(Int64Array::from(vec![i64::MAX, 1]));
+    println!("{}", cumsum(&big, NullTreatment::Skip).unwrap_err());
+}
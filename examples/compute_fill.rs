@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int32Array},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{fill_backward, fill_forward, interpolate_linear},
+    Table,
+};
+
+fn main() {
+    let counts: ArrayRef = Arc::new(Int32Array::from(vec![None, Some(3), None, None, Some(7)]));
+    println!("forward: {:?}", fill_forward(&counts).unwrap());
+    println!("backward: {:?}", fill_backward(&counts).unwrap());
+    println!("interpolated: {:?}", interpolate_linear(&counts).unwrap());
+
+    // Readings of a sensor that missed some samples, loaded in three batches
+    let schema = Schema::new(vec![Field::new("temperature", DataType::Float64, true)]);
+    let batch = |readings: Vec<Option<f64>>| {
+        let temperature: ArrayRef = Arc::new(Float64Array::from(readings));
+        RecordBatch::try_new(Arc::new(schema.clone()), vec![temperature]).unwrap()
+    };
+    let table = Table::new(
+        schema.clone(),
+        vec![
+            batch(vec![Some(20.0), Some(20.5), None]),
+            batch(vec![None, None]),
+            batch(vec![Some(22.5), None]),
+        ],
+    );
+
+    // The gap starts in the first batch and ends in the third one, but it's
+    // filled as a single gap. The result keeps the chunks of the table
+    let filled = table.fill_forward(0).unwrap();
+    println!("forward chunks: {:?}", filled.chunks());
+
+    let interpolated = table.interpolate_linear(0).unwrap();
+    println!("interpolated: {:?}", interpolated.flatten().unwrap());
+
+    println!("{}", table.fill_backward(1).unwrap_err());
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    compute::{sort, sort_to_indices, take},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{ChunkedColumn, Table};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("city", DataType::Utf8, true),
+        Field::new("temperature", DataType::Float64, true),
+    ]);
+
+    let batch = |cities: Vec<Option<&str>>, temperatures: Vec<Option<f64>>| {
+        let city: ArrayRef = Arc::new(StringArray::from(cities));
+        let temperature: ArrayRef = Arc::new(Float64Array::from(temperatures));
+        RecordBatch::try_new(Arc::new(schema.clone()), vec![city, temperature]).unwrap()
+    };
+    let table = Table::new(
+        schema.clone(),
+        vec![
+            batch(vec![Some("Oslo"), None], vec![Some(-3.5), Some(12.0)]),
+            batch(vec![Some("Lima")], vec![None]),
+            batch(
+                vec![Some("Cairo"), Some("Quito")],
+                vec![Some(31.0), Some(18.5)],
+            ),
+        ],
+    );
+
+    // Sorting needs all the values of the column in one array
+    let temperature = table.column(1).unwrap();
+    println!("{} chunks", temperature.chunks().len());
+
+    let temperature = temperature.flatten().unwrap();
+    println!("sorted: {:?}", sort(&temperature, None).unwrap());
+
+    // The other columns can be reordered with the same indices
+    let indices = sort_to_indices(&temperature, None).unwrap();
+    let city = table.column(0).unwrap().flatten().unwrap();
+    println!("cities: {:?}", take(city.as_ref(), &indices, None).unwrap());
+
+    // A column without chunks gives an empty array
+    let empty = ChunkedColumn::try_new(schema.field(0).clone(), vec![]).unwrap();
+    println!("empty: {:?}", empty.flatten().unwrap());
+}
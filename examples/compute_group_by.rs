@@ -0,0 +1,79 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    ipc::{reader::StreamReader, writer::StreamWriter},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{group_by_hash, Aggregate, AggregateFunction, GroupByHash},
+    Table,
+};
+
+fn main() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("region", DataType::Utf8, true),
+        Field::new("units", DataType::Int32, false),
+        Field::new("price", DataType::Float64, true),
+    ]));
+    let batch = |regions: Vec<Option<&str>>, units: Vec<i32>, prices: Vec<Option<f64>>| {
+        let regions: ArrayRef = Arc::new(StringArray::from(regions));
+        let units: ArrayRef = Arc::new(Int32Array::from(units));
+        let prices: ArrayRef = Arc::new(Float64Array::from(prices));
+        RecordBatch::try_new(schema.clone(), vec![regions, units, prices]).unwrap()
+    };
+    let batches = vec![
+        batch(
+            vec![Some("north"), Some("south"), Some("north")],
+            vec![3, 1, 4],
+            vec![Some(2.5), Some(8.0), None],
+        ),
+        batch(
+            vec![None, Some("south"), Some("east")],
+            vec![2, 5, 1],
+            vec![Some(1.0), Some(6.0), Some(3.5)],
+        ),
+    ];
+
+    let aggregates = [
+        Aggregate::new(AggregateFunction::Sum, 1),
+        Aggregate::new(AggregateFunction::Mean, 2),
+        Aggregate::new("count".parse().unwrap(), 2),
+    ];
+
+    // The groups of all the batches are combined. Rows without a region
+    // form their own group
+    let grouped = group_by_hash(&batches, &[0], &aggregates).unwrap();
+    for (field, column) in grouped.schema().fields().iter().zip(grouped.columns()) {
+        println!("{}: {:?}", field.name(), column);
+    }
+
+    let table = Table::new(schema.as_ref().clone(), batches.clone());
+    let by_region = table
+        .group_by(&[0], &[Aggregate::new(AggregateFunction::Max, 2)])
+        .unwrap();
+    println!("max price: {:?}", by_region.column(1).unwrap().chunks());
+
+    // A stream is grouped one batch at a time, without collecting it
+    let mut stream = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    let reader = StreamReader::try_new(Cursor::new(stream)).unwrap();
+    let mut group_by = GroupByHash::new(reader.schema(), &[0], &aggregates).unwrap();
+    for batch in reader {
+        group_by.update(&batch.unwrap()).unwrap();
+        println!("groups so far: {}", group_by.num_groups());
+    }
+    println!("units: {:?}", group_by.finish().unwrap().column(1));
+
+    let error = GroupByHash::new(schema, &[0], &[Aggregate::new(AggregateFunction::Sum, 0)]);
+    println!("{}", error.err().unwrap());
+}
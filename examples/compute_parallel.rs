@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int32Array},
+    datatypes::{DataType, Field, Schema},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{self, par_apply_batches},
+    Table,
+};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("price", DataType::Float64, false),
+        Field::new("units", DataType::Int32, false),
+    ]);
+
+    let batches = (0..64)
+        .map(|i| {
+            let price: ArrayRef = Arc::new(Float64Array::from(vec![i as f64 + 0.5; 10_000]));
+            let units: ArrayRef = Arc::new(Int32Array::from(vec![i; 10_000]));
+            RecordBatch::try_new(Arc::new(schema.clone()), vec![price, units]).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let table = Table::new(schema, batches);
+
+    // Every batch gets a total column computed in a thread of the pool
+    let total_schema = Arc::new(Schema::new(vec![
+        Field::new("price", DataType::Float64, false),
+        Field::new("units", DataType::Int32, false),
+        Field::new("total", DataType::Float64, false),
+    ]));
+    let with_totals = par_apply_batches(&table, |batch| -> Result<RecordBatch> {
+        let total = compute::multiply(batch.column(0), batch.column(1))?;
+        let mut columns = batch.columns().to_vec();
+        columns.push(total);
+        RecordBatch::try_new(total_schema.clone(), columns)
+    })
+    .unwrap();
+
+    // The batches keep the order of the original table
+    println!("rows: {}", with_totals.rows());
+    println!(
+        "last total: {:?}",
+        with_totals.value(2, with_totals.rows() - 1)
+    );
+    println!("sum: {:?}", with_totals.column(2).unwrap().sum().unwrap());
+
+    // The units of the first batch are 0, and the error of the batch is
+    // returned
+    let result = par_apply_batches(&table, |batch| {
+        let per_unit = compute::divide(batch.column(0), batch.column(1))?;
+        RecordBatch::try_new(total_schema.clone(), vec![per_unit])
+    });
+    if let Err(error) = result {
+        println!("{}", error);
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::compute::hash_partition;
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("city", DataType::Utf8, true),
+        Field::new("sales", DataType::Float64, false),
+    ]);
+    let city: ArrayRef = Arc::new(StringArray::from(vec![
+        Some("Oslo"),
+        Some("Lima"),
+        Some("Cairo"),
+        None,
+        Some("Lima"),
+        Some("Oslo"),
+        Some("Quito"),
+        Some("Lima"),
+    ]));
+    let sales: ArrayRef = Arc::new(Float64Array::from(vec![
+        10.0, 4.5, 8.0, 1.0, 2.5, 3.0, 7.25, 1.0,
+    ]));
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![city, sales]).unwrap();
+
+    // All the rows of a city go to the same partition, so the partitions
+    // can be grouped in parallel without merging the results afterwards
+    let partitions = hash_partition(&batch, &[0], 3).unwrap();
+
+    let handles = partitions
+        .into_iter()
+        .enumerate()
+        .map(|(i, partition)| {
+            thread::spawn(move || {
+                let cities = partition
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<StringArray>()
+                    .unwrap();
+                let sales = partition
+                    .column(1)
+                    .as_any()
+                    .downcast_ref::<Float64Array>()
+                    .unwrap();
+
+                let mut totals = HashMap::new();
+                for (city, sales) in cities.iter().zip(sales.iter()) {
+                    *totals.entry(city.map(str::to_string)).or_insert(0.0) += sales.unwrap();
+                }
+                (i, partition.num_rows(), totals)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        let (i, rows, totals) = handle.join().unwrap();
+        println!("partition {} with {} rows: {:?}", i, rows, totals);
+    }
+
+    println!("{}", hash_partition(&batch, &[2], 3).unwrap_err());
+}
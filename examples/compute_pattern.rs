@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, StringArray},
+    compute::filter_record_batch,
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{like, nulls_as_false, or_kleene, regex_match},
+    Table,
+};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("athlete", DataType::Utf8, true),
+        Field::new("event", DataType::Utf8, false),
+    ]);
+    let athlete: ArrayRef = Arc::new(StringArray::from(vec![
+        Some("Usain Bolt"),
+        Some("Allyson Felix"),
+        None,
+        Some("Paavo Nurmi"),
+        Some("Emil Zátopek"),
+    ]));
+    let event: ArrayRef = Arc::new(StringArray::from(vec![
+        "100m",
+        "4x400m relay",
+        "200m",
+        "10,000m",
+        "5,000m",
+    ]));
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![athlete, event]).unwrap();
+
+    // The table is saved as parquet and loaded again in batches of 2 rows
+    let path = std::env::temp_dir().join("compute_pattern.parquet");
+    Table::new(schema, vec![batch]).to_parquet(&path);
+    let table = Table::read_parquet(&path, 2);
+
+    for batch in table.data() {
+        let athlete = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let event = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+
+        // The patterns are compiled once and taken from the cache for the
+        // next batches
+        let long_distance = regex_match(event, r"^\d{1,2},000m$").unwrap();
+        let name = like(athlete, "%l_x").unwrap();
+        let mask = or_kleene(&long_distance, &name).unwrap();
+
+        let selected = filter_record_batch(batch, &nulls_as_false(&mask)).unwrap();
+        println!("{:?}", selected.column(0));
+    }
+
+    // Escaped wildcards match themselves
+    let discounts = StringArray::from(vec!["50% off", "50 off", "5_0"]);
+    println!("{:?}", like(&discounts, r"%\%%").unwrap());
+    println!("{:?}", like(&discounts, r"5\_0").unwrap());
+
+    println!("{}", regex_match(&discounts, "(unclosed").unwrap_err());
+    std::fs::remove_file(path).unwrap();
+}
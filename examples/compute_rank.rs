@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{rank, RankMethod},
+    Table,
+};
+
+fn main() {
+    let scores: ArrayRef = Arc::new(Float64Array::from(vec![
+        Some(10.0),
+        Some(20.0),
+        None,
+        Some(20.0),
+        Some(30.0),
+    ]));
+
+    for method in &[RankMethod::Average, RankMethod::Min, RankMethod::Dense] {
+        println!("{}: {:?}", method, rank(&scores, false, *method).unwrap());
+    }
+
+    // The rows of the table are ranked across all its batches
+    let schema = Schema::new(vec![
+        Field::new("athlete", DataType::Utf8, false),
+        Field::new("time", DataType::Float64, true),
+    ]);
+    let batch = |athletes: Vec<&str>, times: Vec<Option<f64>>| {
+        let athlete: ArrayRef = Arc::new(StringArray::from(athletes));
+        let time: ArrayRef = Arc::new(Float64Array::from(times));
+        RecordBatch::try_new(Arc::new(schema.clone()), vec![athlete, time]).unwrap()
+    };
+    let table = Table::new(
+        schema.clone(),
+        vec![
+            batch(vec!["Bolt", "Blake"], vec![Some(9.63), Some(9.75)]),
+            batch(vec!["Gatlin", "Powell"], vec![Some(9.79), None]),
+            batch(vec!["Gay"], vec![Some(9.80)]),
+        ],
+    );
+
+    let method = "min".parse().unwrap();
+    let ranked = table.with_rank(1, "position", false, method).unwrap();
+    for batch in ranked.data() {
+        println!("{:?}", batch.column(2));
+    }
+}
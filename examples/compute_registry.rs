@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, BooleanArray, Float64Array, Int32Array, StringArray},
+    datatypes::{DataType, Float64Type},
+};
+use arrow_guide::{
+    compute::{apply_unary, KernelRegistry, Signature},
+    downcast::as_primitive,
+};
+
+fn main() {
+    let mut registry = KernelRegistry::with_builtins();
+    println!("functions: {:?}", registry.names());
+
+    // The functions are called by name with any column they support
+    let price: ArrayRef = Arc::new(Float64Array::from(vec![2.5, 4.0, 1.25]));
+    let units: ArrayRef = Arc::new(Int32Array::from(vec![4, 2, 8]));
+    println!(
+        "{:?}",
+        registry
+            .invoke("multiply", &[price.clone(), units])
+            .unwrap()
+    );
+
+    let names: ArrayRef = Arc::new(StringArray::from(vec!["  ada ", "grace"]));
+    let trimmed = registry.invoke("trim", &[names]).unwrap();
+    println!("{:?}", registry.invoke("upper", &[trimmed]).unwrap());
+
+    // Custom kernels are registered next to the built-in ones
+    registry.register("half", Signature::Exact(vec![DataType::Float64]), |args| {
+        let values = as_primitive::<Float64Type>(&args[0])?;
+        let half = apply_unary::<Float64Type, Float64Type, _>(values, |value| Some(value / 2.0));
+        Ok(Arc::new(half) as ArrayRef)
+    });
+    println!("{:?}", registry.invoke("half", &[price]).unwrap());
+
+    // The types are checked before calling the kernel
+    let flags: ArrayRef = Arc::new(BooleanArray::from(vec![true, false]));
+    println!(
+        "{}",
+        registry
+            .invoke("half", std::slice::from_ref(&flags))
+            .unwrap_err()
+    );
+    println!("{}", registry.invoke("round", &[flags]).unwrap_err());
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::compute::{rows_equal, RowHasher};
+
+fn main() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("city", DataType::Utf8, true),
+        Field::new("year", DataType::Int32, false),
+        Field::new("sales", DataType::Float64, false),
+    ]));
+
+    let city: ArrayRef = Arc::new(StringArray::from(vec![
+        Some("Oslo"),
+        Some("Lima"),
+        None,
+        Some("Oslo"),
+    ]));
+    let year: ArrayRef = Arc::new(Int32Array::from(vec![2020, 2020, 2021, 2021]));
+    let sales: ArrayRef = Arc::new(Float64Array::from(vec![10.0, 4.5, 8.0, 1.0]));
+    let left = RecordBatch::try_new(schema.clone(), vec![city, year, sales]).unwrap();
+
+    let city: ArrayRef = Arc::new(StringArray::from(vec![Some("Oslo"), None, Some("Lima")]));
+    let year: ArrayRef = Arc::new(Int32Array::from(vec![2021, 2021, 2019]));
+    let sales: ArrayRef = Arc::new(Float64Array::from(vec![3.0, 2.0, 6.5]));
+    let right = RecordBatch::try_new(schema, vec![city, year, sales]).unwrap();
+
+    // The rows of the left batch are indexed by the hash of (city, year).
+    // Different keys can share a hash, so the candidates are compared with
+    // rows_equal before joining them
+    let keys = [0, 1];
+    let hasher = RowHasher::new(&keys);
+    let mut index = HashMap::<u64, Vec<usize>>::new();
+    for (row, hash) in hasher.hash_rows(&left).unwrap().into_iter().enumerate() {
+        index.entry(hash).or_default().push(row);
+    }
+
+    for row in 0..right.num_rows() {
+        let hash = hasher.hash_row(&right, row).unwrap();
+        let matches = index
+            .get(&hash)
+            .into_iter()
+            .flatten()
+            .filter(|&&candidate| rows_equal(&left, candidate, &right, row, &keys).unwrap())
+            .collect::<Vec<_>>();
+        println!("right row {} matches left rows {:?}", row, matches);
+    }
+
+    // The hash of a single row is the same as the one of the whole batch
+    let hashes = hasher.hash_rows(&right).unwrap();
+    assert_eq!(hashes[1], hasher.hash_row(&right, 1).unwrap());
+
+    if let Err(error) = rows_equal(&left, 4, &right, 0, &keys) {
+        println!("{}", error);
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::{shift, subtract},
+    Table,
+};
+
+fn main() {
+    let names: ArrayRef = Arc::new(StringArray::from(vec!["a", "b", "c", "d"]));
+    println!("shift 1: {:?}", shift(&names, 1).unwrap());
+    println!("shift -2: {:?}", shift(&names, -2).unwrap());
+
+    let schema = Schema::new(vec![Field::new("price", DataType::Float64, true)]);
+    let batch = |prices: Vec<Option<f64>>| {
+        let price: ArrayRef = Arc::new(Float64Array::from(prices));
+        RecordBatch::try_new(Arc::new(schema.clone()), vec![price]).unwrap()
+    };
+    let table = Table::new(
+        schema.clone(),
+        vec![
+            batch(vec![Some(10.0), Some(10.5)]),
+            batch(vec![Some(9.75)]),
+            batch(vec![None, Some(11.0), Some(12.25)]),
+        ],
+    );
+
+    // The previous price of the first row of a batch comes from the last
+    // row of the batch before it
+    let price = table.column(0).unwrap();
+    let previous = table.lag(0, 1).unwrap();
+    for (current, previous) in price.chunks().iter().zip(previous.chunks()) {
+        println!("change: {:?}", subtract(current, previous).unwrap());
+    }
+
+    let next = table.lead(0, 2).unwrap();
+    println!("two rows later: {:?}", next.flatten().unwrap());
+
+    println!("{}", table.lag(3, 1).unwrap_err());
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    compute::{take, SortOptions},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{compute::sort_indices, Table};
+
+fn main() {
+    let team: ArrayRef = Arc::new(StringArray::from(vec![
+        Some("blue"),
+        Some("red"),
+        None,
+        Some("blue"),
+        Some("red"),
+    ]));
+    let score: ArrayRef = Arc::new(Float64Array::from(vec![
+        Some(7.5),
+        Some(9.0),
+        Some(3.0),
+        None,
+        Some(6.5),
+    ]));
+
+    // Teams in ascending order with the rows without a team at the end, and
+    // the best scores first inside every team
+    let ascending = SortOptions {
+        descending: false,
+        nulls_first: false,
+    };
+    let descending = SortOptions {
+        descending: true,
+        nulls_first: false,
+    };
+    let indices = sort_indices(&[(&team, ascending), (&score, descending)]).unwrap();
+    println!("indices: {:?}", indices);
+    println!("teams: {:?}", take(team.as_ref(), &indices, None).unwrap());
+    println!(
+        "scores: {:?}",
+        take(score.as_ref(), &indices, None).unwrap()
+    );
+
+    // A table is sorted across all its batches
+    let schema = Schema::new(vec![
+        Field::new("team", DataType::Utf8, true),
+        Field::new("score", DataType::Float64, true),
+    ]);
+    let slice = |offset, len| {
+        let columns = vec![team.slice(offset, len), score.slice(offset, len)];
+        RecordBatch::try_new(Arc::new(schema.clone()), columns).unwrap()
+    };
+    let table = Table::new(schema.clone(), vec![slice(0, 3), slice(3, 2)]);
+
+    let sorted = table.sort_by(&[(1, descending)]).unwrap();
+    for chunk in sorted.column(1).unwrap().chunks() {
+        println!("sorted chunk: {:?}", chunk);
+    }
+}
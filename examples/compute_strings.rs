@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, LargeStringArray, StringArray};
+use arrow_guide::compute::strings::{concat_columns, length, lower, substring, trim, upper};
+
+fn main() {
+    let names: ArrayRef = Arc::new(StringArray::from(vec![
+        Some("  Ada Lovelace "),
+        None,
+        Some("Grace Hopper"),
+        Some("Ñandú"),
+    ]));
+
+    // The nulls stay in the same rows
+    println!("upper: {:?}", upper(&names).unwrap());
+    println!("lower: {:?}", lower(&names).unwrap());
+    println!("trim: {:?}", trim(&names).unwrap());
+
+    // Lengths and positions count characters, not bytes
+    println!("length: {:?}", length(&names).unwrap());
+    println!("substring: {:?}", substring(&names, 0, Some(3)).unwrap());
+    println!("until the end: {:?}", substring(&names, 6, None).unwrap());
+
+    // Sliced columns start at an offset of their buffers
+    let sliced = names.slice(1, 3);
+    println!("sliced upper: {:?}", upper(&sliced).unwrap());
+
+    let first: ArrayRef = Arc::new(LargeStringArray::from(vec![
+        Some("north"),
+        Some("south"),
+        None,
+    ]));
+    let separator: ArrayRef = Arc::new(LargeStringArray::from(vec!["-", "-", "-"]));
+    let second: ArrayRef = Arc::new(LargeStringArray::from(vec![
+        Some("east"),
+        Some("west"),
+        Some("east"),
+    ]));
+    println!(
+        "concat: {:?}",
+        concat_columns(&[first.clone(), separator, second]).unwrap()
+    );
+
+    // The columns must have the same type
+    println!("{}", concat_columns(&[first, names]).unwrap_err());
+}
@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Date32Array, Float64Array, Int32Array, TimestampSecondArray},
+    datatypes::{DataType, DateUnit, Field, Schema, TimeUnit},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::temporal::{add_duration, day, hour, month, year},
+    ScalarValue, Table,
+};
+
+fn batch(schema: &Arc<Schema>, days: Vec<Option<i32>>, amounts: Vec<f64>) -> RecordBatch {
+    let day: ArrayRef = Arc::new(Date32Array::from(days));
+    let amount: ArrayRef = Arc::new(Float64Array::from(amounts));
+    RecordBatch::try_new(schema.clone(), vec![day, amount]).unwrap()
+}
+
+fn main() {
+    // 2021-01-30, 2021-02-01 and 2021-02-28 as days since 1970-01-01
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("day", DataType::Date32(DateUnit::Day), true),
+        Field::new("amount", DataType::Float64, false),
+    ]));
+    let table = Table::new(
+        schema.as_ref().clone(),
+        vec![
+            batch(&schema, vec![Some(18_657), Some(18_659)], vec![10.0, 2.5]),
+            batch(&schema, vec![None, Some(18_686)], vec![4.0, 7.5]),
+        ],
+    );
+
+    // Group by month: the month of every row is extracted for each batch
+    // and the amounts are added to its group. Rows without a day are skipped
+    let mut totals = BTreeMap::new();
+    for batch in table.data() {
+        let years = year(batch.column(0)).unwrap();
+        let months = month(batch.column(0)).unwrap();
+        let years = years.as_any().downcast_ref::<Int32Array>().unwrap();
+        let months = months.as_any().downcast_ref::<Int32Array>().unwrap();
+        let amounts = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+
+        for (i, (year, month)) in years.iter().zip(months.iter()).enumerate() {
+            if let (Some(year), Some(month)) = (year, month) {
+                *totals.entry((year, month)).or_insert(0.0) += amounts.value(i);
+            }
+        }
+    }
+
+    for ((year, month), total) in totals {
+        println!("{}-{:02}: {}", year, month, total);
+    }
+
+    // Timestamps keep their timezone, but the fields are read as UTC
+    let timestamps: ArrayRef = Arc::new(TimestampSecondArray::from_opt_vec(
+        vec![Some(951_782_400), Some(1_609_459_199), None],
+        Some("UTC".to_string()),
+    ));
+    println!("days: {:?}", day(&timestamps).unwrap());
+    println!("hours: {:?}", hour(&timestamps).unwrap());
+
+    let one_hour = ScalarValue::Duration(Some(3_600_000), TimeUnit::Millisecond);
+    let later = add_duration(&timestamps, &one_hour).unwrap();
+    println!("one hour later: {:?}", later);
+    println!("hours: {:?}", hour(&later).unwrap());
+
+    // A week can be added to a date, but an hour isn't a whole number of days
+    let week = ScalarValue::Duration(Some(7 * 86_400), TimeUnit::Second);
+    let days = table.data()[0].column(0);
+    println!("next week: {:?}", add_duration(days, &week).unwrap());
+    println!("{}", add_duration(days, &one_hour).unwrap_err());
+}
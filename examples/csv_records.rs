@@ -0,0 +1,69 @@
+use arrow::datatypes::{DataType, DateUnit};
+use arrow_guide::{
+    csv_records::{parse_date, parse_number, CsvRecordsBuilder},
+    ScalarValue,
+};
+
+// An export with european numbers, dates as day/month/year and a status
+// column that uses "n/a" for the missing values
+const SALES: &str = "\
+date;store;amount;units;status
+03/01/2021;north;1.234,50;12;shipped
+04/01/2021;south;987,25;n/a;pending
+05/01/2021;north;12.000,00;150;SHIPPED
+07/01/2021;east;45,10;3;n/a
+";
+
+fn main() {
+    let builder = CsvRecordsBuilder::new(2)
+        .with_null_values(&["", "n/a"])
+        .with_parser(
+            "date",
+            0,
+            DataType::Date32(DateUnit::Day),
+            parse_date("%d/%m/%Y"),
+        )
+        .unwrap()
+        .with_column("store", 1, DataType::Utf8)
+        .unwrap()
+        .with_parser(
+            "amount",
+            2,
+            DataType::Float64,
+            parse_number(&DataType::Float64, '.', ',').unwrap(),
+        )
+        .unwrap()
+        .with_column("units", 3, DataType::UInt32)
+        .unwrap()
+        // Any closure can be a parser, here to normalize the case
+        .with_parser("shipped", 4, DataType::Boolean, |text| {
+            Ok(ScalarValue::Boolean(Some(
+                text.eq_ignore_ascii_case("shipped"),
+            )))
+        })
+        .unwrap();
+    println!("{:#?}", builder.schema());
+
+    let reader = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .from_reader(SALES.as_bytes());
+    for batch in builder.read(reader) {
+        let batch = batch.unwrap();
+        println!("Batch with {} rows", batch.num_rows());
+        for column in batch.columns() {
+            println!("{:?}", column);
+        }
+    }
+
+    // The errors say which field and line couldn't be parsed
+    let reader = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .from_reader("units\n12\ntwelve\n".as_bytes());
+    let errors = CsvRecordsBuilder::default()
+        .with_column("units", 0, DataType::UInt32)
+        .unwrap()
+        .read(reader);
+    for batch in errors {
+        println!("{}", batch.unwrap_err());
+    }
+}
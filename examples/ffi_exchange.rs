@@ -0,0 +1,155 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, Int64Array, StringArray},
+    ffi::{FFI_ArrowArray, FFI_ArrowSchema},
+};
+use arrow_guide::ffi::{export_array, free_exported, import_array};
+
+// The functions marked extern "C" play the part of a C library. They only
+// use the structs of the C data interface, declared here as they are in
+// the abi.h header of the specification, so they could be written in C
+// and linked to the program instead
+#[repr(C)]
+struct ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut ArrowSchema,
+    dictionary: *mut ArrowSchema,
+    release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut ArrowArray,
+    dictionary: *mut ArrowArray,
+    release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+// Sums the valid values of an int64 array and releases it, as a consumer
+// must do once it's done with the array
+unsafe extern "C" fn sum_int64(array: *mut ArrowArray, schema: *mut ArrowSchema) -> i64 {
+    let format = CStr::from_ptr((*schema).format).to_str().unwrap();
+    assert_eq!(format, "l", "Expected an int64 array");
+
+    let array_ref = &*array;
+    let validity = *array_ref.buffers as *const u8;
+    let values = *array_ref.buffers.add(1) as *const i64;
+
+    let mut sum = 0;
+    for i in array_ref.offset..array_ref.offset + array_ref.length {
+        let i = i as usize;
+        let valid = validity.is_null() || *validity.add(i / 8) & (1 << (i % 8)) != 0;
+        if valid {
+            sum += *values.add(i);
+        }
+    }
+
+    if let Some(release) = (*array).release {
+        release(array);
+    }
+    if let Some(release) = (*schema).release {
+        release(schema);
+    }
+    sum
+}
+
+// Memory owned by the arrays produced by the library
+struct Produced {
+    values: Vec<f64>,
+    buffers: Vec<*const c_void>,
+}
+
+unsafe extern "C" fn release_produced_array(array: *mut ArrowArray) {
+    drop(Box::from_raw((*array).private_data as *mut Produced));
+    (*array).release = None;
+}
+
+unsafe extern "C" fn release_produced_schema(schema: *mut ArrowSchema) {
+    (*schema).release = None;
+}
+
+// Fills the structs with a float64 array of the squares of 0 to n - 1.
+// The array has no nulls, so its validity buffer is null
+unsafe extern "C" fn produce_squares(n: i64, array: *mut ArrowArray, schema: *mut ArrowSchema) {
+    let values = (0..n).map(|i| (i * i) as f64).collect::<Vec<f64>>();
+    let mut produced = Box::new(Produced {
+        buffers: vec![std::ptr::null(), values.as_ptr() as *const c_void],
+        values,
+    });
+
+    *schema = ArrowSchema {
+        format: b"g\0".as_ptr() as *const c_char,
+        name: std::ptr::null(),
+        metadata: std::ptr::null(),
+        flags: 0,
+        n_children: 0,
+        children: std::ptr::null_mut(),
+        dictionary: std::ptr::null_mut(),
+        release: Some(release_produced_schema),
+        private_data: std::ptr::null_mut(),
+    };
+    *array = ArrowArray {
+        length: produced.values.len() as i64,
+        null_count: 0,
+        offset: 0,
+        n_buffers: 2,
+        n_children: 0,
+        buffers: produced.buffers.as_mut_ptr(),
+        children: std::ptr::null_mut(),
+        dictionary: std::ptr::null_mut(),
+        release: Some(release_produced_array),
+        private_data: Box::into_raw(produced) as *mut c_void,
+    };
+}
+
+fn main() {
+    // Rust to C. The library reads the buffers of the array in place
+    let numbers: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3), Some(4)]));
+    let (array, schema) = export_array(&numbers).unwrap();
+    let sum = unsafe { sum_int64(array as *mut ArrowArray, schema as *mut ArrowSchema) };
+    unsafe { free_exported(array, schema) };
+    println!("Sum computed by the library: {}", sum);
+
+    // A slice is exported with its offset, without copying the values
+    let (array, schema) = export_array(&numbers.slice(2, 2)).unwrap();
+    let sum = unsafe { sum_int64(array as *mut ArrowArray, schema as *mut ArrowSchema) };
+    unsafe { free_exported(array, schema) };
+    println!("Sum of the slice: {}", sum);
+
+    // C to Rust. The library fills the structs and the array keeps its
+    // memory until the array is dropped
+    let squares = unsafe {
+        import_array(|array, schema| {
+            produce_squares(5, array as *mut ArrowArray, schema as *mut ArrowSchema)
+        })
+    }
+    .unwrap();
+    let squares = squares.as_any().downcast_ref::<Float64Array>().unwrap();
+    println!("Squares produced by the library: {:?}", squares);
+
+    // Strings are exchanged too, but nested arrays aren't supported yet
+    let strings: ArrayRef = Arc::new(StringArray::from(vec!["shared", "memory"]));
+    let (array, schema) = export_array(&strings).unwrap();
+    unsafe { free_exported(array, schema) };
+
+    let nested: ArrayRef = Arc::new(arrow_guide::list_array!(Int32, [[1, 2], [3]]));
+    println!("{}", export_array(&nested).unwrap_err());
+
+    // A producer that doesn't fill the structs gives an error
+    let empty = unsafe { import_array(|_: *mut FFI_ArrowArray, _: *mut FFI_ArrowSchema| {}) };
+    println!("{}", empty.unwrap_err());
+}
@@ -0,0 +1,178 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    ffi::{export_stream, export_table, import_stream, import_table},
+    Table,
+};
+
+// The function marked extern "C" plays the part of a C library reading a
+// stream, like DuckDB scanning a table. The structs are declared as they
+// are in the abi.h header of the specification
+#[repr(C)]
+struct ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut ArrowSchema,
+    dictionary: *mut ArrowSchema,
+    release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut ArrowArray,
+    dictionary: *mut ArrowArray,
+    release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArrayStream {
+    get_schema: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowSchema) -> c_int>,
+    get_next: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowArray) -> c_int>,
+    get_last_error: Option<unsafe extern "C" fn(*mut ArrowArrayStream) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(*mut ArrowArrayStream)>,
+    private_data: *mut c_void,
+}
+
+// Sums the int64 column with the name in all the batches of the stream and
+// releases the stream. Returns -1 if the stream fails
+unsafe extern "C" fn sum_column(stream: *mut ArrowArrayStream, name: *const c_char) -> i64 {
+    let mut schema = std::mem::zeroed::<ArrowSchema>();
+    (*stream).get_schema.unwrap()(stream, &mut schema);
+
+    let mut column = None;
+    for i in 0..schema.n_children as usize {
+        let child = &**schema.children.add(i);
+        if CStr::from_ptr(child.name) == CStr::from_ptr(name) {
+            assert_eq!(CStr::from_ptr(child.format).to_bytes(), b"l");
+            column = Some(i);
+        }
+    }
+    schema.release.unwrap()(&mut schema);
+    let column = column.expect("The stream doesn't have the column");
+
+    let mut sum = 0;
+    loop {
+        let mut batch = std::mem::zeroed::<ArrowArray>();
+        if (*stream).get_next.unwrap()(stream, &mut batch) != 0 {
+            let error = (*stream).get_last_error.unwrap()(stream);
+            println!(
+                "The library got: {}",
+                CStr::from_ptr(error).to_str().unwrap()
+            );
+            sum = -1;
+            break;
+        }
+        // A released batch is the end of the stream
+        let release = match batch.release {
+            Some(release) => release,
+            None => break,
+        };
+
+        let values = &**batch.children.add(column);
+        let validity = *values.buffers as *const u8;
+        let data = *values.buffers.add(1) as *const i64;
+        for i in values.offset..values.offset + values.length {
+            let i = i as usize;
+            if validity.is_null() || *validity.add(i / 8) & (1 << (i % 8)) != 0 {
+                sum += *data.add(i);
+            }
+        }
+        release(&mut batch);
+    }
+
+    (*stream).release.unwrap()(stream);
+    sum
+}
+
+fn batch(schema: &Arc<Schema>, ids: Vec<Option<i64>>, names: Vec<&str>) -> RecordBatch {
+    let ids: ArrayRef = Arc::new(Int64Array::from(ids));
+    let names: ArrayRef = Arc::new(StringArray::from(names));
+    RecordBatch::try_new(schema.clone(), vec![ids, names]).unwrap()
+}
+
+fn main() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("name", DataType::Utf8, false),
+    ]));
+    let table = Table::new(
+        schema.as_ref().clone(),
+        vec![
+            batch(&schema, vec![Some(1), Some(2)], vec!["a", "b"]),
+            batch(&schema, vec![None, Some(4), Some(5)], vec!["c", "d", "e"]),
+        ],
+    );
+
+    // Rust to C. The library reads the batches one at a time, and the
+    // columns are the buffers of the table
+    let mut stream = export_table(&table).unwrap();
+    let sum = unsafe {
+        sum_column(
+            &mut stream as *mut _ as *mut ArrowArrayStream,
+            b"id\0".as_ptr() as *const c_char,
+        )
+    };
+    println!("Sum computed by the library: {}", sum);
+
+    // C to Rust. The stream is filled by a producer and read as a table,
+    // here the same table going through the C structs
+    let imported = unsafe { import_table(|out| *out = export_table(&table).unwrap()) }.unwrap();
+    println!(
+        "Imported {} rows in {} batches with schema {:?}",
+        imported.rows(),
+        imported.data().len(),
+        imported.schema()
+    );
+    for batch in imported.data() {
+        println!("{:?}", batch.column(0));
+    }
+
+    // The batches are read lazily, so an iterator that fails midway hands
+    // the error to the consumer through get_last_error
+    let batches = vec![
+        Ok(batch(&schema, vec![Some(1)], vec!["a"])),
+        Err(ArrowError::IoError("The file was truncated".to_string())),
+    ];
+    let mut stream = export_stream(schema.clone(), batches).unwrap();
+    let sum = unsafe {
+        sum_column(
+            &mut stream as *mut _ as *mut ArrowArrayStream,
+            b"id\0".as_ptr() as *const c_char,
+        )
+    };
+    println!("Sum of the failed stream: {}", sum);
+
+    let batches = vec![Err(ArrowError::IoError("The file is missing".to_string()))];
+    let mut reader = unsafe {
+        import_stream(|out| *out = export_stream(schema.clone(), batches).unwrap()).unwrap()
+    };
+    println!("{}", reader.next().unwrap().unwrap_err());
+
+    // Nested columns can't be exchanged yet
+    let nested = Schema::new(vec![Field::new(
+        "list",
+        DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
+        true,
+    )]);
+    let empty = Vec::new();
+    println!("{}", export_stream(Arc::new(nested), empty).err().unwrap());
+}
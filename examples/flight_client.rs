@@ -0,0 +1,29 @@
+use arrow_guide::{
+    flight::{table_descriptor, FlightClient},
+    ScalarValue,
+};
+
+fn main() {
+    // The table is requested from the server started with the
+    // flight_server example
+    let mut runtime = tokio02::runtime::Runtime::new().unwrap();
+    let table = runtime.block_on(async {
+        let mut client = FlightClient::connect("http://127.0.0.1:50051")
+            .await
+            .unwrap();
+
+        client
+            .get_table(table_descriptor("olympics"))
+            .await
+            .unwrap()
+    });
+
+    println!("{:?}", table.schema());
+    println!("Rows received: {}", table.rows());
+
+    for val in table.column_iterator(1) {
+        if let ScalarValue::Utf8(Some(name)) = val.unwrap() {
+            println!("{}", name);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use arrow_guide::{flight::FlightServer, Table};
+
+fn main() {
+    let table = Table::read_parquet("data/olympics.parquet", 2000);
+
+    let mut server = FlightServer::new();
+    server.add_table("olympics", table);
+
+    for name in server.table_names() {
+        println!("Serving table: {}", name);
+    }
+
+    // arrow-flight is built on top of tokio 0.2, so the server has to
+    // run in a runtime from that version
+    let mut runtime = tokio02::runtime::Runtime::new().unwrap();
+    runtime
+        .block_on(server.serve("127.0.0.1:50051".parse().unwrap()))
+        .unwrap();
+}
@@ -0,0 +1,110 @@
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use arrow::{
+    array::Int32Array,
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{BackpressurePolicy, Broadcaster, IpcStreamReader, IpcStreamWriter};
+
+// Reads the broadcast, sleeping after every batch to simulate a slow
+// consumer
+fn subscriber(
+    name: &'static str,
+    addr: std::net::SocketAddr,
+    delay: Duration,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let reader = IpcStreamReader::try_new(TcpStream::connect(addr).unwrap()).unwrap();
+
+        let mut values = Vec::new();
+        for batch in reader {
+            let batch = batch.unwrap();
+            let column = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            values.push(column.value(0));
+            thread::sleep(delay);
+        }
+
+        println!("{} received the batches {:?}", name, values);
+    })
+}
+
+fn main() {
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "sequence",
+        DataType::Int32,
+        false,
+    )]));
+
+    // The producer sends one batch every 50ms to the broadcaster. The
+    // batches are large enough to fill the buffers of the connections
+    let producer_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let producer_addr = producer_listener.local_addr().unwrap();
+    let producer_schema = schema.clone();
+    let producer = thread::spawn(move || {
+        let mut writer = IpcStreamWriter::connect(producer_addr, &producer_schema).unwrap();
+        for i in 0..20 {
+            let batch = RecordBatch::try_new(
+                producer_schema.clone(),
+                vec![Arc::new(Int32Array::from(vec![i; 1_000_000]))],
+            )
+            .unwrap();
+
+            writer.write(&batch).unwrap();
+            thread::sleep(Duration::from_millis(50));
+        }
+        writer.finish().unwrap();
+    });
+
+    // The subscribers connect to a second port. The slow subscriber skips
+    // the batches it can't keep up with
+    let subscribers_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let subscribers_addr = subscribers_listener.local_addr().unwrap();
+    let broadcaster = Broadcaster::new(schema).with_queue_size(2);
+    broadcaster.listen(subscribers_listener, BackpressurePolicy::Drop);
+
+    let mut subscribers = vec![
+        subscriber(
+            "Fast subscriber",
+            subscribers_addr,
+            Duration::from_millis(0),
+        ),
+        subscriber(
+            "Slow subscriber",
+            subscribers_addr,
+            Duration::from_millis(200),
+        ),
+    ];
+
+    let (stream, _) = producer_listener.accept().unwrap();
+    let reader = IpcStreamReader::try_new(stream).unwrap();
+
+    // A late subscriber joins while the stream is being broadcast
+    let late_addr = subscribers_addr;
+    let late = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(500));
+        subscriber("Late subscriber", late_addr, Duration::from_millis(0))
+            .join()
+            .unwrap();
+    });
+
+    let batches = broadcaster.forward(reader).unwrap();
+    println!(
+        "Broadcast {} batches, {} dropped by slow subscribers",
+        batches,
+        broadcaster.dropped()
+    );
+
+    subscribers
+        .drain(..)
+        .for_each(|handle| handle.join().unwrap());
+    late.join().unwrap();
+    producer.join().unwrap();
+}
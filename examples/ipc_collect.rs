@@ -0,0 +1,57 @@
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{collect_stream, IpcStreamReader, IpcStreamWriter};
+
+fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let producer = thread::spawn(move || {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]);
+        let mut writer = IpcStreamWriter::connect(addr, &schema).unwrap();
+
+        for i in 0..5 {
+            let ids = (i * 1000..(i + 1) * 1000).collect::<Vec<i64>>();
+            let names = ids
+                .iter()
+                .map(|id| Some(format!("name {}", id)))
+                .collect::<StringArray>();
+
+            let batch = RecordBatch::try_new(
+                Arc::new(schema.clone()),
+                vec![Arc::new(Int64Array::from(ids)), Arc::new(names)],
+            )
+            .unwrap();
+            writer.write(&batch).unwrap();
+        }
+        writer.finish().unwrap();
+    });
+
+    let (stream, _) = listener.accept().unwrap();
+    let reader = IpcStreamReader::try_new(stream).unwrap();
+
+    // The progress is reported while the batches arrive and the table is
+    // returned once the stream ends
+    let table = collect_stream(reader, |batch, progress| {
+        println!(
+            "Batch with {} rows. Total: {} rows, {} bytes",
+            batch.num_rows(),
+            progress.rows,
+            progress.bytes
+        );
+    })
+    .unwrap();
+
+    println!("Table with {} rows", table.rows());
+    producer.join().unwrap();
+}
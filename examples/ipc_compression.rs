@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{CompressionCodec, IpcStreamReader, IpcStreamWriter};
+
+fn main() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("index", DataType::Int32, false),
+        Field::new("word", DataType::Utf8, false),
+    ]));
+
+    // String columns with repeated values compress really well
+    let words = ["arrow", "parquet", "flight", "datafusion"];
+    let a = Int32Array::from((0..10_000).collect::<Vec<i32>>());
+    let b = StringArray::from(
+        (0..10_000)
+            .map(|i| words[i % words.len()])
+            .collect::<Vec<&str>>(),
+    );
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(a), Arc::new(b)]).unwrap();
+
+    for compression in &[
+        None,
+        Some(CompressionCodec::Lz4Frame),
+        Some(CompressionCodec::Zstd),
+    ] {
+        let mut writer =
+            IpcStreamWriter::try_new_with_compression(Vec::new(), &schema, *compression).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let bytes = writer.into_inner();
+        println!("{:?}: {} bytes", compression, bytes.len());
+
+        // The reader finds the codec in the message metadata
+        let mut reader = IpcStreamReader::try_new(bytes.as_slice()).unwrap();
+        let read_batch = reader.next().unwrap().unwrap();
+
+        assert_eq!(batch.column(0).data(), read_batch.column(0).data());
+        assert_eq!(batch.column(1).data(), read_batch.column(1).data());
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, DictionaryArray, Int32Builder, StringArray, StringBuilder,
+        StringDictionaryBuilder,
+    },
+    datatypes::{DataType, Field, Int32Type, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{IpcStreamReader, IpcStreamWriter};
+
+// Creates a dictionary array with the words. If a previous dictionary is
+// given, the new dictionary starts with its values and the new words are
+// added at the end of it
+fn dictionary_array(words: &[&str], previous: Option<&StringArray>) -> DictionaryArray<Int32Type> {
+    let keys_builder = Int32Builder::new(words.len());
+    let mut builder = match previous {
+        Some(values) => StringDictionaryBuilder::new_with_dictionary(keys_builder, values).unwrap(),
+        None => StringDictionaryBuilder::new(keys_builder, StringBuilder::new(10)),
+    };
+
+    for word in words {
+        builder.append(word).unwrap();
+    }
+
+    builder.finish()
+}
+
+// Collects the values stored in the dictionary of a dictionary array
+fn dictionary_values(array: &ArrayRef) -> Vec<String> {
+    let array = array
+        .as_any()
+        .downcast_ref::<DictionaryArray<Int32Type>>()
+        .unwrap();
+
+    let values = array.values();
+    let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+
+    (0..values.len())
+        .map(|i| values.value(i).to_string())
+        .collect()
+}
+
+fn main() {
+    let data_type = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+    let schema = Arc::new(Schema::new(vec![Field::new_dict(
+        "country", data_type, false, 1, false,
+    )]));
+
+    // The first batch sends the complete dictionary
+    let first = dictionary_array(&["mexico", "peru", "mexico", "chile"], None);
+    let first_values = first.values();
+    let first_values = first_values.as_any().downcast_ref::<StringArray>().unwrap();
+
+    // The second batch only adds "brazil" at the end of the dictionary, so
+    // only that value is sent as a delta dictionary batch
+    let second = dictionary_array(&["peru", "brazil", "chile"], Some(first_values));
+
+    // The third batch uses a different dictionary, which replaces the one
+    // sent before
+    let third = dictionary_array(&["spain", "france", "spain"], None);
+
+    let batches = vec![first, second, third]
+        .into_iter()
+        .map(|array| RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap())
+        .collect::<Vec<RecordBatch>>();
+
+    let mut writer = IpcStreamWriter::try_new(Vec::new(), &schema).unwrap();
+    for batch in batches.iter() {
+        writer.write(batch).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let bytes = writer.into_inner();
+    println!("Stream size: {} bytes", bytes.len());
+
+    let reader = IpcStreamReader::try_new(bytes.as_slice()).unwrap();
+    for (original, batch) in batches.iter().zip(reader) {
+        let batch = batch.unwrap();
+        let values = dictionary_values(batch.column(0));
+        println!("Dictionary: {:?}", values);
+
+        assert_eq!(original.column(0).data(), batch.column(0).data());
+        assert_eq!(dictionary_values(original.column(0)), values);
+    }
+}
@@ -0,0 +1,61 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::sync::Arc;
+
+use arrow::{
+    array::{DictionaryArray, Int32Array, StringArray},
+    datatypes::{DataType, Field, Int8Type, Schema},
+    ipc::writer::StreamWriter,
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::dissect_stream;
+
+// Describes the messages of an Arrow stream. Without arguments the stream
+// is created with arrow's StreamWriter, otherwise the file is read, like a
+// capture made with the ipc_replay example:
+//
+//   cargo run --example ipc_dissect -- capture.arrows
+fn main() {
+    if let Some(path) = std::env::args().nth(1) {
+        let file = BufReader::new(File::open(path).unwrap());
+        dissect_stream(file, io::stdout()).unwrap();
+        return;
+    }
+
+    let dictionary: DictionaryArray<Int8Type> = vec!["a", "b", "a", "c"].into_iter().collect();
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new_dict(
+            "category",
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+            false,
+            0,
+            false,
+        ),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.clone()),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+            Arc::new(StringArray::from(vec![
+                Some("one"),
+                None,
+                Some("three"),
+                None,
+            ])),
+            Arc::new(dictionary),
+        ],
+    )
+    .unwrap();
+
+    let mut bytes = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new(&mut bytes, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+    }
+
+    dissect_stream(bytes.as_slice(), io::stdout()).unwrap();
+}
@@ -0,0 +1,26 @@
+use std::io::Read;
+use std::net::TcpListener;
+
+use arrow_guide::ipc::IpcFileReader;
+
+fn main() {
+    let listener = TcpListener::bind("127.0.0.1:8000").unwrap();
+
+    for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+
+        // The whole file has to be received before it can be read
+        let mut bytes = Vec::new();
+        stream.read_to_end(&mut bytes).unwrap();
+
+        let mut reader = IpcFileReader::from_bytes(bytes).unwrap();
+        println!("{:?}", reader.schema());
+        println!("Batches in file: {}", reader.num_batches());
+
+        // Using the footer to read the batches in any order
+        for index in (0..reader.num_batches()).rev() {
+            let batch = reader.read_batch(index).unwrap();
+            println!("Batch {}: {:?}", index, batch);
+        }
+    }
+}
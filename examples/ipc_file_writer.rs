@@ -0,0 +1,41 @@
+use std::io::Write;
+use std::{net::TcpStream, sync::Arc};
+
+use arrow::{
+    array::{Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::ipc_file_to_bytes;
+
+fn main() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("index", DataType::Int32, false),
+        Field::new("word", DataType::Utf8, false),
+    ]));
+
+    let words = [
+        vec!["one", "two", "three"],
+        vec!["four", "five", "six"],
+        vec!["seven", "eight", "nine"],
+    ];
+
+    let batches = words
+        .iter()
+        .enumerate()
+        .map(|(i, words)| {
+            let start = (i * words.len()) as i32;
+            let a = Int32Array::from((start..start + words.len() as i32).collect::<Vec<i32>>());
+            let b = StringArray::from(words.clone());
+
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(a), Arc::new(b)]).unwrap()
+        })
+        .collect::<Vec<RecordBatch>>();
+
+    // The file format needs to seek to the footer, so the complete file
+    // is created in memory and then sent through the socket
+    let bytes = ipc_file_to_bytes(&schema, &batches).unwrap();
+
+    let mut stream = TcpStream::connect("127.0.0.1:8000").unwrap();
+    stream.write_all(&bytes).unwrap();
+}
@@ -0,0 +1,44 @@
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use arrow::{
+    array::Int32Array,
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{AckStreamReader, AckStreamWriter};
+
+fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Slow consumer. It acknowledges the batches every two batches
+    let consumer = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let reader = AckStreamReader::try_new(stream, 2).unwrap();
+
+        for batch in reader {
+            let batch = batch.unwrap();
+            println!("Consumed batch with {} rows", batch.num_rows());
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+
+    let schema = Schema::new(vec![Field::new("index", DataType::Int32, false)]);
+    let values = Int32Array::from((0..1000).collect::<Vec<i32>>());
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(values)]).unwrap();
+
+    // The producer can't get more than four batches ahead of the consumer
+    let stream = TcpStream::connect(addr).unwrap();
+    let mut writer = AckStreamWriter::try_new(stream, &schema, 4).unwrap();
+    for i in 0..10 {
+        writer.write(&batch).unwrap();
+        println!("Sent batch {}, {} pending", i, writer.pending());
+    }
+    writer.finish_and_wait().unwrap();
+    println!("All the batches were acknowledged");
+
+    consumer.join().unwrap();
+}
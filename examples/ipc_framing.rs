@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    ipc::{self, reader::StreamReader},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{IpcStreamWriter, MessageDeframer, MessageFramer};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("index", DataType::Int32, false),
+        Field::new("word", DataType::Utf8, false),
+    ]);
+
+    let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+    let b = StringArray::from(vec!["one", "two", "three", "four", "five"]);
+    let batch =
+        RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a), Arc::new(b)]).unwrap();
+
+    let mut writer = IpcStreamWriter::try_new(Vec::new(), &schema).unwrap();
+    writer.write(&batch).unwrap();
+    writer.write(&batch).unwrap();
+    writer.finish().unwrap();
+    let stream = writer.into_inner();
+
+    // Each message in the stream is made of a flatbuffer with its metadata
+    // and a body with the buffers of the arrays
+    let mut deframer = MessageDeframer::new(stream.as_slice());
+    let mut framer = MessageFramer::new(Vec::new());
+    while let Some(message) = deframer.read_message().unwrap() {
+        let header = ipc::root_as_message(&message.metadata).unwrap();
+        println!(
+            "{:?} message with {} bytes of metadata and {} bytes of body",
+            header.header_type(),
+            message.metadata.len(),
+            message.body.len()
+        );
+
+        let written = framer
+            .write_message(&message.metadata, &message.body)
+            .unwrap();
+        println!("Framed message with {} bytes", written);
+    }
+    framer.write_end().unwrap();
+
+    // Framing the messages again produces the same stream
+    let framed = framer.into_inner();
+    assert_eq!(stream, framed);
+
+    let reader = StreamReader::try_new(framed.as_slice()).unwrap();
+    for batch in reader {
+        println!("{:?}", batch.unwrap().num_rows());
+    }
+}
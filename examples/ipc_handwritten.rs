@@ -0,0 +1,70 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow::{
+    array::{BooleanArray, Float64Array, Int32Array, StringArray, UInt8Array},
+    datatypes::{DataType, Field, Schema},
+    ipc::reader::StreamReader,
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{dissect_stream, write_stream_by_hand};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("flag", DataType::Boolean, true),
+        Field::new("small", DataType::UInt8, false),
+        Field::new("value", DataType::Float64, true),
+        Field::new("name", DataType::Utf8, true),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.clone()),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])),
+            Arc::new(BooleanArray::from(vec![
+                Some(true),
+                None,
+                Some(false),
+                Some(true),
+                None,
+            ])),
+            Arc::new(UInt8Array::from(vec![10, 20, 30, 40, 50])),
+            Arc::new(Float64Array::from(vec![
+                Some(1.5),
+                Some(2.5),
+                None,
+                Some(4.5),
+                Some(5.5),
+            ])),
+            Arc::new(StringArray::from(vec![
+                Some("one"),
+                None,
+                Some("three"),
+                Some("four"),
+                Some(""),
+            ])),
+        ],
+    )
+    .unwrap();
+
+    // The messages are built with flatbuffers directly, without any of
+    // arrow's writers
+    let mut bytes = Vec::new();
+    write_stream_by_hand(&mut bytes, &schema, &[batch.clone(), batch.clone()]).unwrap();
+    dissect_stream(bytes.as_slice(), std::io::stdout()).unwrap();
+
+    // arrow's reader accepts the stream and returns the same batches
+    let reader = StreamReader::try_new(Cursor::new(bytes)).unwrap();
+    for read in reader {
+        let read = read.unwrap();
+        for i in 0..batch.num_columns() {
+            assert_eq!(read.column(i).data(), batch.column(i).data());
+        }
+        println!(
+            "StreamReader read {} rows and {} columns",
+            read.num_rows(),
+            read.num_columns()
+        );
+    }
+}
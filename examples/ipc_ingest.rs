@@ -0,0 +1,18 @@
+use arrow_guide::ipc::IngestServer;
+
+fn main() {
+    // Every stream sent to the server is stored as parquet files in the
+    // data/ingest directory. The ipc_writer example can be used as client
+    let server = IngestServer::bind("127.0.0.1:8000", "data/ingest")
+        .unwrap()
+        .with_max_file_size(1024 * 1024);
+
+    for spooled in server.start().unwrap() {
+        println!(
+            "{} rows from {} written to {}",
+            spooled.rows,
+            spooled.peer,
+            spooled.path.display()
+        );
+    }
+}
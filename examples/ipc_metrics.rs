@@ -0,0 +1,65 @@
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Float64Array, Int32Array},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{IpcStreamReader, IpcStreamWriter, StreamMetrics};
+
+fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // The same metrics are shared by the writer and the reader. A service
+    // would export the snapshot to its monitoring system
+    let metrics = Arc::new(StreamMetrics::new());
+
+    let writer_metrics = metrics.clone();
+    let producer = thread::spawn(move || {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("value", DataType::Float64, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int32Array::from((0..10_000).collect::<Vec<i32>>())),
+                Arc::new(Float64Array::from(vec![1.0; 10_000])),
+            ],
+        )
+        .unwrap();
+
+        let mut writer = IpcStreamWriter::connect(addr, &schema)
+            .unwrap()
+            .with_observer(writer_metrics);
+        for _ in 0..50 {
+            writer.write(&batch).unwrap();
+        }
+        writer.finish().unwrap();
+    });
+
+    let (stream, _) = listener.accept().unwrap();
+    let reader = IpcStreamReader::try_new(stream)
+        .unwrap()
+        .with_observer(metrics.clone());
+    for batch in reader {
+        batch.unwrap();
+    }
+    producer.join().unwrap();
+
+    let snapshot = metrics.snapshot();
+    println!(
+        "Sent {} batches, {} rows, {} bytes in {:?}",
+        snapshot.batches_sent, snapshot.rows_sent, snapshot.bytes_sent, snapshot.send_time
+    );
+    println!(
+        "Received {} batches, {} rows, {} bytes in {:?}",
+        snapshot.batches_received,
+        snapshot.rows_received,
+        snapshot.bytes_received,
+        snapshot.receive_time
+    );
+}
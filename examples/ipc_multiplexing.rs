@@ -0,0 +1,80 @@
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Float64Array, Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{MuxReader, MuxWriter};
+
+fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Two tables with different schemas are sent through the same
+    // connection, each one in its own channel
+    let producer = thread::spawn(move || {
+        let words_schema = Schema::new(vec![
+            Field::new("index", DataType::Int32, false),
+            Field::new("word", DataType::Utf8, false),
+        ]);
+        let words = RecordBatch::try_new(
+            Arc::new(words_schema.clone()),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec!["one", "two", "three"])),
+            ],
+        )
+        .unwrap();
+
+        let prices_schema = Schema::new(vec![Field::new("price", DataType::Float64, false)]);
+        let prices = RecordBatch::try_new(
+            Arc::new(prices_schema.clone()),
+            vec![Arc::new(Float64Array::from(vec![1.5, 2.5]))],
+        )
+        .unwrap();
+
+        let mut writer = MuxWriter::new(TcpStream::connect(addr).unwrap());
+        writer.open_channel(1, &words_schema).unwrap();
+        writer.open_channel(2, &prices_schema).unwrap();
+
+        for _ in 0..3 {
+            writer.write(1, &words).unwrap();
+            writer.write(2, &prices).unwrap();
+        }
+
+        writer.close_channel(1).unwrap();
+        writer.close_channel(2).unwrap();
+    });
+
+    let (stream, _) = listener.accept().unwrap();
+
+    // Every channel is consumed in its own thread
+    let consumers = MuxReader::new(stream)
+        .map(|reader| {
+            thread::spawn(move || {
+                println!(
+                    "Channel {} opened with {:?}",
+                    reader.channel(),
+                    reader.schema()
+                );
+
+                let channel = reader.channel();
+                for batch in reader {
+                    println!(
+                        "Channel {} received {} rows",
+                        channel,
+                        batch.unwrap().num_rows()
+                    );
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for consumer in consumers {
+        consumer.join().unwrap();
+    }
+    producer.join().unwrap();
+}
@@ -0,0 +1,65 @@
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Float64Array, Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{request_filtered, FilterOp, Predicate, ProjectedStreamWriter};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("score", DataType::Float64, false),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.clone()),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3])),
+            Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            Arc::new(Float64Array::from(vec![0.5, 1.5, 2.5])),
+        ],
+    )
+    .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // The producer waits for the request of the consumer before sending the
+    // batches. Only the requested columns of the rows that match the filter
+    // go through the connection
+    let producer = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = ProjectedStreamWriter::accept(stream, &schema).unwrap();
+        println!(
+            "Consumer requested the columns {:?} where {}",
+            writer.projection(),
+            writer.filter().unwrap()
+        );
+
+        for _ in 0..3 {
+            writer.write(&batch).unwrap();
+        }
+        writer.finish().unwrap();
+    });
+
+    let stream = TcpStream::connect(addr).unwrap();
+    let predicate = Predicate::new("name", FilterOp::NotEq, "b");
+    let reader = request_filtered(stream, &["score", "id"], &predicate).unwrap();
+    println!("Projected schema: {:?}", reader.schema());
+
+    for batch in reader {
+        let batch = batch.unwrap();
+        println!(
+            "Received {} rows with {} columns",
+            batch.num_rows(),
+            batch.num_columns()
+        );
+    }
+
+    producer.join().unwrap();
+}
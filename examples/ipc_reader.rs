@@ -1,25 +1,23 @@
-use std::net::{TcpListener, TcpStream};
-
-use arrow::ipc::reader::StreamReader;
+use arrow_guide::ipc::{IpcServer, ServerEvent};
 
 fn main() {
-    let listener = TcpListener::bind("127.0.0.1:8000").unwrap();
-
-    for stream in listener.incoming() {
-        let stream = stream.unwrap();
-
-        handle_connection(stream);
-    }
-}
-
-fn handle_connection(stream: TcpStream) {
-    let ipc_reader = StreamReader::try_new(stream).unwrap();
-    println!("{:?}", ipc_reader.schema());
-    println!("{:?}", ipc_reader.schema().metadata());
+    // Every client is read in its own thread, so a second writer doesn't
+    // have to wait until the first one finishes
+    let server = IpcServer::bind("127.0.0.1:8000").unwrap();
 
-    for batch in ipc_reader {
-        let batch = batch.unwrap();
-        println!("{:?}", batch);
-        println!("{:?}", batch.schema().metadata());
+    for event in server.start_with_events() {
+        match event {
+            ServerEvent::Batch(received) => {
+                println!("Batch from {}", received.peer);
+                println!("{:?}", received.batch.schema());
+                println!("{:?}", received.batch);
+                println!("{:?}", received.batch.schema().metadata());
+            }
+            ServerEvent::Closed { peer, result } => match result {
+                Ok(()) => println!("{} finished its stream", peer),
+                Err(err) => println!("{} disconnected. {}", peer, err),
+            },
+            ServerEvent::AcceptFailed(err) => println!("Couldn't accept a client. {}", err),
+        }
     }
 }
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use arrow::{
+    array::{Float64Array, Int32Array},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{RedisBatchSink, RedisBatchSource};
+
+// Needs a Redis server listening in the default port
+const URL: &str = "redis://127.0.0.1/";
+const KEY: &str = "arrow_guide:readings";
+
+fn main() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("sensor", DataType::Int32, false),
+        Field::new("reading", DataType::Float64, true),
+    ]));
+
+    // The producer can be any other process with a connection to the
+    // same server
+    let producer = thread::spawn(move || {
+        let mut sink = RedisBatchSink::connect(URL, KEY).unwrap();
+        for round in 0..3 {
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![1, 2, 3])),
+                    Arc::new(Float64Array::from(vec![
+                        Some(20.5 + round as f64),
+                        None,
+                        Some(19.0 - round as f64),
+                    ])),
+                ],
+            )
+            .unwrap();
+            let queued = sink.push(&batch).unwrap();
+            println!("Pushed batch {}, {} in the queue", round, queued);
+        }
+    });
+
+    let mut source = RedisBatchSource::connect(URL, KEY).unwrap();
+
+    // Waits for the first batch and leaves the rest in the queue
+    let first = source.pop_timeout(Duration::from_secs(5)).unwrap();
+    println!("First batch: {:?}", first.map(|batch| batch.num_rows()));
+    producer.join().unwrap();
+
+    // The batches that are left are collected in a Table
+    let table = source.drain().unwrap().unwrap();
+    println!("Table with {} rows", table.rows());
+    for value in table.column_iterator(1) {
+        println!("{:?}", value.unwrap());
+    }
+    println!("Queue is empty: {}", source.is_empty().unwrap());
+}
@@ -0,0 +1,53 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::net::{TcpListener, TcpStream};
+
+use arrow_guide::ipc::{record_stream, replay_stream};
+
+// Records a live Arrow stream to a file and replays it later, which helps
+// debugging a consumer without running the producer that feeds it.
+//
+// Wait for a producer on port 8000 and save its stream:
+//
+//   cargo run --example ipc_replay -- record 127.0.0.1:8000 capture.arrows
+//
+// Send the recording to a consumer listening on port 8000, at 10 batches
+// per second:
+//
+//   cargo run --example ipc_replay -- replay capture.arrows 127.0.0.1:8000 --rate 10
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    match args.as_slice() {
+        ["record", addr, path] => {
+            let listener = TcpListener::bind(addr).unwrap();
+            println!("Waiting for a producer on {}", addr);
+
+            let (stream, peer) = listener.accept().unwrap();
+            let file = BufWriter::new(File::create(path).unwrap());
+            let bytes = record_stream(stream, file).unwrap();
+
+            println!("Recorded {} bytes from {} to {}", bytes, peer, path);
+        }
+        ["replay", path, addr, rest @ ..] => {
+            let rate = match rest {
+                [] => None,
+                ["--rate", rate] => Some(rate.parse::<f64>().expect("Invalid rate")),
+                _ => panic!("Unknown arguments {:?}", rest),
+            };
+
+            let file = BufReader::new(File::open(path).unwrap());
+            let stream = TcpStream::connect(addr).unwrap();
+            match replay_stream(file, stream, rate) {
+                Ok(batches) => println!("Replayed {} batches from {} to {}", batches, path, addr),
+                Err(err) => eprintln!("Replay failed: {}", err),
+            }
+        }
+        _ => {
+            eprintln!("Usage:");
+            eprintln!("  ipc_replay record <addr> <file>");
+            eprintln!("  ipc_replay replay <file> <addr> [--rate <batches per second>]");
+        }
+    }
+}
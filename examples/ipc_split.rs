@@ -0,0 +1,49 @@
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Float64Array, Int64Array},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{IpcStreamReader, IpcStreamWriter, SplittingStreamWriter};
+
+fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let producer = thread::spawn(move || {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("value", DataType::Float64, false),
+        ]);
+
+        let rows = 1_000_000;
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int64Array::from((0..rows).collect::<Vec<i64>>())),
+                Arc::new(Float64Array::from(
+                    (0..rows).map(|i| i as f64 / 2.0).collect::<Vec<f64>>(),
+                )),
+            ],
+        )
+        .unwrap();
+
+        // The batch uses around 16MB. It is sent in batches of 1MB at most
+        let writer = IpcStreamWriter::connect(addr, &schema).unwrap();
+        let mut writer = SplittingStreamWriter::from(writer).with_max_bytes(1024 * 1024);
+
+        let sent = writer.write(&batch).unwrap();
+        println!("Batch of {} rows sent in {} batches", rows, sent);
+        writer.finish().unwrap();
+    });
+
+    let (stream, _) = listener.accept().unwrap();
+    for batch in IpcStreamReader::try_new(stream).unwrap() {
+        println!("Received {} rows", batch.unwrap().num_rows());
+    }
+
+    producer.join().unwrap();
+}
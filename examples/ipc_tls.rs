@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{tls_client_config, tls_server_config, IpcServer, IpcStreamWriter};
+
+// The example expects a certificate authority and a certificate for
+// localhost signed by it in the data/tls directory. They can be created
+// with openssl:
+//
+//   openssl req -x509 -newkey rsa:2048 -nodes -days 365 -subj "/CN=arrow guide ca" \
+//       -keyout ca.key -out ca.pem
+//   openssl req -newkey rsa:2048 -nodes -subj "/CN=localhost" \
+//       -keyout server.key -out server.csr
+//   openssl x509 -req -in server.csr -CA ca.pem -CAkey ca.key -CAcreateserial -days 365 \
+//       -extfile <(printf "subjectAltName=DNS:localhost") -out server.pem
+fn main() {
+    let server_config = tls_server_config("data/tls/server.pem", "data/tls/server.key").unwrap();
+    let server = IpcServer::bind_tls("127.0.0.1:8443", server_config).unwrap();
+    let batches = server.start();
+
+    let producer = thread::spawn(|| {
+        let schema = Schema::new(vec![
+            Field::new("index", DataType::Int32, false),
+            Field::new("word", DataType::Utf8, false),
+        ]);
+
+        let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+        let b = StringArray::from(vec!["one", "two", "three", "four", "five"]);
+
+        let batch =
+            RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a), Arc::new(b)]).unwrap();
+
+        // The client only trusts the certificates signed by the example
+        // certificate authority
+        let client_config = tls_client_config("data/tls/ca.pem").unwrap();
+        let mut writer =
+            IpcStreamWriter::connect_tls("127.0.0.1:8443", "localhost", &client_config, &schema)
+                .unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+    });
+
+    for received in batches.iter().take(2) {
+        println!("Encrypted batch from {}", received.peer);
+        println!("{:?}", received.batch);
+    }
+
+    producer.join().unwrap();
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::{IpcServer, IpcStreamWriter};
+
+const SOCKET_PATH: &str = "/tmp/arrow_guide.sock";
+
+fn main() {
+    // The socket file is left behind when a previous server is stopped
+    let _ = std::fs::remove_file(SOCKET_PATH);
+    let server = IpcServer::bind_unix(SOCKET_PATH).unwrap();
+    let batches = server.start();
+
+    // The producer runs in the same host, so it doesn't need to go
+    // through the TCP loopback
+    let producer = thread::spawn(|| {
+        let schema = Schema::new(vec![
+            Field::new("index", DataType::Int32, false),
+            Field::new("word", DataType::Utf8, false),
+        ]);
+
+        let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+        let b = StringArray::from(vec!["one", "two", "three", "four", "five"]);
+
+        let batch =
+            RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a), Arc::new(b)]).unwrap();
+
+        let mut writer = IpcStreamWriter::connect_unix(SOCKET_PATH, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+    });
+
+    for received in batches.iter().take(2) {
+        println!("Batch from {}", received.peer);
+        println!("{:?}", received.batch);
+    }
+
+    producer.join().unwrap();
+    std::fs::remove_file(SOCKET_PATH).unwrap();
+}
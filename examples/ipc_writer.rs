@@ -1,12 +1,12 @@
 use std::collections::HashMap;
-use std::{net::TcpStream, sync::Arc};
+use std::sync::Arc;
 
 use arrow::{
     array::{Int32Array, StringArray},
     datatypes::{DataType, Field, Schema},
-    ipc::writer::StreamWriter,
     record_batch::RecordBatch,
 };
+use arrow_guide::ipc::ResilientStreamWriter;
 
 fn main() {
     let mut schema_metadata: HashMap<String, String> = HashMap::new();
@@ -26,9 +26,12 @@ fn main() {
     let batch =
         RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a), Arc::new(b)]).unwrap();
 
-    let stream = TcpStream::connect("127.0.0.1:8000").unwrap();
+    // If the reader drops the connection the writer connects again and
+    // sends the batches that weren't sent in a new stream
+    let mut writer = ResilientStreamWriter::new("127.0.0.1:8000", &schema)
+        .unwrap()
+        .with_max_buffered_batches(100);
 
-    let mut writer = StreamWriter::try_new(stream, &schema).unwrap();
     writer.write(&batch).unwrap();
     writer.write(&batch).unwrap();
     writer.write(&batch).unwrap();
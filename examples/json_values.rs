@@ -0,0 +1,82 @@
+use arrow::datatypes::{DataType, DateUnit, Field, Schema};
+use arrow_guide::Table;
+use serde_json::{json, Map, Value};
+
+fn main() {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::UInt32, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("joined", DataType::Date32(DateUnit::Day), true),
+        Field::new(
+            "address",
+            DataType::Struct(vec![
+                Field::new("city", DataType::Utf8, false),
+                Field::new("zip", DataType::Utf8, true),
+            ]),
+            true,
+        ),
+        Field::new(
+            "scores",
+            DataType::List(Box::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ),
+    ]);
+
+    // The body of a request with a list of users. The missing keys are
+    // null and the ones that aren't in the schema are ignored
+    let body = json!([
+        {
+            "id": 1,
+            "name": "Ada",
+            "joined": "2021-02-01",
+            "address": {"city": "London", "zip": "N1"},
+            "scores": [9.5, 7.0]
+        },
+        {
+            "id": 2,
+            "address": null,
+            "scores": [null, 3.25],
+            "session": "ignored"
+        },
+        {
+            "id": 3,
+            "name": "Grace",
+            "address": {"city": "New York"},
+            "scores": []
+        }
+    ]);
+    let rows = objects(body);
+
+    let table = Table::from_json_values(schema.clone(), &rows).unwrap();
+    for batch in table.data() {
+        for column in batch.columns() {
+            println!("{:?}", column);
+        }
+    }
+
+    // The rows come back as objects, ready to be rendered or returned
+    for row in table.to_json_values().unwrap() {
+        println!("{}", Value::Object(row));
+    }
+
+    // Values of the wrong type and nulls in fields that aren't nullable
+    // are errors
+    for body in [json!([{"id": "4"}]), json!([{"name": "Alan"}])].iter() {
+        if let Err(err) = Table::from_json_values(schema.clone(), &objects(body.clone())) {
+            println!("{}", err);
+        }
+    }
+}
+
+fn objects(body: Value) -> Vec<Map<String, Value>> {
+    match body {
+        Value::Array(rows) => rows
+            .into_iter()
+            .filter_map(|row| match row {
+                Value::Object(row) => Some(row),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,95 @@
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        ArrayRef, Float64Array, Int32Array, Int64Array, StringArray, TimestampMillisecondArray,
+    },
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    ipc::IpcFileReader,
+    pandas::{PandasMetadata, PANDAS_METADATA_KEY},
+    Table,
+};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+
+// Metadata of a DataFrame written by pyarrow without an index, which pandas
+// replaces with a RangeIndex
+const FROM_PANDAS: &str = r#"{"index_columns": [{"kind": "range", "name": null, "start": 0, "stop": 3, "step": 1}], "column_indexes": [{"name": null, "field_name": null, "pandas_type": "unicode", "numpy_type": "object", "metadata": {"encoding": "UTF-8"}}], "columns": [{"name": "city", "field_name": "city", "pandas_type": "unicode", "numpy_type": "object", "metadata": null}, {"name": "visits", "field_name": "visits", "pandas_type": "int64", "numpy_type": "Int64", "metadata": null}], "creator": {"library": "pyarrow", "version": "3.0.0"}, "pandas_version": "1.2.1"}"#;
+
+fn main() {
+    let timezone = Some("Europe/Berlin".to_string());
+    let schema = Schema::new(vec![
+        Field::new("station", DataType::Utf8, false),
+        Field::new("temperature", DataType::Float64, true),
+        Field::new("readings", DataType::Int32, true),
+        Field::new(
+            "at",
+            DataType::Timestamp(TimeUnit::Millisecond, timezone.clone()),
+            false,
+        ),
+        Field::new("id", DataType::Int64, false),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec!["north", "south", "east"])),
+        Arc::new(Float64Array::from(vec![Some(3.5), None, Some(8.0)])),
+        Arc::new(Int32Array::from(vec![Some(12), Some(7), None])),
+        Arc::new(TimestampMillisecondArray::from_vec(
+            vec![1_612_137_600_000, 1_612_137_600_000, 1_612_141_200_000],
+            timezone,
+        )),
+        Arc::new(Int64Array::from(vec![10, 20, 30])),
+    ];
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), columns).unwrap();
+
+    // The id becomes the index of the DataFrame, and the readings, which
+    // can be null, an Int32 column instead of a float one
+    let table = Table::new(schema, vec![batch])
+        .with_pandas_metadata(&["id"])
+        .unwrap();
+    println!("{}", table.schema().metadata()[PANDAS_METADATA_KEY]);
+
+    let parquet_path = std::env::temp_dir().join("pandas_metadata.parquet");
+    table.to_parquet(&parquet_path);
+    let ipc_path = std::env::temp_dir().join("pandas_metadata.arrow");
+    table.to_ipc_file(&ipc_path).unwrap();
+
+    // pandas looks for the key in the metadata of the parquet file
+    let reader = SerializedFileReader::new(File::open(&parquet_path).unwrap()).unwrap();
+    let keys = reader
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .iter()
+        .flatten()
+        .map(|pair| pair.key.clone())
+        .collect::<Vec<_>>();
+    println!("Keys of the parquet file: {:?}", keys);
+
+    // The metadata comes back when the files are read
+    let from_parquet = Table::read_parquet(&parquet_path, 1024);
+    let metadata = from_parquet.pandas_metadata().unwrap().unwrap();
+    println!("Index of the parquet file: {:?}", metadata.index_fields());
+    for column in &metadata.columns {
+        println!("{}: {}", column.field_name, column.numpy_type);
+    }
+
+    let reader = IpcFileReader::try_new(File::open(&ipc_path).unwrap()).unwrap();
+    let metadata = PandasMetadata::from_schema(&reader.schema())
+        .unwrap()
+        .unwrap();
+    println!("Index of the IPC file: {:?}", metadata.index_fields());
+
+    // Files written by pandas can have a RangeIndex, which isn't a column
+    let metadata = PandasMetadata::parse(FROM_PANDAS).unwrap();
+    println!("{:?}", metadata.index_columns);
+    println!("{:?}", metadata.column("visits"));
+
+    // The index has to be a column of the table
+    let table = Table::read_parquet(&parquet_path, 1024);
+    if let Err(err) = table.with_pandas_metadata(&["station_id"]) {
+        println!("{}", err);
+    }
+}
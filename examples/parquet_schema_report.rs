@@ -0,0 +1,69 @@
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, LargeStringArray, TimestampMillisecondArray, UInt32Array},
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    record_batch::RecordBatch,
+};
+use arrow_guide::{parquet_schema::explain_schema, Table};
+use parquet::{
+    file::{
+        properties::WriterProperties,
+        writer::{FileWriter, SerializedFileWriter},
+    },
+    schema::parser::parse_message_type,
+};
+
+// Schema of a file written by another tool, with types that don't have
+// an exact arrow equivalent
+const LEGACY_SCHEMA: &str = "
+message legacy {
+    required int96 created;
+    required int32 visits (UINT_32);
+    optional fixed_len_byte_array(12) session (INTERVAL);
+    optional int64 updated (TIMESTAMP_MILLIS);
+    optional binary status (ENUM);
+    optional group tags (LIST) {
+        repeated group list {
+            optional binary element (UTF8);
+        }
+    }
+}
+";
+
+fn main() {
+    // The file only needs the schema, so it's written without rows
+    let legacy = std::env::temp_dir().join("parquet_schema_legacy.parquet");
+    let schema = Arc::new(parse_message_type(LEGACY_SCHEMA).unwrap());
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut writer =
+        SerializedFileWriter::new(File::create(&legacy).unwrap(), schema, properties).unwrap();
+    writer.close().unwrap();
+    println!("{}", explain_schema(&legacy).unwrap());
+
+    // A file written from arrow stores its arrow schema, so the time zone
+    // and the large strings come back when it's read
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::UInt32, false),
+        Field::new(
+            "at",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("+01:00".to_string())),
+            true,
+        ),
+        Field::new("note", DataType::LargeUtf8, true),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt32Array::from(vec![1, 2])),
+        Arc::new(TimestampMillisecondArray::from_opt_vec(
+            vec![Some(1_612_137_600_000), None],
+            Some("+01:00".to_string()),
+        )),
+        Arc::new(LargeStringArray::from(vec!["first", "second"])),
+    ];
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), columns).unwrap();
+
+    let from_arrow = std::env::temp_dir().join("parquet_schema_arrow.parquet");
+    Table::new(schema, vec![batch]).to_parquet(&from_arrow);
+    println!("{}", explain_schema(&from_arrow).unwrap());
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use arrow_guide::{protobuf::ProtobufBatchBuilder, validate::validate_array_data};
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, FileDescriptorSet, MessageOptions,
+};
+
+// The messages prost generates for this proto file:
+//
+// syntax = "proto3";
+// package telemetry;
+//
+// message Location {
+//   float lat = 1;
+//   float lon = 2;
+// }
+//
+// message Reading {
+//   enum Unit { CELSIUS = 0; FAHRENHEIT = 1; }
+//   string sensor = 1;
+//   double value = 2;
+//   Unit unit = 3;
+//   Location location = 4;
+//   repeated sint32 deltas = 5;
+//   map<string, string> tags = 6;
+// }
+#[derive(Clone, PartialEq, prost::Message)]
+struct Location {
+    #[prost(float, tag = "1")]
+    lat: f32,
+    #[prost(float, tag = "2")]
+    lon: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+enum Unit {
+    Celsius = 0,
+    Fahrenheit = 1,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct Reading {
+    #[prost(string, tag = "1")]
+    sensor: String,
+    #[prost(double, tag = "2")]
+    value: f64,
+    #[prost(enumeration = "Unit", tag = "3")]
+    unit: i32,
+    #[prost(message, optional, tag = "4")]
+    location: Option<Location>,
+    #[prost(sint32, repeated, tag = "5")]
+    deltas: Vec<i32>,
+    #[prost(map = "string, string", tag = "6")]
+    tags: HashMap<String, String>,
+}
+
+fn main() {
+    let builder = ProtobufBatchBuilder::try_new(&descriptors(), "telemetry.Reading", 2).unwrap();
+    println!("{:#?}", builder.schema());
+
+    let readings = [
+        Reading {
+            sensor: "roof".to_string(),
+            value: 21.5,
+            unit: Unit::Celsius as i32,
+            location: Some(Location {
+                lat: 52.52,
+                lon: 13.40,
+            }),
+            deltas: vec![1, -2, 3],
+            tags: vec![("floor".to_string(), "5".to_string())]
+                .into_iter()
+                .collect(),
+        },
+        // The fields that aren't set take their default value, except the
+        // message, which is null
+        Reading {
+            sensor: "cellar".to_string(),
+            ..Reading::default()
+        },
+        Reading {
+            sensor: "garden".to_string(),
+            value: 70.1,
+            unit: Unit::Fahrenheit as i32,
+            ..Reading::default()
+        },
+    ];
+
+    // The messages are converted from their bytes, as a gRPC service would
+    // receive them
+    let messages = readings.iter().map(|reading| {
+        let mut bytes = Vec::new();
+        prost::Message::encode(reading, &mut bytes).unwrap();
+        bytes
+    });
+    for batch in builder.read(messages) {
+        let batch = batch.unwrap();
+        println!("Batch with {} rows", batch.num_rows());
+        for column in batch.columns() {
+            validate_array_data(column.data_ref()).unwrap();
+            println!("{:?}", column);
+        }
+    }
+
+    // Bytes that aren't a message of the type are an error
+    let mut builder =
+        ProtobufBatchBuilder::try_new(&descriptors(), "telemetry.Reading", 2).unwrap();
+    println!("{}", builder.append(&[0x0a, 0x05, b'r']).unwrap_err());
+}
+
+// The descriptors are usually written by protoc or prost-build, and read
+// with FileDescriptorSet::decode. Here they are created by hand
+fn descriptors() -> FileDescriptorSet {
+    let location = DescriptorProto {
+        name: Some("Location".to_string()),
+        field: vec![
+            field("lat", 1, Type::Float, Label::Optional, None),
+            field("lon", 2, Type::Float, Label::Optional, None),
+        ],
+        ..Default::default()
+    };
+
+    let unit = EnumDescriptorProto {
+        name: Some("Unit".to_string()),
+        value: ["CELSIUS", "FAHRENHEIT"]
+            .iter()
+            .enumerate()
+            .map(|(number, name)| EnumValueDescriptorProto {
+                name: Some(name.to_string()),
+                number: Some(number as i32),
+                options: None,
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    // A map is a repeated message with the key and the value
+    let tags_entry = DescriptorProto {
+        name: Some("TagsEntry".to_string()),
+        field: vec![
+            field("key", 1, Type::String, Label::Optional, None),
+            field("value", 2, Type::String, Label::Optional, None),
+        ],
+        options: Some(MessageOptions {
+            map_entry: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let reading = DescriptorProto {
+        name: Some("Reading".to_string()),
+        field: vec![
+            field("sensor", 1, Type::String, Label::Optional, None),
+            field("value", 2, Type::Double, Label::Optional, None),
+            field(
+                "unit",
+                3,
+                Type::Enum,
+                Label::Optional,
+                Some(".telemetry.Reading.Unit"),
+            ),
+            field(
+                "location",
+                4,
+                Type::Message,
+                Label::Optional,
+                Some(".telemetry.Location"),
+            ),
+            field("deltas", 5, Type::Sint32, Label::Repeated, None),
+            field(
+                "tags",
+                6,
+                Type::Message,
+                Label::Repeated,
+                Some(".telemetry.Reading.TagsEntry"),
+            ),
+        ],
+        nested_type: vec![tags_entry],
+        enum_type: vec![unit],
+        ..Default::default()
+    };
+
+    FileDescriptorSet {
+        file: vec![FileDescriptorProto {
+            name: Some("telemetry.proto".to_string()),
+            package: Some("telemetry".to_string()),
+            message_type: vec![location, reading],
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        }],
+    }
+}
+
+fn field(
+    name: &str,
+    number: i32,
+    field_type: Type,
+    label: Label,
+    type_name: Option<&str>,
+) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(label as i32),
+        r#type: Some(field_type as i32),
+        type_name: type_name.map(str::to_string),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::Table;
+
+// Schema of the Delta table, with the date as partition column
+const METADATA: &str = r#"{"metaData":{"id":"readings","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"station\",\"type\":\"string\",\"nullable\":true,\"metadata\":{}},{\"name\":\"temperature\",\"type\":\"double\",\"nullable\":true,\"metadata\":{}},{\"name\":\"day\",\"type\":\"date\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":["day"],"configuration":{},"createdTime":1612137600000}}"#;
+const PROTOCOL: &str = r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#;
+
+fn main() {
+    // A Delta table written by hand: the data is in the parquet files of
+    // every partition and the commits say which files are active
+    let dir = std::env::temp_dir().join("reading_delta");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("_delta_log")).unwrap();
+
+    write_file(
+        &dir,
+        "day=2021-02-01/part-0.parquet",
+        &["north", "south"],
+        &[3.5, 8.0],
+    );
+    write_file(&dir, "day=2021-02-02/part-0.parquet", &["north"], &[2.0]);
+    write_file(
+        &dir,
+        "day=2021-02-02/part-1.parquet",
+        &["north", "south"],
+        &[2.25, 7.5],
+    );
+
+    // Version 0 adds the first two files and version 1 replaces the file
+    // of the second day, as an update of the table would do
+    let commits = [
+        vec![
+            PROTOCOL.to_string(),
+            METADATA.to_string(),
+            add("day=2021-02-01/part-0.parquet", "2021-02-01"),
+            add("day=2021-02-02/part-0.parquet", "2021-02-02"),
+        ],
+        vec![
+            r#"{"remove":{"path":"day=2021-02-02/part-0.parquet","dataChange":true}}"#.to_string(),
+            add("day=2021-02-02/part-1.parquet", "2021-02-02"),
+        ],
+    ];
+    for (version, actions) in commits.iter().enumerate() {
+        let commit = dir.join("_delta_log").join(format!("{:020}.json", version));
+        fs::write(commit, actions.join("\n")).unwrap();
+    }
+
+    for version in [Some(0), None].iter() {
+        let table = Table::read_delta(&dir, *version).unwrap();
+        println!("Version {:?} with {} rows", version, table.rows());
+        for batch in table.data() {
+            for column in batch.columns() {
+                println!("{:?}", column);
+            }
+        }
+    }
+
+    // The versions that don't exist are an error
+    if let Err(err) = Table::read_delta(&dir, Some(5)) {
+        println!("{}", err);
+    }
+}
+
+fn write_file(dir: &Path, path: &str, stations: &[&str], temperatures: &[f64]) {
+    let schema = Schema::new(vec![
+        Field::new("station", DataType::Utf8, true),
+        Field::new("temperature", DataType::Float64, true),
+    ]);
+    let station: ArrayRef = Arc::new(StringArray::from(stations.to_vec()));
+    let temperature: ArrayRef = Arc::new(Float64Array::from(temperatures.to_vec()));
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![station, temperature]).unwrap();
+
+    let path = dir.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    Table::new(schema, vec![batch]).to_parquet(path);
+}
+
+fn add(path: &str, day: &str) -> String {
+    format!(
+        r#"{{"add":{{"path":"{}","partitionValues":{{"day":"{}"}},"size":0,"modificationTime":0,"dataChange":true}}}}"#,
+        path, day
+    )
+}
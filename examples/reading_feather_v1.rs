@@ -0,0 +1,236 @@
+use std::fs::{self, File};
+
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow_guide::{ipc::write_ipc_file, Table};
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+
+// Types and metadata of feather's metadata.fbs used by the file
+const INT8: i8 = 1;
+const INT32: i8 = 3;
+const INT64: i8 = 4;
+const DOUBLE: i8 = 10;
+const UTF8: i8 = 11;
+const CATEGORY_METADATA: u8 = 1;
+const TIMESTAMP_METADATA: u8 = 2;
+const DATE_METADATA: u8 = 3;
+const MILLISECOND: i8 = 1;
+
+// Little endian bytes of the values of a type
+macro_rules! le_bytes {
+    ($type:ty; $($value:expr),*) => {
+        [$($value as $type),*]
+            .iter()
+            .flat_map(|value| value.to_le_bytes().to_vec())
+            .collect::<Vec<_>>()
+    };
+}
+
+fn main() {
+    // Nothing in Rust writes Feather v1 files, so this one is written by
+    // hand with the layout R and pandas used: the columns one after the
+    // other and the metadata at the end
+    let path = std::env::temp_dir().join("reading_feather_v1.feather");
+    fs::write(&path, feather_file()).unwrap();
+
+    let table = Table::read_feather_v1(&path).unwrap();
+    for field in table.schema().fields() {
+        println!("{}: {:?}", field.name(), field.data_type());
+    }
+    for batch in table.data() {
+        for column in batch.columns() {
+            println!("{:?}", column);
+        }
+    }
+
+    // Feather v2 files are Arrow IPC files, which have their own reader
+    let path = std::env::temp_dir().join("reading_feather_v2.feather");
+    let schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+    write_ipc_file(File::create(&path).unwrap(), &schema, &[]).unwrap();
+    if let Err(err) = Table::read_feather_v1(&path) {
+        println!("{}", err);
+    }
+}
+
+fn feather_file() -> Vec<u8> {
+    let mut data = b"FEA1\0\0\0\0".to_vec();
+
+    let id = write_array(&mut data, INT32, None, None, &le_bytes!(i32; 1, 2, 3));
+    let score = write_array(
+        &mut data,
+        DOUBLE,
+        Some(&[true, false, true]),
+        None,
+        &le_bytes!(f64; 0.5, 0.0, 2.25),
+    );
+    let name = write_array(
+        &mut data,
+        UTF8,
+        Some(&[true, true, false]),
+        Some(&[0, 3, 8, 8]),
+        b"Adaworld",
+    );
+    // A factor stores the keys of its values in the levels
+    let species = write_array(&mut data, INT8, None, None, &le_bytes!(i8; 0, 1, 0));
+    let levels = write_array(&mut data, UTF8, None, Some(&[0, 6, 15]), b"setosavirginica");
+    let observed = write_array(
+        &mut data,
+        INT64,
+        None,
+        None,
+        &le_bytes!(i64; 1_612_137_600_000, 1_612_141_200_000, 1_612_144_800_000),
+    );
+    let day = write_array(
+        &mut data,
+        INT32,
+        None,
+        None,
+        &le_bytes!(i32; 18659, 18660, 18661),
+    );
+
+    let mut fbb = FlatBufferBuilder::new();
+    let columns = [
+        column(&mut fbb, "id", &id, 0, None),
+        column(&mut fbb, "score", &score, 0, None),
+        column(&mut fbb, "name", &name, 0, None),
+        {
+            let levels = primitive_array(&mut fbb, &levels);
+            let start = fbb.start_table();
+            fbb.push_slot_always(4, levels);
+            let metadata = fbb.end_table(start).as_union_value();
+            column(
+                &mut fbb,
+                "species",
+                &species,
+                CATEGORY_METADATA,
+                Some(metadata),
+            )
+        },
+        {
+            let timezone = fbb.create_string("UTC");
+            let start = fbb.start_table();
+            fbb.push_slot::<i8>(4, MILLISECOND, 0);
+            fbb.push_slot_always(6, timezone);
+            let metadata = fbb.end_table(start).as_union_value();
+            column(
+                &mut fbb,
+                "observed",
+                &observed,
+                TIMESTAMP_METADATA,
+                Some(metadata),
+            )
+        },
+        {
+            let start = fbb.start_table();
+            let metadata = fbb.end_table(start).as_union_value();
+            column(&mut fbb, "day", &day, DATE_METADATA, Some(metadata))
+        },
+    ];
+    let columns = fbb.create_vector(&columns);
+
+    // CTable with the number of rows, the columns and the version
+    let start = fbb.start_table();
+    fbb.push_slot::<i64>(6, 3, 0);
+    fbb.push_slot_always(8, columns);
+    fbb.push_slot::<i32>(10, 2, 0);
+    let ctable = fbb.end_table(start);
+    fbb.finish_minimal(ctable);
+
+    let metadata = fbb.finished_data();
+    data.extend_from_slice(metadata);
+    data.extend_from_slice(&(metadata.len() as u32).to_le_bytes());
+    data.extend_from_slice(b"FEA1");
+    data
+}
+
+// Position of the buffers of an array in the file
+struct ArrayMeta {
+    type_: i8,
+    offset: i64,
+    length: i64,
+    null_count: i64,
+    total_bytes: i64,
+}
+
+fn write_array(
+    data: &mut Vec<u8>,
+    type_: i8,
+    validity: Option<&[bool]>,
+    offsets: Option<&[i32]>,
+    values: &[u8],
+) -> ArrayMeta {
+    let offset = data.len();
+    let mut null_count = 0;
+
+    if let Some(validity) = validity {
+        let mut bitmap = vec![0u8; validity.len().div_ceil(8)];
+        for (i, valid) in validity.iter().enumerate() {
+            bitmap[i / 8] |= (*valid as u8) << (i % 8);
+        }
+        write_padded(data, &bitmap);
+        null_count = validity.iter().filter(|valid| !**valid).count();
+    }
+    let length = match offsets {
+        Some(offsets) => {
+            let bytes = offsets
+                .iter()
+                .flat_map(|offset| offset.to_le_bytes().to_vec());
+            write_padded(data, &bytes.collect::<Vec<_>>());
+            offsets.len() - 1
+        }
+        None => values.len() / width(type_),
+    };
+    write_padded(data, values);
+
+    ArrayMeta {
+        type_,
+        offset: offset as i64,
+        length: length as i64,
+        null_count: null_count as i64,
+        total_bytes: (data.len() - offset) as i64,
+    }
+}
+
+fn write_padded(data: &mut Vec<u8>, bytes: &[u8]) {
+    data.extend_from_slice(bytes);
+    data.resize(data.len().div_ceil(8) * 8, 0);
+}
+
+fn width(type_: i8) -> usize {
+    match type_ {
+        INT8 => 1,
+        INT32 => 4,
+        _ => 8,
+    }
+}
+
+fn primitive_array<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    array: &ArrayMeta,
+) -> WIPOffset<flatbuffers::TableFinishedWIPOffset> {
+    let start = fbb.start_table();
+    fbb.push_slot::<i8>(4, array.type_, 0);
+    fbb.push_slot::<i64>(8, array.offset, 0);
+    fbb.push_slot::<i64>(10, array.length, 0);
+    fbb.push_slot::<i64>(12, array.null_count, 0);
+    fbb.push_slot::<i64>(14, array.total_bytes, 0);
+    fbb.end_table(start)
+}
+
+fn column<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    name: &str,
+    values: &ArrayMeta,
+    metadata_type: u8,
+    metadata: Option<WIPOffset<flatbuffers::UnionWIPOffset>>,
+) -> WIPOffset<flatbuffers::TableFinishedWIPOffset> {
+    let name = fbb.create_string(name);
+    let values = primitive_array(fbb, values);
+    let start = fbb.start_table();
+    fbb.push_slot_always(4, name);
+    fbb.push_slot_always(6, values);
+    fbb.push_slot::<u8>(8, metadata_type, 0);
+    if let Some(metadata) = metadata {
+        fbb.push_slot_always(10, metadata);
+    }
+    fbb.end_table(start)
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use arrow_guide::{serde_to_arrow::to_record_batch, validate::validate_array_data};
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+enum Medal {
+    Gold,
+    Silver,
+}
+
+#[derive(Serialize)]
+struct Country {
+    name: String,
+    code: [char; 3],
+}
+
+#[derive(Serialize)]
+struct Athlete {
+    name: &'static str,
+    age: u8,
+    height: Option<f32>,
+    country: Option<Country>,
+    medals: Vec<Medal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    coach: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct Scores {
+    scores: HashMap<String, u32>,
+}
+
+fn main() {
+    let athletes = vec![
+        Athlete {
+            name: "Biles",
+            age: 24,
+            height: Some(1.42),
+            country: Some(Country {
+                name: "United States".to_string(),
+                code: ['U', 'S', 'A'],
+            }),
+            medals: vec![Medal::Gold, Medal::Gold, Medal::Silver],
+            coach: Some("Boorman"),
+        },
+        Athlete {
+            name: "Andrade",
+            age: 22,
+            height: None,
+            country: None,
+            medals: vec![Medal::Silver],
+            coach: None,
+        },
+    ];
+
+    // The struct becomes the schema and its fields the columns. A field
+    // that's skipped in some rows is nullable
+    let batch = to_record_batch(&athletes).unwrap();
+    println!("{:#?}", batch.schema());
+    for column in batch.columns() {
+        validate_array_data(column.data_ref()).unwrap();
+        println!("{:?}", column);
+    }
+
+    // Maps aren't supported, and the schema needs at least one row
+    let scores = vec![Scores {
+        scores: HashMap::new(),
+    }];
+    println!("{}", to_record_batch(&scores).unwrap_err());
+    println!("{}", to_record_batch::<Athlete>(&[]).unwrap_err());
+}
@@ -0,0 +1,28 @@
+use std::env;
+use std::process::Command;
+
+// Generates the Rust code of the flatbuffer schema every time the .fbs file
+// changes, so the code used by the example always matches the schema. The
+// flatc binary is taken from the FLATC environment variable or the PATH,
+// and it has to generate code for the flatbuffers version in Cargo.toml
+// (flatc 2.0.0 works with flatbuffers 0.8)
+fn main() {
+    let schemas = ["src/ipc_schema.fbs", "src/ipc_schema_v1.fbs"];
+    for schema in &schemas {
+        println!("cargo:rerun-if-changed={}", schema);
+    }
+    println!("cargo:rerun-if-env-changed=FLATC");
+
+    let flatc = env::var("FLATC").unwrap_or_else(|_| "flatc".to_string());
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    let status = Command::new(&flatc)
+        .args(["--rust", "-o", &out_dir])
+        .args(schemas)
+        .status()
+        .unwrap_or_else(|err| panic!("Unable to run {}: {}", flatc, err));
+
+    if !status.success() {
+        panic!("{} failed to compile {:?}", flatc, schemas);
+    }
+}
@@ -0,0 +1,36 @@
+#![no_main]
+
+// Feeds arbitrary bytes to the reading path of the schema. A malformed
+// buffer has to be rejected by the verifier, and a buffer that passes it
+// has to be readable without panics. Run it from examples/simple_schema:
+//
+//   cargo fuzz run schema_buffer
+//
+// The verifier of flatbuffers 0.8 negates the offset of a table without
+// checking for overflow, so building with debug assertions (-a) stops at
+// buffers that the release build rejects as out of bounds
+use flatbuffers::FlatBufferBuilder;
+use libfuzzer_sys::fuzz_target;
+use simple_schema::convert::{from_arrow_schema, to_arrow_schema};
+use simple_schema::verify::verify_schema_buffer;
+
+fuzz_target!(|data: &[u8]| {
+    let fb = match verify_schema_buffer(data) {
+        Ok(fb) => fb,
+        Err(_) => return,
+    };
+
+    let schema = match to_arrow_schema(fb) {
+        Ok(schema) => schema,
+        Err(_) => return,
+    };
+
+    // Every schema read from a buffer can be written again and it is read
+    // back unchanged
+    let mut builder = FlatBufferBuilder::new();
+    let written = from_arrow_schema(&mut builder, &schema, fb.rows()).unwrap();
+    builder.finish(written, None);
+
+    let fb = verify_schema_buffer(builder.finished_data()).unwrap();
+    assert_eq!(to_arrow_schema(fb).unwrap(), schema);
+});
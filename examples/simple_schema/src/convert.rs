@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+
+use arrow::datatypes::{self, DataType, DateUnit};
+use arrow::error::{ArrowError, Result};
+use flatbuffers::{FlatBufferBuilder, ForwardsUOffset, UnionWIPOffset, Vector, WIPOffset};
+
+use crate::ipc_schema_generated::my_struct::schema::{
+    BasicType, Binary, BinaryArgs, Bool, BoolArgs, Date, DateArgs, DateUnit as FbDateUnit, Field,
+    FieldArgs, FloatingPoint, FloatingPointArgs, Int, IntArgs, KeyValue, KeyValueArgs, List,
+    ListArgs, Null, NullArgs, Precision, Schema, SchemaArgs, Struct_, Struct_Args, Type, Utf8,
+    Utf8Args,
+};
+use crate::version::{check_compatibility, MIN_READER_VERSION, SCHEMA_VERSION};
+
+type FbMetadata<'a> = Vector<'a, ForwardsUOffset<KeyValue<'a>>>;
+
+// Metadata key used for the description of a field before version 2
+const DESCRIPTION_KEY: &str = "description";
+
+/// Converts the flatbuffer Schema into an arrow Schema. The rows of the
+/// flatbuffer aren't part of the arrow Schema. Fails if the buffer was
+/// written by a version that this one can't read
+pub fn to_arrow_schema(fb: Schema) -> Result<datatypes::Schema> {
+    check_compatibility(fb)?;
+
+    let fields = fb
+        .fields()
+        .map(|fields| fields.iter().map(to_arrow_field).collect())
+        .unwrap_or_default();
+
+    let metadata = read_metadata(fb.custom_metadata()).into_iter().collect();
+    Ok(datatypes::Schema::new_with_metadata(fields, metadata))
+}
+
+fn to_arrow_field(fb: Field) -> datatypes::Field {
+    let children = fb
+        .children()
+        .map(|children| children.iter().map(to_arrow_field).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    // Buffers written before version 3 only have the basic type
+    let data_type = match fb.data_type_type() {
+        Type::NONE => basic_data_type(fb.dtype(), children),
+        _ => union_data_type(fb, children),
+    };
+
+    // Buffers written by version 1 only have the description in the
+    // metadata
+    let mut metadata = read_metadata(fb.custom_metadata());
+    if let Some(description) = fb.description() {
+        metadata
+            .entry(DESCRIPTION_KEY.to_string())
+            .or_insert_with(|| description.to_string());
+    }
+
+    let mut field = datatypes::Field::new(fb.name().unwrap_or_default(), data_type, fb.nullable());
+    field.set_metadata(Some(metadata));
+    field
+}
+
+// Types that can't be read are read as Null
+fn union_data_type(fb: Field, children: Vec<datatypes::Field>) -> DataType {
+    if let Some(int) = fb.data_type_as_int() {
+        return match (int.bit_width(), int.is_signed()) {
+            (8, true) => DataType::Int8,
+            (16, true) => DataType::Int16,
+            (32, true) => DataType::Int32,
+            (64, true) => DataType::Int64,
+            (8, false) => DataType::UInt8,
+            (16, false) => DataType::UInt16,
+            (32, false) => DataType::UInt32,
+            (64, false) => DataType::UInt64,
+            _ => DataType::Null,
+        };
+    }
+
+    if let Some(float) = fb.data_type_as_floating_point() {
+        return match float.precision() {
+            Precision::HALF => DataType::Float16,
+            Precision::SINGLE => DataType::Float32,
+            Precision::DOUBLE => DataType::Float64,
+            _ => DataType::Null,
+        };
+    }
+
+    if let Some(date) = fb.data_type_as_date() {
+        return match date.unit() {
+            FbDateUnit::DAY => DataType::Date32(DateUnit::Day),
+            FbDateUnit::MILLISECOND => DataType::Date64(DateUnit::Millisecond),
+            _ => DataType::Null,
+        };
+    }
+
+    match fb.data_type_type() {
+        Type::Bool => DataType::Boolean,
+        Type::Utf8 => DataType::Utf8,
+        Type::Binary => DataType::Binary,
+        Type::List => list_type(children),
+        Type::Struct_ => DataType::Struct(children),
+        _ => DataType::Null,
+    }
+}
+
+fn basic_data_type(dtype: BasicType, children: Vec<datatypes::Field>) -> DataType {
+    match dtype {
+        BasicType::Boolean => DataType::Boolean,
+        BasicType::Int8 => DataType::Int8,
+        BasicType::Int16 => DataType::Int16,
+        BasicType::Int32 => DataType::Int32,
+        BasicType::Int64 => DataType::Int64,
+        BasicType::UInt8 => DataType::UInt8,
+        BasicType::UInt16 => DataType::UInt16,
+        BasicType::UInt32 => DataType::UInt32,
+        BasicType::UInt64 => DataType::UInt64,
+        BasicType::Float32 => DataType::Float32,
+        BasicType::Float64 => DataType::Float64,
+        BasicType::Utf8 => DataType::Utf8,
+        BasicType::Binary => DataType::Binary,
+        BasicType::Date32 => DataType::Date32(DateUnit::Day),
+        BasicType::Date64 => DataType::Date64(DateUnit::Millisecond),
+        BasicType::List => list_type(children),
+        BasicType::Struct => DataType::Struct(children),
+        _ => DataType::Null,
+    }
+}
+
+// A list without its item field is read as a list of nulls
+fn list_type(children: Vec<datatypes::Field>) -> DataType {
+    DataType::List(Box::new(children.into_iter().next().unwrap_or_else(|| {
+        datatypes::Field::new("item", DataType::Null, true)
+    })))
+}
+
+fn read_metadata(fb: Option<FbMetadata>) -> BTreeMap<String, String> {
+    fb.into_iter()
+        .flatten()
+        .filter_map(|key_value| {
+            let key = key_value.key()?.to_string();
+            let value = key_value.value().unwrap_or_default().to_string();
+            Some((key, value))
+        })
+        .collect()
+}
+
+/// Writes an arrow Schema as the flatbuffer Schema. Fails if a field uses a
+/// type that can't be described by the flatbuffer
+pub fn from_arrow_schema<'a>(
+    builder: &mut FlatBufferBuilder<'a>,
+    schema: &datatypes::Schema,
+    rows: i64,
+) -> Result<WIPOffset<Schema<'a>>> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| from_arrow_field(builder, field))
+        .collect::<Result<Vec<_>>>()?;
+    let fields = builder.create_vector(&fields);
+
+    // The keys are sorted so the same schema always produces the same bytes
+    let metadata = schema
+        .metadata()
+        .iter()
+        .collect::<BTreeMap<&String, &String>>();
+    let metadata = write_metadata(builder, metadata);
+
+    Ok(Schema::create(
+        builder,
+        &SchemaArgs {
+            rows,
+            fields: Some(fields),
+            custom_metadata: metadata,
+            schema_version: SCHEMA_VERSION,
+            min_reader_version: MIN_READER_VERSION,
+        },
+    ))
+}
+
+fn from_arrow_field<'a>(
+    builder: &mut FlatBufferBuilder<'a>,
+    field: &datatypes::Field,
+) -> Result<WIPOffset<Field<'a>>> {
+    let children = match field.data_type() {
+        DataType::List(item) => vec![from_arrow_field(builder, item)?],
+        DataType::Struct(children) => children
+            .iter()
+            .map(|child| from_arrow_field(builder, child))
+            .collect::<Result<Vec<_>>>()?,
+        _ => vec![],
+    };
+    let (dtype, data_type_type, data_type) = write_type(builder, field)?;
+
+    let name = builder.create_string(field.name());
+    let children = builder.create_vector(&children);
+
+    // The description stays in the metadata too, where version 1 readers
+    // look for it
+    let description = field
+        .metadata()
+        .as_ref()
+        .and_then(|metadata| metadata.get(DESCRIPTION_KEY))
+        .map(|description| builder.create_string(description));
+
+    let metadata = field
+        .metadata()
+        .as_ref()
+        .map(|metadata| metadata.iter().collect::<BTreeMap<&String, &String>>())
+        .unwrap_or_default();
+    let metadata = write_metadata(builder, metadata);
+
+    Ok(Field::create(
+        builder,
+        &FieldArgs {
+            name: Some(name),
+            dtype,
+            nullable: field.is_nullable(),
+            children: Some(children),
+            custom_metadata: metadata,
+            description,
+            data_type_type,
+            data_type: Some(data_type),
+        },
+    ))
+}
+
+// The type is written in the union and as the basic type read by versions
+// older than 3. The types that didn't exist in those versions are written
+// as Null
+fn write_type<'a>(
+    builder: &mut FlatBufferBuilder<'a>,
+    field: &datatypes::Field,
+) -> Result<(BasicType, Type, WIPOffset<UnionWIPOffset>)> {
+    let written = match field.data_type() {
+        DataType::Null => (
+            BasicType::Null,
+            Type::Null,
+            Null::create(builder, &NullArgs {}).as_union_value(),
+        ),
+        DataType::Boolean => (
+            BasicType::Boolean,
+            Type::Bool,
+            Bool::create(builder, &BoolArgs {}).as_union_value(),
+        ),
+        DataType::Int8 => (BasicType::Int8, Type::Int, write_int(builder, 8, true)),
+        DataType::Int16 => (BasicType::Int16, Type::Int, write_int(builder, 16, true)),
+        DataType::Int32 => (BasicType::Int32, Type::Int, write_int(builder, 32, true)),
+        DataType::Int64 => (BasicType::Int64, Type::Int, write_int(builder, 64, true)),
+        DataType::UInt8 => (BasicType::UInt8, Type::Int, write_int(builder, 8, false)),
+        DataType::UInt16 => (BasicType::UInt16, Type::Int, write_int(builder, 16, false)),
+        DataType::UInt32 => (BasicType::UInt32, Type::Int, write_int(builder, 32, false)),
+        DataType::UInt64 => (BasicType::UInt64, Type::Int, write_int(builder, 64, false)),
+        DataType::Float16 => (
+            BasicType::Null,
+            Type::FloatingPoint,
+            write_float(builder, Precision::HALF),
+        ),
+        DataType::Float32 => (
+            BasicType::Float32,
+            Type::FloatingPoint,
+            write_float(builder, Precision::SINGLE),
+        ),
+        DataType::Float64 => (
+            BasicType::Float64,
+            Type::FloatingPoint,
+            write_float(builder, Precision::DOUBLE),
+        ),
+        DataType::Utf8 => (
+            BasicType::Utf8,
+            Type::Utf8,
+            Utf8::create(builder, &Utf8Args {}).as_union_value(),
+        ),
+        DataType::Binary => (
+            BasicType::Binary,
+            Type::Binary,
+            Binary::create(builder, &BinaryArgs {}).as_union_value(),
+        ),
+        DataType::Date32(DateUnit::Day) => (
+            BasicType::Date32,
+            Type::Date,
+            write_date(builder, FbDateUnit::DAY),
+        ),
+        DataType::Date64(DateUnit::Millisecond) => (
+            BasicType::Date64,
+            Type::Date,
+            write_date(builder, FbDateUnit::MILLISECOND),
+        ),
+        DataType::List(_) => (
+            BasicType::List,
+            Type::List,
+            List::create(builder, &ListArgs {}).as_union_value(),
+        ),
+        DataType::Struct(_) => (
+            BasicType::Struct,
+            Type::Struct_,
+            Struct_::create(builder, &Struct_Args {}).as_union_value(),
+        ),
+        other => {
+            return Err(ArrowError::SchemaError(format!(
+                "The type {:?} of the field {} can't be written",
+                other,
+                field.name()
+            )))
+        }
+    };
+
+    Ok(written)
+}
+
+fn write_int(
+    builder: &mut FlatBufferBuilder,
+    bit_width: i32,
+    is_signed: bool,
+) -> WIPOffset<UnionWIPOffset> {
+    Int::create(
+        builder,
+        &IntArgs {
+            bit_width,
+            is_signed,
+        },
+    )
+    .as_union_value()
+}
+
+fn write_float(builder: &mut FlatBufferBuilder, precision: Precision) -> WIPOffset<UnionWIPOffset> {
+    FloatingPoint::create(builder, &FloatingPointArgs { precision }).as_union_value()
+}
+
+fn write_date(builder: &mut FlatBufferBuilder, unit: FbDateUnit) -> WIPOffset<UnionWIPOffset> {
+    Date::create(builder, &DateArgs { unit }).as_union_value()
+}
+
+// Empty metadata isn't written, the same as arrow does
+fn write_metadata<'a>(
+    builder: &mut FlatBufferBuilder<'a>,
+    metadata: BTreeMap<&String, &String>,
+) -> Option<WIPOffset<FbMetadata<'a>>> {
+    if metadata.is_empty() {
+        return None;
+    }
+
+    let key_values = metadata
+        .into_iter()
+        .map(|(key, value)| {
+            let key = builder.create_string(key);
+            let value = builder.create_string(value);
+            KeyValue::create(
+                builder,
+                &KeyValueArgs {
+                    key: Some(key),
+                    value: Some(value),
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Some(builder.create_vector(&key_values))
+}
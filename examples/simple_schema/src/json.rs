@@ -0,0 +1,109 @@
+use arrow::error::{ArrowError, Result};
+use flatbuffers::{ForwardsUOffset, Vector};
+use serde_json::{json, Map, Value};
+
+use crate::ipc_schema_generated::my_struct::schema::{Field, KeyValue, Schema};
+use crate::verify::verify_schema_buffer;
+
+/// Reads a Schema buffer as JSON with the same layout as the flatbuffer.
+/// Every field of the tables is included, and the ones missing from the
+/// buffer are null, so the JSON shows exactly what the buffer contains
+pub fn schema_to_json(bytes: &[u8]) -> Result<Value> {
+    let fb = verify_schema_buffer(bytes)?;
+    Ok(schema_json(fb))
+}
+
+/// Checks that the JSON describes the same Schema as the buffer. The error
+/// has the path of the first value that doesn't match
+pub fn check_json_mirror(bytes: &[u8], json: &Value) -> Result<()> {
+    let expected = schema_to_json(bytes)?;
+    compare(&expected, json, "$")
+}
+
+fn schema_json(fb: Schema) -> Value {
+    json!({
+        "rows": fb.rows(),
+        "fields": fb.fields().map(|fields| fields.iter().map(field_json).collect::<Vec<_>>()),
+        "custom_metadata": metadata_json(fb.custom_metadata()),
+        "schema_version": fb.schema_version(),
+        "min_reader_version": fb.min_reader_version(),
+    })
+}
+
+fn field_json(fb: Field) -> Value {
+    json!({
+        "name": fb.name(),
+        "dtype": format!("{:?}", fb.dtype()),
+        "nullable": fb.nullable(),
+        "children": fb.children().map(|children| children.iter().map(field_json).collect::<Vec<_>>()),
+        "custom_metadata": metadata_json(fb.custom_metadata()),
+        "description": fb.description(),
+        "data_type": data_type_json(fb),
+    })
+}
+
+// The union is written as the name of the table with its fields
+fn data_type_json(fb: Field) -> Value {
+    let mut table = Map::new();
+    if let Some(int) = fb.data_type_as_int() {
+        table.insert("bit_width".to_string(), json!(int.bit_width()));
+        table.insert("is_signed".to_string(), json!(int.is_signed()));
+    } else if let Some(float) = fb.data_type_as_floating_point() {
+        table.insert(
+            "precision".to_string(),
+            json!(format!("{:?}", float.precision())),
+        );
+    } else if let Some(date) = fb.data_type_as_date() {
+        table.insert("unit".to_string(), json!(format!("{:?}", date.unit())));
+    } else if fb.data_type().is_none() {
+        return Value::Null;
+    }
+
+    let mut union = Map::new();
+    union.insert(format!("{:?}", fb.data_type_type()), Value::Object(table));
+    Value::Object(union)
+}
+
+fn metadata_json(fb: Option<Vector<ForwardsUOffset<KeyValue>>>) -> Value {
+    match fb {
+        Some(key_values) => key_values
+            .iter()
+            .map(|key_value| json!({ "key": key_value.key(), "value": key_value.value() }))
+            .collect(),
+        None => Value::Null,
+    }
+}
+
+fn compare(expected: &Value, actual: &Value, path: &str) -> Result<()> {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            if let Some(key) = actual.keys().find(|key| !expected.contains_key(*key)) {
+                return Err(mismatch(
+                    &format!("{}.{}", path, key),
+                    &Value::Null,
+                    &actual[key],
+                ));
+            }
+            for (key, value) in expected {
+                let actual = actual.get(key).unwrap_or(&Value::Null);
+                compare(value, actual, &format!("{}.{}", path, key))?;
+            }
+            Ok(())
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                compare(expected, actual, &format!("{}[{}]", path, i))?;
+            }
+            Ok(())
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(mismatch(path, expected, actual)),
+    }
+}
+
+fn mismatch(path: &str, expected: &Value, actual: &Value) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "The JSON doesn't match the buffer at {}: the buffer has {} and the JSON has {}",
+        path, expected, actual
+    ))
+}
@@ -0,0 +1,17 @@
+// Code generated by build.rs from ipc_schema.fbs
+#[allow(warnings, clippy::all)]
+pub mod ipc_schema_generated {
+    include!(concat!(env!("OUT_DIR"), "/ipc_schema_generated.rs"));
+}
+
+// First version of the schema, used to show the compatibility between
+// versions
+#[allow(warnings, clippy::all)]
+pub mod ipc_schema_v1_generated {
+    include!(concat!(env!("OUT_DIR"), "/ipc_schema_v1_generated.rs"));
+}
+
+pub mod convert;
+pub mod json;
+pub mod verify;
+pub mod version;
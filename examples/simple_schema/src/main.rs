@@ -1,52 +1,229 @@
-mod ipc_schema_generated;
-use ipc_schema_generated::my_struct::schema::{
-    root_as_schema, Field, FieldArgs, Schema, SchemaArgs,
-};
+use std::collections::{BTreeMap, HashMap};
+
+use arrow::datatypes::{DataType, DateUnit, Field as ArrowField, Schema as ArrowSchema};
+use flatbuffers::{FlatBufferBuilder, ForwardsUOffset, Vector};
+use simple_schema::convert::{from_arrow_schema, to_arrow_schema};
+use simple_schema::ipc_schema_generated::my_struct::schema::{Field, KeyValue, Schema, SchemaArgs};
+use simple_schema::ipc_schema_v1_generated::my_struct::schema_v1;
+use simple_schema::json::{check_json_mirror, schema_to_json};
+use simple_schema::verify::verify_schema_buffer;
+use simple_schema::version::{check_compatibility, Compatibility, SCHEMA_VERSION};
+
+fn print_metadata(metadata: Option<Vector<ForwardsUOffset<KeyValue>>>, depth: usize) {
+    for key_value in metadata.into_iter().flatten() {
+        println!(
+            "{}{:?} = {:?}",
+            "  ".repeat(depth),
+            key_value.key(),
+            key_value.value()
+        );
+    }
+}
+
+// The parameters of the type are read from the table in the union
+fn describe_type(field: Field) -> String {
+    if let Some(int) = field.data_type_as_int() {
+        return format!(
+            "Int(bit_width: {}, signed: {})",
+            int.bit_width(),
+            int.is_signed()
+        );
+    }
+    if let Some(float) = field.data_type_as_floating_point() {
+        return format!("FloatingPoint({:?})", float.precision());
+    }
+    if let Some(date) = field.data_type_as_date() {
+        return format!("Date({:?})", date.unit());
+    }
+    format!("{:?}", field.data_type_type())
+}
+
+fn print_field(field: Field, depth: usize) {
+    println!(
+        "{}{:?}: {}, nullable: {}",
+        "  ".repeat(depth),
+        field.name(),
+        describe_type(field),
+        field.nullable()
+    );
+    if let Some(description) = field.description() {
+        println!("{}description: {:?}", "  ".repeat(depth + 1), description);
+    }
+    print_metadata(field.custom_metadata(), depth + 1);
+
+    if let Some(children) = field.children() {
+        for child in children {
+            print_field(child, depth + 1);
+        }
+    }
+}
+
+fn arrow_schema() -> ArrowSchema {
+    let mut col_1 = ArrowField::new("col_1", DataType::Int32, false);
+    let mut description = BTreeMap::new();
+    description.insert("description".to_string(), "Row identifier".to_string());
+    col_1.set_metadata(Some(description));
+
+    // col_2 is a list of ints
+    let item = ArrowField::new("item", DataType::Int32, true);
+    let col_2 = ArrowField::new("col_2", DataType::List(Box::new(item)), true);
+
+    // col_3 is a struct with an int and a string
+    let a = ArrowField::new("a", DataType::Int32, false);
+    let b = ArrowField::new("b", DataType::Utf8, true);
+    let col_3 = ArrowField::new("col_3", DataType::Struct(vec![a, b]), false);
+
+    // The union keeps the parameters of the type, like the precision of a
+    // float or the unit of a date
+    let col_4 = ArrowField::new("col_4", DataType::Float64, true);
+    let col_5 = ArrowField::new("col_5", DataType::Date32(DateUnit::Day), true);
+
+    let mut metadata = HashMap::new();
+    metadata.insert("source".to_string(), "simple_schema".to_string());
+    metadata.insert("version".to_string(), "1".to_string());
+
+    ArrowSchema::new_with_metadata(vec![col_1, col_2, col_3, col_4, col_5], metadata)
+}
 
 fn main() {
-    let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(1024);
+    let schema = arrow_schema();
+
+    let mut builder = FlatBufferBuilder::new_with_capacity(1024);
+    let fb_schema = from_arrow_schema(&mut builder, &schema, 100).unwrap();
+
+    builder.finish(fb_schema, None);
+    let buf = builder.finished_data();
+
+    println!("{:?}", buf);
+
+    // Reading the data
+    let recovered_schema = verify_schema_buffer(buf).unwrap();
+    println!("{:?}", recovered_schema.rows());
+    print_metadata(recovered_schema.custom_metadata(), 0);
 
-    let field_1_name = builder.create_string("col_1");
-    let field_1_dtype = builder.create_string("int");
-    let field_1 = Field::create(
+    let recovered_fields = recovered_schema.fields().unwrap();
+    for f in recovered_fields {
+        print_field(f, 0);
+    }
+
+    // The flatbuffer describes the same schema used by arrow
+    let recovered_arrow = to_arrow_schema(recovered_schema).unwrap();
+    assert_eq!(recovered_arrow, schema);
+    println!("{:?}", recovered_arrow);
+
+    // The JSON mirror shows the same values as the buffer
+    let json = schema_to_json(buf).unwrap();
+    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    check_json_mirror(buf, &json).unwrap();
+
+    let mut changed = json.clone();
+    changed["fields"][1]["nullable"] = false.into();
+    println!("{}", check_json_mirror(buf, &changed).unwrap_err());
+
+    // Damaged buffers are rejected before reading them
+    let truncated = &buf[..buf.len() / 2];
+    println!("{}", verify_schema_buffer(truncated).unwrap_err());
+
+    let mut corrupted = buf.to_vec();
+    corrupted[0] = 0xff;
+    println!("{}", verify_schema_buffer(&corrupted).unwrap_err());
+
+    schema_evolution(buf);
+}
+
+// Buffer written by the first version of the schema, before the field
+// description and the versions were added
+fn version_1_buffer() -> Vec<u8> {
+    let mut builder = FlatBufferBuilder::new();
+
+    let key = builder.create_string("description");
+    let value = builder.create_string("Row identifier");
+    let description = schema_v1::KeyValue::create(
         &mut builder,
-        &FieldArgs {
-            name: Some(field_1_name),
-            dtype: Some(field_1_dtype),
+        &schema_v1::KeyValueArgs {
+            key: Some(key),
+            value: Some(value),
         },
     );
+    let metadata = builder.create_vector(&[description]);
 
-    let field_2_name = builder.create_string("col_2");
-    let field_2_dtype = builder.create_string("int");
-    let field_2 = Field::create(
+    let name = builder.create_string("id");
+    let field = schema_v1::Field::create(
         &mut builder,
-        &FieldArgs {
-            name: Some(field_2_name),
-            dtype: Some(field_2_dtype),
+        &schema_v1::FieldArgs {
+            name: Some(name),
+            dtype: schema_v1::Type::Int64,
+            nullable: false,
+            children: None,
+            custom_metadata: Some(metadata),
         },
     );
+    let fields = builder.create_vector(&[field]);
 
-    let fields = builder.create_vector(&[field_1, field_2]);
-    let schema = Schema::create(
+    let schema = schema_v1::Schema::create(
         &mut builder,
-        &SchemaArgs {
-            rows: 100,
+        &schema_v1::SchemaArgs {
+            rows: 10,
             fields: Some(fields),
+            custom_metadata: None,
         },
     );
 
     builder.finish(schema, None);
-    let buf = builder.finished_data();
+    builder.finished_data().to_vec()
+}
 
-    println!("{:?}", buf);
+fn schema_evolution(current_buf: &[u8]) {
+    println!("Schema version {}", SCHEMA_VERSION);
 
-    // Reading the data
-    let recovered_schema = root_as_schema(buf).unwrap();
-    println!("{:?}", recovered_schema.rows());
+    // Old buffers are read by the current code. The fields that didn't
+    // exist have their default values
+    let old_buf = version_1_buffer();
+    let old_schema = verify_schema_buffer(&old_buf).unwrap();
+    let compatibility = check_compatibility(old_schema).unwrap();
+    println!(
+        "Version 1 buffer: schema_version {}, {:?}",
+        old_schema.schema_version(),
+        compatibility
+    );
+    assert_eq!(compatibility, Compatibility::Older(1));
 
-    let recovered_fields = recovered_schema.fields().unwrap();
-    for f in recovered_fields {
-        println!("{:?}", f.name());
-        println!("{:?}", f.dtype());
+    let old_field = old_schema.fields().unwrap().get(0);
+    assert_eq!(old_field.description(), None);
+
+    // The description is still found in the metadata
+    let old_arrow = to_arrow_schema(old_schema).unwrap();
+    println!("{:?}", old_arrow);
+    assert_eq!(
+        old_arrow.field(0).metadata().as_ref().unwrap()["description"],
+        "Row identifier"
+    );
+
+    // New buffers are read by the old code, which ignores the fields it
+    // doesn't know
+    let schema = schema_v1::root_as_schema(current_buf).unwrap();
+    for field in schema.fields().unwrap() {
+        println!(
+            "Read by version 1: {:?}: {:?}, nullable: {}",
+            field.name(),
+            field.dtype(),
+            field.nullable()
+        );
     }
+
+    // A buffer that needs a newer reader is rejected
+    let mut builder = FlatBufferBuilder::new();
+    let future = Schema::create(
+        &mut builder,
+        &SchemaArgs {
+            schema_version: SCHEMA_VERSION + 1,
+            min_reader_version: SCHEMA_VERSION + 1,
+            ..Default::default()
+        },
+    );
+    builder.finish(future, None);
+
+    let future = verify_schema_buffer(builder.finished_data()).unwrap();
+    let err = to_arrow_schema(future).unwrap_err();
+    println!("{}", err);
 }
@@ -0,0 +1,25 @@
+use arrow::error::{ArrowError, Result};
+use flatbuffers::VerifierOptions;
+
+use crate::ipc_schema_generated::my_struct::schema::{root_as_schema_with_opts, Schema};
+
+// A schema is a small buffer, so the limits are much lower than the
+// flatbuffers defaults. The depth also bounds the recursion used to read
+// the nested fields
+fn verifier_options() -> VerifierOptions {
+    VerifierOptions {
+        max_depth: 32,
+        max_tables: 10_000,
+        max_apparent_size: 1 << 24,
+        ..VerifierOptions::default()
+    }
+}
+
+/// Verifies the bytes before reading them as a Schema. Every offset,
+/// vector, string and enum of the buffer is checked, so the accessors of
+/// the returned Schema never read outside the buffer or panic, even if the
+/// bytes came from an untrusted source
+pub fn verify_schema_buffer(bytes: &[u8]) -> Result<Schema<'_>> {
+    root_as_schema_with_opts(&verifier_options(), bytes)
+        .map_err(|err| ArrowError::ParseError(format!("Invalid schema buffer: {}", err)))
+}
@@ -0,0 +1,47 @@
+use std::cmp::Ordering;
+
+use arrow::error::{ArrowError, Result};
+
+use crate::ipc_schema_generated::my_struct::schema::Schema;
+
+/// Version of ipc_schema.fbs written by this code. It increases every time
+/// fields are added to the schema
+pub const SCHEMA_VERSION: u16 = 3;
+
+/// Oldest version that can read the buffers written by this code. The
+/// fields added after version 1 are optional and the basic type is still
+/// written, so version 1 can read the buffers ignoring the new fields
+pub const MIN_READER_VERSION: u16 = 1;
+
+/// How the version that wrote a buffer relates to this one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Written by this version
+    Current,
+    /// Written by an older version. The fields added afterwards are read
+    /// with their defaults
+    Older(u16),
+    /// Written by a newer version that this one can still read. The fields
+    /// it doesn't know are ignored
+    Newer(u16),
+}
+
+/// Checks if the buffer can be read by this version. A buffer is rejected
+/// when its writer requires a newer reader
+pub fn check_compatibility(fb: Schema) -> Result<Compatibility> {
+    let version = fb.schema_version();
+    if fb.min_reader_version() > SCHEMA_VERSION {
+        return Err(ArrowError::SchemaError(format!(
+            "The schema was written by version {} and needs version {} to be read, but this is version {}",
+            version,
+            fb.min_reader_version(),
+            SCHEMA_VERSION
+        )));
+    }
+
+    Ok(match version.cmp(&SCHEMA_VERSION) {
+        Ordering::Equal => Compatibility::Current,
+        Ordering::Less => Compatibility::Older(version),
+        Ordering::Greater => Compatibility::Newer(version),
+    })
+}
@@ -0,0 +1,89 @@
+// Exchanges IPC streams with Arrow JS in the browser. The example is
+// compiled to a WebAssembly module loaded by wasm_ipc.html, which reads
+// the stream written here as an Arrow JS table and sends a table written
+// by Arrow JS back to be described. It's built with
+//
+//     cargo build --release --example wasm_ipc --target wasm32-unknown-unknown \
+//         --no-default-features --features wasm
+//     cp target/wasm32-unknown-unknown/release/examples/wasm_ipc.wasm examples
+//
+// and the examples directory is served with any static file server
+use std::fmt::Write;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use arrow_guide::{
+    compute::aggregate,
+    ipc::{IpcStreamReader, IpcStreamWriter},
+    wasm::{from_js, to_js},
+};
+
+/// Writes a stream with the squares of 0 to n - 1 in batches of 100 rows
+#[no_mangle]
+pub extern "C" fn squares_stream(n: u32) -> *mut u8 {
+    to_js(write_squares(n))
+}
+
+/// Reads a stream written by Arrow JS with `tableToIPC(table, "stream")`
+/// and describes its batches and the sums of its numeric columns
+///
+/// # Safety
+///
+/// The bytes must have been allocated with arrow_guide_alloc
+#[no_mangle]
+pub unsafe extern "C" fn describe_stream(ptr: *const u8, len: usize) -> *mut u8 {
+    to_js(describe(from_js(ptr, len)).map(String::into_bytes))
+}
+
+fn write_squares(n: u32) -> Result<Vec<u8>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("label", DataType::Utf8, false),
+        Field::new("square", DataType::Float64, false),
+    ]));
+
+    let mut writer = IpcStreamWriter::try_new(Vec::new(), &schema)?;
+    let mut start = 0;
+    while start < n {
+        let end = (start + 100).min(n);
+        let labels: ArrayRef = Arc::new(
+            (start..end)
+                .map(|i| Some(format!("{}²", i)))
+                .collect::<StringArray>(),
+        );
+        let squares: ArrayRef = Arc::new(Float64Array::from(
+            (start..end).map(|i| (i * i) as f64).collect::<Vec<_>>(),
+        ));
+        let batch = RecordBatch::try_new(schema.clone(), vec![labels, squares])?;
+        writer.write(&batch)?;
+        start = end;
+    }
+    writer.finish()?;
+
+    Ok(writer.into_inner())
+}
+
+fn describe(bytes: &[u8]) -> Result<String> {
+    let reader = IpcStreamReader::try_new(bytes)?;
+    let schema = reader.schema();
+    let batches = reader.collect::<Result<Vec<_>>>()?;
+
+    let rows = batches.iter().map(RecordBatch::num_rows).sum::<usize>();
+    let mut description = format!("{} rows in {} batches\n", rows, batches.len());
+    for (i, field) in schema.fields().iter().enumerate() {
+        let _ = write!(description, "{}: {:?}", field.name(), field.data_type());
+        let sums = batches
+            .iter()
+            .map(|batch| aggregate::sum(batch.column(i)))
+            .collect::<Result<Vec<_>>>();
+        if let Ok(sums) = sums {
+            let _ = write!(description, ", sums of the batches {:?}", sums);
+        }
+        description.push('\n');
+    }
+    Ok(description)
+}
@@ -0,0 +1,12 @@
+use arrow_guide::ipc::{IpcStreamReader, WebSocketReader};
+
+fn main() {
+    // Reads the batches sent by the websocket_server example
+    let (socket, _) = tungstenite::connect("ws://127.0.0.1:9001").unwrap();
+    let reader = IpcStreamReader::try_new(WebSocketReader::new(socket)).unwrap();
+
+    println!("{:?}", reader.schema());
+    for batch in reader {
+        println!("{:?}", batch.unwrap());
+    }
+}
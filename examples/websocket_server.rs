@@ -0,0 +1,51 @@
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    array::{Int32Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_guide::ipc::WebSocketStreamWriter;
+
+// The stream can be read from a browser using Arrow JS. Every binary frame
+// contains a message, and the frames are read in order by the reader:
+//
+//   const socket = new WebSocket("ws://127.0.0.1:9001");
+//   socket.binaryType = "arraybuffer";
+//   const frames = [];
+//   socket.onmessage = (event) => frames.push(new Uint8Array(event.data));
+//   socket.onclose = () => {
+//     const table = Arrow.tableFromIPC(frames);
+//     console.log(table.toArray());
+//   };
+fn main() {
+    let server = TcpListener::bind("127.0.0.1:9001").unwrap();
+
+    for stream in server.incoming() {
+        let stream = stream.unwrap();
+
+        thread::spawn(move || {
+            let socket = tungstenite::accept(stream).unwrap();
+
+            let schema = Schema::new(vec![
+                Field::new("index", DataType::Int32, false),
+                Field::new("word", DataType::Utf8, false),
+            ]);
+
+            let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+            let b = StringArray::from(vec!["one", "two", "three", "four", "five"]);
+
+            let batch =
+                RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a), Arc::new(b)])
+                    .unwrap();
+
+            let mut writer = WebSocketStreamWriter::try_new(socket, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.write(&batch).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        });
+    }
+}
@@ -0,0 +1,78 @@
+// Helpers for the validity bitmaps of the arrays. A bitmap uses one bit per
+// row, starting from the least significant bit of the first byte, and a set
+// bit means that the row is valid
+use arrow::{
+    array::{Array, ArrayRef},
+    buffer::Buffer,
+    error::{ArrowError, Result},
+    util::bit_util,
+};
+
+/// Number of bits set in the `len` bits of the buffer starting at bit
+/// `offset`, which is the number of valid rows for a null buffer
+pub fn count_set_bits(buffer: &Buffer, offset: usize, len: usize) -> usize {
+    buffer.count_set_bits_offset(offset, len)
+}
+
+/// Returns true if no row of the array is null
+pub fn all_valid(array: &dyn Array) -> bool {
+    array.null_count() == 0
+}
+
+/// Returns true if at least one row of the array is null
+pub fn any_null(array: &dyn Array) -> bool {
+    array.null_count() > 0
+}
+
+/// Packs the flags in a bitmap, for example to use it as the null buffer of
+/// an ArrayData
+pub fn from_bools(bits: &[bool]) -> Buffer {
+    from_iter(bits.iter().copied())
+}
+
+/// Packs the flags returned by an iterator in a bitmap
+pub fn from_iter<I: IntoIterator<Item = bool>>(bits: I) -> Buffer {
+    let mut bytes = Vec::new();
+    for (i, bit) in bits.into_iter().enumerate() {
+        if i % 8 == 0 {
+            bytes.push(0);
+        }
+        if bit {
+            bit_util::set_bit(&mut bytes, i);
+        }
+    }
+    Buffer::from(bytes)
+}
+
+/// Unpacks `len` bits of the bitmap starting at bit `offset`
+pub fn to_bools(buffer: &Buffer, offset: usize, len: usize) -> Vec<bool> {
+    (offset..offset + len)
+        .map(|i| bit_util::get_bit(buffer.as_slice(), i))
+        .collect()
+}
+
+/// Null buffer of the rows that are valid in all the columns, as needed by
+/// kernels that combine several columns row by row. None is returned when
+/// no row is null, since arrays without nulls don't need a null buffer
+pub fn combine_validity(columns: &[ArrayRef]) -> Result<Option<Buffer>> {
+    let len = match columns.first() {
+        Some(column) => column.len(),
+        None => return Ok(None),
+    };
+
+    if let Some(column) = columns.iter().find(|column| column.len() != len) {
+        return Err(ArrowError::ComputeError(format!(
+            "Can't combine the validity of columns with {} and {} rows",
+            len,
+            column.len()
+        )));
+    }
+
+    if columns.iter().all(|column| all_valid(column.as_ref())) {
+        return Ok(None);
+    }
+
+    Ok(Some(from_iter(
+        (0..len).map(|i| columns.iter().all(|column| column.is_valid(i))),
+    )))
+}
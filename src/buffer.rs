@@ -0,0 +1,199 @@
+// Helpers for the buffers of the arrays. Buffers can take the memory of a
+// Vec instead of copying it: the memory of a Buffer is freed by arrow with
+// its own alignment unless it comes from the C data interface, where the
+// producer of the array frees it with a release callback. A Vec is handed
+// over as if it was a foreign array, so the callback drops the Vec when the
+// last Buffer using it is dropped
+use std::any::Any;
+use std::fmt;
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr::{self, NonNull};
+use std::sync::Arc;
+
+use arrow::{
+    array::ArrayData,
+    buffer::Buffer,
+    datatypes::{ArrowNativeType, DataType},
+    ffi::FFI_ArrowArray,
+    util::bit_util,
+};
+
+use crate::ffi::RawArray;
+
+// Alignment and padding recommended by the Arrow format
+const ARROW_ALIGNMENT: usize = 64;
+
+// Owner of the memory, boxed again so the private data is a thin pointer
+type Owner = Box<dyn Any + Send + Sync>;
+
+/// Creates a buffer that owns the memory of the Vec, without copying it.
+/// `Buffer::from` copies the bytes to a new allocation, which for large
+/// arrays costs as much as creating the values. The values keep the
+/// alignment of T instead of the 64 bytes arrow uses for its allocations,
+/// which is enough for all the kernels except the SIMD ones
+pub fn from_vec<T: ArrowNativeType>(values: Vec<T>) -> Buffer {
+    let len = values.len() * mem::size_of::<T>();
+    // The pointer of a Vec is never null, even if it's empty. Moving the
+    // Vec to the box doesn't move its values
+    let ptr = NonNull::new(values.as_ptr() as *mut u8).unwrap();
+    let owner: Owner = Box::new(values);
+
+    let array = RawArray {
+        release: Some(release_owner),
+        private_data: Box::into_raw(Box::new(owner)) as *mut c_void,
+        ..RawArray::empty()
+    };
+
+    // The fields of FFI_ArrowArray aren't public, so the struct is filled
+    // with the same layout and transmuted. The release callback frees the
+    // Vec once, when the last clone of the buffer drops the array. The
+    // array only holds the Vec, which is Send and Sync, so the Arc can be
+    // shared like any Buffer
+    #[allow(clippy::arc_with_non_send_sync)]
+    unsafe {
+        let array = mem::transmute::<RawArray, FFI_ArrowArray>(array);
+        Buffer::from_unowned(ptr, len, Arc::new(array))
+    }
+}
+
+// Drops the owner of the memory and marks the array as released, as the
+// C data interface requires
+unsafe extern "C" fn release_owner(array: *mut RawArray) {
+    let array = &mut *array;
+    if !array.private_data.is_null() {
+        drop(Box::from_raw(array.private_data as *mut Owner));
+        array.private_data = ptr::null_mut();
+    }
+    array.release = None;
+}
+
+/// Memory layout of one of the buffers of an array
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferLayout {
+    /// What the buffer holds, like `values` or `item.offsets` for the
+    /// offsets of a child called item
+    pub name: String,
+    /// Address of the first byte of the buffer
+    pub address: usize,
+    /// Largest power of two that divides the address, up to 64
+    pub alignment: usize,
+    /// Bytes used by the buffer
+    pub len: usize,
+    /// Bytes allocated for the buffer. It's 0 when the memory wasn't
+    /// allocated by arrow, like the buffers created with `from_vec`
+    pub capacity: usize,
+    /// Bytes needed after the buffer to reach a multiple of 64 bytes
+    pub padding: usize,
+}
+
+impl BufferLayout {
+    fn new(name: String, buffer: &Buffer) -> Self {
+        let address = buffer.as_ptr() as usize;
+        let alignment = match address {
+            0 => ARROW_ALIGNMENT,
+            address => (1 << address.trailing_zeros()).min(ARROW_ALIGNMENT),
+        };
+
+        Self {
+            name,
+            address,
+            alignment,
+            len: buffer.len(),
+            capacity: buffer.capacity(),
+            padding: bit_util::round_upto_multiple_of_64(buffer.len()) - buffer.len(),
+        }
+    }
+
+    /// Returns true if the buffer starts at a multiple of 64 bytes, as the
+    /// Arrow format recommends
+    pub fn is_aligned(&self) -> bool {
+        self.alignment == ARROW_ALIGNMENT
+    }
+
+    /// Returns true if the allocation covers the padding, so the buffer
+    /// can be read in blocks of 64 bytes
+    pub fn is_padded(&self) -> bool {
+        self.capacity >= self.len + self.padding
+    }
+}
+
+/// Layout of all the buffers of an array and its children
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferReport {
+    pub buffers: Vec<BufferLayout>,
+}
+
+impl fmt::Display for BufferReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for buffer in &self.buffers {
+            let capacity = match buffer.capacity {
+                0 => "unknown capacity".to_string(),
+                capacity => format!("{} allocated", capacity),
+            };
+            writeln!(
+                f,
+                "{}: {:#x}, aligned to {} bytes, {} bytes used of {}, {} bytes of padding{}",
+                buffer.name,
+                buffer.address,
+                buffer.alignment,
+                buffer.len,
+                capacity,
+                buffer.padding,
+                if buffer.is_padded() { "" } else { " missing" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports the alignment, the length, the capacity and the padding of the
+/// validity bitmap and the buffers of the data and of its children
+pub fn buffer_report(data: &ArrayData) -> BufferReport {
+    let mut buffers = Vec::new();
+    add_buffers(data, "", &mut buffers);
+    BufferReport { buffers }
+}
+
+fn add_buffers(data: &ArrayData, path: &str, buffers: &mut Vec<BufferLayout>) {
+    let name = |name: &str| match path.is_empty() {
+        true => name.to_string(),
+        false => format!("{}.{}", path, name),
+    };
+
+    if let Some(bitmap) = data.null_buffer() {
+        buffers.push(BufferLayout::new(name("validity"), bitmap));
+    }
+
+    let names: &[&str] = match data.data_type() {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary => {
+            &["offsets", "values"]
+        }
+        DataType::List(_) | DataType::LargeList(_) => &["offsets"],
+        DataType::Union(_) => &["type ids", "offsets"],
+        DataType::Dictionary(_, _) => &["keys"],
+        _ => &["values"],
+    };
+    for (i, buffer) in data.buffers().iter().enumerate() {
+        let buffer_name = names.get(i).copied().unwrap_or("values");
+        buffers.push(BufferLayout::new(name(buffer_name), buffer));
+    }
+
+    let child_names = match data.data_type() {
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) => {
+            vec![field.name().clone()]
+        }
+        DataType::Struct(fields) | DataType::Union(fields) => {
+            fields.iter().map(|field| field.name().clone()).collect()
+        }
+        DataType::Dictionary(_, _) => vec!["values".to_string()],
+        _ => Vec::new(),
+    };
+    for (i, child) in data.child_data().iter().enumerate() {
+        let child_name = child_names
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| format!("child {}", i));
+        add_buffers(child, &name(&child_name), buffers);
+    }
+}
@@ -0,0 +1,156 @@
+// Binary columns hold blobs like images or serialized messages. The blobs
+// are written one after the other in the values buffer, and the offsets
+// mark where every one of them starts. The builder here writes the bytes
+// straight into the values buffer, so a blob can be copied from a reader
+// without keeping it in memory twice
+use std::io::{self, Read, Write};
+
+use arrow::{
+    array::{ArrayData, BinaryOffsetSizeTrait, GenericBinaryArray},
+    buffer::Buffer,
+    datatypes::ToByteSlice,
+    error::{ArrowError, Result},
+};
+
+use crate::bitmap;
+
+/// Builder of Binary or LargeBinary arrays. Every value is written with
+/// `append_value`, `append_reader`, or by writing its bytes with the
+/// `Write` implementation and closing it with `finish_value`
+pub struct GenericBinaryStreamBuilder<O: BinaryOffsetSizeTrait> {
+    offsets: Vec<O>,
+    values: Vec<u8>,
+    validity: Vec<bool>,
+    // Bytes written since the last value was closed
+    pending: bool,
+}
+
+/// Builder of Binary arrays, whose values can't exceed 2GB in total
+pub type BinaryStreamBuilder = GenericBinaryStreamBuilder<i32>;
+/// Builder of LargeBinary arrays
+pub type LargeBinaryStreamBuilder = GenericBinaryStreamBuilder<i64>;
+
+impl<O: BinaryOffsetSizeTrait> Default for GenericBinaryStreamBuilder<O> {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl<O: BinaryOffsetSizeTrait> GenericBinaryStreamBuilder<O> {
+    /// Creates a builder with room for `capacity` values with a total of
+    /// `bytes` bytes
+    pub fn new(capacity: usize, bytes: usize) -> Self {
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(O::zero());
+        Self {
+            offsets,
+            values: Vec::with_capacity(bytes),
+            validity: Vec::with_capacity(capacity),
+            pending: false,
+        }
+    }
+
+    /// Number of values appended since the last `finish`
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_empty()
+    }
+
+    /// Appends a value with the bytes
+    pub fn append_value(&mut self, value: &[u8]) -> Result<()> {
+        self.check_pending()?;
+        self.values.extend_from_slice(value);
+        self.finish_value()
+    }
+
+    /// Appends a value with all the bytes of the reader, returning the
+    /// number of bytes read
+    pub fn append_reader<R: Read>(&mut self, mut reader: R) -> Result<u64> {
+        self.check_pending()?;
+        let read = io::copy(&mut reader, &mut self.values)?;
+        self.finish_value()?;
+        Ok(read)
+    }
+
+    /// Appends a null
+    pub fn append_null(&mut self) -> Result<()> {
+        self.check_pending()?;
+        self.offsets.push(self.offset()?);
+        self.validity.push(false);
+        Ok(())
+    }
+
+    /// Closes the value with the bytes written since the last value
+    pub fn finish_value(&mut self) -> Result<()> {
+        self.offsets.push(self.offset()?);
+        self.validity.push(true);
+        self.pending = false;
+        Ok(())
+    }
+
+    /// Builds the array with the values appended and empties the builder.
+    /// Bytes written without calling `finish_value` are an error, like in
+    /// the other append functions
+    pub fn finish(&mut self) -> Result<GenericBinaryArray<O>> {
+        self.check_pending()?;
+
+        let offsets = std::mem::replace(&mut self.offsets, vec![O::zero()]);
+        let values = std::mem::take(&mut self.values);
+        let validity = std::mem::take(&mut self.validity);
+
+        let mut builder = ArrayData::builder(O::DATA_TYPE)
+            .len(validity.len())
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .add_buffer(Buffer::from(values));
+        if validity.contains(&false) {
+            builder = builder.null_bit_buffer(bitmap::from_bools(&validity));
+        }
+        Ok(GenericBinaryArray::from(builder.build()))
+    }
+
+    // Binary columns use i32 offsets, so their values can't exceed 2GB
+    fn offset(&self) -> Result<O> {
+        O::from_usize(self.values.len()).ok_or_else(|| {
+            ArrowError::ComputeError("The values don't fit in a Binary column".to_string())
+        })
+    }
+
+    fn check_pending(&self) -> Result<()> {
+        match self.pending {
+            true => Err(ArrowError::InvalidArgumentError(
+                "There are bytes written without calling finish_value".to_string(),
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<O: BinaryOffsetSizeTrait> Write for GenericBinaryStreamBuilder<O> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending = true;
+        self.values.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Builds a Binary or LargeBinary array from byte slices, with None for the
+/// nulls. An error is returned if the values don't fit in the offsets
+pub fn binary_from_slices<O: BinaryOffsetSizeTrait>(
+    values: Vec<Option<&[u8]>>,
+) -> Result<GenericBinaryArray<O>> {
+    let bytes = values.iter().flatten().map(|value| value.len()).sum();
+    let mut builder = GenericBinaryStreamBuilder::<O>::new(values.len(), bytes);
+    for value in values {
+        match value {
+            Some(value) => builder.append_value(value)?,
+            None => builder.append_null()?,
+        }
+    }
+    builder.finish()
+}
@@ -0,0 +1,177 @@
+// Common interface of the builders. Code that produces values without
+// knowing the concrete builder, like a reader that converts rows to columns
+// or a function that returns a column of any type, appends ScalarValues to
+// a `dyn MutableColumn` and gets an ArrayRef back
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        ArrayRef, BooleanBuilder, Date32Builder, DurationMicrosecondBuilder,
+        DurationMillisecondBuilder, DurationNanosecondBuilder, DurationSecondBuilder,
+        Float32Builder, Float64Builder, Int16Builder, Int32Builder, Int64Builder, Int8Builder,
+        LargeStringBuilder, StringBuilder, Time64MicrosecondBuilder, Time64NanosecondBuilder,
+        UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder,
+    },
+    datatypes::{DataType, DateUnit, TimeUnit},
+    error::{ArrowError, Result},
+};
+
+use super::ScalarBuilder;
+use crate::ScalarValue;
+
+/// Builder of a column that takes ScalarValues. Values of a different type
+/// than the column are rejected with an error and nothing is appended
+pub trait MutableColumn {
+    /// Type of the array built
+    fn data_type(&self) -> DataType;
+
+    /// Number of values appended since the last `finish`
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the value, which can be a ScalarValue holding None
+    fn append_scalar(&mut self, value: &ScalarValue) -> Result<()>;
+
+    /// Appends a null
+    fn append_null(&mut self) -> Result<()>;
+
+    /// Builds the array with the values appended and empties the builder
+    fn finish(&mut self) -> ArrayRef;
+}
+
+// Implements the trait for the builder of a type with a ScalarValue. The
+// value is matched with the pattern of the variant, so the durations
+// match their unit too
+macro_rules! mutable_column {
+    ($BUILDER:ty, $data_type:expr, $value:ident, $PATTERN:pat => $append:expr) => {
+        impl MutableColumn for $BUILDER {
+            fn data_type(&self) -> DataType {
+                $data_type
+            }
+
+            fn len(&self) -> usize {
+                arrow::array::ArrayBuilder::len(self)
+            }
+
+            fn append_scalar(&mut self, value: &ScalarValue) -> Result<()> {
+                match value {
+                    $PATTERN => match $append {
+                        Some($value) => self.append_value($value),
+                        None => <$BUILDER>::append_null(self),
+                    },
+                    other => Err(mismatch(other, &$data_type)),
+                }
+            }
+
+            fn append_null(&mut self) -> Result<()> {
+                <$BUILDER>::append_null(self)
+            }
+
+            fn finish(&mut self) -> ArrayRef {
+                Arc::new(<$BUILDER>::finish(self))
+            }
+        }
+    };
+}
+
+mutable_column!(BooleanBuilder, DataType::Boolean, v, ScalarValue::Boolean(v) => *v);
+mutable_column!(Int8Builder, DataType::Int8, v, ScalarValue::Int8(v) => *v);
+mutable_column!(Int16Builder, DataType::Int16, v, ScalarValue::Int16(v) => *v);
+mutable_column!(Int32Builder, DataType::Int32, v, ScalarValue::Int32(v) => *v);
+mutable_column!(Int64Builder, DataType::Int64, v, ScalarValue::Int64(v) => *v);
+mutable_column!(UInt8Builder, DataType::UInt8, v, ScalarValue::UInt8(v) => *v);
+mutable_column!(UInt16Builder, DataType::UInt16, v, ScalarValue::UInt16(v) => *v);
+mutable_column!(UInt32Builder, DataType::UInt32, v, ScalarValue::UInt32(v) => *v);
+mutable_column!(UInt64Builder, DataType::UInt64, v, ScalarValue::UInt64(v) => *v);
+mutable_column!(Float32Builder, DataType::Float32, v, ScalarValue::Float32(v) => *v);
+mutable_column!(Float64Builder, DataType::Float64, v, ScalarValue::Float64(v) => *v);
+mutable_column!(StringBuilder, DataType::Utf8, v, ScalarValue::Utf8(v) => v.as_deref());
+mutable_column!(
+    LargeStringBuilder,
+    DataType::LargeUtf8,
+    v,
+    ScalarValue::LargeUtf8(v) => v.as_deref()
+);
+mutable_column!(
+    Date32Builder,
+    DataType::Date32(DateUnit::Day),
+    v,
+    ScalarValue::Date32(v) => *v
+);
+mutable_column!(
+    Time64MicrosecondBuilder,
+    DataType::Time64(TimeUnit::Microsecond),
+    v,
+    ScalarValue::TimeMicrosecond(v) => *v
+);
+mutable_column!(
+    Time64NanosecondBuilder,
+    DataType::Time64(TimeUnit::Nanosecond),
+    v,
+    ScalarValue::TimeNanosecond(v) => *v
+);
+mutable_column!(
+    DurationSecondBuilder,
+    DataType::Duration(TimeUnit::Second),
+    v,
+    ScalarValue::Duration(v, TimeUnit::Second) => *v
+);
+mutable_column!(
+    DurationMillisecondBuilder,
+    DataType::Duration(TimeUnit::Millisecond),
+    v,
+    ScalarValue::Duration(v, TimeUnit::Millisecond) => *v
+);
+mutable_column!(
+    DurationMicrosecondBuilder,
+    DataType::Duration(TimeUnit::Microsecond),
+    v,
+    ScalarValue::Duration(v, TimeUnit::Microsecond) => *v
+);
+mutable_column!(
+    DurationNanosecondBuilder,
+    DataType::Duration(TimeUnit::Nanosecond),
+    v,
+    ScalarValue::Duration(v, TimeUnit::Nanosecond) => *v
+);
+
+// The ScalarBuilder covers the rest of the types, like lists, since it
+// already appends ScalarValues of any type
+impl MutableColumn for ScalarBuilder {
+    fn data_type(&self) -> DataType {
+        ScalarBuilder::data_type(self).clone()
+    }
+
+    fn len(&self) -> usize {
+        ScalarBuilder::len(self)
+    }
+
+    fn append_scalar(&mut self, value: &ScalarValue) -> Result<()> {
+        self.append(value)
+    }
+
+    fn append_null(&mut self) -> Result<()> {
+        ScalarBuilder::append_null(self)
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        ScalarBuilder::finish(self)
+    }
+}
+
+/// Creates a column for the type. The types are the ones supported by
+/// `ScalarBuilder`
+pub fn make_column(data_type: &DataType, capacity: usize) -> Result<Box<dyn MutableColumn>> {
+    Ok(Box::new(ScalarBuilder::new(data_type, capacity)?))
+}
+
+fn mismatch(value: &ScalarValue, data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Can't append a value of type {:?} to an array of type {:?}",
+        value.get_datatype(),
+        data_type
+    ))
+}
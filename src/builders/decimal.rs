@@ -0,0 +1,141 @@
+// Decimal arrays store every value as an i128 with a fixed number of
+// decimal digits. The builder of arrow accepts any i128, so a value with
+// more digits than the precision of the column is only found when another
+// reader rejects the file. These helpers check the values as they are added
+use arrow::{
+    array::{DecimalArray, DecimalBuilder},
+    error::{ArrowError, Result},
+};
+
+// Largest precision that fits in an i128
+const MAX_PRECISION: usize = 38;
+
+/// Builds a decimal array from strings like "-12.50", which can be `&str`
+/// or `Option<&str>` for nulls. The strings can't have more decimal digits
+/// than the scale unless the extra ones are zeros
+pub fn decimal_array_from_strings<'a, I, S>(
+    values: I,
+    precision: usize,
+    scale: usize,
+) -> Result<DecimalArray>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<Option<&'a str>>,
+{
+    check_precision(precision, scale)?;
+    let values = values
+        .into_iter()
+        .map(|value| {
+            value
+                .into()
+                .map(|value| parse_decimal(value, precision, scale))
+                .transpose()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    decimal_array_from_i128(values, precision, scale)
+}
+
+/// Builds a decimal array from the i128 representation of the values, the
+/// value multiplied by 10^scale. Every value must fit in the precision
+pub fn decimal_array_from_i128<I>(values: I, precision: usize, scale: usize) -> Result<DecimalArray>
+where
+    I: IntoIterator<Item = Option<i128>>,
+{
+    check_precision(precision, scale)?;
+    let values = values.into_iter();
+    let mut builder = DecimalBuilder::new(values.size_hint().0, precision, scale);
+    for value in values {
+        match value {
+            Some(value) => {
+                check_value(value, precision)?;
+                builder.append_value(value)?;
+            }
+            None => builder.append_null()?,
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Parses a decimal string to its i128 representation with the scale
+pub fn parse_decimal(value: &str, precision: usize, scale: usize) -> Result<i128> {
+    let invalid = || ArrowError::ParseError(format!("Invalid decimal {:?}", value));
+
+    let trimmed = value.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let (integer, fraction) = match digits.find('.') {
+        Some(point) => (&digits[..point], &digits[point + 1..]),
+        None => (digits, ""),
+    };
+    let is_digits = |digits: &str| digits.bytes().all(|digit| digit.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+
+    // Digits after the scale are only accepted if they don't change the value
+    let (fraction, extra) = fraction.split_at(fraction.len().min(scale));
+    if extra.bytes().any(|digit| digit != b'0') {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "The decimal {} has more than {} decimal digits",
+            value, scale
+        )));
+    }
+
+    let mut result = 0i128;
+    let padding = std::iter::repeat_n(b'0', scale - fraction.len());
+    for digit in integer.bytes().chain(fraction.bytes()).chain(padding) {
+        result = result
+            .checked_mul(10)
+            .and_then(|result| result.checked_add((digit - b'0') as i128))
+            .ok_or_else(|| out_of_precision(value, precision))?;
+    }
+    if negative {
+        result = -result;
+    }
+
+    check_value(result, precision).map_err(|_| out_of_precision(value, precision))?;
+    Ok(result)
+}
+
+/// Formats the i128 representation of a decimal with the scale
+pub fn format_decimal(value: i128, scale: usize) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+fn check_precision(precision: usize, scale: usize) -> Result<()> {
+    if precision == 0 || precision > MAX_PRECISION || scale > precision {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Invalid decimal precision {} and scale {}. The precision goes from 1 to {}",
+            precision, scale, MAX_PRECISION
+        )));
+    }
+    Ok(())
+}
+
+fn check_value(value: i128, precision: usize) -> Result<()> {
+    let limit = 10i128.pow(precision as u32);
+    match value.unsigned_abs() < limit as u128 {
+        true => Ok(()),
+        false => Err(ArrowError::InvalidArgumentError(format!(
+            "The value {} doesn't fit in a decimal with precision {}",
+            value, precision
+        ))),
+    }
+}
+
+fn out_of_precision(value: &str, precision: usize) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "The decimal {} doesn't fit in precision {}",
+        value, precision
+    ))
+}
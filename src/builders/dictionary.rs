@@ -0,0 +1,125 @@
+// Dictionary arrays of strings with Int32 keys. Every batch built on its
+// own gets its own dictionary, so batches of the same column are unified
+// to share a single dictionary before writing them, and the writer only
+// has to send it once
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayData, ArrayRef, DictionaryArray, Int32Array, Int32Builder, StringArray,
+        StringBuilder, StringDictionaryBuilder,
+    },
+    datatypes::{DataType, Int32Type},
+    error::{ArrowError, Result},
+};
+
+use crate::downcast::downcast_array;
+
+/// Builds a dictionary array from strings, which can be `&str` or
+/// `Option<&str>` for nulls. Every distinct string gets the next key in
+/// the order they are found
+pub fn dictionary_from_strs<'a, I, S>(values: I) -> Result<DictionaryArray<Int32Type>>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<Option<&'a str>>,
+{
+    let mut builder = StringDictionaryBuilder::new(Int32Builder::new(0), StringBuilder::new(0));
+    for value in values {
+        match value.into() {
+            Some(value) => builder.append(value).map(|_| ())?,
+            None => builder.append_null()?,
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Converts a dictionary array back to the strings it represents
+pub fn dictionary_to_strings(array: &DictionaryArray<Int32Type>) -> Result<StringArray> {
+    let values = string_values(array)?;
+    let values = downcast_array::<StringArray>(values.as_ref())?;
+    Ok(array
+        .keys()
+        .iter()
+        .map(|key| key.map(|key| values.value(key as usize)))
+        .collect())
+}
+
+/// Rewrites the keys of the arrays so all of them use the same dictionary.
+/// The dictionary has the values of the first array followed by the new
+/// values of the next ones, so the first array keeps its keys
+pub fn unify_dictionaries(
+    arrays: &[DictionaryArray<Int32Type>],
+) -> Result<Vec<DictionaryArray<Int32Type>>> {
+    let dictionaries = arrays
+        .iter()
+        .map(string_values)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut positions = HashMap::<&str, i32>::new();
+    let mut merged = Vec::<&str>::new();
+    // New key of every key of the dictionary of each array
+    let mut mappings = Vec::with_capacity(arrays.len());
+
+    for values in &dictionaries {
+        let values = downcast_array::<StringArray>(values.as_ref())?;
+        let mapping = (0..values.len())
+            .map(|i| {
+                let value = values.value(i);
+                match positions.get(value) {
+                    Some(&key) => Ok(key),
+                    None => {
+                        let key = i32::try_from(merged.len()).map_err(|_| {
+                            ArrowError::ComputeError(
+                                "The merged dictionary doesn't fit in Int32 keys".to_string(),
+                            )
+                        })?;
+                        positions.insert(value, key);
+                        merged.push(value);
+                        Ok(key)
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        mappings.push(mapping);
+    }
+
+    let values: ArrayRef = Arc::new(StringArray::from(merged));
+    Ok(arrays
+        .iter()
+        .zip(mappings)
+        .map(|(array, mapping)| {
+            let keys = array
+                .keys()
+                .iter()
+                .map(|key| key.map(|key| mapping[key as usize]))
+                .collect::<Int32Array>();
+            with_values(&keys, &values)
+        })
+        .collect())
+}
+
+// Values of the dictionary, which must be strings
+fn string_values(array: &DictionaryArray<Int32Type>) -> Result<ArrayRef> {
+    match array.value_type() {
+        DataType::Utf8 => Ok(array.values()),
+        other => Err(ArrowError::InvalidArgumentError(format!(
+            "Expected a dictionary of Utf8 values but got {:?}",
+            other
+        ))),
+    }
+}
+
+fn with_values(keys: &Int32Array, values: &ArrayRef) -> DictionaryArray<Int32Type> {
+    let keys = keys.data_ref();
+    let data_type = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+    let mut builder = ArrayData::builder(data_type)
+        .len(keys.len())
+        .add_buffer(keys.buffers()[0].clone())
+        .add_child_data(values.data());
+    if let Some(nulls) = keys.null_buffer() {
+        builder = builder.null_bit_buffer(nulls.clone());
+    }
+    DictionaryArray::from(builder.build())
+}
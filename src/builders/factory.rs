@@ -0,0 +1,158 @@
+// Creates the builder of a DataType. The builder of arrow panics with the
+// types it doesn't know, and it can't create lists or dictionaries, so
+// this one covers them and returns an error for the rest
+use std::any::Any;
+
+use arrow::{
+    array::{
+        ArrayBuilder, ArrayRef, BinaryBuilder, BooleanBuilder, Date32Builder, Date64Builder,
+        DecimalBuilder, DurationMicrosecondBuilder, DurationMillisecondBuilder,
+        DurationNanosecondBuilder, DurationSecondBuilder, FixedSizeBinaryBuilder, Float32Builder,
+        Float64Builder, Int16Builder, Int32Builder, Int64Builder, Int8Builder, LargeBinaryBuilder,
+        LargeListBuilder, LargeStringBuilder, ListBuilder, PrimitiveBuilder, StringBuilder,
+        StringDictionaryBuilder, StructBuilder, Time64MicrosecondBuilder, Time64NanosecondBuilder,
+        TimestampMicrosecondBuilder, TimestampMillisecondBuilder, TimestampNanosecondBuilder,
+        TimestampSecondBuilder, UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder,
+    },
+    datatypes::{
+        DataType, DateUnit, Int16Type, Int32Type, Int64Type, Int8Type, TimeUnit, UInt16Type,
+        UInt32Type, UInt64Type, UInt8Type,
+    },
+    error::{ArrowError, Result},
+};
+
+// Dictionary builder with string values and keys of the given type
+macro_rules! string_dictionary {
+    ($KEYTYPE:ident, $capacity:expr) => {
+        Box::new(StringDictionaryBuilder::new(
+            PrimitiveBuilder::<$KEYTYPE>::new($capacity),
+            StringBuilder::new($capacity),
+        ))
+    };
+}
+
+/// Builder of any type behind a box. The list builders need a concrete
+/// builder for their values, so the values of the lists created by
+/// `make_builder` are kept in a `BoxedBuilder`. The builder inside is
+/// downcast through the field, as in `values.0.as_any_mut()`
+pub struct BoxedBuilder(pub Box<dyn ArrayBuilder>);
+
+impl ArrayBuilder for BoxedBuilder {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        self.0.finish()
+    }
+
+    // The list builders downcast their values builder to its own type, so
+    // these return the box and not the builder inside
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Creates an empty builder for arrays of the type, with room for
+/// `capacity` values. The builder returned can be downcast to the builder
+/// of the type with `as_any_mut`, for example `Int32Builder` for Int32 or
+/// `ListBuilder<BoxedBuilder>` for a list. Struct fields and list values
+/// get builders created in the same way, and dictionaries must have string
+/// values. The lists built always name their values field "item"
+pub fn make_builder(data_type: &DataType, capacity: usize) -> Result<Box<dyn ArrayBuilder>> {
+    Ok(match data_type {
+        DataType::Boolean => Box::new(BooleanBuilder::new(capacity)),
+        DataType::Int8 => Box::new(Int8Builder::new(capacity)),
+        DataType::Int16 => Box::new(Int16Builder::new(capacity)),
+        DataType::Int32 => Box::new(Int32Builder::new(capacity)),
+        DataType::Int64 => Box::new(Int64Builder::new(capacity)),
+        DataType::UInt8 => Box::new(UInt8Builder::new(capacity)),
+        DataType::UInt16 => Box::new(UInt16Builder::new(capacity)),
+        DataType::UInt32 => Box::new(UInt32Builder::new(capacity)),
+        DataType::UInt64 => Box::new(UInt64Builder::new(capacity)),
+        DataType::Float32 => Box::new(Float32Builder::new(capacity)),
+        DataType::Float64 => Box::new(Float64Builder::new(capacity)),
+        DataType::Utf8 => Box::new(StringBuilder::new(capacity)),
+        DataType::LargeUtf8 => Box::new(LargeStringBuilder::new(capacity)),
+        DataType::Binary => Box::new(BinaryBuilder::new(capacity)),
+        DataType::LargeBinary => Box::new(LargeBinaryBuilder::new(capacity)),
+        DataType::FixedSizeBinary(width) => Box::new(FixedSizeBinaryBuilder::new(capacity, *width)),
+        DataType::Decimal(precision, scale) => {
+            Box::new(DecimalBuilder::new(capacity, *precision, *scale))
+        }
+        DataType::Date32(DateUnit::Day) => Box::new(Date32Builder::new(capacity)),
+        DataType::Date64(DateUnit::Millisecond) => Box::new(Date64Builder::new(capacity)),
+        DataType::Time64(TimeUnit::Microsecond) => {
+            Box::new(Time64MicrosecondBuilder::new(capacity))
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => Box::new(Time64NanosecondBuilder::new(capacity)),
+        DataType::Timestamp(TimeUnit::Second, _) => Box::new(TimestampSecondBuilder::new(capacity)),
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            Box::new(TimestampMillisecondBuilder::new(capacity))
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            Box::new(TimestampMicrosecondBuilder::new(capacity))
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            Box::new(TimestampNanosecondBuilder::new(capacity))
+        }
+        DataType::Duration(TimeUnit::Second) => Box::new(DurationSecondBuilder::new(capacity)),
+        DataType::Duration(TimeUnit::Millisecond) => {
+            Box::new(DurationMillisecondBuilder::new(capacity))
+        }
+        DataType::Duration(TimeUnit::Microsecond) => {
+            Box::new(DurationMicrosecondBuilder::new(capacity))
+        }
+        DataType::Duration(TimeUnit::Nanosecond) => {
+            Box::new(DurationNanosecondBuilder::new(capacity))
+        }
+        DataType::List(field) => Box::new(ListBuilder::new(BoxedBuilder(make_builder(
+            field.data_type(),
+            capacity,
+        )?))),
+        DataType::LargeList(field) => Box::new(LargeListBuilder::new(BoxedBuilder(make_builder(
+            field.data_type(),
+            capacity,
+        )?))),
+        DataType::Struct(fields) => {
+            let builders = fields
+                .iter()
+                .map(|field| make_builder(field.data_type(), capacity))
+                .collect::<Result<Vec<_>>>()?;
+            Box::new(StructBuilder::new(fields.clone(), builders))
+        }
+        DataType::Dictionary(key_type, value_type) if **value_type == DataType::Utf8 => {
+            match key_type.as_ref() {
+                DataType::Int8 => string_dictionary!(Int8Type, capacity),
+                DataType::Int16 => string_dictionary!(Int16Type, capacity),
+                DataType::Int32 => string_dictionary!(Int32Type, capacity),
+                DataType::Int64 => string_dictionary!(Int64Type, capacity),
+                DataType::UInt8 => string_dictionary!(UInt8Type, capacity),
+                DataType::UInt16 => string_dictionary!(UInt16Type, capacity),
+                DataType::UInt32 => string_dictionary!(UInt32Type, capacity),
+                DataType::UInt64 => string_dictionary!(UInt64Type, capacity),
+                _ => return Err(unsupported(data_type)),
+            }
+        }
+        other => return Err(unsupported(other)),
+    })
+}
+
+fn unsupported(data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Can't create a builder for arrays of type {:?}",
+        data_type
+    ))
+}
@@ -0,0 +1,74 @@
+// FixedSizeList columns hold lists that always have the same number of
+// values, like embeddings or coordinates. The values of all the lists are
+// stored one after the other in a single child array, so a null list still
+// takes its place in the values
+use std::convert::TryFrom;
+
+use arrow::{
+    array::{Array, ArrayData, FixedSizeListArray, PrimitiveArray},
+    buffer::Buffer,
+    datatypes::{ArrowPrimitiveType, DataType, Field, ToByteSlice},
+    error::{ArrowError, Result},
+};
+
+use crate::bitmap;
+use crate::downcast::downcast_array;
+
+/// Builds a FixedSizeList array with lists of N primitive values. A null
+/// list is stored as N zeros marked as null
+pub fn fixed_size_list_from_vecs<T: ArrowPrimitiveType, const N: usize>(
+    data: Vec<Option<[T::Native; N]>>,
+) -> Result<FixedSizeListArray> {
+    let size = i32::try_from(N).map_err(|_| {
+        ArrowError::InvalidArgumentError(format!("The lists can't have {} values", N))
+    })?;
+
+    let mut values = Vec::with_capacity(data.len() * N);
+    for list in &data {
+        values.extend_from_slice(&list.unwrap_or([T::Native::default(); N]));
+    }
+    let values = ArrayData::builder(T::DATA_TYPE)
+        .len(values.len())
+        .add_buffer(Buffer::from(values.to_byte_slice()))
+        .build();
+
+    let item = Field::new("item", T::DATA_TYPE, false);
+    let mut builder = ArrayData::builder(DataType::FixedSizeList(Box::new(item), size))
+        .len(data.len())
+        .add_child_data(values);
+    // Arrays without nulls don't need a validity bitmap
+    if data.iter().any(Option::is_none) {
+        builder = builder.null_bit_buffer(bitmap::from_iter(data.iter().map(Option::is_some)));
+    }
+    Ok(FixedSizeListArray::from(builder.build()))
+}
+
+/// Reads the lists of a FixedSizeList array of N primitive values. The
+/// type and the size of the lists must match the array
+pub fn fixed_size_list_to_vecs<T: ArrowPrimitiveType, const N: usize>(
+    array: &FixedSizeListArray,
+) -> Result<Vec<Option<[T::Native; N]>>> {
+    if array.value_length() as usize != N {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "The lists have {} values, not {}",
+            array.value_length(),
+            N
+        )));
+    }
+
+    let values = array.values();
+    let values = downcast_array::<PrimitiveArray<T>>(values.as_ref())?;
+    Ok((0..array.len())
+        .map(|i| match array.is_valid(i) {
+            true => {
+                let start = array.value_offset(i) as usize;
+                let mut list = [T::Native::default(); N];
+                for (j, value) in list.iter_mut().enumerate() {
+                    *value = values.value(start + j);
+                }
+                Some(list)
+            }
+            false => None,
+        })
+        .collect())
+}
@@ -0,0 +1,140 @@
+// Maps are lists of key-value entries. Arrow 3 doesn't have the Map type
+// yet, so the maps are built with its physical layout: a list of
+// "entries" structs with a non-nullable "key" field and a "value" field.
+// Readers that know the Map type can read the same buffers as a map
+use arrow::{
+    array::{Array, ArrayData, ListArray},
+    datatypes::{DataType, Field},
+    error::{ArrowError, Result},
+};
+
+use super::ListOfStructBuilder;
+use crate::ScalarValue;
+
+/// Types that can be the keys or the values of the maps built by
+/// `map_array_from_vecs`. An `Option` value gives a null
+pub trait MapValue {
+    fn data_type() -> DataType;
+    fn into_scalar(self) -> ScalarValue;
+    fn null() -> ScalarValue;
+}
+
+macro_rules! map_value {
+    ($NATIVE:ty, $DATATYPE:ident, $value:ident => $convert:expr) => {
+        impl MapValue for $NATIVE {
+            fn data_type() -> DataType {
+                DataType::$DATATYPE
+            }
+
+            fn into_scalar(self) -> ScalarValue {
+                let $value = self;
+                ScalarValue::$DATATYPE(Some($convert))
+            }
+
+            fn null() -> ScalarValue {
+                ScalarValue::$DATATYPE(None)
+            }
+        }
+    };
+}
+
+map_value!(bool, Boolean, value => value);
+map_value!(i32, Int32, value => value);
+map_value!(i64, Int64, value => value);
+map_value!(u32, UInt32, value => value);
+map_value!(u64, UInt64, value => value);
+map_value!(f32, Float32, value => value);
+map_value!(f64, Float64, value => value);
+map_value!(String, Utf8, value => value);
+map_value!(&str, Utf8, value => value.to_string());
+
+impl<T: MapValue> MapValue for Option<T> {
+    fn data_type() -> DataType {
+        T::data_type()
+    }
+
+    fn into_scalar(self) -> ScalarValue {
+        match self {
+            Some(value) => value.into_scalar(),
+            None => T::null(),
+        }
+    }
+
+    fn null() -> ScalarValue {
+        T::null()
+    }
+}
+
+/// Builds a map column from the entries of every map, or None for a null
+/// map. The keys can't be null
+pub fn map_array_from_vecs<K: MapValue, V: MapValue>(
+    maps: Vec<Option<Vec<(K, V)>>>,
+) -> Result<ListArray> {
+    let fields = vec![
+        Field::new("key", K::data_type(), false),
+        Field::new("value", V::data_type(), true),
+    ];
+    let mut builder = ListOfStructBuilder::new(fields, maps.len())?;
+    for map in maps {
+        let entries = map.map(|entries| {
+            entries
+                .into_iter()
+                .map(|(key, value)| vec![key.into_scalar(), value.into_scalar()])
+                .collect::<Vec<_>>()
+        });
+        builder.append(entries.as_deref())?;
+    }
+
+    // The builder names the items of the lists "item", so the lists are
+    // rebuilt with the "entries" field of the Map layout
+    let lists = builder.finish();
+    let data = lists.data_ref();
+    let entries = &data.child_data()[0];
+    let field = Field::new("entries", entries.data_type().clone(), false);
+    let mut rebuilt = ArrayData::builder(DataType::List(Box::new(field)))
+        .len(data.len())
+        .buffers(data.buffers().to_vec())
+        .add_child_data(entries.clone());
+    if let Some(nulls) = data.null_buffer() {
+        rebuilt = rebuilt.null_bit_buffer(nulls.clone());
+    }
+    let maps = ListArray::from(rebuilt.build());
+
+    check_map_entries(&maps)?;
+    Ok(maps)
+}
+
+/// Checks that a list array has the layout of a map: its items are
+/// non-nullable structs with a key and a value field, and none of the keys
+/// are null
+pub fn check_map_entries(maps: &ListArray) -> Result<()> {
+    let invalid = |reason: &str| {
+        ArrowError::InvalidArgumentError(format!("The array isn't a map: {}", reason))
+    };
+
+    let item = match maps.data_type() {
+        DataType::List(item) => item,
+        _ => return Err(invalid("it isn't a list")),
+    };
+    if item.is_nullable() {
+        return Err(invalid("the entries are nullable"));
+    }
+    let fields = match item.data_type() {
+        DataType::Struct(fields) => fields,
+        _ => return Err(invalid("the entries aren't structs")),
+    };
+    if fields.len() != 2 {
+        return Err(invalid("the entries must have a key and a value field"));
+    }
+    if fields[0].is_nullable() {
+        return Err(invalid("the key field is nullable"));
+    }
+
+    let entries = maps.values();
+    let keys = &entries.data_ref().child_data()[0];
+    if keys.null_count() > 0 {
+        return Err(invalid("some of the keys are null"));
+    }
+
+    Ok(())
+}
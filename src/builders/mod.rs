@@ -0,0 +1,33 @@
+// Helpers to build arrays when the types are only known at runtime or when
+// building them by hand would mean writing the buffers directly
+mod binary;
+mod column;
+mod decimal;
+mod dictionary;
+mod factory;
+mod fixed_size_list;
+mod map;
+mod nested;
+mod offsets;
+mod pool;
+mod primitive;
+mod scalar;
+mod union;
+
+pub use binary::{
+    binary_from_slices, BinaryStreamBuilder, GenericBinaryStreamBuilder, LargeBinaryStreamBuilder,
+};
+pub use column::{make_column, MutableColumn};
+pub use decimal::{
+    decimal_array_from_i128, decimal_array_from_strings, format_decimal, parse_decimal,
+};
+pub use dictionary::{dictionary_from_strs, dictionary_to_strings, unify_dictionaries};
+pub use factory::{make_builder, BoxedBuilder};
+pub use fixed_size_list::{fixed_size_list_from_vecs, fixed_size_list_to_vecs};
+pub use map::{check_map_entries, map_array_from_vecs, MapValue};
+pub use nested::{ListOfStructBuilder, StructArrayBuilder};
+pub use offsets::OffsetsBuilder;
+pub use pool::{BatchBuilders, BuilderPool};
+pub use primitive::{array_from_iter, array_from_trusted_len_iter, array_from_vec};
+pub use scalar::ScalarBuilder;
+pub use union::{dense_union, sparse_union, UnionArrayBuilder, UnionMode};
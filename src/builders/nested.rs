@@ -0,0 +1,166 @@
+// Builders for structs with list fields and lists of structs. Composing
+// ListBuilder<StructBuilder> by hand means appending to every field and
+// then to the struct and the list, and forgetting any of them gives arrays
+// with different lengths. These take whole rows of ScalarValues instead
+use arrow::{
+    array::{Array, ArrayData, ListArray, StructArray},
+    datatypes::{DataType, Field},
+    error::{ArrowError, Result},
+};
+
+use super::{OffsetsBuilder, ScalarBuilder};
+use crate::{bitmap, ScalarValue};
+
+/// Builds a StructArray row by row. Every row has a value for each field,
+/// so lists and other nested values are appended as `ScalarValue::List`.
+/// The rows of the struct are never null
+pub struct StructArrayBuilder {
+    fields: Vec<Field>,
+    builders: Vec<ScalarBuilder>,
+}
+
+impl StructArrayBuilder {
+    /// Creates a builder for structs with the fields, with room for
+    /// `capacity` rows
+    pub fn new(fields: Vec<Field>, capacity: usize) -> Result<Self> {
+        let builders = fields
+            .iter()
+            .map(|field| ScalarBuilder::new(field.data_type(), capacity))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { fields, builders })
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Number of rows appended since the last `finish`
+    pub fn len(&self) -> usize {
+        self.builders.first().map_or(0, |builder| builder.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a row with a value for every field. The values are checked
+    /// before appending any of them, so a wrong row leaves the builder as
+    /// it was
+    pub fn append(&mut self, row: &[ScalarValue]) -> Result<()> {
+        self.check(row)?;
+        self.append_unchecked(row)
+    }
+
+    /// Builds the array with the rows appended and empties the builder
+    pub fn finish(&mut self) -> StructArray {
+        // The list builders name their values field "item", so the fields
+        // take the type of the arrays built
+        let columns = self
+            .fields
+            .iter()
+            .zip(self.builders.iter_mut())
+            .map(|(field, builder)| {
+                let values = builder.finish();
+                let field = Field::new(
+                    field.name(),
+                    values.data_type().clone(),
+                    field.is_nullable(),
+                );
+                (field, values)
+            })
+            .collect::<Vec<_>>();
+        StructArray::from(columns)
+    }
+
+    fn check(&self, row: &[ScalarValue]) -> Result<()> {
+        if row.len() != self.fields.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The row has {} values but the struct has {} fields",
+                row.len(),
+                self.fields.len()
+            )));
+        }
+
+        for ((field, builder), value) in self.fields.iter().zip(&self.builders).zip(row) {
+            if value.is_null() && !field.is_nullable() {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "The field {} can't be null",
+                    field.name()
+                )));
+            }
+            builder.check(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn append_unchecked(&mut self, row: &[ScalarValue]) -> Result<()> {
+        for (builder, value) in self.builders.iter_mut().zip(row) {
+            builder.append(value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a `List<Struct>` array. Every list is appended at once as a slice
+/// of struct rows, or None for a null list
+pub struct ListOfStructBuilder {
+    items: StructArrayBuilder,
+    offsets: OffsetsBuilder<i32>,
+    validity: Vec<bool>,
+}
+
+impl ListOfStructBuilder {
+    /// Creates a builder for lists of structs with the fields, with room
+    /// for `capacity` structs
+    pub fn new(fields: Vec<Field>, capacity: usize) -> Result<Self> {
+        Ok(Self {
+            items: StructArrayBuilder::new(fields, capacity)?,
+            offsets: OffsetsBuilder::new(capacity),
+            validity: Vec::new(),
+        })
+    }
+
+    /// Number of lists appended since the last `finish`
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_empty()
+    }
+
+    /// Appends a list with the structs of the rows. All the rows are
+    /// checked before appending them
+    pub fn append(&mut self, rows: Option<&[Vec<ScalarValue>]>) -> Result<()> {
+        let valid = rows.is_some();
+        let rows = rows.unwrap_or(&[]);
+        for row in rows {
+            self.items.check(row)?;
+        }
+        self.offsets.append_length(rows.len())?;
+        for row in rows {
+            self.items.append_unchecked(row)?;
+        }
+
+        self.validity.push(valid);
+        Ok(())
+    }
+
+    /// Builds the array with the lists appended and empties the builder
+    pub fn finish(&mut self) -> ListArray {
+        let items = self.items.finish();
+        let offsets = self.offsets.finish();
+        let validity = std::mem::take(&mut self.validity);
+
+        let item = Field::new("item", items.data_type().clone(), false);
+        let mut builder = ArrayData::builder(DataType::List(Box::new(item)))
+            .len(validity.len())
+            .add_buffer(offsets)
+            .add_child_data(items.data());
+        if validity.contains(&false) {
+            builder = builder.null_bit_buffer(bitmap::from_bools(&validity));
+        }
+        ListArray::from(builder.build())
+    }
+}
@@ -0,0 +1,95 @@
+// Offsets of variable-size arrays like strings and lists. The offset of a
+// row is where its values start, and the next offset is where they end, so
+// there's one offset more than rows and they can never decrease. Writing
+// the buffer by hand accepts any numbers, so this builder checks them as
+// they are appended
+use arrow::{
+    array::OffsetSizeTrait,
+    buffer::Buffer,
+    datatypes::ToByteSlice,
+    error::{ArrowError, Result},
+};
+
+/// Builds the offsets buffer of an array with i32 or i64 offsets. It starts
+/// with the offset 0 and tracks the length of the values, which is the last
+/// offset
+#[derive(Debug)]
+pub struct OffsetsBuilder<O: OffsetSizeTrait> {
+    offsets: Vec<O>,
+    values_len: usize,
+}
+
+impl<O: OffsetSizeTrait> Default for OffsetsBuilder<O> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<O: OffsetSizeTrait> OffsetsBuilder<O> {
+    /// Creates a builder with room for the offsets of `capacity` rows
+    pub fn new(capacity: usize) -> Self {
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(O::zero());
+        Self {
+            offsets,
+            values_len: 0,
+        }
+    }
+
+    /// Number of rows appended
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns true if no row has been appended
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of values covered by the offsets, which is the length the
+    /// values of the array need
+    pub fn values_len(&self) -> usize {
+        self.values_len
+    }
+
+    /// Appends a row with `len` values after the values of the last row.
+    /// Null and empty rows have 0 values
+    pub fn append_length(&mut self, len: usize) -> Result<()> {
+        let end = self
+            .values_len
+            .checked_add(len)
+            .ok_or_else(too_large::<O>)?;
+        self.append_end(end)
+    }
+
+    /// Appends a row whose values end at `end`. It can't be before the end
+    /// of the last row
+    pub fn append_end(&mut self, end: usize) -> Result<()> {
+        if end < self.values_len {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The offsets can't decrease, but {} comes after {}",
+                end, self.values_len
+            )));
+        }
+
+        let offset = O::from_usize(end).ok_or_else(too_large::<O>)?;
+        self.offsets.push(offset);
+        self.values_len = end;
+        Ok(())
+    }
+
+    /// Returns the buffer with the offsets and empties the builder
+    pub fn finish(&mut self) -> Buffer {
+        let offsets = std::mem::replace(&mut self.offsets, vec![O::zero()]);
+        self.values_len = 0;
+        Buffer::from(offsets.to_byte_slice())
+    }
+}
+
+fn too_large<O: OffsetSizeTrait>() -> ArrowError {
+    ArrowError::ComputeError(format!(
+        "The values don't fit in the offsets of a {}List or {}Utf8 array",
+        O::prefix(),
+        O::prefix()
+    ))
+}
@@ -0,0 +1,156 @@
+// Pool of the builders used to produce batches of the same schema over and
+// over. Creating the builders of a schema means walking its fields and
+// allocating every builder with its capacity, so producers that emit
+// batches in a loop take the builders from the pool and give them back
+// once the batch is finished. Arrow hands the buffers of a builder to the
+// array it finishes, so a builder taken back after `finish` keeps its
+// structure but grows its buffers again while it's filled
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayBuilder, ArrayRef},
+    datatypes::SchemaRef,
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::make_builder;
+use super::scalar::downcast_builder;
+
+// Idle sets of builders kept for every schema unless a different number is
+// set
+const DEFAULT_MAX_IDLE: usize = 4;
+
+/// Builders for all the columns of a schema, handed out by a BuilderPool
+pub struct BatchBuilders {
+    schema: SchemaRef,
+    builders: Vec<Box<dyn ArrayBuilder>>,
+}
+
+impl BatchBuilders {
+    /// Schema of the batches built
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Number of rows appended so far, which is the length of the first
+    /// column
+    pub fn len(&self) -> usize {
+        self.builders.first().map_or(0, |builder| builder.len())
+    }
+
+    /// Returns true if no row has been appended
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Builder of a column downcast to its type. The types are the ones
+    /// returned by `make_builder`, for example `Int32Builder` for an Int32
+    /// column or `ListBuilder<BoxedBuilder>` for a list
+    pub fn builder<T: ArrayBuilder>(&mut self, column: usize) -> Result<&mut T> {
+        let builder = self.builders.get_mut(column).ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!("The schema doesn't have column {}", column))
+        })?;
+        downcast_builder(builder.as_mut())
+    }
+
+    /// Builders of all the columns, in the order of the schema
+    pub fn builders_mut(&mut self) -> &mut [Box<dyn ArrayBuilder>] {
+        &mut self.builders
+    }
+
+    /// Finishes all the builders and returns the batch. The builders are
+    /// left empty, so they can be filled again or returned to the pool
+    pub fn finish(&mut self) -> Result<RecordBatch> {
+        let columns = self
+            .builders
+            .iter_mut()
+            .map(|builder| builder.finish())
+            .collect::<Vec<ArrayRef>>();
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+/// Pool of builders keyed by schema. `take` returns an idle set of
+/// builders for the schema or creates a new one, and `put_back` keeps the
+/// builders for the next batch
+pub struct BuilderPool {
+    capacity: usize,
+    max_idle: usize,
+    idle: Vec<(SchemaRef, Vec<BatchBuilders>)>,
+}
+
+impl BuilderPool {
+    /// Creates an empty pool. The builders are created with room for
+    /// `capacity` rows
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            max_idle: DEFAULT_MAX_IDLE,
+            idle: Vec::new(),
+        }
+    }
+
+    /// Number of idle sets of builders kept for every schema. The builders
+    /// returned when the pool is full are dropped
+    pub fn with_max_idle(mut self, max_idle: usize) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    /// Number of idle sets of builders of all the schemas
+    pub fn idle(&self) -> usize {
+        self.idle.iter().map(|(_, builders)| builders.len()).sum()
+    }
+
+    /// Takes the builders of the schema from the pool, or creates them if
+    /// there are none idle. Schemas are compared by value, so two equal
+    /// schemas share their builders
+    pub fn take(&mut self, schema: &SchemaRef) -> Result<BatchBuilders> {
+        let idle = self
+            .idle
+            .iter_mut()
+            .find(|(idle_schema, _)| idle_schema == schema)
+            .and_then(|(_, builders)| builders.pop());
+        if let Some(builders) = idle {
+            return Ok(builders);
+        }
+
+        let builders = schema
+            .fields()
+            .iter()
+            .map(|field| make_builder(field.data_type(), self.capacity))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(BatchBuilders {
+            schema: Arc::clone(schema),
+            builders,
+        })
+    }
+
+    /// Returns the builders to the pool. Builders with rows that weren't
+    /// finished are dropped with an error, since the rows would end in
+    /// another batch
+    pub fn put_back(&mut self, builders: BatchBuilders) -> Result<()> {
+        if builders.builders.iter().any(|builder| !builder.is_empty()) {
+            return Err(ArrowError::InvalidArgumentError(
+                "The builders have rows that weren't finished".to_string(),
+            ));
+        }
+
+        let position = self
+            .idle
+            .iter()
+            .position(|(schema, _)| *schema == builders.schema);
+        let idle = match position {
+            Some(position) => &mut self.idle[position].1,
+            None => {
+                self.idle.push((builders.schema.clone(), Vec::new()));
+                &mut self.idle.last_mut().unwrap().1
+            }
+        };
+        if idle.len() < self.max_idle {
+            idle.push(builders);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+// Primitive arrays built straight from iterators, for generated data and
+// for the results of user functions. The values and the validity bitmap are
+// written to plain vectors, so there's no builder or intermediate Vec of
+// options for every type
+use arrow::{
+    array::{ArrayData, PrimitiveArray},
+    buffer::Buffer,
+    datatypes::{ArrowPrimitiveType, ToByteSlice},
+    error::{ArrowError, Result},
+    util::bit_util,
+};
+
+use crate::buffer;
+
+/// Builds a primitive array from the values returned by an iterator, where
+/// None gives a null. The lower bound of the size hint is reserved up front
+pub fn array_from_iter<T, I>(iter: I) -> PrimitiveArray<T>
+where
+    T: ArrowPrimitiveType,
+    I: IntoIterator<Item = Option<T::Native>>,
+{
+    let iter = iter.into_iter();
+    let (lower, _) = iter.size_hint();
+
+    let mut values = Vec::with_capacity(lower);
+    let mut validity = Vec::with_capacity(bit_util::ceil(lower, 8));
+    let mut null_count = 0;
+    for (i, value) in iter.enumerate() {
+        if i % 8 == 0 {
+            validity.push(0);
+        }
+        match value {
+            Some(value) => {
+                values.push(value);
+                bit_util::set_bit(&mut validity, i);
+            }
+            None => {
+                values.push(T::Native::default());
+                null_count += 1;
+            }
+        }
+    }
+
+    build_primitive(values, validity, null_count)
+}
+
+/// Builds a primitive array without nulls that owns the values of the Vec,
+/// without copying them
+pub fn array_from_vec<T: ArrowPrimitiveType>(values: Vec<T::Native>) -> PrimitiveArray<T> {
+    let data = ArrayData::builder(T::DATA_TYPE)
+        .len(values.len())
+        .add_buffer(buffer::from_vec(values))
+        .build();
+    PrimitiveArray::<T>::from(data)
+}
+
+/// Builds a primitive array from an iterator that knows its length. The
+/// buffers are allocated once with their final size and the values are
+/// written in place. An iterator that returns a different number of values
+/// than its length gives an error
+pub fn array_from_trusted_len_iter<T, I>(iter: I) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    I: IntoIterator<Item = Option<T::Native>>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let mut iter = iter.into_iter();
+    let len = iter.len();
+
+    let mut values = vec![T::Native::default(); len];
+    let mut validity = vec![0; bit_util::ceil(len, 8)];
+    let mut null_count = 0;
+    for (i, slot) in values.iter_mut().enumerate() {
+        match iter.next() {
+            Some(Some(value)) => {
+                *slot = value;
+                bit_util::set_bit(&mut validity, i);
+            }
+            Some(None) => null_count += 1,
+            None => return Err(wrong_length(len, i)),
+        }
+    }
+    if iter.next().is_some() {
+        return Err(wrong_length(len, len + 1 + iter.count()));
+    }
+
+    Ok(build_primitive(values, validity, null_count))
+}
+
+// Arrays without nulls don't need a validity bitmap
+fn build_primitive<T: ArrowPrimitiveType>(
+    values: Vec<T::Native>,
+    validity: Vec<u8>,
+    null_count: usize,
+) -> PrimitiveArray<T> {
+    let mut builder = ArrayData::builder(T::DATA_TYPE)
+        .len(values.len())
+        .add_buffer(Buffer::from(values.to_byte_slice()));
+    if null_count > 0 {
+        builder = builder.null_bit_buffer(Buffer::from(validity));
+    }
+    PrimitiveArray::<T>::from(builder.build())
+}
+
+fn wrong_length(expected: usize, found: usize) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "The iterator said it had {} values but returned {}",
+        expected, found
+    ))
+}
@@ -0,0 +1,190 @@
+// Builds an array from ScalarValues. Code that converts rows to columns
+// gets the values one by one without knowing their type, so the builder
+// checks every value against the type of the array
+use arrow::{
+    array::{
+        ArrayBuilder, ArrayRef, BooleanBuilder, Date32Builder, DurationMicrosecondBuilder,
+        DurationMillisecondBuilder, DurationNanosecondBuilder, DurationSecondBuilder,
+        Float32Builder, Float64Builder, Int16Builder, Int32Builder, Int64Builder, Int8Builder,
+        LargeStringBuilder, ListBuilder, StringBuilder, Time64MicrosecondBuilder,
+        Time64NanosecondBuilder, UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder,
+    },
+    datatypes::{DataType, DateUnit, TimeUnit},
+    error::{ArrowError, Result},
+};
+
+use super::{make_builder, BoxedBuilder};
+use crate::ScalarValue;
+
+// Appends the value, or a null, to the builder of its type
+macro_rules! typed_append {
+    ($builder:expr, $BUILDERTYPE:ty, $value:expr) => {{
+        let builder = downcast_builder::<$BUILDERTYPE>($builder)?;
+        match $value {
+            Some(value) => builder.append_value(value),
+            None => builder.append_null(),
+        }
+    }};
+}
+
+/// Builder that appends ScalarValues to an array of any type. Every value
+/// must have the type of the array, and a null is appended as a
+/// ScalarValue of that type holding None
+pub struct ScalarBuilder {
+    data_type: DataType,
+    builder: Box<dyn ArrayBuilder>,
+}
+
+impl ScalarBuilder {
+    /// Creates a builder for arrays of the type with room for `capacity`
+    /// values. The types are the ones supported by `make_builder`
+    pub fn new(data_type: &DataType, capacity: usize) -> Result<Self> {
+        Ok(Self {
+            data_type: data_type.clone(),
+            builder: make_builder(data_type, capacity)?,
+        })
+    }
+
+    /// Type of the array built
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    /// Number of values appended since the last `finish`
+    pub fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.builder.is_empty()
+    }
+
+    /// Appends the value. An error is returned if its type isn't the type
+    /// of the array, and nothing is appended
+    pub fn append(&mut self, value: &ScalarValue) -> Result<()> {
+        self.check(value)?;
+        append_value(self.builder.as_mut(), value)
+    }
+
+    /// Appends a null
+    pub fn append_null(&mut self) -> Result<()> {
+        let value = null_value(&self.data_type)?;
+        append_value(self.builder.as_mut(), &value)
+    }
+
+    /// Checks if the value can be appended without appending it
+    pub fn check(&self, value: &ScalarValue) -> Result<()> {
+        check_type(&self.data_type, value)
+    }
+
+    /// Builds the array with the values appended and empties the builder
+    pub fn finish(&mut self) -> ArrayRef {
+        self.builder.finish()
+    }
+}
+
+// Null ScalarValue of the type. Only the types with a ScalarValue can have
+// their nulls appended
+fn null_value(data_type: &DataType) -> Result<ScalarValue> {
+    Ok(match data_type {
+        DataType::Boolean => ScalarValue::Boolean(None),
+        DataType::Float32 => ScalarValue::Float32(None),
+        DataType::Float64 => ScalarValue::Float64(None),
+        DataType::Int8 => ScalarValue::Int8(None),
+        DataType::Int16 => ScalarValue::Int16(None),
+        DataType::Int32 => ScalarValue::Int32(None),
+        DataType::Int64 => ScalarValue::Int64(None),
+        DataType::UInt8 => ScalarValue::UInt8(None),
+        DataType::UInt16 => ScalarValue::UInt16(None),
+        DataType::UInt32 => ScalarValue::UInt32(None),
+        DataType::UInt64 => ScalarValue::UInt64(None),
+        DataType::Utf8 => ScalarValue::Utf8(None),
+        DataType::LargeUtf8 => ScalarValue::LargeUtf8(None),
+        DataType::List(field) => ScalarValue::List(None, field.data_type().clone()),
+        DataType::Date32(DateUnit::Day) => ScalarValue::Date32(None),
+        DataType::Time64(TimeUnit::Microsecond) => ScalarValue::TimeMicrosecond(None),
+        DataType::Time64(TimeUnit::Nanosecond) => ScalarValue::TimeNanosecond(None),
+        DataType::Duration(unit) => ScalarValue::Duration(None, unit.clone()),
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't append a null to an array of type {:?}",
+                other
+            )))
+        }
+    })
+}
+
+// The items of a list are checked too, so a list can't be half appended
+fn check_type(data_type: &DataType, value: &ScalarValue) -> Result<()> {
+    match (data_type, value) {
+        (DataType::List(field), ScalarValue::List(values, item_type))
+            if field.data_type() == item_type =>
+        {
+            values
+                .iter()
+                .flatten()
+                .try_for_each(|value| check_type(item_type, value))
+        }
+        _ if value.get_datatype() == *data_type => Ok(()),
+        _ => Err(ArrowError::InvalidArgumentError(format!(
+            "Can't append a value of type {:?} to an array of type {:?}",
+            value.get_datatype(),
+            data_type
+        ))),
+    }
+}
+
+fn append_value(builder: &mut dyn ArrayBuilder, value: &ScalarValue) -> Result<()> {
+    match value {
+        ScalarValue::Boolean(value) => typed_append!(builder, BooleanBuilder, *value),
+        ScalarValue::Float32(value) => typed_append!(builder, Float32Builder, *value),
+        ScalarValue::Float64(value) => typed_append!(builder, Float64Builder, *value),
+        ScalarValue::Int8(value) => typed_append!(builder, Int8Builder, *value),
+        ScalarValue::Int16(value) => typed_append!(builder, Int16Builder, *value),
+        ScalarValue::Int32(value) => typed_append!(builder, Int32Builder, *value),
+        ScalarValue::Int64(value) => typed_append!(builder, Int64Builder, *value),
+        ScalarValue::UInt8(value) => typed_append!(builder, UInt8Builder, *value),
+        ScalarValue::UInt16(value) => typed_append!(builder, UInt16Builder, *value),
+        ScalarValue::UInt32(value) => typed_append!(builder, UInt32Builder, *value),
+        ScalarValue::UInt64(value) => typed_append!(builder, UInt64Builder, *value),
+        ScalarValue::Utf8(value) => typed_append!(builder, StringBuilder, value.as_deref()),
+        ScalarValue::LargeUtf8(value) => {
+            typed_append!(builder, LargeStringBuilder, value.as_deref())
+        }
+        ScalarValue::Date32(value) => typed_append!(builder, Date32Builder, *value),
+        ScalarValue::TimeMicrosecond(value) => {
+            typed_append!(builder, Time64MicrosecondBuilder, *value)
+        }
+        ScalarValue::TimeNanosecond(value) => {
+            typed_append!(builder, Time64NanosecondBuilder, *value)
+        }
+        ScalarValue::Duration(value, TimeUnit::Second) => {
+            typed_append!(builder, DurationSecondBuilder, *value)
+        }
+        ScalarValue::Duration(value, TimeUnit::Millisecond) => {
+            typed_append!(builder, DurationMillisecondBuilder, *value)
+        }
+        ScalarValue::Duration(value, TimeUnit::Microsecond) => {
+            typed_append!(builder, DurationMicrosecondBuilder, *value)
+        }
+        ScalarValue::Duration(value, TimeUnit::Nanosecond) => {
+            typed_append!(builder, DurationNanosecondBuilder, *value)
+        }
+        ScalarValue::List(values, _) => {
+            let builder = downcast_builder::<ListBuilder<BoxedBuilder>>(builder)?;
+            for value in values.iter().flatten() {
+                append_value(builder.values().0.as_mut(), value)?;
+            }
+            builder.append(values.is_some())
+        }
+    }
+}
+
+pub(super) fn downcast_builder<T: ArrayBuilder>(builder: &mut dyn ArrayBuilder) -> Result<&mut T> {
+    builder.as_any_mut().downcast_mut::<T>().ok_or_else(|| {
+        ArrowError::InvalidArgumentError(format!(
+            "The builder isn't a {}",
+            std::any::type_name::<T>()
+        ))
+    })
+}
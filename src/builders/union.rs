@@ -0,0 +1,211 @@
+// Union arrays hold values of several types in one column. Every row has
+// a type id that chooses its child array. Sparse unions have children as
+// long as the union, while dense unions only store the values of every
+// child and use an offset to find the value of the row
+use std::fmt;
+use std::str::FromStr;
+
+use arrow::{
+    array::{ArrayRef, UnionArray},
+    buffer::Buffer,
+    datatypes::{Field, ToByteSlice},
+    error::{ArrowError, Result},
+};
+
+use super::ScalarBuilder;
+use crate::ScalarValue;
+
+/// How the values of the children of a union are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnionMode {
+    Sparse,
+    Dense,
+}
+
+impl fmt::Display for UnionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            UnionMode::Sparse => "sparse",
+            UnionMode::Dense => "dense",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for UnionMode {
+    type Err = ArrowError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sparse" => Ok(UnionMode::Sparse),
+            "dense" => Ok(UnionMode::Dense),
+            other => Err(ArrowError::ParseError(format!(
+                "Unknown union mode {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Builds a sparse union from its type ids and children. All the children
+/// must have a value for every row, and the type id of a row is the index
+/// of the child holding its value
+pub fn sparse_union(type_ids: &[i8], children: Vec<(Field, ArrayRef)>) -> Result<UnionArray> {
+    check_type_ids(type_ids, children.len())?;
+    for (field, child) in &children {
+        if child.len() != type_ids.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The child {} has {} values but the union has {} rows",
+                field.name(),
+                child.len(),
+                type_ids.len()
+            )));
+        }
+    }
+
+    UnionArray::try_new(Buffer::from(type_ids.to_byte_slice()), None, children, None)
+}
+
+/// Builds a dense union from its type ids, offsets and children. The value
+/// of a row is at its offset in the child chosen by its type id
+pub fn dense_union(
+    type_ids: &[i8],
+    offsets: &[i32],
+    children: Vec<(Field, ArrayRef)>,
+) -> Result<UnionArray> {
+    check_type_ids(type_ids, children.len())?;
+    if offsets.len() != type_ids.len() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "There are {} offsets for {} type ids",
+            offsets.len(),
+            type_ids.len()
+        )));
+    }
+    for (row, (&type_id, &offset)) in type_ids.iter().zip(offsets).enumerate() {
+        let (field, child) = &children[type_id as usize];
+        if offset < 0 || offset as usize >= child.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The offset {} of row {} is out of the child {} with {} values",
+                offset,
+                row,
+                field.name(),
+                child.len()
+            )));
+        }
+    }
+
+    UnionArray::try_new(
+        Buffer::from(type_ids.to_byte_slice()),
+        Some(Buffer::from(offsets.to_byte_slice())),
+        children,
+        None,
+    )
+}
+
+/// Builds a union row by row from ScalarValues. The children are given as
+/// fields, and every value is appended with the type id of its child
+pub struct UnionArrayBuilder {
+    mode: UnionMode,
+    fields: Vec<Field>,
+    children: Vec<ScalarBuilder>,
+    type_ids: Vec<i8>,
+    offsets: Vec<i32>,
+}
+
+impl UnionArrayBuilder {
+    /// Creates a builder for a union of the fields, with room for
+    /// `capacity` rows
+    pub fn new(mode: UnionMode, fields: Vec<Field>, capacity: usize) -> Result<Self> {
+        if fields.len() > i8::MAX as usize + 1 {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "A union can't have {} children",
+                fields.len()
+            )));
+        }
+
+        let children = fields
+            .iter()
+            .map(|field| ScalarBuilder::new(field.data_type(), capacity))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            mode,
+            fields,
+            children,
+            type_ids: Vec::with_capacity(capacity),
+            offsets: Vec::with_capacity(capacity),
+        })
+    }
+
+    pub fn mode(&self) -> UnionMode {
+        self.mode
+    }
+
+    /// Number of rows appended since the last `finish`
+    pub fn len(&self) -> usize {
+        self.type_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.type_ids.is_empty()
+    }
+
+    /// Appends a value to the child with the type id. In sparse unions the
+    /// other children get a null
+    pub fn append(&mut self, type_id: i8, value: &ScalarValue) -> Result<()> {
+        check_type_ids(&[type_id], self.children.len())?;
+        let child = &mut self.children[type_id as usize];
+        child.check(value)?;
+
+        self.offsets.push(child.len() as i32);
+        child.append(value)?;
+        if self.mode == UnionMode::Sparse {
+            for (i, child) in self.children.iter_mut().enumerate() {
+                if i != type_id as usize {
+                    child.append_null()?;
+                }
+            }
+        }
+        self.type_ids.push(type_id);
+        Ok(())
+    }
+
+    /// Builds the union with the rows appended and empties the builder
+    pub fn finish(&mut self) -> Result<UnionArray> {
+        let children = self
+            .fields
+            .iter()
+            .zip(self.children.iter_mut())
+            .map(|(field, child)| {
+                // The children of sparse unions have nulls in the rows of
+                // the other children
+                let values = child.finish();
+                let field = Field::new(
+                    field.name(),
+                    values.data_type().clone(),
+                    field.is_nullable() || values.null_count() > 0,
+                );
+                (field, values)
+            })
+            .collect::<Vec<_>>();
+        let type_ids = std::mem::take(&mut self.type_ids);
+        let offsets = std::mem::take(&mut self.offsets);
+
+        match self.mode {
+            UnionMode::Sparse => sparse_union(&type_ids, children),
+            UnionMode::Dense => dense_union(&type_ids, &offsets, children),
+        }
+    }
+}
+
+fn check_type_ids(type_ids: &[i8], children: usize) -> Result<()> {
+    match type_ids
+        .iter()
+        .find(|&&type_id| type_id < 0 || type_id as usize >= children)
+    {
+        Some(type_id) => Err(ArrowError::InvalidArgumentError(format!(
+            "The type id {} doesn't match any of the {} children",
+            type_id, children
+        ))),
+        None => Ok(()),
+    }
+}
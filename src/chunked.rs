@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{make_array, ArrayData, ArrayRef},
+    buffer::Buffer,
+    compute::concat,
+    datatypes::{DataType, DateUnit, Field, ToByteSlice},
+    error::{ArrowError, Result},
+};
+
+use crate::compute::{self, aggregate, NullTreatment, TDigest};
+use crate::ScalarValue;
+
+/// Column of a Table formed by one array for every RecordBatch. The
+/// aggregates are calculated for every chunk and the partial results are
+/// combined, so the chunks are never concatenated
+#[derive(Debug, Clone)]
+pub struct ChunkedColumn {
+    field: Field,
+    chunks: Vec<ArrayRef>,
+}
+
+impl ChunkedColumn {
+    /// Creates the column from its chunks. All of them must have the type
+    /// of the field
+    pub fn try_new(field: Field, chunks: Vec<ArrayRef>) -> Result<Self> {
+        if let Some(chunk) = chunks
+            .iter()
+            .find(|chunk| chunk.data_type() != field.data_type())
+        {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The column {} has type {:?} but a chunk has type {:?}",
+                field.name(),
+                field.data_type(),
+                chunk.data_type()
+            )));
+        }
+
+        Ok(Self { field, chunks })
+    }
+
+    pub fn field(&self) -> &Field {
+        &self.field
+    }
+
+    pub fn data_type(&self) -> &DataType {
+        self.field.data_type()
+    }
+
+    pub fn chunks(&self) -> &[ArrayRef] {
+        &self.chunks
+    }
+
+    /// Number of values in all the chunks
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.null_count()).sum()
+    }
+
+    /// Sum of the values of the column. See compute::aggregate::sum
+    pub fn sum(&self) -> Result<ScalarValue> {
+        let mut result = aggregate::sum(&self.empty()?)?;
+        for chunk in &self.chunks {
+            result = aggregate::combine_sums(result, aggregate::sum(chunk)?)?;
+        }
+        Ok(result)
+    }
+
+    /// Smallest value of the column
+    pub fn min(&self) -> Result<ScalarValue> {
+        let mut result = aggregate::min(&self.empty()?)?;
+        for chunk in &self.chunks {
+            result = aggregate::combine_mins(result, aggregate::min(chunk)?)?;
+        }
+        Ok(result)
+    }
+
+    /// Largest value of the column
+    pub fn max(&self) -> Result<ScalarValue> {
+        let mut result = aggregate::max(&self.empty()?)?;
+        for chunk in &self.chunks {
+            result = aggregate::combine_maxs(result, aggregate::max(chunk)?)?;
+        }
+        Ok(result)
+    }
+
+    /// Average of the values of the column as a Float64
+    pub fn mean(&self) -> Result<ScalarValue> {
+        aggregate::mean_from_partials(&self.sum()?, &self.count())
+    }
+
+    /// Number of values that aren't null
+    pub fn count(&self) -> ScalarValue {
+        ScalarValue::UInt64(Some((self.len() - self.null_count()) as u64))
+    }
+
+    /// Sample variance of the column. The moments of every chunk are
+    /// merged, so the result is as precise as with a single chunk
+    pub fn var(&self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(self.moments()?.var()))
+    }
+
+    /// Sample standard deviation of the column
+    pub fn stddev(&self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(self.moments()?.stddev()))
+    }
+
+    /// Skewness of the column. See compute::aggregate::skew
+    pub fn skew(&self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(self.moments()?.skew()))
+    }
+
+    /// Estimates the quantile `q` of the column in a single pass. The
+    /// chunks are added to the same t-digest one after the other
+    pub fn approx_quantile(&self, q: f64) -> Result<ScalarValue> {
+        let mut digest = TDigest::default();
+        for chunk in &self.chunks {
+            digest.add_array(chunk)?;
+        }
+        Ok(ScalarValue::Float64(digest.quantile(q)?))
+    }
+
+    /// Concatenates the chunks into a single array, for the operations that
+    /// need the whole column in contiguous buffers like sorting or joins. A
+    /// column with a single chunk returns it without copying
+    pub fn flatten(&self) -> Result<ArrayRef> {
+        match self.chunks.as_slice() {
+            [] => self.empty(),
+            [chunk] => Ok(chunk.clone()),
+            chunks => {
+                compute::check_supported_type(self.data_type())?;
+                let chunks = chunks
+                    .iter()
+                    .map(|chunk| compute::compact_offsets(&compute::decode_dictionary(chunk)?))
+                    .collect::<Result<Vec<_>>>()?;
+                let flattened = concat(
+                    &chunks
+                        .iter()
+                        .map(|chunk| chunk.as_ref())
+                        .collect::<Vec<_>>(),
+                )?;
+                compute::encode_dictionary(&flattened, self.data_type())
+            }
+        }
+    }
+
+    /// Moves the values of the column `offset` rows, like compute::shift.
+    /// The values move across the chunks, which keep their lengths
+    pub fn shift(&self, offset: i64) -> Result<Self> {
+        Ok(Self {
+            field: self.field.clone(),
+            chunks: compute::shift_chunks(&self.chunks, offset)?,
+        })
+    }
+
+    /// Fills the nulls of the column with the last value before them. The
+    /// values are carried across the chunks
+    pub fn fill_forward(&self) -> Result<Self> {
+        self.apply_flattened(compute::fill_forward)
+    }
+
+    /// Fills the nulls of the column with the first value after them
+    pub fn fill_backward(&self) -> Result<Self> {
+        self.apply_flattened(compute::fill_backward)
+    }
+
+    /// Interpolates the nulls between two values of the column, even if the
+    /// values are in different chunks. The result is a Float64 column
+    pub fn interpolate_linear(&self) -> Result<Self> {
+        self.apply_flattened(compute::interpolate_linear)
+    }
+
+    /// Cumulative sum of the column across all the chunks, see
+    /// compute::cumsum
+    pub fn cumsum(&self, nulls: NullTreatment) -> Result<Self> {
+        self.apply_flattened(|column| compute::cumsum(column, nulls))
+    }
+
+    // Applies a kernel to the whole column and splits the result in chunks
+    // with the lengths of the original ones
+    fn apply_flattened<F>(&self, kernel: F) -> Result<Self>
+    where
+        F: Fn(&ArrayRef) -> Result<ArrayRef>,
+    {
+        let result = kernel(&self.flatten()?)?;
+        let field = Field::new(
+            self.field.name(),
+            result.data_type().clone(),
+            self.field.is_nullable(),
+        );
+
+        let mut offset = 0;
+        let mut chunks = Vec::with_capacity(self.chunks.len());
+        for chunk in &self.chunks {
+            chunks.push(result.slice(offset, chunk.len()));
+            offset += chunk.len();
+        }
+
+        Ok(Self { field, chunks })
+    }
+
+    fn moments(&self) -> Result<aggregate::Moments> {
+        let mut moments = aggregate::moments(&self.empty()?)?;
+        for chunk in &self.chunks {
+            moments = moments.merge(aggregate::moments(chunk)?);
+        }
+        Ok(moments)
+    }
+
+    // The aggregates of an empty array are the starting point, so a column
+    // without chunks returns a null of the right type. The empty array is
+    // only needed for the types supported by the aggregates and flatten
+    fn empty(&self) -> Result<ArrayRef> {
+        let values = Buffer::from(&[0u8; 0]);
+        let buffers = match self.data_type() {
+            DataType::Utf8 => vec![Buffer::from(0i32.to_byte_slice()), values],
+            DataType::Boolean | DataType::Date32(DateUnit::Day) => vec![values],
+            data_type if DataType::is_numeric(data_type) => vec![values],
+            other => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Can't create an empty column of type {:?}",
+                    other
+                )))
+            }
+        };
+
+        let data = ArrayData::new(
+            self.data_type().clone(),
+            0,
+            Some(0),
+            None,
+            0,
+            buffers,
+            vec![],
+        );
+        Ok(make_array(Arc::new(data)))
+    }
+}
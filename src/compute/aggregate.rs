@@ -0,0 +1,377 @@
+use std::cmp::Ordering;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array, Int16Array,
+        Int32Array, Int64Array, Int8Array, StringArray, UInt16Array, UInt32Array, UInt64Array,
+        UInt8Array,
+    },
+    compute::kernels::aggregate,
+    datatypes::{DataType, DateUnit},
+    error::{ArrowError, Result},
+};
+
+use super::TDigest;
+use crate::downcast::downcast_array;
+use crate::ScalarValue;
+
+// Adds the values of an integer array using a wider type. The sum is null
+// if the array doesn't have any value
+macro_rules! sum_ints {
+    ($array:expr, $ARRAYTYPE:ident, $SCALAR:ident, $NATIVE:ty) => {{
+        let array = downcast_array::<$ARRAYTYPE>($array.as_ref())?;
+        if array.null_count() == array.len() {
+            ScalarValue::$SCALAR(None)
+        } else {
+            let sum = array
+                .iter()
+                .flatten()
+                .try_fold(0 as $NATIVE, |sum, value| sum.checked_add(value as $NATIVE))
+                .ok_or_else(|| overflow("sum"))?;
+            ScalarValue::$SCALAR(Some(sum))
+        }
+    }};
+}
+
+macro_rules! sum_floats {
+    ($array:expr, $ARRAYTYPE:ident) => {{
+        let array = downcast_array::<$ARRAYTYPE>($array.as_ref())?;
+        if array.null_count() == array.len() {
+            ScalarValue::Float64(None)
+        } else {
+            ScalarValue::Float64(Some(array.iter().flatten().map(|value| value as f64).sum()))
+        }
+    }};
+}
+
+// Applies an arrow aggregate kernel that returns a value of the same type
+// as the array
+macro_rules! typed_aggregate {
+    ($array:expr, $ARRAYTYPE:ident, $SCALAR:ident, $KERNEL:path) => {{
+        let array = downcast_array::<$ARRAYTYPE>($array.as_ref())?;
+        ScalarValue::$SCALAR($KERNEL(array))
+    }};
+}
+
+// Adds every value of the array to the moments
+macro_rules! typed_moments {
+    ($array:expr, $ARRAYTYPE:ident) => {{
+        let array = downcast_array::<$ARRAYTYPE>($array.as_ref())?;
+        let mut moments = Moments::default();
+        for value in array.iter().flatten() {
+            moments.push(value as f64);
+        }
+        moments
+    }};
+}
+
+/// Adds the values of a numeric column ignoring the nulls. Integers are
+/// added as Int64 or UInt64 and floats as Float64, so the sum of a small
+/// type doesn't overflow. The result is null if the column doesn't have
+/// any value
+pub fn sum(array: &ArrayRef) -> Result<ScalarValue> {
+    Ok(match array.data_type() {
+        DataType::Int8 => sum_ints!(array, Int8Array, Int64, i64),
+        DataType::Int16 => sum_ints!(array, Int16Array, Int64, i64),
+        DataType::Int32 => sum_ints!(array, Int32Array, Int64, i64),
+        DataType::Int64 => sum_ints!(array, Int64Array, Int64, i64),
+        DataType::UInt8 => sum_ints!(array, UInt8Array, UInt64, u64),
+        DataType::UInt16 => sum_ints!(array, UInt16Array, UInt64, u64),
+        DataType::UInt32 => sum_ints!(array, UInt32Array, UInt64, u64),
+        DataType::UInt64 => sum_ints!(array, UInt64Array, UInt64, u64),
+        DataType::Float32 => sum_floats!(array, Float32Array),
+        DataType::Float64 => sum_floats!(array, Float64Array),
+        other => return Err(unsupported("sum", other)),
+    })
+}
+
+/// Smallest value of the column ignoring the nulls. Besides the numeric
+/// types it works with booleans, strings and dates
+pub fn min(array: &ArrayRef) -> Result<ScalarValue> {
+    Ok(match array.data_type() {
+        DataType::Boolean => typed_aggregate!(array, BooleanArray, Boolean, aggregate::min_boolean),
+        DataType::Int8 => typed_aggregate!(array, Int8Array, Int8, aggregate::min),
+        DataType::Int16 => typed_aggregate!(array, Int16Array, Int16, aggregate::min),
+        DataType::Int32 => typed_aggregate!(array, Int32Array, Int32, aggregate::min),
+        DataType::Int64 => typed_aggregate!(array, Int64Array, Int64, aggregate::min),
+        DataType::UInt8 => typed_aggregate!(array, UInt8Array, UInt8, aggregate::min),
+        DataType::UInt16 => typed_aggregate!(array, UInt16Array, UInt16, aggregate::min),
+        DataType::UInt32 => typed_aggregate!(array, UInt32Array, UInt32, aggregate::min),
+        DataType::UInt64 => typed_aggregate!(array, UInt64Array, UInt64, aggregate::min),
+        DataType::Float32 => typed_aggregate!(array, Float32Array, Float32, aggregate::min),
+        DataType::Float64 => typed_aggregate!(array, Float64Array, Float64, aggregate::min),
+        DataType::Date32(DateUnit::Day) => {
+            typed_aggregate!(array, Date32Array, Date32, aggregate::min)
+        }
+        DataType::Utf8 => {
+            let array = downcast_array::<StringArray>(array.as_ref())?;
+            ScalarValue::Utf8(aggregate::min_string(array).map(str::to_string))
+        }
+        other => return Err(unsupported("min", other)),
+    })
+}
+
+/// Largest value of the column ignoring the nulls. It works with the same
+/// types as min
+pub fn max(array: &ArrayRef) -> Result<ScalarValue> {
+    Ok(match array.data_type() {
+        DataType::Boolean => typed_aggregate!(array, BooleanArray, Boolean, aggregate::max_boolean),
+        DataType::Int8 => typed_aggregate!(array, Int8Array, Int8, aggregate::max),
+        DataType::Int16 => typed_aggregate!(array, Int16Array, Int16, aggregate::max),
+        DataType::Int32 => typed_aggregate!(array, Int32Array, Int32, aggregate::max),
+        DataType::Int64 => typed_aggregate!(array, Int64Array, Int64, aggregate::max),
+        DataType::UInt8 => typed_aggregate!(array, UInt8Array, UInt8, aggregate::max),
+        DataType::UInt16 => typed_aggregate!(array, UInt16Array, UInt16, aggregate::max),
+        DataType::UInt32 => typed_aggregate!(array, UInt32Array, UInt32, aggregate::max),
+        DataType::UInt64 => typed_aggregate!(array, UInt64Array, UInt64, aggregate::max),
+        DataType::Float32 => typed_aggregate!(array, Float32Array, Float32, aggregate::max),
+        DataType::Float64 => typed_aggregate!(array, Float64Array, Float64, aggregate::max),
+        DataType::Date32(DateUnit::Day) => {
+            typed_aggregate!(array, Date32Array, Date32, aggregate::max)
+        }
+        DataType::Utf8 => {
+            let array = downcast_array::<StringArray>(array.as_ref())?;
+            ScalarValue::Utf8(aggregate::max_string(array).map(str::to_string))
+        }
+        other => return Err(unsupported("max", other)),
+    })
+}
+
+/// Average of the values of a numeric column as a Float64, ignoring the
+/// nulls
+pub fn mean(array: &ArrayRef) -> Result<ScalarValue> {
+    mean_from_partials(&sum(array)?, &count(array))
+}
+
+/// Number of values in the column that aren't null
+pub fn count(array: &ArrayRef) -> ScalarValue {
+    ScalarValue::UInt64(Some((array.len() - array.null_count()) as u64))
+}
+
+/// Sample variance of a numeric column as a Float64, ignoring the nulls.
+/// It's null if the column has less than two values
+pub fn var(array: &ArrayRef) -> Result<ScalarValue> {
+    Ok(ScalarValue::Float64(moments(array)?.var()))
+}
+
+/// Sample standard deviation of a numeric column as a Float64
+pub fn stddev(array: &ArrayRef) -> Result<ScalarValue> {
+    Ok(ScalarValue::Float64(moments(array)?.stddev()))
+}
+
+/// Skewness of a numeric column as a Float64. It's positive when the tail
+/// of values larger than the mean is longer, and null if all the values
+/// are the same
+pub fn skew(array: &ArrayRef) -> Result<ScalarValue> {
+    Ok(ScalarValue::Float64(moments(array)?.skew()))
+}
+
+/// Estimates the quantile `q` of a numeric column with a t-digest, without
+/// sorting the values. `q` goes from 0 to 1, so 0.5 gives the median. The
+/// result is a Float64 that is null if the column doesn't have any value
+pub fn approx_quantile(array: &ArrayRef, q: f64) -> Result<ScalarValue> {
+    let mut digest = TDigest::default();
+    digest.add_array(array)?;
+    Ok(ScalarValue::Float64(digest.quantile(q)?))
+}
+
+/// Count, mean and the sums of the second and third powers of the
+/// differences to the mean. They are updated one value at a time using
+/// Welford's method, which doesn't lose precision like adding the squares
+/// of the values
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Moments {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+}
+
+impl Moments {
+    fn push(&mut self, value: f64) {
+        let n = (self.count + 1) as f64;
+        let delta = value - self.mean;
+        let delta_n = delta / n;
+        let term = delta * delta_n * (n - 1.0);
+
+        self.mean += delta_n;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+        self.count += 1;
+    }
+
+    /// Combines the moments of two chunks as if all the values were pushed
+    /// to the same one. See Pébay, "Formulas for robust, one-pass parallel
+    /// computation of covariances and arbitrary-order statistical moments"
+    pub(crate) fn merge(self, other: Moments) -> Moments {
+        if self.count == 0 {
+            return other;
+        }
+        if other.count == 0 {
+            return self;
+        }
+
+        let (a, b) = (self.count as f64, other.count as f64);
+        let n = a + b;
+        let delta = other.mean - self.mean;
+
+        Moments {
+            count: self.count + other.count,
+            mean: self.mean + delta * b / n,
+            m2: self.m2 + other.m2 + delta * delta * a * b / n,
+            m3: self.m3
+                + other.m3
+                + delta.powi(3) * a * b * (a - b) / (n * n)
+                + 3.0 * delta * (a * other.m2 - b * self.m2) / n,
+        }
+    }
+
+    pub(crate) fn var(&self) -> Option<f64> {
+        match self.count {
+            0 | 1 => None,
+            count => Some(self.m2 / (count - 1) as f64),
+        }
+    }
+
+    pub(crate) fn stddev(&self) -> Option<f64> {
+        self.var().map(f64::sqrt)
+    }
+
+    pub(crate) fn skew(&self) -> Option<f64> {
+        match self.m2 > 0.0 {
+            true => Some((self.count as f64).sqrt() * self.m3 / self.m2.powf(1.5)),
+            false => None,
+        }
+    }
+}
+
+pub(crate) fn moments(array: &ArrayRef) -> Result<Moments> {
+    Ok(match array.data_type() {
+        DataType::Int8 => typed_moments!(array, Int8Array),
+        DataType::Int16 => typed_moments!(array, Int16Array),
+        DataType::Int32 => typed_moments!(array, Int32Array),
+        DataType::Int64 => typed_moments!(array, Int64Array),
+        DataType::UInt8 => typed_moments!(array, UInt8Array),
+        DataType::UInt16 => typed_moments!(array, UInt16Array),
+        DataType::UInt32 => typed_moments!(array, UInt32Array),
+        DataType::UInt64 => typed_moments!(array, UInt64Array),
+        DataType::Float32 => typed_moments!(array, Float32Array),
+        DataType::Float64 => typed_moments!(array, Float64Array),
+        other => return Err(unsupported("moments", other)),
+    })
+}
+
+// The partial results of every chunk of a column are combined to get the
+// result of the whole column
+
+pub(crate) fn combine_sums(left: ScalarValue, right: ScalarValue) -> Result<ScalarValue> {
+    Ok(match (left, right) {
+        (ScalarValue::Int64(left), ScalarValue::Int64(right)) => {
+            ScalarValue::Int64(combine_options(left, right, i64::checked_add)?)
+        }
+        (ScalarValue::UInt64(left), ScalarValue::UInt64(right)) => {
+            ScalarValue::UInt64(combine_options(left, right, u64::checked_add)?)
+        }
+        (ScalarValue::Float64(left), ScalarValue::Float64(right)) => {
+            ScalarValue::Float64(combine_options(left, right, |a, b| Some(a + b))?)
+        }
+        (left, right) => return Err(mismatch(&left, &right)),
+    })
+}
+
+/// Keeps the smallest of two partial results. Nulls are ignored
+pub(crate) fn combine_mins(left: ScalarValue, right: ScalarValue) -> Result<ScalarValue> {
+    combine_by(left, right, Ordering::Less)
+}
+
+/// Keeps the largest of two partial results. Nulls are ignored
+pub(crate) fn combine_maxs(left: ScalarValue, right: ScalarValue) -> Result<ScalarValue> {
+    combine_by(left, right, Ordering::Greater)
+}
+
+pub(crate) fn mean_from_partials(sum: &ScalarValue, count: &ScalarValue) -> Result<ScalarValue> {
+    let count = match count {
+        ScalarValue::UInt64(Some(count)) => *count,
+        other => return Err(unsupported("mean", &other.get_datatype())),
+    };
+
+    let sum = match sum {
+        ScalarValue::Int64(sum) => sum.map(|sum| sum as f64),
+        ScalarValue::UInt64(sum) => sum.map(|sum| sum as f64),
+        ScalarValue::Float64(sum) => *sum,
+        other => return Err(unsupported("mean", &other.get_datatype())),
+    };
+
+    Ok(ScalarValue::Float64(
+        sum.filter(|_| count > 0).map(|sum| sum / count as f64),
+    ))
+}
+
+fn combine_options<T, F>(left: Option<T>, right: Option<T>, op: F) -> Result<Option<T>>
+where
+    F: Fn(T, T) -> Option<T>,
+{
+    Ok(match (left, right) {
+        (Some(left), Some(right)) => Some(op(left, right).ok_or_else(|| overflow("sum"))?),
+        (left, right) => left.or(right),
+    })
+}
+
+fn combine_by(left: ScalarValue, right: ScalarValue, keep: Ordering) -> Result<ScalarValue> {
+    if left.is_null() {
+        return Ok(right);
+    }
+    if right.is_null() {
+        return Ok(left);
+    }
+
+    match compare(&left, &right) {
+        Some(ordering) if ordering == keep => Ok(left),
+        Some(_) => Ok(right),
+        None => Err(mismatch(&left, &right)),
+    }
+}
+
+// Compares two values of the same type. Floats that can't be compared,
+// like NaN, keep the left value
+fn compare(left: &ScalarValue, right: &ScalarValue) -> Option<Ordering> {
+    match (left, right) {
+        (ScalarValue::Boolean(l), ScalarValue::Boolean(r)) => l.partial_cmp(r),
+        (ScalarValue::Int8(l), ScalarValue::Int8(r)) => l.partial_cmp(r),
+        (ScalarValue::Int16(l), ScalarValue::Int16(r)) => l.partial_cmp(r),
+        (ScalarValue::Int32(l), ScalarValue::Int32(r)) => l.partial_cmp(r),
+        (ScalarValue::Int64(l), ScalarValue::Int64(r)) => l.partial_cmp(r),
+        (ScalarValue::UInt8(l), ScalarValue::UInt8(r)) => l.partial_cmp(r),
+        (ScalarValue::UInt16(l), ScalarValue::UInt16(r)) => l.partial_cmp(r),
+        (ScalarValue::UInt32(l), ScalarValue::UInt32(r)) => l.partial_cmp(r),
+        (ScalarValue::UInt64(l), ScalarValue::UInt64(r)) => l.partial_cmp(r),
+        (ScalarValue::Float32(l), ScalarValue::Float32(r)) => {
+            Some(l.partial_cmp(r).unwrap_or(Ordering::Equal))
+        }
+        (ScalarValue::Float64(l), ScalarValue::Float64(r)) => {
+            Some(l.partial_cmp(r).unwrap_or(Ordering::Equal))
+        }
+        (ScalarValue::Date32(l), ScalarValue::Date32(r)) => l.partial_cmp(r),
+        (ScalarValue::Utf8(l), ScalarValue::Utf8(r)) => l.partial_cmp(r),
+        _ => None,
+    }
+}
+
+fn unsupported(name: &str, data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Can't calculate the {} of a column of type {:?}",
+        name, data_type
+    ))
+}
+
+fn mismatch(left: &ScalarValue, right: &ScalarValue) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Can't combine partial results of types {:?} and {:?}",
+        left.get_datatype(),
+        right.get_datatype()
+    ))
+}
+
+fn overflow(name: &str) -> ArrowError {
+    ArrowError::ComputeError(format!("Overflow calculating the {}", name))
+}
@@ -0,0 +1,103 @@
+// Custom functions applied to every value of a column. The typed version
+// works on the native values of a PrimitiveArray, and the dynamic one
+// works on ScalarValues so it can be used with any column
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array, Int16Array,
+        Int32Array, Int64Array, Int8Array, LargeStringArray, PrimitiveArray, StringArray,
+        UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    datatypes::{ArrowPrimitiveType, DataType, DateUnit},
+    error::{ArrowError, Result},
+};
+
+use crate::ScalarValue;
+
+// Builds an array with the values that have the variant of the type
+macro_rules! typed_build {
+    ($values:expr, $data_type:expr, $ARRAYTYPE:ident, $SCALAR:ident) => {{
+        let array = $values
+            .into_iter()
+            .map(|value| match value {
+                ScalarValue::$SCALAR(value) => Ok(value),
+                value if value.is_null() => Ok(None),
+                other => Err(mismatch($data_type, &other)),
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .collect::<$ARRAYTYPE>();
+        Arc::new(array) as ArrayRef
+    }};
+}
+
+/// Calls the function with every valid value of the array. The nulls of
+/// the array are kept without calling the function, and the function
+/// returns None to make the value of a row null
+pub fn apply_unary<T, R, F>(array: &PrimitiveArray<T>, op: F) -> PrimitiveArray<R>
+where
+    T: ArrowPrimitiveType,
+    R: ArrowPrimitiveType,
+    F: Fn(T::Native) -> Option<R::Native>,
+{
+    (0..array.len())
+        .map(|i| match array.is_valid(i) {
+            true => op(array.value(i)),
+            false => None,
+        })
+        .collect()
+}
+
+/// Calls the function with the value of every row of the column as a
+/// ScalarValue, nulls included, and builds a column of type `data_type`
+/// with the results. It's slower than `apply_unary` but it works with
+/// columns of any type without downcasting them
+pub fn apply_scalar<F>(column: &ArrayRef, data_type: &DataType, op: F) -> Result<ArrayRef>
+where
+    F: Fn(ScalarValue) -> Result<ScalarValue>,
+{
+    let values = (0..column.len())
+        .map(|i| {
+            let value =
+                ScalarValue::try_from_array(column, i).map_err(ArrowError::InvalidArgumentError)?;
+            op(value)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    build_array(values, data_type)
+}
+
+// Builds a column of the type from the values. Nulls of any type are
+// accepted, but the valid values must have the type of the column
+pub(crate) fn build_array(values: Vec<ScalarValue>, data_type: &DataType) -> Result<ArrayRef> {
+    Ok(match data_type {
+        DataType::Boolean => typed_build!(values, data_type, BooleanArray, Boolean),
+        DataType::Int8 => typed_build!(values, data_type, Int8Array, Int8),
+        DataType::Int16 => typed_build!(values, data_type, Int16Array, Int16),
+        DataType::Int32 => typed_build!(values, data_type, Int32Array, Int32),
+        DataType::Int64 => typed_build!(values, data_type, Int64Array, Int64),
+        DataType::UInt8 => typed_build!(values, data_type, UInt8Array, UInt8),
+        DataType::UInt16 => typed_build!(values, data_type, UInt16Array, UInt16),
+        DataType::UInt32 => typed_build!(values, data_type, UInt32Array, UInt32),
+        DataType::UInt64 => typed_build!(values, data_type, UInt64Array, UInt64),
+        DataType::Float32 => typed_build!(values, data_type, Float32Array, Float32),
+        DataType::Float64 => typed_build!(values, data_type, Float64Array, Float64),
+        DataType::Utf8 => typed_build!(values, data_type, StringArray, Utf8),
+        DataType::LargeUtf8 => typed_build!(values, data_type, LargeStringArray, LargeUtf8),
+        DataType::Date32(DateUnit::Day) => typed_build!(values, data_type, Date32Array, Date32),
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't build a column of type {:?} from scalar values",
+                other
+            )))
+        }
+    })
+}
+
+fn mismatch(data_type: &DataType, value: &ScalarValue) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Expected a value of type {:?} but got {:?}",
+        data_type, value
+    ))
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        PrimitiveArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    datatypes::{ArrowPrimitiveType, DataType},
+    error::{ArrowError, Result},
+};
+
+use super::coerce_columns;
+use crate::downcast::downcast_array;
+
+// Downcasts both columns and applies the operation with the overflow mode
+macro_rules! typed_mode_op {
+    ($left:expr, $right:expr, $ARRAYTYPE:ident, $op:expr, $mode:expr) => {{
+        let left = downcast_array::<$ARRAYTYPE>($left.as_ref())?;
+        let right = downcast_array::<$ARRAYTYPE>($right.as_ref())?;
+        Ok(Arc::new(mode_kernel(left, right, $op, $mode)?) as ArrayRef)
+    }};
+}
+
+// Chooses the array type from the DataType of the columns, which was
+// already checked to be the same in both, and calls the typed macro
+macro_rules! numeric_op {
+    ($left:expr, $right:expr, $name:expr, $TYPED:ident $(, $args:expr)*) => {{
+        match $left.data_type() {
+            DataType::Int8 => $TYPED!($left, $right, Int8Array $(, $args)*),
+            DataType::Int16 => $TYPED!($left, $right, Int16Array $(, $args)*),
+            DataType::Int32 => $TYPED!($left, $right, Int32Array $(, $args)*),
+            DataType::Int64 => $TYPED!($left, $right, Int64Array $(, $args)*),
+            DataType::UInt8 => $TYPED!($left, $right, UInt8Array $(, $args)*),
+            DataType::UInt16 => $TYPED!($left, $right, UInt16Array $(, $args)*),
+            DataType::UInt32 => $TYPED!($left, $right, UInt32Array $(, $args)*),
+            DataType::UInt64 => $TYPED!($left, $right, UInt64Array $(, $args)*),
+            DataType::Float32 => $TYPED!($left, $right, Float32Array $(, $args)*),
+            DataType::Float64 => $TYPED!($left, $right, Float64Array $(, $args)*),
+            other => Err(ArrowError::InvalidArgumentError(format!(
+                "Can't {} columns of type {:?}",
+                $name, other
+            ))),
+        }
+    }};
+}
+
+// Integer operations for every overflow mode. Floats don't overflow, they
+// become infinite, so the mode doesn't change them
+macro_rules! int_arithmetic {
+    ($($NATIVE:ty),*) => {
+        $(
+            impl ModeArithmetic for $NATIVE {
+                fn apply(self, other: Self, op: Op, mode: ArithmeticMode) -> Option<Self> {
+                    match (op, mode) {
+                        (Op::Add, ArithmeticMode::Wrapping) => Some(self.wrapping_add(other)),
+                        (Op::Add, ArithmeticMode::Checked) => self.checked_add(other),
+                        (Op::Add, ArithmeticMode::Saturating) => Some(self.saturating_add(other)),
+                        (Op::Subtract, ArithmeticMode::Wrapping) => Some(self.wrapping_sub(other)),
+                        (Op::Subtract, ArithmeticMode::Checked) => self.checked_sub(other),
+                        (Op::Subtract, ArithmeticMode::Saturating) => {
+                            Some(self.saturating_sub(other))
+                        }
+                        (Op::Multiply, ArithmeticMode::Wrapping) => Some(self.wrapping_mul(other)),
+                        (Op::Multiply, ArithmeticMode::Checked) => self.checked_mul(other),
+                        (Op::Multiply, ArithmeticMode::Saturating) => {
+                            Some(self.saturating_mul(other))
+                        }
+                        // MIN / -1 doesn't fit in the type in any mode
+                        (Op::Divide, _) => self.checked_div(other),
+                    }
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! float_arithmetic {
+    ($($NATIVE:ty),*) => {
+        $(
+            impl ModeArithmetic for $NATIVE {
+                fn apply(self, other: Self, op: Op, _mode: ArithmeticMode) -> Option<Self> {
+                    Some(match op {
+                        Op::Add => self + other,
+                        Op::Subtract => self - other,
+                        Op::Multiply => self * other,
+                        Op::Divide => self / other,
+                    })
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0.0
+                }
+            }
+        )*
+    };
+}
+
+/// What the integer kernels do when a result doesn't fit in the type of
+/// the columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArithmeticMode {
+    /// The result wraps around, so i32::MAX + 1 is i32::MIN. It's the mode
+    /// used by add, subtract and multiply
+    #[default]
+    Wrapping,
+    /// The kernel fails with a ComputeError
+    Checked,
+    /// The result is clamped to the minimum or the maximum of the type
+    Saturating,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Op::Add => "add",
+            Op::Subtract => "subtract",
+            Op::Multiply => "multiply",
+            Op::Divide => "divide",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+trait ModeArithmetic: Sized {
+    fn apply(self, other: Self, op: Op, mode: ArithmeticMode) -> Option<Self>;
+
+    fn is_zero(&self) -> bool;
+}
+
+int_arithmetic!(i8, i16, i32, i64, u8, u16, u32, u64);
+float_arithmetic!(f32, f64);
+
+/// Adds two numeric columns. Columns of different types are cast to their
+/// common type first. The result is null where any of the columns is null
+pub fn add(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    add_with_mode(left, right, ArithmeticMode::default())
+}
+
+/// Subtracts the right column from the left one. Both columns have to be
+/// numeric, and they are cast to their common type
+pub fn subtract(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    subtract_with_mode(left, right, ArithmeticMode::default())
+}
+
+/// Multiplies two numeric columns, casting them to their common type
+pub fn multiply(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    multiply_with_mode(left, right, ArithmeticMode::default())
+}
+
+/// Divides the left column by the right one. Fails with DivideByZero if a
+/// value of the right column is zero, including floats. Integer columns
+/// give an integer division even if they have different types, except a
+/// UInt64 with a signed integer, which are divided as Float64. It fails
+/// with a ComputeError if the quotient overflows, like MIN / -1
+pub fn divide(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    binary_with_mode(left, right, Op::Divide, ArithmeticMode::Checked)
+}
+
+/// Adds two numeric columns handling the integer overflows with the mode
+pub fn add_with_mode(left: &ArrayRef, right: &ArrayRef, mode: ArithmeticMode) -> Result<ArrayRef> {
+    binary_with_mode(left, right, Op::Add, mode)
+}
+
+/// Subtracts the right column from the left one handling the integer
+/// overflows with the mode
+pub fn subtract_with_mode(
+    left: &ArrayRef,
+    right: &ArrayRef,
+    mode: ArithmeticMode,
+) -> Result<ArrayRef> {
+    binary_with_mode(left, right, Op::Subtract, mode)
+}
+
+/// Multiplies two numeric columns handling the integer overflows with the
+/// mode
+pub fn multiply_with_mode(
+    left: &ArrayRef,
+    right: &ArrayRef,
+    mode: ArithmeticMode,
+) -> Result<ArrayRef> {
+    binary_with_mode(left, right, Op::Multiply, mode)
+}
+
+/// Adds two numeric columns failing if a sum overflows
+pub fn add_checked(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    add_with_mode(left, right, ArithmeticMode::Checked)
+}
+
+/// Multiplies two numeric columns failing if a product overflows
+pub fn multiply_checked(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    multiply_with_mode(left, right, ArithmeticMode::Checked)
+}
+
+/// Adds two numeric columns clamping the sums that overflow
+pub fn add_saturating(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    add_with_mode(left, right, ArithmeticMode::Saturating)
+}
+
+/// Multiplies two numeric columns clamping the products that overflow
+pub fn multiply_saturating(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef> {
+    multiply_with_mode(left, right, ArithmeticMode::Saturating)
+}
+
+fn binary_with_mode(
+    left: &ArrayRef,
+    right: &ArrayRef,
+    op: Op,
+    mode: ArithmeticMode,
+) -> Result<ArrayRef> {
+    let (left, right) = coerce_columns(left, right)?;
+    numeric_op!(left, right, op, typed_mode_op, op, mode)
+}
+
+fn mode_kernel<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+    op: Op,
+    mode: ArithmeticMode,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: ModeArithmetic,
+{
+    if left.len() != right.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Can't {} columns with {} and {} rows",
+            op,
+            left.len(),
+            right.len()
+        )));
+    }
+
+    (0..left.len())
+        .map(|i| {
+            if left.is_null(i) || right.is_null(i) {
+                return Ok(None);
+            }
+            if let Op::Divide = op {
+                if right.value(i).is_zero() {
+                    return Err(ArrowError::DivideByZero);
+                }
+            }
+            left.value(i)
+                .apply(right.value(i), op, mode)
+                .map(Some)
+                .ok_or_else(|| {
+                    ArrowError::ComputeError(format!("Overflow in the {} of row {}", op, i))
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,77 @@
+use arrow::{
+    array::{Array, BooleanArray},
+    compute::kernels::boolean,
+    error::{ArrowError, Result},
+};
+
+/// Logical and of two masks. The result is null where any of them is null
+pub fn and(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
+    boolean::and(left, right)
+}
+
+/// Logical or of two masks. The result is null where any of them is null
+pub fn or(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
+    boolean::or(left, right)
+}
+
+/// Negates a mask, keeping its nulls
+pub fn not(mask: &BooleanArray) -> Result<BooleanArray> {
+    boolean::not(mask)
+}
+
+/// Logical and using Kleene logic, where null means unknown. A false value
+/// decides the result even if the other value is null, so `false and null`
+/// is false and `true and null` is null
+pub fn and_kleene(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
+    check_lengths(left, right)?;
+
+    Ok(left
+        .iter()
+        .zip(right.iter())
+        .map(|values| match values {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Logical or using Kleene logic. A true value decides the result even if
+/// the other value is null, so `true or null` is true and `false or null`
+/// is null
+pub fn or_kleene(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
+    check_lengths(left, right)?;
+
+    Ok(left
+        .iter()
+        .zip(right.iter())
+        .map(|values| match values {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Replaces the nulls of a mask with false. Arrow's filter kernel doesn't
+/// accept masks with nulls, and a row where the predicate is unknown isn't
+/// selected
+pub fn nulls_as_false(mask: &BooleanArray) -> BooleanArray {
+    if mask.null_count() == 0 {
+        return BooleanArray::from(mask.data());
+    }
+
+    mask.iter().map(|value| Some(value == Some(true))).collect()
+}
+
+fn check_lengths(left: &BooleanArray, right: &BooleanArray) -> Result<()> {
+    if left.len() != right.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Can't combine masks of {} and {} values",
+            left.len(),
+            right.len()
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+// Type promotion for kernels that combine two numeric columns. The common
+// type can hold the values of both columns, so Int8 and Int64 give Int64
+// and an integer with a float gives a float
+use arrow::{
+    array::ArrayRef,
+    compute::cast,
+    datatypes::DataType,
+    error::{ArrowError, Result},
+};
+
+/// Common type of two numeric types. Integers of the same sign use the
+/// widest of them, and a signed with an unsigned integer gives a signed
+/// type that holds both. No signed type holds a UInt64, so UInt64 with a
+/// signed integer gives Float64. An integer with a float gives Float64,
+/// unless the integer fits exactly in a Float32
+pub fn coerce_types(left: &DataType, right: &DataType) -> Result<DataType> {
+    if left == right && is_numeric(left) {
+        return Ok(left.clone());
+    }
+
+    Ok(match (numeric_class(left)?, numeric_class(right)?) {
+        (Numeric::Float(left), Numeric::Float(right)) => float_type(left.max(right)),
+        (Numeric::Float(float), Numeric::Int(_, int))
+        | (Numeric::Int(_, int), Numeric::Float(float)) => {
+            // A Float32 has 24 bits of mantissa
+            match float == 32 && int <= 16 {
+                true => DataType::Float32,
+                false => DataType::Float64,
+            }
+        }
+        (Numeric::Int(true, left), Numeric::Int(true, right)) => int_type(true, left.max(right)),
+        (Numeric::Int(false, left), Numeric::Int(false, right)) => int_type(false, left.max(right)),
+        (Numeric::Int(true, signed), Numeric::Int(false, unsigned))
+        | (Numeric::Int(false, unsigned), Numeric::Int(true, signed)) => match unsigned {
+            64 => DataType::Float64,
+            _ => int_type(true, signed.max(unsigned * 2)),
+        },
+    })
+}
+
+/// Casts both columns to their common type, so they can be used with the
+/// kernels that need columns of the same type
+pub fn coerce_columns(left: &ArrayRef, right: &ArrayRef) -> Result<(ArrayRef, ArrayRef)> {
+    let data_type = coerce_types(left.data_type(), right.data_type())?;
+    Ok((cast_to(left, &data_type)?, cast_to(right, &data_type)?))
+}
+
+// Integers are described by their sign and width, floats by their width
+enum Numeric {
+    Int(bool, u8),
+    Float(u8),
+}
+
+fn numeric_class(data_type: &DataType) -> Result<Numeric> {
+    Ok(match data_type {
+        DataType::Int8 => Numeric::Int(true, 8),
+        DataType::Int16 => Numeric::Int(true, 16),
+        DataType::Int32 => Numeric::Int(true, 32),
+        DataType::Int64 => Numeric::Int(true, 64),
+        DataType::UInt8 => Numeric::Int(false, 8),
+        DataType::UInt16 => Numeric::Int(false, 16),
+        DataType::UInt32 => Numeric::Int(false, 32),
+        DataType::UInt64 => Numeric::Int(false, 64),
+        DataType::Float32 => Numeric::Float(32),
+        DataType::Float64 => Numeric::Float(64),
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't find a common numeric type for {:?}",
+                other
+            )))
+        }
+    })
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    numeric_class(data_type).is_ok()
+}
+
+fn int_type(signed: bool, width: u8) -> DataType {
+    match (signed, width) {
+        (true, 8) => DataType::Int8,
+        (true, 16) => DataType::Int16,
+        (true, 32) => DataType::Int32,
+        (true, _) => DataType::Int64,
+        (false, 8) => DataType::UInt8,
+        (false, 16) => DataType::UInt16,
+        (false, 32) => DataType::UInt32,
+        (false, _) => DataType::UInt64,
+    }
+}
+
+fn float_type(width: u8) -> DataType {
+    match width {
+        32 => DataType::Float32,
+        _ => DataType::Float64,
+    }
+}
+
+fn cast_to(column: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    match column.data_type() == data_type {
+        true => Ok(column.clone()),
+        false => cast(column, data_type),
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+use std::str::FromStr;
+
+use arrow::{
+    array::{
+        ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array, Int16Array, Int32Array,
+        Int64Array, Int8Array, StringArray, Time64MicrosecondArray, Time64NanosecondArray,
+        UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    compute::kernels::comparison,
+    datatypes::{DataType, DateUnit, TimeUnit},
+    error::{ArrowError, Result},
+};
+
+use crate::downcast::downcast_array;
+use crate::ScalarValue;
+
+/// Comparison between the values of a column and a scalar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Operator::Eq => "=",
+            Operator::NotEq => "!=",
+            Operator::Lt => "<",
+            Operator::LtEq => "<=",
+            Operator::Gt => ">",
+            Operator::GtEq => ">=",
+        };
+        write!(f, "{}", op)
+    }
+}
+
+impl FromStr for Operator {
+    type Err = ArrowError;
+
+    fn from_str(op: &str) -> Result<Self> {
+        match op {
+            "=" => Ok(Operator::Eq),
+            "!=" => Ok(Operator::NotEq),
+            "<" => Ok(Operator::Lt),
+            "<=" => Ok(Operator::LtEq),
+            ">" => Ok(Operator::Gt),
+            ">=" => Ok(Operator::GtEq),
+            other => Err(ArrowError::InvalidArgumentError(format!(
+                "Unknown comparison operator {}",
+                other
+            ))),
+        }
+    }
+}
+
+// Downcasts the column and compares it with the value using arrow's scalar
+// kernels
+macro_rules! compare_primitive {
+    ($array:expr, $ARRAYTYPE:ident, $op:expr, $value:expr) => {{
+        let array = downcast_array::<$ARRAYTYPE>($array.as_ref())?;
+        let value = *$value;
+        match $op {
+            Operator::Eq => comparison::eq_scalar(array, value),
+            Operator::NotEq => comparison::neq_scalar(array, value),
+            Operator::Lt => comparison::lt_scalar(array, value),
+            Operator::LtEq => comparison::lt_eq_scalar(array, value),
+            Operator::Gt => comparison::gt_scalar(array, value),
+            Operator::GtEq => comparison::gt_eq_scalar(array, value),
+        }
+    }};
+}
+
+/// Compares every value of the column with the scalar. The scalar must
+/// have the type of the column, and numeric, utf8, date32 and time64
+/// columns are supported. The result is null where the column is null, and
+/// comparing with a null scalar gives only nulls
+pub fn compare_scalar(
+    array: &ArrayRef,
+    op: Operator,
+    scalar: &ScalarValue,
+) -> Result<BooleanArray> {
+    if scalar.get_datatype() != *array.data_type() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Can't compare a column of type {:?} with a value of type {:?}",
+            array.data_type(),
+            scalar.get_datatype()
+        )));
+    }
+
+    match (array.data_type(), scalar) {
+        (_, scalar) if scalar.is_null() => Ok(vec![None; array.len()].into()),
+        (DataType::Int8, ScalarValue::Int8(Some(v))) => compare_primitive!(array, Int8Array, op, v),
+        (DataType::Int16, ScalarValue::Int16(Some(v))) => {
+            compare_primitive!(array, Int16Array, op, v)
+        }
+        (DataType::Int32, ScalarValue::Int32(Some(v))) => {
+            compare_primitive!(array, Int32Array, op, v)
+        }
+        (DataType::Int64, ScalarValue::Int64(Some(v))) => {
+            compare_primitive!(array, Int64Array, op, v)
+        }
+        (DataType::UInt8, ScalarValue::UInt8(Some(v))) => {
+            compare_primitive!(array, UInt8Array, op, v)
+        }
+        (DataType::UInt16, ScalarValue::UInt16(Some(v))) => {
+            compare_primitive!(array, UInt16Array, op, v)
+        }
+        (DataType::UInt32, ScalarValue::UInt32(Some(v))) => {
+            compare_primitive!(array, UInt32Array, op, v)
+        }
+        (DataType::UInt64, ScalarValue::UInt64(Some(v))) => {
+            compare_primitive!(array, UInt64Array, op, v)
+        }
+        (DataType::Float32, ScalarValue::Float32(Some(v))) => {
+            compare_primitive!(array, Float32Array, op, v)
+        }
+        (DataType::Float64, ScalarValue::Float64(Some(v))) => {
+            compare_primitive!(array, Float64Array, op, v)
+        }
+        (DataType::Date32(DateUnit::Day), ScalarValue::Date32(Some(v))) => {
+            compare_primitive!(array, Date32Array, op, v)
+        }
+        (DataType::Time64(TimeUnit::Microsecond), ScalarValue::TimeMicrosecond(Some(v))) => {
+            compare_primitive!(array, Time64MicrosecondArray, op, v)
+        }
+        (DataType::Time64(TimeUnit::Nanosecond), ScalarValue::TimeNanosecond(Some(v))) => {
+            compare_primitive!(array, Time64NanosecondArray, op, v)
+        }
+        (DataType::Utf8, ScalarValue::Utf8(Some(v))) => {
+            let array = downcast_array::<StringArray>(array.as_ref())?;
+            match op {
+                Operator::Eq => comparison::eq_utf8_scalar(array, v),
+                Operator::NotEq => comparison::neq_utf8_scalar(array, v),
+                Operator::Lt => comparison::lt_utf8_scalar(array, v),
+                Operator::LtEq => comparison::lt_eq_utf8_scalar(array, v),
+                Operator::Gt => comparison::gt_utf8_scalar(array, v),
+                Operator::GtEq => comparison::gt_eq_utf8_scalar(array, v),
+            }
+        }
+        (data_type, _) => Err(ArrowError::InvalidArgumentError(format!(
+            "Columns of type {:?} can't be compared",
+            data_type
+        ))),
+    }
+}
@@ -0,0 +1,163 @@
+// Running aggregates that give a value for every row of the column, for
+// example the balance of an account after every transaction
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        ArrayRef, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    datatypes::DataType,
+    error::{ArrowError, Result},
+};
+
+use crate::downcast::downcast_array;
+
+// Reads the values of the array in the wider native type and accumulates
+// them with the function
+macro_rules! typed_cumsum {
+    ($column:expr, $nulls:expr, $ARRAYTYPE:ident, $NATIVE:ty, $add:expr) => {{
+        let array = downcast_array::<$ARRAYTYPE>($column.as_ref())?;
+        running(
+            array
+                .iter()
+                .map(|value| value.map(|value| value as $NATIVE)),
+            $nulls,
+            0 as $NATIVE,
+            $add,
+        )?
+    }};
+}
+
+/// What a running aggregate does with the null rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullTreatment {
+    /// The null rows are null in the result, and the rows after them keep
+    /// accumulating the valid values
+    #[default]
+    Skip,
+    /// The first null makes the rest of the result null
+    Propagate,
+}
+
+impl fmt::Display for NullTreatment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            NullTreatment::Skip => "skip",
+            NullTreatment::Propagate => "propagate",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for NullTreatment {
+    type Err = ArrowError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "skip" => Ok(NullTreatment::Skip),
+            "propagate" => Ok(NullTreatment::Propagate),
+            other => Err(ArrowError::ParseError(format!(
+                "Unknown null treatment {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Cumulative sum of a numeric column. Like `aggregate::sum`, signed
+/// integers are added as Int64, unsigned integers as UInt64 and floats as
+/// Float64, and an integer sum that overflows is an error
+pub fn cumsum(column: &ArrayRef, nulls: NullTreatment) -> Result<ArrayRef> {
+    let signed = |sum: i64, value: i64| sum.checked_add(value);
+    let unsigned = |sum: u64, value: u64| sum.checked_add(value);
+    let float = |sum: f64, value: f64| Some(sum + value);
+
+    Ok(match column.data_type() {
+        DataType::Int8 => Arc::new(Int64Array::from(typed_cumsum!(
+            column, nulls, Int8Array, i64, signed
+        ))),
+        DataType::Int16 => Arc::new(Int64Array::from(typed_cumsum!(
+            column, nulls, Int16Array, i64, signed
+        ))),
+        DataType::Int32 => Arc::new(Int64Array::from(typed_cumsum!(
+            column, nulls, Int32Array, i64, signed
+        ))),
+        DataType::Int64 => Arc::new(Int64Array::from(typed_cumsum!(
+            column, nulls, Int64Array, i64, signed
+        ))),
+        DataType::UInt8 => Arc::new(UInt64Array::from(typed_cumsum!(
+            column, nulls, UInt8Array, u64, unsigned
+        ))),
+        DataType::UInt16 => Arc::new(UInt64Array::from(typed_cumsum!(
+            column,
+            nulls,
+            UInt16Array,
+            u64,
+            unsigned
+        ))),
+        DataType::UInt32 => Arc::new(UInt64Array::from(typed_cumsum!(
+            column,
+            nulls,
+            UInt32Array,
+            u64,
+            unsigned
+        ))),
+        DataType::UInt64 => Arc::new(UInt64Array::from(typed_cumsum!(
+            column,
+            nulls,
+            UInt64Array,
+            u64,
+            unsigned
+        ))),
+        DataType::Float32 => Arc::new(Float64Array::from(typed_cumsum!(
+            column,
+            nulls,
+            Float32Array,
+            f64,
+            float
+        ))),
+        DataType::Float64 => Arc::new(Float64Array::from(typed_cumsum!(
+            column,
+            nulls,
+            Float64Array,
+            f64,
+            float
+        ))),
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't calculate the cumulative sum of a column of type {:?}",
+                other
+            )))
+        }
+    })
+}
+
+fn running<I, N, F>(values: I, nulls: NullTreatment, zero: N, add: F) -> Result<Vec<Option<N>>>
+where
+    I: Iterator<Item = Option<N>>,
+    N: Copy,
+    F: Fn(N, N) -> Option<N>,
+{
+    let mut total = Some(zero);
+    values
+        .map(|value| match (value, total) {
+            (Some(value), Some(sum)) => {
+                let sum = add(sum, value).ok_or_else(|| {
+                    ArrowError::ComputeError("Overflow in the cumulative sum".to_string())
+                })?;
+                total = Some(sum);
+                Ok(total)
+            }
+            (None, _) => {
+                if nulls == NullTreatment::Propagate {
+                    total = None;
+                }
+                Ok(None)
+            }
+            (Some(_), None) => Ok(None),
+        })
+        .collect()
+}
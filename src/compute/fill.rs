@@ -0,0 +1,118 @@
+// Kernels that fill the nulls of a numeric column with the values around
+// them, for example the gaps of a sensor that stopped sending readings
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        PrimitiveArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    datatypes::{ArrowPrimitiveType, DataType},
+    error::{ArrowError, Result},
+};
+
+use crate::downcast::downcast_array;
+
+// Reads the values of the array as floats
+macro_rules! typed_floats {
+    ($column:expr, $ARRAYTYPE:ident) => {{
+        let array = downcast_array::<$ARRAYTYPE>($column.as_ref())?;
+        array
+            .iter()
+            .map(|value| value.map(|value| value as f64))
+            .collect::<Vec<_>>()
+    }};
+}
+
+/// Replaces every null with the last value before it. The nulls at the
+/// start of the column stay null
+pub fn fill_forward(column: &ArrayRef) -> Result<ArrayRef> {
+    fill(column, false)
+}
+
+/// Replaces every null with the first value after it. The nulls at the
+/// end of the column stay null
+pub fn fill_backward(column: &ArrayRef) -> Result<ArrayRef> {
+    fill(column, true)
+}
+
+/// Replaces the nulls between two values with the points of the line that
+/// joins them, as if the rows were evenly spaced. The result is a Float64
+/// column, and the nulls at the start and the end stay null
+pub fn interpolate_linear(column: &ArrayRef) -> Result<ArrayRef> {
+    let values = match column.data_type() {
+        DataType::Int8 => typed_floats!(column, Int8Array),
+        DataType::Int16 => typed_floats!(column, Int16Array),
+        DataType::Int32 => typed_floats!(column, Int32Array),
+        DataType::Int64 => typed_floats!(column, Int64Array),
+        DataType::UInt8 => typed_floats!(column, UInt8Array),
+        DataType::UInt16 => typed_floats!(column, UInt16Array),
+        DataType::UInt32 => typed_floats!(column, UInt32Array),
+        DataType::UInt64 => typed_floats!(column, UInt64Array),
+        DataType::Float32 => typed_floats!(column, Float32Array),
+        DataType::Float64 => typed_floats!(column, Float64Array),
+        other => return Err(unsupported("interpolate", other)),
+    };
+    let mut result = values.clone();
+
+    let mut previous: Option<(usize, f64)> = None;
+    for (i, value) in values.iter().enumerate() {
+        let value = match value {
+            Some(value) => *value,
+            None => continue,
+        };
+
+        if let Some((start, start_value)) = previous {
+            let step = (value - start_value) / (i - start) as f64;
+            for (gap, slot) in result[start + 1..i].iter_mut().enumerate() {
+                *slot = Some(start_value + step * (gap + 1) as f64);
+            }
+        }
+        previous = Some((i, value));
+    }
+
+    Ok(Arc::new(Float64Array::from(result)))
+}
+
+fn fill(column: &ArrayRef, backward: bool) -> Result<ArrayRef> {
+    Ok(match column.data_type() {
+        DataType::Int8 => fill_typed(downcast_array::<Int8Array>(column.as_ref())?, backward),
+        DataType::Int16 => fill_typed(downcast_array::<Int16Array>(column.as_ref())?, backward),
+        DataType::Int32 => fill_typed(downcast_array::<Int32Array>(column.as_ref())?, backward),
+        DataType::Int64 => fill_typed(downcast_array::<Int64Array>(column.as_ref())?, backward),
+        DataType::UInt8 => fill_typed(downcast_array::<UInt8Array>(column.as_ref())?, backward),
+        DataType::UInt16 => fill_typed(downcast_array::<UInt16Array>(column.as_ref())?, backward),
+        DataType::UInt32 => fill_typed(downcast_array::<UInt32Array>(column.as_ref())?, backward),
+        DataType::UInt64 => fill_typed(downcast_array::<UInt64Array>(column.as_ref())?, backward),
+        DataType::Float32 => fill_typed(downcast_array::<Float32Array>(column.as_ref())?, backward),
+        DataType::Float64 => fill_typed(downcast_array::<Float64Array>(column.as_ref())?, backward),
+        other => return Err(unsupported("fill", other)),
+    })
+}
+
+fn fill_typed<T: ArrowPrimitiveType>(array: &PrimitiveArray<T>, backward: bool) -> ArrayRef {
+    let mut values = (0..array.len())
+        .map(|i| match array.is_valid(i) {
+            true => Some(array.value(i)),
+            false => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Filling backward is filling forward from the end of the column
+    let mut last = None;
+    let mut fill = |value: &mut Option<T::Native>| match value {
+        Some(value) => last = Some(*value),
+        None => *value = last,
+    };
+    if backward {
+        values.iter_mut().rev().for_each(&mut fill);
+    } else {
+        values.iter_mut().for_each(&mut fill);
+    }
+
+    Arc::new(values.into_iter().collect::<PrimitiveArray<T>>())
+}
+
+fn unsupported(name: &str, data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!("Can't {} a column of type {:?}", name, data_type))
+}
@@ -0,0 +1,367 @@
+// Hash aggregation. The rows are assigned to a group using the values of
+// the key columns, and every row is added to the partial result of its
+// group in a single pass over the batch. The partial results are kept
+// between batches, so a stream can be grouped without keeping it in memory
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::{
+    array::ArrayRef,
+    datatypes::{DataType, DateUnit, Field, Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::aggregate::{self, combine_maxs, combine_mins, combine_sums, mean_from_partials};
+use super::build_array;
+use crate::ScalarValue;
+
+/// Aggregate function calculated for every group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    Sum,
+    Min,
+    Max,
+    Mean,
+    Count,
+}
+
+impl fmt::Display for AggregateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            AggregateFunction::Sum => "sum",
+            AggregateFunction::Min => "min",
+            AggregateFunction::Max => "max",
+            AggregateFunction::Mean => "mean",
+            AggregateFunction::Count => "count",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for AggregateFunction {
+    type Err = ArrowError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sum" => Ok(AggregateFunction::Sum),
+            "min" => Ok(AggregateFunction::Min),
+            "max" => Ok(AggregateFunction::Max),
+            "mean" => Ok(AggregateFunction::Mean),
+            "count" => Ok(AggregateFunction::Count),
+            other => Err(ArrowError::ParseError(format!(
+                "Unknown aggregate function {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// An aggregate function applied to a column of the batches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aggregate {
+    pub function: AggregateFunction,
+    pub column: usize,
+}
+
+impl Aggregate {
+    pub fn new(function: AggregateFunction, column: usize) -> Self {
+        Self { function, column }
+    }
+}
+
+// Partial result of an aggregate for a group
+#[derive(Debug, Clone)]
+enum Accumulator {
+    Sum(ScalarValue),
+    Min(ScalarValue),
+    Max(ScalarValue),
+    Mean(ScalarValue, ScalarValue),
+    Count(ScalarValue),
+}
+
+impl Accumulator {
+    fn new(function: AggregateFunction, values: &ArrayRef) -> Result<Self> {
+        Ok(match function {
+            AggregateFunction::Sum => Accumulator::Sum(aggregate::sum(values)?),
+            AggregateFunction::Min => Accumulator::Min(aggregate::min(values)?),
+            AggregateFunction::Max => Accumulator::Max(aggregate::max(values)?),
+            AggregateFunction::Mean => {
+                Accumulator::Mean(aggregate::sum(values)?, aggregate::count(values))
+            }
+            AggregateFunction::Count => Accumulator::Count(aggregate::count(values)),
+        })
+    }
+
+    // Adds the value of a row to the partial result
+    fn update(&mut self, values: &ArrayRef, row: usize) -> Result<()> {
+        let count = || ScalarValue::UInt64(Some(values.is_valid(row) as u64));
+        *self = match self {
+            Accumulator::Sum(sum) => {
+                Accumulator::Sum(combine_sums(sum.clone(), sum_value(values, row)?)?)
+            }
+            Accumulator::Min(min) => {
+                Accumulator::Min(combine_mins(min.clone(), row_value(values, row)?)?)
+            }
+            Accumulator::Max(max) => {
+                Accumulator::Max(combine_maxs(max.clone(), row_value(values, row)?)?)
+            }
+            Accumulator::Mean(sum, total) => Accumulator::Mean(
+                combine_sums(sum.clone(), sum_value(values, row)?)?,
+                combine_sums(total.clone(), count())?,
+            ),
+            Accumulator::Count(total) => Accumulator::Count(combine_sums(total.clone(), count())?),
+        };
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        match self {
+            Accumulator::Sum(value)
+            | Accumulator::Min(value)
+            | Accumulator::Max(value)
+            | Accumulator::Count(value) => Ok(value.clone()),
+            Accumulator::Mean(sum, count) => mean_from_partials(sum, count),
+        }
+    }
+}
+
+/// State of a hash aggregation. Every batch passed to `update` adds its
+/// rows to the groups, and `finish` returns a batch with one row per group
+/// holding the keys followed by the aggregates. The groups keep the order
+/// in which their first row was found
+pub struct GroupByHash {
+    input_schema: SchemaRef,
+    schema: SchemaRef,
+    key_columns: Vec<usize>,
+    aggregates: Vec<Aggregate>,
+    groups: HashMap<Vec<ScalarValue>, usize>,
+    keys: Vec<Vec<ScalarValue>>,
+    accumulators: Vec<Vec<Accumulator>>,
+}
+
+impl GroupByHash {
+    /// Creates an empty aggregation for batches with the schema. The types
+    /// of the key columns and the aggregated columns are checked here
+    pub fn new(schema: SchemaRef, key_columns: &[usize], aggregates: &[Aggregate]) -> Result<Self> {
+        let mut fields = Vec::with_capacity(key_columns.len() + aggregates.len());
+        for &column in key_columns {
+            let field = input_field(&schema, column)?;
+            check_key_type(field.data_type())?;
+            fields.push(Field::new(field.name(), field.data_type().clone(), true));
+        }
+        for aggregate in aggregates {
+            let field = input_field(&schema, aggregate.column)?;
+            fields.push(Field::new(
+                &format!("{}({})", aggregate.function, field.name()),
+                output_type(aggregate.function, field.data_type())?,
+                true,
+            ));
+        }
+
+        Ok(Self {
+            input_schema: schema,
+            schema: Arc::new(Schema::new(fields)),
+            key_columns: key_columns.to_vec(),
+            aggregates: aggregates.to_vec(),
+            groups: HashMap::new(),
+            keys: Vec::new(),
+            accumulators: Vec::new(),
+        })
+    }
+
+    /// Schema of the batch returned by `finish`
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Number of groups found so far
+    pub fn num_groups(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Adds the rows of the batch to their groups. The batch must have the
+    /// schema given to `new`
+    pub fn update(&mut self, batch: &RecordBatch) -> Result<()> {
+        let schema = batch.schema();
+        let same_types = schema.fields().len() == self.input_schema.fields().len()
+            && schema
+                .fields()
+                .iter()
+                .zip(self.input_schema.fields())
+                .all(|(field, expected)| field.data_type() == expected.data_type());
+        if !same_types {
+            return Err(ArrowError::SchemaError(
+                "The batch doesn't have the schema of the group by".to_string(),
+            ));
+        }
+
+        // Partial results of a new group, calculated from no rows
+        let empty = self
+            .aggregates
+            .iter()
+            .map(|aggregate| {
+                Accumulator::new(
+                    aggregate.function,
+                    &batch.column(aggregate.column).slice(0, 0),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for row in 0..batch.num_rows() {
+            let key = self
+                .key_columns
+                .iter()
+                .map(|&column| row_value(batch.column(column), row))
+                .collect::<Result<Vec<_>>>()?;
+
+            let next = self.keys.len();
+            let group = *self.groups.entry(key.clone()).or_insert(next);
+            if group == next {
+                self.keys.push(key);
+                self.accumulators.push(empty.clone());
+            }
+
+            for (accumulator, aggregate) in
+                self.accumulators[group].iter_mut().zip(&self.aggregates)
+            {
+                accumulator.update(batch.column(aggregate.column), row)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds a batch with the keys and the aggregates of every group. The
+    /// state is kept, so more batches can be added after it
+    pub fn finish(&self) -> Result<RecordBatch> {
+        let mut columns = Vec::with_capacity(self.schema.fields().len());
+        for (i, field) in self.schema.fields()[..self.key_columns.len()]
+            .iter()
+            .enumerate()
+        {
+            let values = self.keys.iter().map(|key| key[i].clone()).collect();
+            columns.push(build_array(values, field.data_type())?);
+        }
+
+        let aggregate_fields = &self.schema.fields()[self.key_columns.len()..];
+        for (i, field) in aggregate_fields.iter().enumerate() {
+            let values = self
+                .accumulators
+                .iter()
+                .map(|accumulators| accumulators[i].evaluate())
+                .collect::<Result<Vec<_>>>()?;
+            columns.push(build_array(values, field.data_type())?);
+        }
+
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+/// Groups the rows of the batches by the key columns and calculates the
+/// aggregates of every group. All the batches must have the same schema
+pub fn group_by_hash(
+    batches: &[RecordBatch],
+    key_columns: &[usize],
+    aggregates: &[Aggregate],
+) -> Result<RecordBatch> {
+    let first = batches.first().ok_or_else(|| {
+        ArrowError::InvalidArgumentError("There are no batches to group".to_string())
+    })?;
+
+    let mut group_by = GroupByHash::new(first.schema(), key_columns, aggregates)?;
+    for batch in batches {
+        group_by.update(batch)?;
+    }
+    group_by.finish()
+}
+
+fn input_field(schema: &Schema, column: usize) -> Result<&Field> {
+    schema.fields().get(column).ok_or_else(|| {
+        ArrowError::InvalidArgumentError(format!("The batches don't have column {}", column))
+    })
+}
+
+// Keys are read as scalars and the groups are built back into arrays, so
+// they need a type supported by both conversions
+fn check_key_type(data_type: &DataType) -> Result<()> {
+    match data_type {
+        DataType::Boolean
+        | DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Date32(DateUnit::Day) => Ok(()),
+        other => Err(ArrowError::InvalidArgumentError(format!(
+            "Can't group by a column of type {:?}",
+            other
+        ))),
+    }
+}
+
+fn row_value(values: &ArrayRef, row: usize) -> Result<ScalarValue> {
+    ScalarValue::try_from_array(values, row).map_err(ArrowError::InvalidArgumentError)
+}
+
+// Value of a row widened to the type of its sum, like the sum kernel does
+fn sum_value(values: &ArrayRef, row: usize) -> Result<ScalarValue> {
+    Ok(match row_value(values, row)? {
+        ScalarValue::Int8(value) => ScalarValue::Int64(value.map(i64::from)),
+        ScalarValue::Int16(value) => ScalarValue::Int64(value.map(i64::from)),
+        ScalarValue::Int32(value) => ScalarValue::Int64(value.map(i64::from)),
+        ScalarValue::Int64(value) => ScalarValue::Int64(value),
+        ScalarValue::UInt8(value) => ScalarValue::UInt64(value.map(u64::from)),
+        ScalarValue::UInt16(value) => ScalarValue::UInt64(value.map(u64::from)),
+        ScalarValue::UInt32(value) => ScalarValue::UInt64(value.map(u64::from)),
+        ScalarValue::UInt64(value) => ScalarValue::UInt64(value),
+        ScalarValue::Float32(value) => ScalarValue::Float64(value.map(f64::from)),
+        ScalarValue::Float64(value) => ScalarValue::Float64(value),
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't calculate the sum of a column of type {:?}",
+                other.get_datatype()
+            )))
+        }
+    })
+}
+
+// Type of the aggregate of a column, as returned by the kernels of the
+// aggregate module
+fn output_type(function: AggregateFunction, data_type: &DataType) -> Result<DataType> {
+    let numeric = match data_type {
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            Some(DataType::Int64)
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            Some(DataType::UInt64)
+        }
+        DataType::Float32 | DataType::Float64 => Some(DataType::Float64),
+        _ => None,
+    };
+    let ordered = numeric.is_some()
+        || matches!(
+            data_type,
+            DataType::Boolean | DataType::Utf8 | DataType::Date32(DateUnit::Day)
+        );
+
+    match function {
+        AggregateFunction::Count => Ok(DataType::UInt64),
+        AggregateFunction::Sum if numeric.is_some() => Ok(numeric.unwrap()),
+        AggregateFunction::Mean if numeric.is_some() => Ok(DataType::Float64),
+        AggregateFunction::Min | AggregateFunction::Max if ordered => Ok(data_type.clone()),
+        _ => Err(ArrowError::InvalidArgumentError(format!(
+            "Can't calculate the {} of a column of type {:?}",
+            function, data_type
+        ))),
+    }
+}
@@ -0,0 +1,50 @@
+// Kernels that work with the columns of a Table without downcasting them.
+// The kernel used is chosen from the DataType of the columns
+pub mod aggregate;
+mod apply;
+mod arithmetic;
+mod boolean;
+mod coercion;
+mod comparison;
+mod cumulative;
+mod fill;
+mod group_by;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod partition;
+mod pattern;
+mod rank;
+mod registry;
+mod row;
+mod shift;
+mod sort;
+pub mod strings;
+mod tdigest;
+pub mod temporal;
+
+pub use apply::{apply_scalar, apply_unary};
+pub use arithmetic::{
+    add, add_checked, add_saturating, add_with_mode, divide, multiply, multiply_checked,
+    multiply_saturating, multiply_with_mode, subtract, subtract_with_mode, ArithmeticMode,
+};
+pub use boolean::{and, and_kleene, not, nulls_as_false, or, or_kleene};
+pub use coercion::{coerce_columns, coerce_types};
+pub use comparison::{compare_scalar, Operator};
+pub use cumulative::{cumsum, NullTreatment};
+pub use fill::{fill_backward, fill_forward, interpolate_linear};
+pub use group_by::{group_by_hash, Aggregate, AggregateFunction, GroupByHash};
+#[cfg(feature = "rayon")]
+pub use parallel::par_apply_batches;
+pub use partition::hash_partition;
+pub use pattern::{like, regex_match};
+pub use rank::{rank, RankMethod};
+pub use registry::{Kernel, KernelRegistry, Signature};
+pub use row::{rows_equal, RowHasher};
+pub use shift::shift;
+pub use sort::sort_indices;
+pub use tdigest::TDigest;
+
+pub(crate) use apply::build_array;
+pub(crate) use shift::{
+    check_supported_type, compact_offsets, decode_dictionary, encode_dictionary, shift_chunks,
+};
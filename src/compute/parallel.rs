@@ -0,0 +1,42 @@
+// Runs a kernel over the batches of a Table using the rayon thread pool.
+// Every batch is independent, so any transformation that works batch by
+// batch can be run in parallel
+use arrow::{
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use rayon::prelude::*;
+
+use crate::Table;
+
+/// Applies the function to every batch of the table in parallel and builds
+/// a new table with the results in the order of the original batches. All
+/// the batches returned must have the same schema. If the function fails
+/// for some batches, the error of the first of them in the table is
+/// returned
+pub fn par_apply_batches<F>(table: &Table, op: F) -> Result<Table>
+where
+    F: Fn(&RecordBatch) -> Result<RecordBatch> + Send + Sync,
+{
+    // The results are collected before looking at the errors, so the error
+    // returned doesn't depend on which thread fails first
+    let results = table.data().par_iter().map(&op).collect::<Vec<_>>();
+    let batches = results.into_iter().collect::<Result<Vec<_>>>()?;
+
+    let schema = match batches.first() {
+        Some(batch) => batch.schema().as_ref().clone(),
+        None => table.schema().clone(),
+    };
+    if let Some(batch) = batches
+        .iter()
+        .find(|batch| batch.schema().as_ref() != &schema)
+    {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "The batches returned have different schemas: {:?} and {:?}",
+            schema,
+            batch.schema()
+        )));
+    }
+
+    Ok(Table::new(schema, batches))
+}
@@ -0,0 +1,50 @@
+// Hash partitioning sends all the rows with the same key to the same
+// partition, so every partition can be grouped or joined on its own, for
+// example in a different thread
+use arrow::{
+    array::UInt32Builder,
+    compute::take,
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::RowHasher;
+
+/// Splits the batch in `num_partitions` batches using the hash of the key
+/// columns of every row. Rows with the same keys always end up in the same
+/// partition, and the rows keep their order inside every partition. Some of
+/// the partitions may be empty
+pub fn hash_partition(
+    batch: &RecordBatch,
+    key_columns: &[usize],
+    num_partitions: usize,
+) -> Result<Vec<RecordBatch>> {
+    if num_partitions == 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "The number of partitions must be greater than 0".to_string(),
+        ));
+    }
+
+    let hashes = RowHasher::new(key_columns).hash_rows(batch)?;
+
+    let mut indices = (0..num_partitions)
+        .map(|_| UInt32Builder::new(batch.num_rows() / num_partitions))
+        .collect::<Vec<_>>();
+    for (row, hash) in hashes.into_iter().enumerate() {
+        let partition = (hash % num_partitions as u64) as usize;
+        indices[partition].append_value(row as u32)?;
+    }
+
+    indices
+        .iter_mut()
+        .map(|indices| {
+            let indices = indices.finish();
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|column| take(column.as_ref(), &indices, None))
+                .collect::<Result<Vec<_>>>()?;
+            RecordBatch::try_new(batch.schema(), columns)
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+// Predicates that match the values of a text column against a pattern.
+// Compiling a regex is much slower than matching it, so the compiled
+// patterns are kept in a small cache shared by all the threads
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use arrow::{
+    array::{Array, BooleanArray, StringArray},
+    error::{ArrowError, Result},
+};
+use regex::Regex;
+
+// The cache is cleared when it's full instead of tracking which patterns
+// were used last
+const CACHE_SIZE: usize = 64;
+
+static CACHE: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+
+/// Matches the strings with a SQL LIKE pattern, where `%` matches any
+/// number of characters and `_` a single one. The pattern has to match the
+/// complete string, and `\` escapes the next character so `\%` matches a
+/// percent sign. Null strings give null
+pub fn like(array: &StringArray, pattern: &str) -> Result<BooleanArray> {
+    let regex = compiled(&like_to_regex(pattern))?;
+    Ok(match_strings(array, &regex))
+}
+
+/// Checks if the strings contain a match of the regular expression. Use
+/// `^` and `$` to match the complete string. Null strings give null
+pub fn regex_match(array: &StringArray, regex: &str) -> Result<BooleanArray> {
+    let regex = compiled(regex)?;
+    Ok(match_strings(array, &regex))
+}
+
+fn match_strings(array: &StringArray, regex: &Regex) -> BooleanArray {
+    (0..array.len())
+        .map(|i| match array.is_valid(i) {
+            true => Some(regex.is_match(array.value(i))),
+            false => None,
+        })
+        .collect()
+}
+
+// Regexes are reference counted, so the cached one is cloned instead of
+// holding the lock while matching
+fn compiled(pattern: &str) -> Result<Regex> {
+    let mut cache = CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap();
+
+    if let Some(regex) = cache.get(pattern) {
+        return Ok(regex.clone());
+    }
+
+    let regex = Regex::new(pattern).map_err(|e| {
+        ArrowError::InvalidArgumentError(format!("Invalid pattern {}: {}", pattern, e))
+    })?;
+
+    if cache.len() >= CACHE_SIZE {
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), regex.clone());
+
+    Ok(regex)
+}
+
+// The regex is anchored at both ends and `.` also matches new lines, like
+// the wildcards of LIKE
+fn like_to_regex(pattern: &str) -> String {
+    let mut regex = String::from("(?s)^");
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '\\' => match chars.next() {
+                Some(escaped) => regex.push_str(&regex::escape(&escaped.to_string())),
+                None => regex.push_str(r"\\"),
+            },
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    regex.push('$');
+    regex
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Float64Array, UInt32Array},
+    compute::{sort_to_indices, SortOptions},
+    error::{ArrowError, Result},
+};
+
+use crate::ScalarValue;
+
+/// How the rows with the same value are ranked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankMethod {
+    /// Ties get the average of the positions they take, so the ranks are
+    /// a Float64 column. The values 10, 20, 20, 30 are ranked 1, 2.5, 2.5, 4
+    Average,
+    /// Ties get the lowest of their positions: 1, 2, 2, 4
+    Min,
+    /// Like Min, but the rank after a tie is the next number: 1, 2, 2, 3
+    Dense,
+}
+
+impl fmt::Display for RankMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            RankMethod::Average => "average",
+            RankMethod::Min => "min",
+            RankMethod::Dense => "dense",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for RankMethod {
+    type Err = ArrowError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "average" => Ok(RankMethod::Average),
+            "min" => Ok(RankMethod::Min),
+            "dense" => Ok(RankMethod::Dense),
+            other => Err(ArrowError::ParseError(format!(
+                "Unknown rank method {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Ranks the values of the column starting from 1, from the smallest
+/// value or from the largest one if `descending` is true. The ranks are a
+/// UInt32 column, except with the Average method that gives a Float64
+/// column. Null values get a null rank
+pub fn rank(array: &ArrayRef, descending: bool, method: RankMethod) -> Result<ArrayRef> {
+    let options = SortOptions {
+        descending,
+        nulls_first: false,
+    };
+    let sorted = sort_to_indices(array, Some(options))?;
+
+    // Rows with the same value are next to each other once sorted. Every
+    // group of ties is ranked when the next value is found
+    let mut ranks = vec![None; array.len()];
+    let mut ties: Vec<usize> = Vec::new();
+    let mut previous: Option<ScalarValue> = None;
+    let mut position = 0;
+    let mut dense = 0;
+
+    for index in sorted.values().iter().map(|&index| index as usize) {
+        if array.is_null(index) {
+            continue;
+        }
+
+        let value =
+            ScalarValue::try_from_array(array, index).map_err(ArrowError::InvalidArgumentError)?;
+        if previous.as_ref() != Some(&value) {
+            dense += 1;
+            assign(&mut ranks, &ties, position, dense - 1, method);
+            position += ties.len();
+            ties.clear();
+            previous = Some(value);
+        }
+        ties.push(index);
+    }
+    assign(&mut ranks, &ties, position, dense, method);
+
+    Ok(match method {
+        RankMethod::Average => Arc::new(Float64Array::from(ranks)),
+        _ => Arc::new(UInt32Array::from(
+            ranks
+                .into_iter()
+                .map(|rank| rank.map(|rank| rank as u32))
+                .collect::<Vec<_>>(),
+        )),
+    })
+}
+
+// Ranks a group of ties that starts after `position` rows
+fn assign(
+    ranks: &mut [Option<f64>],
+    ties: &[usize],
+    position: usize,
+    dense: usize,
+    method: RankMethod,
+) {
+    let rank = match method {
+        RankMethod::Average => position as f64 + (ties.len() as f64 + 1.0) / 2.0,
+        RankMethod::Min => (position + 1) as f64,
+        RankMethod::Dense => dense as f64,
+    };
+
+    for &index in ties {
+        ranks[index] = Some(rank);
+    }
+}
@@ -0,0 +1,175 @@
+// Table of the kernels that can be called by name. Every function can have
+// several implementations, chosen from the types of the columns it's
+// called with, so the callers don't need to match the types themselves
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, BooleanArray},
+    datatypes::DataType,
+    error::{ArrowError, Result},
+};
+
+use super::{
+    add, and, and_kleene, divide, fill_backward, fill_forward, interpolate_linear, multiply, not,
+    or, or_kleene, strings, subtract, temporal,
+};
+use crate::downcast::downcast_array;
+
+/// Implementation of a function. It receives the columns the function is
+/// called with, which always match the signature it was registered with
+pub type Kernel = Arc<dyn Fn(&[ArrayRef]) -> Result<ArrayRef> + Send + Sync>;
+
+// Built-in kernels that take a single column
+type UnaryKernel = fn(&ArrayRef) -> Result<ArrayRef>;
+
+/// Input types accepted by a kernel
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Signature {
+    /// Columns with exactly these types
+    Exact(Vec<DataType>),
+    /// This number of columns of any type. The kernel checks the types
+    Any(usize),
+    /// Any number of columns of any type
+    Variadic,
+}
+
+impl Signature {
+    fn accepts(&self, types: &[DataType]) -> bool {
+        match self {
+            Signature::Exact(expected) => expected.as_slice() == types,
+            Signature::Any(count) => *count == types.len(),
+            Signature::Variadic => true,
+        }
+    }
+
+    // Exact signatures are preferred to generic ones
+    fn priority(&self) -> u8 {
+        match self {
+            Signature::Exact(_) => 0,
+            Signature::Any(_) => 1,
+            Signature::Variadic => 2,
+        }
+    }
+}
+
+/// Kernels registered by function name and input types
+#[derive(Clone, Default)]
+pub struct KernelRegistry {
+    functions: HashMap<String, Vec<(Signature, Kernel)>>,
+}
+
+impl KernelRegistry {
+    /// Creates a registry without any kernel
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with the kernels of this crate that work only
+    /// with columns: arithmetic, boolean logic, strings, dates and fills
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+
+        registry.register("add", Signature::Any(2), |args| add(&args[0], &args[1]));
+        registry.register("subtract", Signature::Any(2), |args| {
+            subtract(&args[0], &args[1])
+        });
+        registry.register("multiply", Signature::Any(2), |args| {
+            multiply(&args[0], &args[1])
+        });
+        registry.register("divide", Signature::Any(2), |args| {
+            divide(&args[0], &args[1])
+        });
+
+        let booleans = Signature::Exact(vec![DataType::Boolean, DataType::Boolean]);
+        registry.register("and", booleans.clone(), boolean_kernel(and));
+        registry.register("or", booleans.clone(), boolean_kernel(or));
+        registry.register("and_kleene", booleans.clone(), boolean_kernel(and_kleene));
+        registry.register("or_kleene", booleans, boolean_kernel(or_kleene));
+        registry.register("not", Signature::Exact(vec![DataType::Boolean]), |args| {
+            let mask = downcast_array::<BooleanArray>(args[0].as_ref())?;
+            Ok(Arc::new(not(mask)?) as ArrayRef)
+        });
+
+        let unary: [(&str, UnaryKernel); 11] = [
+            ("upper", strings::upper),
+            ("lower", strings::lower),
+            ("trim", strings::trim),
+            ("length", strings::length),
+            ("year", temporal::year),
+            ("month", temporal::month),
+            ("day", temporal::day),
+            ("hour", temporal::hour),
+            ("fill_forward", fill_forward),
+            ("fill_backward", fill_backward),
+            ("interpolate_linear", interpolate_linear),
+        ];
+        for (name, kernel) in unary.iter().copied() {
+            registry.register(name, Signature::Any(1), move |args| kernel(&args[0]));
+        }
+        registry.register("concat", Signature::Variadic, strings::concat_columns);
+
+        registry
+    }
+
+    /// Registers a kernel for the function. A kernel registered before with
+    /// the same name and signature is replaced, so the built-in kernels can
+    /// be overridden
+    pub fn register<F>(&mut self, name: &str, signature: Signature, kernel: F)
+    where
+        F: Fn(&[ArrayRef]) -> Result<ArrayRef> + Send + Sync + 'static,
+    {
+        let kernels = self.functions.entry(name.to_string()).or_default();
+        kernels.retain(|(registered, _)| registered != &signature);
+        kernels.push((signature, Arc::new(kernel)));
+        kernels.sort_by_key(|(signature, _)| signature.priority());
+    }
+
+    /// Finds the kernel of the function for columns of these types
+    pub fn resolve(&self, name: &str, types: &[DataType]) -> Result<Kernel> {
+        let kernels = self.functions.get(name).ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!("There is no function called {}", name))
+        })?;
+
+        kernels
+            .iter()
+            .find(|(signature, _)| signature.accepts(types))
+            .map(|(_, kernel)| kernel.clone())
+            .ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "The function {} can't be called with columns of types {:?}",
+                    name, types
+                ))
+            })
+    }
+
+    /// Calls the function with the columns
+    pub fn invoke(&self, name: &str, args: &[ArrayRef]) -> Result<ArrayRef> {
+        let types = args
+            .iter()
+            .map(|arg| arg.data_type().clone())
+            .collect::<Vec<_>>();
+        self.resolve(name, &types)?(args)
+    }
+
+    /// Names of the registered functions in alphabetical order
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = self
+            .functions
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+}
+
+fn boolean_kernel(
+    op: fn(&BooleanArray, &BooleanArray) -> Result<BooleanArray>,
+) -> impl Fn(&[ArrayRef]) -> Result<ArrayRef> {
+    move |args| {
+        let left = downcast_array::<BooleanArray>(args[0].as_ref())?;
+        let right = downcast_array::<BooleanArray>(args[1].as_ref())?;
+        Ok(Arc::new(op(left, right)?) as ArrayRef)
+    }
+}
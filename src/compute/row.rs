@@ -0,0 +1,285 @@
+// Hashing and comparison of the rows of a batch over a set of key columns.
+// The values are read from the typed arrays directly, so no ScalarValue is
+// built for every row. Joins, distinct and upserts use them to find the
+// rows with the same keys
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Date32Array, Date64Array, Float32Array, Float64Array,
+        Int16Array, Int32Array, Int64Array, Int8Array, LargeStringArray, StringArray,
+        TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
+        TimestampSecondArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    datatypes::{DataType, TimeUnit},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use crate::downcast::downcast_array;
+use crate::scalar::{f32_bits, f64_bits};
+
+// Multiplier used by the Fx hash of rustc. It isn't resistant to
+// collisions made on purpose, but it's much faster than SipHash
+const SEED: u64 = 0x517c_c1b7_2722_0a95;
+// Hash of a null value, so nulls are also grouped together
+const NULL_HASH: u64 = 0x9e37_79b9_7f4a_7c15;
+
+// Mixes the values of the rows starting at `start` into their hashes
+macro_rules! typed_hash {
+    ($column:expr, $start:expr, $hashes:expr, $ARRAYTYPE:ident, $hash_value:ident) => {{
+        let array = downcast_array::<$ARRAYTYPE>($column.as_ref())?;
+        for (i, hash) in ($start..).zip($hashes.iter_mut()) {
+            *hash = match array.is_valid(i) {
+                true => $hash_value(*hash, array.value(i)),
+                false => mix(*hash, NULL_HASH),
+            };
+        }
+    }};
+}
+
+// Compares a row of each column. Two nulls are equal
+macro_rules! typed_equal {
+    ($left:expr, $row_left:expr, $right:expr, $row_right:expr, $ARRAYTYPE:ident, $eq:ident) => {{
+        let left = downcast_array::<$ARRAYTYPE>($left.as_ref())?;
+        let right = downcast_array::<$ARRAYTYPE>($right.as_ref())?;
+        match (left.is_valid($row_left), right.is_valid($row_right)) {
+            (true, true) => $eq(left.value($row_left), right.value($row_right)),
+            (valid_left, valid_right) => valid_left == valid_right,
+        }
+    }};
+}
+
+/// Hashes the rows of a batch using the values of the key columns. Rows
+/// with the same keys always get the same hash, also when they are in
+/// different batches, and the hashes of `hash_rows` and `hash_row` are the
+/// same. Nulls are hashed as a value of their own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowHasher {
+    columns: Vec<usize>,
+}
+
+impl RowHasher {
+    pub fn new(columns: &[usize]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+        }
+    }
+
+    /// Key columns hashed
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// Hash of every row of the batch
+    pub fn hash_rows(&self, batch: &RecordBatch) -> Result<Vec<u64>> {
+        let mut hashes = vec![0; batch.num_rows()];
+        for &index in &self.columns {
+            hash_column(key_column(batch, index)?, 0, &mut hashes)?;
+        }
+        Ok(hashes.into_iter().map(finish).collect())
+    }
+
+    /// Hash of a single row of the batch
+    pub fn hash_row(&self, batch: &RecordBatch, row: usize) -> Result<u64> {
+        check_row(batch, row)?;
+
+        let mut hash = [0];
+        for &index in &self.columns {
+            hash_column(key_column(batch, index)?, row, &mut hash)?;
+        }
+        Ok(finish(hash[0]))
+    }
+}
+
+/// Checks if a row of each batch has the same values in the key columns.
+/// The columns must have the same types in both batches. Two nulls are
+/// equal, and floats are compared like `RowHasher` and `ScalarValue` do, so
+/// all the NaNs are equal and 0.0 is equal to -0.0
+pub fn rows_equal(
+    batch_a: &RecordBatch,
+    row_a: usize,
+    batch_b: &RecordBatch,
+    row_b: usize,
+    columns: &[usize],
+) -> Result<bool> {
+    check_row(batch_a, row_a)?;
+    check_row(batch_b, row_b)?;
+
+    for &index in columns {
+        let left = key_column(batch_a, index)?;
+        let right = key_column(batch_b, index)?;
+        if left.data_type() != right.data_type() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't compare column {} of type {:?} with type {:?}",
+                index,
+                left.data_type(),
+                right.data_type()
+            )));
+        }
+
+        if !values_equal(left, row_a, right, row_b)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn key_column(batch: &RecordBatch, index: usize) -> Result<&ArrayRef> {
+    batch.columns().get(index).ok_or_else(|| {
+        ArrowError::InvalidArgumentError(format!("The batch doesn't have column {}", index))
+    })
+}
+
+fn check_row(batch: &RecordBatch, row: usize) -> Result<()> {
+    match row < batch.num_rows() {
+        true => Ok(()),
+        false => Err(ArrowError::InvalidArgumentError(format!(
+            "The batch doesn't have row {}",
+            row
+        ))),
+    }
+}
+
+// Mixes the values of the rows from `start` to `start + hashes.len()` into
+// the hashes
+fn hash_column(column: &ArrayRef, start: usize, hashes: &mut [u64]) -> Result<()> {
+    match column.data_type() {
+        DataType::Boolean => typed_hash!(column, start, hashes, BooleanArray, hash_int),
+        DataType::Int8 => typed_hash!(column, start, hashes, Int8Array, hash_int),
+        DataType::Int16 => typed_hash!(column, start, hashes, Int16Array, hash_int),
+        DataType::Int32 => typed_hash!(column, start, hashes, Int32Array, hash_int),
+        DataType::Int64 => typed_hash!(column, start, hashes, Int64Array, hash_int),
+        DataType::UInt8 => typed_hash!(column, start, hashes, UInt8Array, hash_int),
+        DataType::UInt16 => typed_hash!(column, start, hashes, UInt16Array, hash_int),
+        DataType::UInt32 => typed_hash!(column, start, hashes, UInt32Array, hash_int),
+        DataType::UInt64 => typed_hash!(column, start, hashes, UInt64Array, mix),
+        DataType::Float32 => typed_hash!(column, start, hashes, Float32Array, hash_f32),
+        DataType::Float64 => typed_hash!(column, start, hashes, Float64Array, hash_f64),
+        DataType::Utf8 => typed_hash!(column, start, hashes, StringArray, hash_str),
+        DataType::LargeUtf8 => typed_hash!(column, start, hashes, LargeStringArray, hash_str),
+        DataType::Date32(_) => typed_hash!(column, start, hashes, Date32Array, hash_int),
+        DataType::Date64(_) => typed_hash!(column, start, hashes, Date64Array, hash_int),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            typed_hash!(column, start, hashes, TimestampSecondArray, hash_int)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            typed_hash!(column, start, hashes, TimestampMillisecondArray, hash_int)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            typed_hash!(column, start, hashes, TimestampMicrosecondArray, hash_int)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            typed_hash!(column, start, hashes, TimestampNanosecondArray, hash_int)
+        }
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't hash a column of type {:?}",
+                other
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+fn values_equal(
+    left: &ArrayRef,
+    row_left: usize,
+    right: &ArrayRef,
+    row_right: usize,
+) -> Result<bool> {
+    let (l, r) = (row_left, row_right);
+    Ok(match left.data_type() {
+        DataType::Boolean => typed_equal!(left, l, right, r, BooleanArray, eq),
+        DataType::Int8 => typed_equal!(left, l, right, r, Int8Array, eq),
+        DataType::Int16 => typed_equal!(left, l, right, r, Int16Array, eq),
+        DataType::Int32 => typed_equal!(left, l, right, r, Int32Array, eq),
+        DataType::Int64 => typed_equal!(left, l, right, r, Int64Array, eq),
+        DataType::UInt8 => typed_equal!(left, l, right, r, UInt8Array, eq),
+        DataType::UInt16 => typed_equal!(left, l, right, r, UInt16Array, eq),
+        DataType::UInt32 => typed_equal!(left, l, right, r, UInt32Array, eq),
+        DataType::UInt64 => typed_equal!(left, l, right, r, UInt64Array, eq),
+        DataType::Float32 => typed_equal!(left, l, right, r, Float32Array, eq_f32),
+        DataType::Float64 => typed_equal!(left, l, right, r, Float64Array, eq_f64),
+        DataType::Utf8 => typed_equal!(left, l, right, r, StringArray, eq),
+        DataType::LargeUtf8 => typed_equal!(left, l, right, r, LargeStringArray, eq),
+        DataType::Date32(_) => typed_equal!(left, l, right, r, Date32Array, eq),
+        DataType::Date64(_) => typed_equal!(left, l, right, r, Date64Array, eq),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            typed_equal!(left, l, right, r, TimestampSecondArray, eq)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            typed_equal!(left, l, right, r, TimestampMillisecondArray, eq)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            typed_equal!(left, l, right, r, TimestampMicrosecondArray, eq)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            typed_equal!(left, l, right, r, TimestampNanosecondArray, eq)
+        }
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't compare a column of type {:?}",
+                other
+            )))
+        }
+    })
+}
+
+fn hash_int<T: Into<i64>>(hash: u64, value: T) -> u64 {
+    mix(hash, value.into() as u64)
+}
+
+// Floats are hashed by their bits like a ScalarValue, with a single NaN
+// and -0.0 folded into 0.0
+fn hash_f32(hash: u64, value: f32) -> u64 {
+    mix(hash, f32_bits(value) as u64)
+}
+
+fn hash_f64(hash: u64, value: f64) -> u64 {
+    mix(hash, f64_bits(value))
+}
+
+// The bytes are mixed 8 at a time. The length is added at the end so
+// strings padded with zeros don't collide
+fn hash_str(mut hash: u64, value: &str) -> u64 {
+    let bytes = value.as_bytes();
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+        hash = mix(hash, u64::from_le_bytes(word));
+    }
+
+    let mut word = [0; 8];
+    word[..chunks.remainder().len()].copy_from_slice(chunks.remainder());
+    hash = mix(hash, u64::from_le_bytes(word));
+    mix(hash, bytes.len() as u64)
+}
+
+fn mix(hash: u64, value: u64) -> u64 {
+    (hash.rotate_left(5) ^ value).wrapping_mul(SEED)
+}
+
+// The low bits of the Fx hash are poorly distributed, and they are the
+// ones used by hash tables and partitions. The finalizer of MurmurHash3
+// spreads the high bits into them
+fn finish(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+fn eq<T: PartialEq>(left: T, right: T) -> bool {
+    left == right
+}
+
+fn eq_f32(left: f32, right: f32) -> bool {
+    f32_bits(left) == f32_bits(right)
+}
+
+fn eq_f64(left: f64, right: f64) -> bool {
+    f64_bits(left) == f64_bits(right)
+}
@@ -0,0 +1,130 @@
+// Shifts move the values of a column some positions down or up, so every
+// row can be compared with the previous or the following ones
+use arrow::{
+    array::{make_array, ArrayRef, MutableArrayData, UInt32Array},
+    compute::{cast, take},
+    datatypes::DataType,
+    error::{ArrowError, Result},
+};
+use std::sync::Arc;
+
+/// Moves the values of the array `offset` positions. With a positive
+/// offset the values move down and the first rows are null, while a
+/// negative offset moves them up and the last rows are null. The result
+/// has the length of the array
+pub fn shift(array: &ArrayRef, offset: i64) -> Result<ArrayRef> {
+    let mut chunks = shift_chunks(std::slice::from_ref(array), offset)?;
+    Ok(chunks.remove(0))
+}
+
+// Shifts the values of a column split in chunks as if it was one array.
+// The values cross the boundaries between the chunks, and every chunk of
+// the result has the length of the original one
+pub(crate) fn shift_chunks(chunks: &[ArrayRef], offset: i64) -> Result<Vec<ArrayRef>> {
+    let data_type = match chunks.first() {
+        Some(chunk) => chunk.data_type().clone(),
+        None => return Ok(Vec::new()),
+    };
+    check_supported_type(&data_type)?;
+
+    let chunks = chunks
+        .iter()
+        .map(|chunk| compact_offsets(&decode_dictionary(chunk)?))
+        .collect::<Result<Vec<_>>>()?;
+    let total = chunks.iter().map(|chunk| chunk.len() as i64).sum::<i64>();
+    let sources = chunks
+        .iter()
+        .map(|chunk| chunk.data_ref().as_ref())
+        .collect::<Vec<_>>();
+
+    let mut shifted = Vec::with_capacity(chunks.len());
+    let mut start = 0;
+    for chunk in &chunks {
+        let end = start + chunk.len() as i64;
+        let mut result = MutableArrayData::new(sources.clone(), true, chunk.len());
+
+        // Rows of the column that end up in this chunk. The ones that fall
+        // outside of the column are nulls
+        let from = start - offset;
+        let to = end - offset;
+        let leading = (0.min(to) - from).max(0).min(end - start);
+        let trailing = (to - total.max(from)).max(0).min(end - start);
+        if leading > 0 {
+            result.extend_nulls(leading as usize);
+        }
+
+        let mut chunk_start = 0;
+        for (index, source) in chunks.iter().enumerate() {
+            let chunk_end = chunk_start + source.len() as i64;
+            let copy_from = from.max(chunk_start);
+            let copy_to = to.min(chunk_end);
+            if copy_from < copy_to {
+                result.extend(
+                    index,
+                    (copy_from - chunk_start) as usize,
+                    (copy_to - chunk_start) as usize,
+                );
+            }
+            chunk_start = chunk_end;
+        }
+
+        if trailing > 0 {
+            result.extend_nulls(trailing as usize);
+        }
+
+        let chunk = make_array(Arc::new(result.freeze()));
+        shifted.push(encode_dictionary(&chunk, &data_type)?);
+        start = end;
+    }
+
+    Ok(shifted)
+}
+
+// Arrow builds the shifted arrays with MutableArrayData, which panics with
+// the types it doesn't support, so they are rejected before using it
+pub(crate) fn check_supported_type(data_type: &DataType) -> Result<()> {
+    match data_type {
+        DataType::Float16
+        | DataType::FixedSizeList(_, _)
+        | DataType::Union(_)
+        | DataType::Decimal(_, _) => Err(ArrowError::InvalidArgumentError(format!(
+            "Can't copy the values of a column of type {:?}",
+            data_type
+        ))),
+        _ => Ok(()),
+    }
+}
+
+// MutableArrayData and concat in arrow 3 keep the dictionary of the first
+// chunk, so the keys of the other chunks would point to the wrong values.
+// Dictionary chunks are decoded to their values before copying them, and
+// the result is encoded again with encode_dictionary
+pub(crate) fn decode_dictionary(chunk: &ArrayRef) -> Result<ArrayRef> {
+    match chunk.data_type() {
+        DataType::Dictionary(_, value_type) => cast(chunk, value_type),
+        _ => Ok(chunk.clone()),
+    }
+}
+
+// Encodes the decoded values back to the dictionary type of the column
+pub(crate) fn encode_dictionary(array: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    match data_type {
+        DataType::Dictionary(_, _) => cast(array, data_type),
+        _ => Ok(array.clone()),
+    }
+}
+
+// MutableArrayData in arrow 3 reads the values of a sliced string or binary
+// array from the wrong position, so those chunks are copied to a new array
+// that starts at offset 0 before using it
+pub(crate) fn compact_offsets(chunk: &ArrayRef) -> Result<ArrayRef> {
+    match chunk.data_type() {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary
+            if chunk.offset() > 0 =>
+        {
+            let indices = UInt32Array::from((0..chunk.len() as u32).collect::<Vec<_>>());
+            take(chunk.as_ref(), &indices, None)
+        }
+        _ => Ok(chunk.clone()),
+    }
+}
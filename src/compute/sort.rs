@@ -0,0 +1,22 @@
+// Sort permutations. The indices returned can be used with `take` to sort
+// any column of the batch in the order of the key columns
+use arrow::{
+    array::{ArrayRef, UInt32Array},
+    compute::{lexsort_to_indices, SortColumn, SortOptions},
+    error::Result,
+};
+
+/// Indices that sort the rows by the key columns. The rows are compared by
+/// the first column, and the ties by the following ones, each of them with
+/// its own direction and placement of the nulls. The sort is stable
+pub fn sort_indices(columns: &[(&ArrayRef, SortOptions)]) -> Result<UInt32Array> {
+    let columns = columns
+        .iter()
+        .map(|(values, options)| SortColumn {
+            values: (*values).clone(),
+            options: Some(*options),
+        })
+        .collect::<Vec<_>>();
+
+    lexsort_to_indices(&columns)
+}
@@ -0,0 +1,237 @@
+// String kernels for Utf8 and LargeUtf8 columns. The results are built
+// writing the offsets and values buffers directly, and the validity bitmap
+// of the input is reused so the nulls stay in the same rows
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        make_array, Array, ArrayData, ArrayRef, GenericStringArray, LargeStringArray, StringArray,
+        StringOffsetSizeTrait,
+    },
+    buffer::Buffer,
+    datatypes::{DataType, ToByteSlice},
+    error::{ArrowError, Result},
+};
+
+use crate::bitmap::combine_validity;
+use crate::downcast::downcast_array;
+
+// Downcasts the column to StringArray or LargeStringArray and calls the
+// generic function with it
+macro_rules! string_op {
+    ($column:expr, $name:expr, $FUNC:ident $(, $args:expr)*) => {{
+        match $column.data_type() {
+            DataType::Utf8 => $FUNC(
+                downcast_array::<StringArray>($column.as_ref())?
+                $(, $args)*
+            ),
+            DataType::LargeUtf8 => $FUNC(
+                downcast_array::<LargeStringArray>($column.as_ref())?
+                $(, $args)*
+            ),
+            other => Err(unsupported($name, other)),
+        }
+    }};
+}
+
+/// Converts the strings of the column to uppercase
+pub fn upper(column: &ArrayRef) -> Result<ArrayRef> {
+    string_op!(column, "upper", map_strings, |value| {
+        Cow::Owned(value.to_uppercase())
+    })
+}
+
+/// Converts the strings of the column to lowercase
+pub fn lower(column: &ArrayRef) -> Result<ArrayRef> {
+    string_op!(column, "lower", map_strings, |value| {
+        Cow::Owned(value.to_lowercase())
+    })
+}
+
+/// Removes the leading and trailing whitespace of the strings
+pub fn trim(column: &ArrayRef) -> Result<ArrayRef> {
+    string_op!(column, "trim", map_strings, |value| {
+        Cow::Borrowed(value.trim())
+    })
+}
+
+/// Number of characters of every string. The result is an Int32 column
+/// for Utf8 and an Int64 column for LargeUtf8
+pub fn length(column: &ArrayRef) -> Result<ArrayRef> {
+    string_op!(column, "length", string_lengths)
+}
+
+/// Takes `length` characters of every string starting from the character
+/// `start`. Without a length the strings are taken until their end.
+/// Strings shorter than `start` give an empty string
+pub fn substring(column: &ArrayRef, start: usize, length: Option<usize>) -> Result<ArrayRef> {
+    string_op!(column, "substring", map_strings, |value| {
+        Cow::Borrowed(char_slice(value, start, length))
+    })
+}
+
+/// Concatenates the strings of several columns row by row. All the columns
+/// must have the same type and length, and a row is null if it's null in
+/// any of the columns
+pub fn concat_columns(columns: &[ArrayRef]) -> Result<ArrayRef> {
+    let first = columns.first().ok_or_else(|| {
+        ArrowError::InvalidArgumentError("There are no columns to concatenate".to_string())
+    })?;
+
+    for column in columns {
+        if column.data_type() != first.data_type() || column.len() != first.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Can't concatenate columns of type {:?} with {} rows and type {:?} with {} rows",
+                first.data_type(),
+                first.len(),
+                column.data_type(),
+                column.len()
+            )));
+        }
+    }
+
+    match first.data_type() {
+        DataType::Utf8 => concat_strings::<i32>(columns),
+        DataType::LargeUtf8 => concat_strings::<i64>(columns),
+        other => Err(unsupported("concatenate", other)),
+    }
+}
+
+// Writes the value returned by the closure for every valid row. Null rows get an
+// empty value, so their offset is repeated
+fn map_strings<O, F>(array: &GenericStringArray<O>, mut op: F) -> Result<ArrayRef>
+where
+    O: StringOffsetSizeTrait,
+    F: for<'a> FnMut(&'a str) -> Cow<'a, str>,
+{
+    let mut offsets = Vec::with_capacity(array.len() + 1);
+    let mut values = Vec::new();
+    offsets.push(O::zero());
+
+    for i in 0..array.len() {
+        if array.is_valid(i) {
+            values.extend_from_slice(op(array.value(i)).as_bytes());
+        }
+        offsets.push(to_offset(values.len())?);
+    }
+
+    Ok(build_strings(
+        array.data_type().clone(),
+        array.len(),
+        validity(array),
+        offsets,
+        values,
+    ))
+}
+
+fn string_lengths<O: StringOffsetSizeTrait>(array: &GenericStringArray<O>) -> Result<ArrayRef> {
+    let lengths = (0..array.len())
+        .map(|i| match array.is_valid(i) {
+            true => to_offset::<O>(array.value(i).chars().count()),
+            false => Ok(O::zero()),
+        })
+        .collect::<Result<Vec<O>>>()?;
+
+    let data_type = match array.data_type() {
+        DataType::LargeUtf8 => DataType::Int64,
+        _ => DataType::Int32,
+    };
+
+    let data = ArrayData::new(
+        data_type,
+        array.len(),
+        None,
+        validity(array),
+        0,
+        vec![Buffer::from(lengths.to_byte_slice())],
+        vec![],
+    );
+    Ok(make_array(Arc::new(data)))
+}
+
+fn concat_strings<O: StringOffsetSizeTrait>(columns: &[ArrayRef]) -> Result<ArrayRef> {
+    let arrays = columns
+        .iter()
+        .map(|column| downcast_array::<GenericStringArray<O>>(column.as_ref()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let len = columns[0].len();
+    let mut offsets = Vec::with_capacity(len + 1);
+    let mut values = Vec::new();
+    offsets.push(O::zero());
+
+    for i in 0..len {
+        if arrays.iter().all(|array| array.is_valid(i)) {
+            for array in &arrays {
+                values.extend_from_slice(array.value(i).as_bytes());
+            }
+        }
+        offsets.push(to_offset(values.len())?);
+    }
+
+    Ok(build_strings(
+        columns[0].data_type().clone(),
+        len,
+        combine_validity(columns)?,
+        offsets,
+        values,
+    ))
+}
+
+fn build_strings<O: StringOffsetSizeTrait>(
+    data_type: DataType,
+    len: usize,
+    validity: Option<Buffer>,
+    offsets: Vec<O>,
+    values: Vec<u8>,
+) -> ArrayRef {
+    let data = ArrayData::new(
+        data_type,
+        len,
+        None,
+        validity,
+        0,
+        vec![Buffer::from(offsets.to_byte_slice()), Buffer::from(values)],
+        vec![],
+    );
+    make_array(Arc::new(data))
+}
+
+// The validity bitmap of a sliced array starts at its offset, so it's
+// sliced to start at the first bit like the result
+fn validity(array: &dyn Array) -> Option<Buffer> {
+    array
+        .data_ref()
+        .null_buffer()
+        .map(|buffer| buffer.bit_slice(array.offset(), array.len()))
+}
+
+// The positions are counted in characters, so a multibyte character is
+// never split
+fn char_slice(value: &str, start: usize, length: Option<usize>) -> &str {
+    let byte_index = |chars: usize| {
+        value
+            .char_indices()
+            .nth(chars)
+            .map_or(value.len(), |(index, _)| index)
+    };
+
+    let begin = byte_index(start);
+    let end = match length {
+        Some(length) => byte_index(start.saturating_add(length)),
+        None => value.len(),
+    };
+    &value[begin..end]
+}
+
+// Utf8 columns use i32 offsets, so their values can't exceed 2GB
+fn to_offset<O: StringOffsetSizeTrait>(len: usize) -> Result<O> {
+    O::from_usize(len).ok_or_else(|| {
+        ArrowError::ComputeError("The values don't fit in a Utf8 column".to_string())
+    })
+}
+
+fn unsupported(name: &str, data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!("Can't {} a column of type {:?}", name, data_type))
+}
@@ -0,0 +1,236 @@
+// The t-digest summarizes a column with a small number of centroids, each
+// of them the mean of a group of neighbouring values. The groups are small
+// near the extremes and large in the middle, so the quantiles close to 0
+// and 1 are the most precise. See Dunning and Ertl, "Computing extremely
+// accurate quantiles using t-digests"
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+use arrow::{
+    array::{
+        ArrayRef, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    datatypes::DataType,
+    error::{ArrowError, Result},
+};
+
+use crate::downcast::downcast_array;
+
+const DEFAULT_COMPRESSION: f64 = 100.0;
+
+// Adds every value of the array to the digest
+macro_rules! typed_add {
+    ($digest:expr, $array:expr, $ARRAYTYPE:ident) => {{
+        let array = downcast_array::<$ARRAYTYPE>($array.as_ref())?;
+        for value in array.iter().flatten() {
+            $digest.add(value as f64);
+        }
+    }};
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Approximate distribution of the values of a column, built in a single
+/// pass. The values can be added in batches as they are read and the
+/// digests of different parts of a column can be merged
+#[derive(Debug, Clone)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    // Values added since the last compression
+    buffer: Vec<f64>,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPRESSION)
+    }
+}
+
+impl TDigest {
+    /// Creates an empty digest. The compression limits the number of
+    /// centroids kept, so a larger one gives more precise quantiles using
+    /// more memory. 100 is a good default
+    pub fn new(compression: f64) -> Self {
+        Self {
+            compression: compression.max(1.0),
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Number of values added to the digest
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds a value. NaN values are ignored
+    pub fn add(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+
+        self.buffer.push(value);
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        if self.buffer.len() >= 5 * self.compression as usize {
+            self.compress();
+        }
+    }
+
+    /// Adds the values of a numeric array ignoring the nulls
+    pub fn add_array(&mut self, array: &ArrayRef) -> Result<()> {
+        match array.data_type() {
+            DataType::Int8 => typed_add!(self, array, Int8Array),
+            DataType::Int16 => typed_add!(self, array, Int16Array),
+            DataType::Int32 => typed_add!(self, array, Int32Array),
+            DataType::Int64 => typed_add!(self, array, Int64Array),
+            DataType::UInt8 => typed_add!(self, array, UInt8Array),
+            DataType::UInt16 => typed_add!(self, array, UInt16Array),
+            DataType::UInt32 => typed_add!(self, array, UInt32Array),
+            DataType::UInt64 => typed_add!(self, array, UInt64Array),
+            DataType::Float32 => typed_add!(self, array, Float32Array),
+            DataType::Float64 => typed_add!(self, array, Float64Array),
+            other => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Can't calculate the quantiles of a column of type {:?}",
+                    other
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds all the values summarized by another digest
+    pub fn merge(&mut self, other: &TDigest) {
+        self.centroids.extend_from_slice(&other.centroids);
+        self.buffer.extend_from_slice(&other.buffer);
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress();
+    }
+
+    /// Estimates the value below which a fraction `q` of the values fall,
+    /// for example 0.5 for the median. None is returned if the digest is
+    /// empty
+    pub fn quantile(&mut self, q: f64) -> Result<Option<f64>> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The quantile must be between 0 and 1 but it's {}",
+                q
+            )));
+        }
+
+        self.compress();
+        if self.centroids.is_empty() {
+            return Ok(None);
+        }
+
+        // Every centroid represents the values around its mean, so the
+        // rank of the quantile is interpolated between the centers of the
+        // two closest centroids. The extremes are interpolated with the
+        // minimum and the maximum, which are exact
+        let total = self.count as f64;
+        let rank = q * total;
+
+        let first = self.centroids[0];
+        if rank <= first.weight / 2.0 {
+            return Ok(Some(interpolate(
+                (0.0, self.min),
+                (first.weight / 2.0, first.mean),
+                rank,
+            )));
+        }
+
+        let mut center = first.weight / 2.0;
+        for pair in self.centroids.windows(2) {
+            let next_center = center + (pair[0].weight + pair[1].weight) / 2.0;
+            if rank <= next_center {
+                return Ok(Some(interpolate(
+                    (center, pair[0].mean),
+                    (next_center, pair[1].mean),
+                    rank,
+                )));
+            }
+            center = next_center;
+        }
+
+        let last = self.centroids[self.centroids.len() - 1];
+        Ok(Some(interpolate(
+            (center, last.mean),
+            (total, self.max),
+            rank,
+        )))
+    }
+
+    // Sorts the buffered values with the centroids and merges neighbours
+    // while the group stays under the size allowed for its quantile
+    fn compress(&mut self) {
+        if self.buffer.is_empty() && self.centroids.len() <= 1 {
+            return;
+        }
+
+        let mut all = std::mem::take(&mut self.centroids);
+        all.extend(
+            self.buffer
+                .drain(..)
+                .map(|mean| Centroid { mean, weight: 1.0 }),
+        );
+        all.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(Ordering::Equal));
+
+        let total = all.iter().map(|centroid| centroid.weight).sum::<f64>();
+        let mut merged = Vec::new();
+        let mut current = all[0];
+        let mut weight_before = 0.0;
+        let mut limit = self.q_limit(0.0);
+
+        for centroid in all.into_iter().skip(1) {
+            let q = (weight_before + current.weight + centroid.weight) / total;
+            if q <= limit {
+                current.weight += centroid.weight;
+                current.mean += (centroid.mean - current.mean) * centroid.weight / current.weight;
+            } else {
+                weight_before += current.weight;
+                merged.push(current);
+                limit = self.q_limit(weight_before / total);
+                current = centroid;
+            }
+        }
+        merged.push(current);
+
+        self.centroids = merged;
+    }
+
+    // Largest quantile that a group starting at q can reach. The scale
+    // function k(q) = compression / 2π * asin(2q - 1) grows fast near the
+    // extremes, and every group can span one unit of k
+    fn q_limit(&self, q: f64) -> f64 {
+        let k = self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin() + 1.0;
+        if k >= self.compression / 4.0 {
+            return 1.0;
+        }
+        ((2.0 * PI * k / self.compression).sin() + 1.0) / 2.0
+    }
+}
+
+fn interpolate(from: (f64, f64), to: (f64, f64), x: f64) -> f64 {
+    if to.0 <= from.0 {
+        return from.1;
+    }
+    from.1 + (to.1 - from.1) * (x - from.0) / (to.0 - from.0)
+}
@@ -0,0 +1,219 @@
+// Kernels for Date32, Date64 and Timestamp columns. The values are read as
+// seconds since the Unix epoch and converted to a calendar date by hand, so
+// timestamps are always read as UTC even if they have a timezone
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, Date32Array, Date64Array, Int32Array, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+    },
+    datatypes::{DataType, DateUnit, TimeUnit},
+    error::{ArrowError, Result},
+};
+
+use crate::downcast::downcast_array;
+use crate::ScalarValue;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const NANOS_PER_DAY: i64 = SECONDS_PER_DAY * 1_000_000_000;
+
+// Converts every valid value of the array to seconds since the epoch
+macro_rules! typed_seconds {
+    ($column:expr, $ARRAYTYPE:ident, $per_second:expr) => {{
+        let array = downcast_array::<$ARRAYTYPE>($column.as_ref())?;
+        (0..array.len())
+            .map(|i| match array.is_valid(i) {
+                true => Some((array.value(i) as i64).div_euclid($per_second)),
+                false => None,
+            })
+            .collect::<Vec<Option<i64>>>()
+    }};
+}
+
+// Adds the delta to every valid value of the array. A null delta gives
+// nulls in all the rows
+macro_rules! typed_add {
+    ($column:expr, $ARRAYTYPE:ident, $delta:expr) => {{
+        let array = downcast_array::<$ARRAYTYPE>($column.as_ref())?;
+        (0..array.len())
+            .map(|i| match ($delta, array.is_valid(i)) {
+                (Some(delta), true) => {
+                    array.value(i).checked_add(delta).map(Some).ok_or_else(|| {
+                        ArrowError::ComputeError("Overflow adding the duration".to_string())
+                    })
+                }
+                _ => Ok(None),
+            })
+            .collect::<Result<Vec<_>>>()?
+    }};
+}
+
+/// Year of every date or timestamp, as an Int32 column
+pub fn year(column: &ArrayRef) -> Result<ArrayRef> {
+    extract(column, |seconds| civil_date(seconds).0)
+}
+
+/// Month of every date or timestamp, from 1 to 12
+pub fn month(column: &ArrayRef) -> Result<ArrayRef> {
+    extract(column, |seconds| civil_date(seconds).1)
+}
+
+/// Day of the month of every date or timestamp, from 1 to 31
+pub fn day(column: &ArrayRef) -> Result<ArrayRef> {
+    extract(column, |seconds| civil_date(seconds).2)
+}
+
+/// Hour of every date or timestamp, from 0 to 23. Dates start at
+/// midnight, so Date32 columns always give 0
+pub fn hour(column: &ArrayRef) -> Result<ArrayRef> {
+    extract(column, |seconds| {
+        (seconds.rem_euclid(SECONDS_PER_DAY) / 3600) as i32
+    })
+}
+
+/// Adds a `ScalarValue::Duration` to a date or timestamp column. The
+/// duration is converted to the unit of the column, so it must be a whole
+/// number of days for Date32 and of milliseconds for Date64. A null
+/// duration gives a column of nulls
+pub fn add_duration(column: &ArrayRef, duration: &ScalarValue) -> Result<ArrayRef> {
+    let (value, unit) = match duration {
+        ScalarValue::Duration(value, unit) => (value, unit),
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Expected a duration but got {:?}",
+                other
+            )))
+        }
+    };
+
+    let nanos = value
+        .map(|value| {
+            value.checked_mul(nanos_per_unit(unit)).ok_or_else(|| {
+                ArrowError::ComputeError("The duration doesn't fit in nanoseconds".to_string())
+            })
+        })
+        .transpose()?;
+
+    Ok(match column.data_type() {
+        DataType::Date32(DateUnit::Day) => {
+            let days = to_unit(nanos, NANOS_PER_DAY, "days")?
+                .map(|days| {
+                    i32::try_from(days).map_err(|_| {
+                        ArrowError::ComputeError("The duration doesn't fit in Date32".to_string())
+                    })
+                })
+                .transpose()?;
+            Arc::new(Date32Array::from(typed_add!(column, Date32Array, days)))
+        }
+        DataType::Date64(DateUnit::Millisecond) => {
+            let millis = to_unit(nanos, 1_000_000, "milliseconds")?;
+            Arc::new(Date64Array::from(typed_add!(column, Date64Array, millis)))
+        }
+        DataType::Timestamp(unit, timezone) => {
+            let delta = to_unit(nanos, nanos_per_unit(unit), "the unit of the column")?;
+            let timezone = timezone.clone();
+            match unit {
+                TimeUnit::Second => Arc::new(TimestampSecondArray::from_opt_vec(
+                    typed_add!(column, TimestampSecondArray, delta),
+                    timezone,
+                )),
+                TimeUnit::Millisecond => Arc::new(TimestampMillisecondArray::from_opt_vec(
+                    typed_add!(column, TimestampMillisecondArray, delta),
+                    timezone,
+                )),
+                TimeUnit::Microsecond => Arc::new(TimestampMicrosecondArray::from_opt_vec(
+                    typed_add!(column, TimestampMicrosecondArray, delta),
+                    timezone,
+                )),
+                TimeUnit::Nanosecond => Arc::new(TimestampNanosecondArray::from_opt_vec(
+                    typed_add!(column, TimestampNanosecondArray, delta),
+                    timezone,
+                )),
+            }
+        }
+        other => return Err(unsupported(other)),
+    })
+}
+
+fn extract<F: Fn(i64) -> i32>(column: &ArrayRef, op: F) -> Result<ArrayRef> {
+    let seconds = match column.data_type() {
+        // Date32 counts days, so the macro returns days
+        DataType::Date32(DateUnit::Day) => typed_seconds!(column, Date32Array, 1)
+            .into_iter()
+            .map(|days| days.map(|days| days * SECONDS_PER_DAY))
+            .collect(),
+        DataType::Date64(DateUnit::Millisecond) => typed_seconds!(column, Date64Array, 1_000),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            typed_seconds!(column, TimestampSecondArray, 1)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            typed_seconds!(column, TimestampMillisecondArray, 1_000)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            typed_seconds!(column, TimestampMicrosecondArray, 1_000_000)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            typed_seconds!(column, TimestampNanosecondArray, 1_000_000_000)
+        }
+        other => return Err(unsupported(other)),
+    };
+
+    let values = seconds
+        .into_iter()
+        .map(|seconds| seconds.map(&op))
+        .collect::<Vec<Option<i32>>>();
+    Ok(Arc::new(Int32Array::from(values)))
+}
+
+// Converts seconds since 1970-01-01 to the (year, month, day) of the proleptic
+// Gregorian calendar. The days are counted in eras of 400 years starting
+// on March 1st, so the leap day is the last day of the year
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_date(seconds: i64) -> (i32, i32, i32) {
+    let days = seconds.div_euclid(SECONDS_PER_DAY) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year as i32, month as i32, day as i32)
+}
+
+fn nanos_per_unit(unit: &TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    }
+}
+
+// Durations that aren't a whole number of units of the column would be
+// truncated, so they are rejected
+fn to_unit(nanos: Option<i64>, nanos_per_unit: i64, unit: &str) -> Result<Option<i64>> {
+    match nanos {
+        Some(nanos) if nanos % nanos_per_unit != 0 => Err(ArrowError::InvalidArgumentError(
+            format!("The duration isn't a whole number of {}", unit),
+        )),
+        _ => Ok(nanos.map(|nanos| nanos / nanos_per_unit)),
+    }
+}
+
+fn unsupported(data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Expected a date or timestamp column but got {:?}",
+        data_type
+    ))
+}
@@ -0,0 +1,290 @@
+// Lower level ingestion of CSV files that arrow's csv reader can't infer,
+// like dates written in a custom format or numbers with thousands
+// separators. The records are read with the csv crate and every column has
+// its own parser, a closure that turns the text of a field into the
+// ScalarValue appended to the builder of the column
+use std::io::Read;
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, DateUnit, Field, Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use chrono::NaiveDate;
+use csv::StringRecord;
+
+use crate::builders::{make_column, MutableColumn};
+use crate::ScalarValue;
+
+/// Parses the text of a field into a value of the type of its column
+pub type FieldParser = Box<dyn Fn(&str) -> Result<ScalarValue> + Send>;
+
+// Rows of the batches unless a different size is set
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+struct CsvColumn {
+    // Position of the field in the records
+    index: usize,
+    parser: FieldParser,
+    builder: Box<dyn MutableColumn>,
+}
+
+/// Builds RecordBatches from `csv::StringRecord`s. Every column reads one
+/// field of the records and parses it with its own parser, so the fields
+/// can be in any order and the ones without a column are skipped.
+/// A batch is returned every time the batch size is reached
+pub struct CsvRecordsBuilder {
+    fields: Vec<Field>,
+    columns: Vec<CsvColumn>,
+    null_values: Vec<String>,
+    batch_size: usize,
+}
+
+impl Default for CsvRecordsBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_BATCH_SIZE)
+    }
+}
+
+impl CsvRecordsBuilder {
+    /// Creates a builder without columns that returns batches of
+    /// batch_size rows
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            fields: Vec::new(),
+            columns: Vec::new(),
+            null_values: vec![String::new()],
+            batch_size: batch_size.max(1),
+        }
+    }
+
+    /// Adds a column with the field at index of the records, parsed with
+    /// the default parser of its type, see `default_parser`
+    pub fn with_column(self, name: &str, index: usize, data_type: DataType) -> Result<Self> {
+        let parser = default_parser(&data_type)?;
+        self.with_parser(name, index, data_type, parser)
+    }
+
+    /// Adds a column with the field at index of the records, parsed with
+    /// a custom parser. The parser has to return values of the data type
+    pub fn with_parser<F>(
+        mut self,
+        name: &str,
+        index: usize,
+        data_type: DataType,
+        parser: F,
+    ) -> Result<Self>
+    where
+        F: Fn(&str) -> Result<ScalarValue> + Send + 'static,
+    {
+        let builder = make_column(&data_type, self.batch_size)?;
+        self.fields.push(Field::new(name, data_type, true));
+        self.columns.push(CsvColumn {
+            index,
+            parser: Box::new(parser),
+            builder,
+        });
+        Ok(self)
+    }
+
+    /// Texts that are read as nulls without calling the parsers. By
+    /// default only the empty field is a null
+    pub fn with_null_values(mut self, null_values: &[&str]) -> Self {
+        self.null_values = null_values.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        Arc::new(Schema::new(self.fields.clone()))
+    }
+
+    /// Number of rows appended since the last batch
+    pub fn len(&self) -> usize {
+        self.columns
+            .first()
+            .map_or(0, |column| column.builder.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parses the fields of the record and appends them to the columns.
+    /// The batch is returned once it has batch_size rows. A record that
+    /// fails doesn't leave values in the columns
+    pub fn append(&mut self, record: &StringRecord) -> Result<Option<RecordBatch>> {
+        let values = self
+            .columns
+            .iter()
+            .map(|column| {
+                let text = record.get(column.index).ok_or_else(|| {
+                    record_error(record, column.index, "the record doesn't have the field")
+                })?;
+                if self.null_values.iter().any(|null| null == text) {
+                    return Ok(None);
+                }
+                (column.parser)(text)
+                    .map(Some)
+                    .map_err(|error| match error {
+                        ArrowError::ParseError(message) => {
+                            record_error(record, column.index, message)
+                        }
+                        error => record_error(record, column.index, error),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for (column, value) in self.columns.iter_mut().zip(&values) {
+            match value {
+                Some(value) => column.builder.append_scalar(value)?,
+                None => column.builder.append_null()?,
+            }
+        }
+
+        if self.len() >= self.batch_size {
+            self.finish()
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the rows appended since the last batch, or None if there
+    /// aren't any, and resets the columns
+    pub fn finish(&mut self) -> Result<Option<RecordBatch>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        let columns = self
+            .columns
+            .iter_mut()
+            .map(|column| column.builder.finish())
+            .collect();
+        RecordBatch::try_new(self.schema(), columns).map(Some)
+    }
+
+    /// Reads every record of the reader and returns the batches. The last
+    /// batch has the remaining rows
+    pub fn read<R: Read>(
+        mut self,
+        mut reader: csv::Reader<R>,
+    ) -> impl Iterator<Item = Result<RecordBatch>> {
+        let mut record = StringRecord::new();
+        let mut done = false;
+        std::iter::from_fn(move || {
+            while !done {
+                let batch = match reader.read_record(&mut record) {
+                    Ok(true) => self.append(&record),
+                    Ok(false) => {
+                        done = true;
+                        self.finish()
+                    }
+                    Err(error) => {
+                        done = true;
+                        Err(ArrowError::CsvError(error.to_string()))
+                    }
+                };
+                match batch {
+                    Ok(None) => continue,
+                    Ok(Some(batch)) => return Some(Ok(batch)),
+                    Err(error) => {
+                        done = true;
+                        return Some(Err(error));
+                    }
+                }
+            }
+            None
+        })
+    }
+}
+
+/// Parser used when a column doesn't have one. Numbers and booleans are
+/// parsed with `str::parse` and dates have to be written as %Y-%m-%d
+pub fn default_parser(data_type: &DataType) -> Result<FieldParser> {
+    macro_rules! parse {
+        ($variant:ident) => {
+            Box::new(|text: &str| {
+                text.trim()
+                    .parse()
+                    .map(|value| ScalarValue::$variant(Some(value)))
+                    .map_err(|error| ArrowError::ParseError(format!("{:?}: {}", text, error)))
+            })
+        };
+    }
+
+    let parser: FieldParser = match data_type {
+        DataType::Boolean => parse!(Boolean),
+        DataType::Int8 => parse!(Int8),
+        DataType::Int16 => parse!(Int16),
+        DataType::Int32 => parse!(Int32),
+        DataType::Int64 => parse!(Int64),
+        DataType::UInt8 => parse!(UInt8),
+        DataType::UInt16 => parse!(UInt16),
+        DataType::UInt32 => parse!(UInt32),
+        DataType::UInt64 => parse!(UInt64),
+        DataType::Float32 => parse!(Float32),
+        DataType::Float64 => parse!(Float64),
+        DataType::Utf8 => Box::new(|text: &str| Ok(ScalarValue::Utf8(Some(text.to_string())))),
+        DataType::LargeUtf8 => {
+            Box::new(|text: &str| Ok(ScalarValue::LargeUtf8(Some(text.to_string()))))
+        }
+        DataType::Date32(DateUnit::Day) => parse_date("%Y-%m-%d"),
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "There isn't a default parser for {:?}, the column needs its own parser",
+                other
+            )))
+        }
+    };
+    Ok(parser)
+}
+
+/// Parses Date32 values written with a chrono format, like "%d/%m/%Y"
+pub fn parse_date(format: &str) -> FieldParser {
+    let format = format.to_string();
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    Box::new(move |text: &str| {
+        NaiveDate::parse_from_str(text.trim(), &format)
+            .map(|date| ScalarValue::Date32(Some((date - epoch).num_days() as i32)))
+            .map_err(|error| {
+                ArrowError::ParseError(format!("{:?} with format {:?}: {}", text, format, error))
+            })
+    })
+}
+
+/// Parses numbers written with thousands separators and a decimal mark
+/// other than the dot, like "1.234.567,89". The separators are removed
+/// and the number is parsed by the default parser of the data type
+pub fn parse_number(data_type: &DataType, thousands: char, decimal: char) -> Result<FieldParser> {
+    if !DataType::is_numeric(data_type) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Can't parse numbers as {:?}",
+            data_type
+        )));
+    }
+
+    let parser = default_parser(data_type)?;
+    Ok(Box::new(move |text: &str| {
+        let number = text
+            .chars()
+            .filter(|c| *c != thousands)
+            .map(|c| if c == decimal { '.' } else { c })
+            .collect::<String>();
+        parser(&number)
+    }))
+}
+
+// The position of the record is the line where it starts, which is the
+// one shown by editors
+fn record_error<E: ToString>(record: &StringRecord, index: usize, error: E) -> ArrowError {
+    let line = record
+        .position()
+        .map_or(String::from("?"), |position| position.line().to_string());
+    ArrowError::ParseError(format!(
+        "Can't read field {} of line {}: {}",
+        index,
+        line,
+        error.to_string()
+    ))
+}
@@ -0,0 +1,370 @@
+// Minimal reader of Delta Lake tables. A Delta table is a directory of
+// parquet files with a _delta_log directory, where every commit is a JSON
+// file with one action per line. Replaying the add and remove actions of
+// the commits up to a version gives the files that form the table at that
+// version. The values of the partition columns aren't stored in the files,
+// they come from the add actions. Checkpoints, deletion vectors and column
+// mapping aren't supported
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef},
+    compute::concat,
+    datatypes::{DataType, DateUnit, Field, Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use parquet::{
+    arrow::{ArrowReader, ParquetFileArrowReader},
+    file::reader::SerializedFileReader,
+};
+use serde_json::Value;
+
+use crate::builders::make_column;
+use crate::csv_records::default_parser;
+
+const LOG_DIR: &str = "_delta_log";
+
+// Partition values of a file by column name. A null value is None
+type PartitionValues = HashMap<String, Option<String>>;
+
+// Files and metadata of the table at a version
+struct Snapshot {
+    // Fields of the table from the metaData action
+    fields: Vec<(String, Value)>,
+    partition_columns: Vec<String>,
+    // Active files in the order they were added
+    files: Vec<(String, PartitionValues)>,
+}
+
+/// Reads the table at the version, or the latest one if there isn't a
+/// version. The columns of the parquet files come first, followed by the
+/// partition columns
+pub(crate) fn read_delta(
+    path: &Path,
+    version: Option<u64>,
+    chunk_size: usize,
+) -> Result<(Schema, Vec<RecordBatch>)> {
+    let snapshot = replay_log(&path.join(LOG_DIR), version)?;
+
+    let partition_fields = snapshot
+        .partition_columns
+        .iter()
+        .map(|name| {
+            let delta_type = snapshot
+                .fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, delta_type)| delta_type)
+                .ok_or_else(|| delta_error(format!("Missing partition column {}", name)))?;
+            Ok(Field::new(name, data_type(delta_type)?, true))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut data_fields: Option<Vec<Field>> = None;
+    let mut batches = Vec::new();
+    for (file, values) in &snapshot.files {
+        let file_path = path.join(percent_decode(file)?);
+        let (file_fields, file_batches) = read_file(&file_path, chunk_size)?;
+
+        match &data_fields {
+            Some(fields) if fields != &file_fields => {
+                return Err(delta_error(format!(
+                    "The schema of {} is different from the schema of the other files",
+                    file
+                )))
+            }
+            Some(_) => (),
+            None => data_fields = Some(file_fields),
+        }
+
+        for batch in file_batches {
+            let mut columns = batch.columns().to_vec();
+            for field in &partition_fields {
+                let value = values.get(field.name()).cloned().flatten();
+                columns.push(repeat_value(field, value.as_deref(), batch.num_rows())?);
+            }
+            batches.push(columns);
+        }
+    }
+
+    // Without files the schema of the data columns comes from the log
+    let data_fields = match data_fields {
+        Some(fields) => fields,
+        None => snapshot
+            .fields
+            .iter()
+            .filter(|(name, _)| !snapshot.partition_columns.contains(name))
+            .map(|(name, delta_type)| Ok(Field::new(name, data_type(delta_type)?, true)))
+            .collect::<Result<Vec<_>>>()?,
+    };
+
+    let mut fields = data_fields;
+    fields.extend(partition_fields);
+    let schema = Schema::new(fields);
+
+    let batches = rechunk(Arc::new(schema.clone()), batches, chunk_size)?;
+    Ok((schema, batches))
+}
+
+// Replays the commits from the first one up to the version
+fn replay_log(log_dir: &Path, version: Option<u64>) -> Result<Snapshot> {
+    let mut commits = fs::read_dir(log_dir)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let number = name.strip_suffix(".json")?;
+            if number.len() != 20 {
+                return None;
+            }
+            Some((number.parse::<u64>().ok()?, path))
+        })
+        .collect::<Vec<(u64, PathBuf)>>();
+    commits.sort();
+
+    let latest = match commits.last() {
+        Some((latest, _)) => *latest,
+        None => return Err(delta_error("The table doesn't have commits".to_string())),
+    };
+    let version = version.unwrap_or(latest);
+    if version > latest {
+        return Err(delta_error(format!(
+            "The table doesn't have version {}, the latest is {}",
+            version, latest
+        )));
+    }
+
+    // Old commits are removed once there is a checkpoint, and then the log
+    // can only be read from the checkpoint
+    if commits
+        .iter()
+        .take_while(|(number, _)| *number <= version)
+        .enumerate()
+        .any(|(expected, (number, _))| expected as u64 != *number)
+    {
+        return Err(delta_error(
+            "Commits are missing from the log, tables with checkpoints aren't supported"
+                .to_string(),
+        ));
+    }
+
+    let mut snapshot = Snapshot {
+        fields: Vec::new(),
+        partition_columns: Vec::new(),
+        files: Vec::new(),
+    };
+    for (_, commit) in commits.iter().take_while(|(number, _)| *number <= version) {
+        for line in BufReader::new(File::open(commit)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let action: Value = serde_json::from_str(&line)
+                .map_err(|err| delta_error(format!("Invalid action in {:?}: {}", commit, err)))?;
+            apply_action(&mut snapshot, &action)?;
+        }
+    }
+
+    Ok(snapshot)
+}
+
+fn apply_action(snapshot: &mut Snapshot, action: &Value) -> Result<()> {
+    if let Some(add) = action.get("add") {
+        let path = string_field(add, "path")?;
+        let values = match add.get("partitionValues").and_then(Value::as_object) {
+            Some(values) => values
+                .iter()
+                .map(|(name, value)| (name.clone(), value.as_str().map(str::to_string)))
+                .collect(),
+            None => PartitionValues::new(),
+        };
+        // Adding a file again replaces the previous add
+        snapshot.files.retain(|(file, _)| *file != path);
+        snapshot.files.push((path, values));
+    } else if let Some(remove) = action.get("remove") {
+        let path = string_field(remove, "path")?;
+        snapshot.files.retain(|(file, _)| *file != path);
+    } else if let Some(metadata) = action.get("metaData") {
+        let schema: Value = serde_json::from_str(&string_field(metadata, "schemaString")?)
+            .map_err(|err| delta_error(format!("Invalid schemaString: {}", err)))?;
+        snapshot.fields = schema
+            .get("fields")
+            .and_then(Value::as_array)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(|field| {
+                        let name = field.get("name")?.as_str()?.to_string();
+                        Some((name, field.get("type")?.clone()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        snapshot.partition_columns = metadata
+            .get("partitionColumns")
+            .and_then(Value::as_array)
+            .map(|columns| {
+                columns
+                    .iter()
+                    .filter_map(|column| column.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+    // The protocol, commitInfo and txn actions don't change the data
+
+    Ok(())
+}
+
+fn read_file(path: &Path, chunk_size: usize) -> Result<(Vec<Field>, Vec<RecordBatch>)> {
+    let file = File::open(path)?;
+    let file_reader =
+        SerializedFileReader::new(file).map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+    let mut arrow_reader = ParquetFileArrowReader::new(Arc::new(file_reader));
+
+    let schema = arrow_reader
+        .get_schema()
+        .map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+    let batches = arrow_reader
+        .get_record_reader(chunk_size)
+        .map_err(|e| ArrowError::ParquetError(e.to_string()))?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((schema.fields().clone(), batches))
+}
+
+// Only primitive types can be partition columns, and the table schema only
+// needs to be read from the log when there aren't any files
+fn data_type(delta_type: &Value) -> Result<DataType> {
+    let data_type = match delta_type.as_str() {
+        Some("string") => DataType::Utf8,
+        Some("long") => DataType::Int64,
+        Some("integer") => DataType::Int32,
+        Some("short") => DataType::Int16,
+        Some("byte") => DataType::Int8,
+        Some("float") => DataType::Float32,
+        Some("double") => DataType::Float64,
+        Some("boolean") => DataType::Boolean,
+        Some("date") => DataType::Date32(DateUnit::Day),
+        _ => {
+            return Err(delta_error(format!(
+                "The Delta type {} isn't supported",
+                delta_type
+            )))
+        }
+    };
+    Ok(data_type)
+}
+
+// The partition values are written as text, dates as %Y-%m-%d, so they are
+// parsed like the fields of a csv
+fn repeat_value(field: &Field, value: Option<&str>, len: usize) -> Result<ArrayRef> {
+    let mut column = make_column(field.data_type(), len)?;
+    match value {
+        Some(value) => {
+            let scalar = default_parser(field.data_type())?(value)?;
+            for _ in 0..len {
+                column.append_scalar(&scalar)?;
+            }
+        }
+        None => {
+            for _ in 0..len {
+                column.append_null()?;
+            }
+        }
+    }
+    Ok(column.finish())
+}
+
+// Table expects every batch but the last one to have chunk_size rows,
+// while the last batch of every file is usually shorter
+fn rechunk(
+    schema: SchemaRef,
+    batches: Vec<Vec<ArrayRef>>,
+    chunk_size: usize,
+) -> Result<Vec<RecordBatch>> {
+    let mut chunks = Vec::new();
+    let mut pending: Vec<Vec<ArrayRef>> = Vec::new();
+    let mut pending_rows = 0;
+
+    let mut flush = |pending: &mut Vec<Vec<ArrayRef>>| -> Result<()> {
+        let columns = (0..schema.fields().len())
+            .map(|column| match pending.as_slice() {
+                [single] => Ok(single[column].clone()),
+                parts => concat(
+                    &parts
+                        .iter()
+                        .map(|part| part[column].as_ref())
+                        .collect::<Vec<&dyn Array>>(),
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        chunks.push(RecordBatch::try_new(schema.clone(), columns)?);
+        pending.clear();
+        Ok(())
+    };
+
+    for columns in batches {
+        let rows = columns.first().map_or(0, |column| column.len());
+        let mut offset = 0;
+        while offset < rows {
+            let len = (chunk_size - pending_rows).min(rows - offset);
+            pending.push(
+                columns
+                    .iter()
+                    .map(|column| column.slice(offset, len))
+                    .collect(),
+            );
+            pending_rows += len;
+            offset += len;
+
+            if pending_rows == chunk_size {
+                flush(&mut pending)?;
+                pending_rows = 0;
+            }
+        }
+    }
+    if pending_rows > 0 {
+        flush(&mut pending)?;
+    }
+
+    Ok(chunks)
+}
+
+// The paths of the add actions are URIs, so characters like spaces are
+// percent encoded
+fn percent_decode(path: &str) -> Result<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = path
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| delta_error(format!("Invalid path {}", path)))?;
+            decoded.push(hex);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| delta_error(format!("Invalid path {}", path)))
+}
+
+fn string_field(action: &Value, name: &str) -> Result<String> {
+    action
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| delta_error(format!("The action {} doesn't have {}", action, name)))
+}
+
+fn delta_error(message: String) -> ArrowError {
+    ArrowError::ParseError(format!("Delta log: {}", message))
+}
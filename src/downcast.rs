@@ -0,0 +1,76 @@
+// Downcasting an ArrayRef needs the concrete type of the array, and
+// `downcast_ref` only says that it failed. These helpers infer the type
+// from the call site and report the type the array really has
+use std::any::type_name;
+use std::error::Error;
+use std::fmt;
+
+use arrow::{
+    array::{Array, ArrayRef, PrimitiveArray},
+    datatypes::{ArrowPrimitiveType, DataType},
+    error::ArrowError,
+};
+
+/// Error returned when an array isn't of the type it's downcast to
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    /// Name of the array type that was requested
+    pub expected: String,
+    /// DataType of the array
+    pub found: DataType,
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Can't downcast an array of type {:?} to {}",
+            self.found, self.expected
+        )
+    }
+}
+
+impl Error for TypeMismatch {}
+
+impl From<TypeMismatch> for ArrowError {
+    fn from(error: TypeMismatch) -> Self {
+        ArrowError::InvalidArgumentError(error.to_string())
+    }
+}
+
+/// Downcasts the array to its concrete type. Sliced arrays keep their
+/// concrete type, so they are downcast like any other array
+pub fn downcast_array<T: Array + 'static>(array: &dyn Array) -> Result<&T, TypeMismatch> {
+    array
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| TypeMismatch {
+            expected: short_type_name(type_name::<T>()),
+            found: array.data_type().clone(),
+        })
+}
+
+/// Downcasts the array to a PrimitiveArray of the arrow type `T`, for
+/// example `as_primitive::<Int32Type>(&column)`
+pub fn as_primitive<T: ArrowPrimitiveType>(
+    array: &ArrayRef,
+) -> Result<&PrimitiveArray<T>, TypeMismatch> {
+    downcast_array::<PrimitiveArray<T>>(array.as_ref())
+}
+
+// Removes the module paths from a type name, so
+// arrow::array::PrimitiveArray<arrow::datatypes::Int32Type> becomes
+// PrimitiveArray<Int32Type>
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    for c in name.chars() {
+        short.push(c);
+        if short.ends_with("::") {
+            let start = short[..short.len() - 2]
+                .rfind(|c: char| !c.is_alphanumeric() && c != '_')
+                .map_or(0, |index| index + 1);
+            short.truncate(start);
+        }
+    }
+    short
+}
@@ -0,0 +1,478 @@
+// Reads files of the first version of Feather, the format R and pandas
+// wrote before Feather became the Arrow IPC file format. The file starts
+// and ends with the magic bytes FEA1 and the columns are stored one after
+// the other, each one with its null bitmap, offsets and values. Before the
+// magic bytes at the end there's a flatbuffer with the type of every
+// column and the position of its buffers in the file, followed by its
+// length. The tables of the flatbuffer, from feather's metadata.fbs, are
+// read without generated code
+use std::convert::{TryFrom, TryInto};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::{
+    array::{make_array, ArrayData, ArrayRef},
+    buffer::Buffer,
+    datatypes::{DataType, DateUnit, Field, Schema, TimeUnit},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+    util::bit_util,
+};
+use flatbuffers::{
+    Follow, ForwardsUOffset, InvalidFlatbuffer, VOffsetT, Vector, Verifiable, Verifier,
+};
+
+use crate::validate::validate_array_data;
+
+const MAGIC: &[u8] = b"FEA1";
+const ARROW_MAGIC: &[u8] = b"ARROW1";
+
+// The buffers of a column are padded to multiples of 8 bytes since
+// version 2 of the metadata
+const ALIGNMENT: usize = 8;
+const PADDED_VERSION: i32 = 2;
+
+// Variants of the TypeMetadata union of a column
+const CATEGORY_METADATA: u8 = 1;
+const TIMESTAMP_METADATA: u8 = 2;
+const DATE_METADATA: u8 = 3;
+const TIME_METADATA: u8 = 4;
+
+/// Reads the columns of a Feather v1 file into a single batch
+pub(crate) fn read_feather_v1(path: &Path) -> Result<(Schema, Vec<RecordBatch>)> {
+    let bytes = fs::read(path)?;
+    if bytes.starts_with(ARROW_MAGIC) {
+        return Err(ArrowError::InvalidArgumentError(
+            "The file is a Feather v2 file, which is an Arrow IPC file and is read with \
+             IpcFileReader"
+                .to_string(),
+        ));
+    }
+
+    let footer = bytes
+        .len()
+        .checked_sub(MAGIC.len() + 4)
+        .filter(|_| bytes.starts_with(MAGIC) && bytes.ends_with(MAGIC))
+        .ok_or_else(|| invalid("the magic bytes FEA1 are missing"))?;
+    let metadata_len = u32::from_le_bytes(bytes[footer..footer + 4].try_into().unwrap());
+    let metadata_start = footer
+        .checked_sub(metadata_len as usize)
+        .ok_or_else(|| invalid("the metadata is larger than the file"))?;
+
+    let ctable = flatbuffers::root::<CTable>(&bytes[metadata_start..footer])
+        .map_err(|e| invalid(&format!("the metadata can't be read, {}", e)))?;
+    let num_rows = to_usize(ctable.num_rows()).map_err(|e| invalid(&e))?;
+
+    let mut fields = Vec::new();
+    let mut columns = Vec::new();
+    for column in ctable.columns().iter().flat_map(|columns| columns.iter()) {
+        let name = column.name().unwrap_or_default();
+        let array = read_column(&bytes, &column, ctable.version())
+            .map_err(|e| invalid(&format!("the column {} can't be read, {}", name, e)))?;
+        if array.len() != num_rows {
+            return Err(invalid(&format!(
+                "the column {} has {} rows instead of {}",
+                name,
+                array.len(),
+                num_rows
+            )));
+        }
+
+        // The format doesn't say if a column can have nulls
+        fields.push(Field::new(name, array.data_type().clone(), true));
+        columns.push(array);
+    }
+
+    let schema = Schema::new(fields);
+    if columns.is_empty() {
+        return Ok((schema, Vec::new()));
+    }
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), columns)?;
+    Ok((schema, vec![batch]))
+}
+
+fn read_column(
+    bytes: &[u8],
+    column: &Column,
+    version: i32,
+) -> std::result::Result<ArrayRef, String> {
+    let values = column
+        .values()
+        .ok_or_else(|| "the values are missing".to_string())?;
+    let physical_type = physical_type(values.type_())?;
+
+    let data_type = match column.metadata_type() {
+        CATEGORY_METADATA => {
+            let levels = column
+                .metadata::<CategoryMetadata>()
+                .and_then(|metadata| metadata.levels())
+                .ok_or_else(|| "the levels of the category are missing".to_string())?;
+            return read_category(bytes, &values, &levels, version);
+        }
+        TIMESTAMP_METADATA => {
+            let metadata = column
+                .metadata::<TimestampMetadata>()
+                .ok_or_else(|| "the timestamp metadata is missing".to_string())?;
+            let timezone = metadata
+                .timezone()
+                .filter(|timezone| !timezone.is_empty())
+                .map(str::to_string);
+            expect_physical(&physical_type, DataType::Int64)?;
+            DataType::Timestamp(time_unit(metadata.unit())?, timezone)
+        }
+        DATE_METADATA => {
+            expect_physical(&physical_type, DataType::Int32)?;
+            DataType::Date32(DateUnit::Day)
+        }
+        TIME_METADATA => {
+            let unit = column
+                .metadata::<TimeMetadata>()
+                .ok_or_else(|| "the time metadata is missing".to_string())
+                .and_then(|metadata| time_unit(metadata.unit()))?;
+            match (&physical_type, unit) {
+                (DataType::Int32, unit @ TimeUnit::Second)
+                | (DataType::Int32, unit @ TimeUnit::Millisecond) => DataType::Time32(unit),
+                (DataType::Int64, unit @ TimeUnit::Microsecond)
+                | (DataType::Int64, unit @ TimeUnit::Nanosecond) => DataType::Time64(unit),
+                (physical, unit) => {
+                    return Err(format!(
+                        "times in {:?} can't be stored as {:?}",
+                        unit, physical
+                    ))
+                }
+            }
+        }
+        _ => physical_type,
+    };
+
+    read_array(bytes, &values, data_type, version).map(make_array)
+}
+
+// A category, or factor in R, is stored as the integer keys of its values
+// in the levels, which become a dictionary
+fn read_category(
+    bytes: &[u8],
+    keys: &PrimitiveArray,
+    levels: &PrimitiveArray,
+    version: i32,
+) -> std::result::Result<ArrayRef, String> {
+    let key_type = physical_type(keys.type_())?;
+    let levels = read_array(bytes, levels, physical_type(levels.type_())?, version)?;
+    let keys = read_array(bytes, keys, key_type.clone(), version)?;
+
+    let data_type = DataType::Dictionary(Box::new(key_type), Box::new(levels.data_type().clone()));
+    let mut builder = ArrayData::builder(data_type)
+        .len(keys.len())
+        .add_buffer(keys.buffers()[0].clone())
+        .add_child_data(levels);
+    if let Some(bitmap) = keys.null_buffer() {
+        builder = builder.null_bit_buffer(bitmap.clone());
+    }
+
+    let data = builder.build();
+    validate_array_data(&data).map_err(|e| e.to_string())?;
+    Ok(make_array(data))
+}
+
+// The buffers of an array are together in the file: the null bitmap if the
+// array has nulls, the offsets for the variable-size types and the values
+fn read_array(
+    bytes: &[u8],
+    array: &PrimitiveArray,
+    data_type: DataType,
+    version: i32,
+) -> std::result::Result<Arc<ArrayData>, String> {
+    let len = to_usize(array.length())?;
+    let null_count = to_usize(array.null_count())?;
+    let start = to_usize(array.offset())?;
+    let buffers = to_usize(array.total_bytes())
+        .ok()
+        .and_then(|total_bytes| bytes.get(start..start.checked_add(total_bytes)?))
+        .ok_or_else(|| "the buffers are outside of the file".to_string())?;
+
+    // Every row takes at least a bit, which also keeps the sizes below from
+    // overflowing
+    if len > buffers.len().saturating_mul(8) {
+        return Err(format!("{} rows don't fit in {} bytes", len, buffers.len()));
+    }
+
+    let padded = |size: usize| match version < PADDED_VERSION {
+        true => size,
+        false => size.div_ceil(ALIGNMENT) * ALIGNMENT,
+    };
+    // The values take the rest of the bytes of the array
+    let mut position = 0;
+    let mut next_buffer = |size: Option<usize>| {
+        let end = size.map_or(buffers.len(), |size| position + size);
+        let buffer = buffers
+            .get(position..end)
+            .map(Buffer::from)
+            .ok_or_else(|| format!("the buffers need {} bytes, but have {}", end, buffers.len()));
+        position = end;
+        buffer
+    };
+
+    let mut builder = ArrayData::builder(data_type.clone()).len(len);
+    if null_count > 0 {
+        builder = builder.null_bit_buffer(next_buffer(Some(padded(bit_util::ceil(len, 8))))?);
+    }
+    let offset_width = match data_type {
+        DataType::Utf8 | DataType::Binary => Some(4),
+        DataType::LargeUtf8 | DataType::LargeBinary => Some(8),
+        _ => None,
+    };
+    if let Some(width) = offset_width {
+        builder = builder.add_buffer(next_buffer(Some(padded((len + 1) * width)))?);
+    }
+    builder = builder.add_buffer(next_buffer(None)?);
+
+    let data = builder.build();
+    validate_array_data(&data).map_err(|e| e.to_string())?;
+    if data.null_count() != null_count {
+        return Err(format!(
+            "the null bitmap has {} nulls instead of {}",
+            data.null_count(),
+            null_count
+        ));
+    }
+    Ok(data)
+}
+
+fn physical_type(value: i8) -> std::result::Result<DataType, String> {
+    let data_type = match value {
+        0 => DataType::Boolean,
+        1 => DataType::Int8,
+        2 => DataType::Int16,
+        3 => DataType::Int32,
+        4 => DataType::Int64,
+        5 => DataType::UInt8,
+        6 => DataType::UInt16,
+        7 => DataType::UInt32,
+        8 => DataType::UInt64,
+        9 => DataType::Float32,
+        10 => DataType::Float64,
+        11 => DataType::Utf8,
+        12 => DataType::Binary,
+        17 => DataType::LargeUtf8,
+        18 => DataType::LargeBinary,
+        other => return Err(format!("the type {} isn't supported", other)),
+    };
+    Ok(data_type)
+}
+
+fn expect_physical(physical: &DataType, expected: DataType) -> std::result::Result<(), String> {
+    match *physical == expected {
+        true => Ok(()),
+        false => Err(format!(
+            "expected values of type {:?}, found {:?}",
+            expected, physical
+        )),
+    }
+}
+
+fn time_unit(value: i8) -> std::result::Result<TimeUnit, String> {
+    match value {
+        0 => Ok(TimeUnit::Second),
+        1 => Ok(TimeUnit::Millisecond),
+        2 => Ok(TimeUnit::Microsecond),
+        3 => Ok(TimeUnit::Nanosecond),
+        other => Err(format!("the time unit {} isn't supported", other)),
+    }
+}
+
+fn to_usize(value: i64) -> std::result::Result<usize, String> {
+    usize::try_from(value).map_err(|_| format!("{} isn't a valid size", value))
+}
+
+fn invalid(reason: &str) -> ArrowError {
+    ArrowError::ParseError(format!("Invalid Feather file: {}", reason))
+}
+
+// The tables of metadata.fbs. A field is read from the slot of its
+// position in the table, and the verifier checks that the offsets of the
+// fields stay inside the metadata before anything is read
+macro_rules! feather_table {
+    ($name:ident { $($field:literal: $field_type:ty),* }) => {
+        #[derive(Clone, Copy)]
+        struct $name<'a>(flatbuffers::Table<'a>);
+
+        impl<'a> Follow<'a> for $name<'a> {
+            type Inner = Self;
+
+            fn follow(buf: &'a [u8], loc: usize) -> Self {
+                Self(flatbuffers::Table::new(buf, loc))
+            }
+        }
+
+        impl Verifiable for $name<'_> {
+            #[allow(unused_mut, unused_variables)]
+            fn run_verifier(
+                verifier: &mut Verifier,
+                pos: usize,
+            ) -> std::result::Result<(), InvalidFlatbuffer> {
+                let mut table = verifier.visit_table(pos)?;
+                $(table = table.visit_field::<$field_type>(stringify!($field), slot($field), false)?;)*
+                table.finish();
+                Ok(())
+            }
+        }
+    };
+}
+
+fn slot(field: VOffsetT) -> VOffsetT {
+    4 + 2 * field
+}
+
+feather_table!(CTable {
+    0: ForwardsUOffset<&str>,
+    1: i64,
+    2: ForwardsUOffset<Vector<'_, ForwardsUOffset<Column>>>,
+    3: i32,
+    4: ForwardsUOffset<&str>
+});
+feather_table!(PrimitiveArray {
+    0: i8,
+    1: i8,
+    2: i64,
+    3: i64,
+    4: i64,
+    5: i64
+});
+feather_table!(CategoryMetadata {
+    0: ForwardsUOffset<PrimitiveArray>,
+    1: bool
+});
+feather_table!(TimestampMetadata {
+    0: i8,
+    1: ForwardsUOffset<&str>
+});
+feather_table!(TimeMetadata { 0: i8 });
+
+// The column has a union with the metadata of its type, so its verifier
+// can't be generated with the other tables
+#[derive(Clone, Copy)]
+struct Column<'a>(flatbuffers::Table<'a>);
+
+impl<'a> Follow<'a> for Column<'a> {
+    type Inner = Self;
+
+    fn follow(buf: &'a [u8], loc: usize) -> Self {
+        Self(flatbuffers::Table::new(buf, loc))
+    }
+}
+
+impl Verifiable for Column<'_> {
+    fn run_verifier(
+        verifier: &mut Verifier,
+        pos: usize,
+    ) -> std::result::Result<(), InvalidFlatbuffer> {
+        verifier
+            .visit_table(pos)?
+            .visit_field::<ForwardsUOffset<&str>>("name", slot(0), false)?
+            .visit_field::<ForwardsUOffset<PrimitiveArray>>("values", slot(1), false)?
+            .visit_union::<u8, _>(
+                "metadata_type",
+                slot(2),
+                "metadata",
+                slot(3),
+                false,
+                |key, verifier, pos| match key {
+                    CATEGORY_METADATA => verifier
+                        .verify_union_variant::<ForwardsUOffset<CategoryMetadata>>("category", pos),
+                    TIMESTAMP_METADATA => verifier
+                        .verify_union_variant::<ForwardsUOffset<TimestampMetadata>>(
+                            "timestamp",
+                            pos,
+                        ),
+                    TIME_METADATA => {
+                        verifier.verify_union_variant::<ForwardsUOffset<TimeMetadata>>("time", pos)
+                    }
+                    // The date metadata doesn't have fields
+                    _ => Ok(()),
+                },
+            )?
+            .visit_field::<ForwardsUOffset<&str>>("user_metadata", slot(4), false)?
+            .finish();
+        Ok(())
+    }
+}
+
+impl<'a> CTable<'a> {
+    fn num_rows(&self) -> i64 {
+        self.0.get::<i64>(slot(1), Some(0)).unwrap()
+    }
+
+    fn columns(&self) -> Option<Vector<'a, ForwardsUOffset<Column<'a>>>> {
+        self.0
+            .get::<ForwardsUOffset<Vector<ForwardsUOffset<Column>>>>(slot(2), None)
+    }
+
+    fn version(&self) -> i32 {
+        self.0.get::<i32>(slot(3), Some(0)).unwrap()
+    }
+}
+
+impl<'a> Column<'a> {
+    fn name(&self) -> Option<&'a str> {
+        self.0.get::<ForwardsUOffset<&str>>(slot(0), None)
+    }
+
+    fn values(&self) -> Option<PrimitiveArray<'a>> {
+        self.0.get::<ForwardsUOffset<PrimitiveArray>>(slot(1), None)
+    }
+
+    fn metadata_type(&self) -> u8 {
+        self.0.get::<u8>(slot(2), Some(0)).unwrap()
+    }
+
+    fn metadata<T: Follow<'a, Inner = T> + 'a>(&self) -> Option<T> {
+        self.0
+            .get::<ForwardsUOffset<flatbuffers::Table<'a>>>(slot(3), None)
+            .map(|table| T::follow(table.buf, table.loc))
+    }
+}
+
+impl<'a> PrimitiveArray<'a> {
+    fn type_(&self) -> i8 {
+        self.0.get::<i8>(slot(0), Some(0)).unwrap()
+    }
+
+    fn offset(&self) -> i64 {
+        self.0.get::<i64>(slot(2), Some(0)).unwrap()
+    }
+
+    fn length(&self) -> i64 {
+        self.0.get::<i64>(slot(3), Some(0)).unwrap()
+    }
+
+    fn null_count(&self) -> i64 {
+        self.0.get::<i64>(slot(4), Some(0)).unwrap()
+    }
+
+    fn total_bytes(&self) -> i64 {
+        self.0.get::<i64>(slot(5), Some(0)).unwrap()
+    }
+}
+
+impl<'a> CategoryMetadata<'a> {
+    fn levels(&self) -> Option<PrimitiveArray<'a>> {
+        self.0.get::<ForwardsUOffset<PrimitiveArray>>(slot(0), None)
+    }
+}
+
+impl<'a> TimestampMetadata<'a> {
+    fn unit(&self) -> i8 {
+        self.0.get::<i8>(slot(0), Some(0)).unwrap()
+    }
+
+    fn timezone(&self) -> Option<&'a str> {
+        self.0.get::<ForwardsUOffset<&str>>(slot(1), None)
+    }
+}
+
+impl TimeMetadata<'_> {
+    fn unit(&self) -> i8 {
+        self.0.get::<i8>(slot(0), Some(0)).unwrap()
+    }
+}
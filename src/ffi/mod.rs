@@ -0,0 +1,165 @@
+// Sharing arrays with other languages through the Arrow C data interface.
+// An array is described by two C structs, ArrowArray with its buffers and
+// ArrowSchema with its type, and both have a release callback that the
+// consumer calls once it's done with them. The buffers aren't copied, so a
+// C library reads the same memory the Rust array uses. Arrow 3 can only
+// exchange arrays without children, like primitives and strings, and
+// batches are exchanged as streams with one child per column
+mod stream;
+
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::sync::Arc;
+
+use arrow::{
+    array::{make_array_from_raw, ArrayRef},
+    error::{ArrowError, Result},
+    ffi::{ArrowArray, FFI_ArrowArray, FFI_ArrowSchema},
+};
+
+use crate::validate::validate_array_data;
+
+pub use stream::{export_stream, export_table, import_stream, import_table};
+pub use stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+
+// The ArrowArray struct of the C data interface, which is the layout of
+// FFI_ArrowArray. Its fields aren't public, so they are read and written
+// through this struct
+#[repr(C)]
+pub(crate) struct RawArray {
+    pub(crate) length: i64,
+    pub(crate) null_count: i64,
+    pub(crate) offset: i64,
+    pub(crate) n_buffers: i64,
+    pub(crate) n_children: i64,
+    pub(crate) buffers: *mut *const c_void,
+    pub(crate) children: *mut *mut RawArray,
+    pub(crate) dictionary: *mut RawArray,
+    pub(crate) release: Option<unsafe extern "C" fn(array: *mut RawArray)>,
+    pub(crate) private_data: *mut c_void,
+}
+
+impl RawArray {
+    // A released array, which is how a consumer passes the struct to fill
+    pub(crate) fn empty() -> Self {
+        Self {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: ptr::null_mut(),
+            children: ptr::null_mut(),
+            dictionary: ptr::null_mut(),
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+}
+
+// The ArrowSchema struct of the C data interface, the layout of
+// FFI_ArrowSchema
+#[repr(C)]
+struct RawSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut RawSchema,
+    dictionary: *mut RawSchema,
+    release: Option<unsafe extern "C" fn(schema: *mut RawSchema)>,
+    private_data: *mut c_void,
+}
+
+impl RawSchema {
+    fn empty() -> Self {
+        Self {
+            format: ptr::null(),
+            name: ptr::null(),
+            metadata: ptr::null(),
+            flags: 0,
+            n_children: 0,
+            children: ptr::null_mut(),
+            dictionary: ptr::null_mut(),
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+}
+
+/// Exports the array to the C data interface. The returned pointers are
+/// handed to the consumer, which calls the release callback of both structs
+/// when it's done, and the structs are freed with `free_exported`. The
+/// buffers are shared with the array, so they stay alive until the release
+pub fn export_array(array: &ArrayRef) -> Result<(*const FFI_ArrowArray, *const FFI_ArrowSchema)> {
+    // Arrow 3 would export the array without its children
+    if !array.data_ref().child_data().is_empty() {
+        return Err(ArrowError::CDataInterface(format!(
+            "Arrays of type {:?} have children and can't be exported",
+            array.data_type()
+        )));
+    }
+    array.to_raw()
+}
+
+/// Imports the array filled by a producer, like a C function that takes
+/// the two structs to write. The structs are allocated here and freed when
+/// the array is dropped. The data is validated before creating the array,
+/// so buffers that are too short give an error instead of reads out of
+/// bounds
+///
+/// # Safety
+///
+/// The producer must fill the structs following the C data interface, or
+/// leave them untouched if it fails
+pub unsafe fn import_array<F>(produce: F) -> Result<ArrayRef>
+where
+    F: FnOnce(*mut FFI_ArrowArray, *mut FFI_ArrowSchema),
+{
+    let (array, schema) = ArrowArray::into_raw(ArrowArray::empty());
+    produce(array as *mut FFI_ArrowArray, schema as *mut FFI_ArrowSchema);
+
+    let raw_array = &*(array as *const RawArray);
+    let raw_schema = &*(schema as *const RawSchema);
+    if raw_array.release.is_none() || raw_schema.release.is_none() || raw_schema.format.is_null() {
+        // The structs are still owned here, so they are freed before
+        // returning the error
+        free_exported(array, schema);
+        return Err(ArrowError::CDataInterface(
+            "The producer didn't fill the array".to_string(),
+        ));
+    }
+
+    import_raw(array, schema)
+}
+
+/// Imports the structs returned by `export_array`, or filled by a producer
+/// in structs allocated by arrow. The data is validated before creating the
+/// array
+///
+/// # Safety
+///
+/// The pointers must come from `export_array` or from `ArrowArray::into_raw`
+/// and hold a valid array, and they can't be used after this call
+pub unsafe fn import_raw(
+    array: *const FFI_ArrowArray,
+    schema: *const FFI_ArrowSchema,
+) -> Result<ArrayRef> {
+    let array = make_array_from_raw(array, schema)?;
+    validate_array_data(array.data_ref())?;
+    Ok(array)
+}
+
+/// Frees the structs returned by `export_array` once the consumer is done
+/// with them. The structs that the consumer didn't release are released
+/// here
+///
+/// # Safety
+///
+/// The pointers must come from `export_array` and can't be used after this
+/// call
+pub unsafe fn free_exported(array: *const FFI_ArrowArray, schema: *const FFI_ArrowSchema) {
+    drop(Arc::from_raw(array));
+    drop(Arc::from_raw(schema));
+}
@@ -0,0 +1,584 @@
+// The C stream interface hands a sequence of batches to another library,
+// like DuckDB or pandas. A stream is a struct with callbacks: get_schema
+// describes the batches, get_next fills the next batch and release frees
+// the stream. Every batch is an ArrowArray of type struct with one child
+// per column. Arrow 3 doesn't have the stream interface and can't exchange
+// arrays with children, so the struct of the batch is built here around
+// the columns exported by arrow, which can't have children either
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, DateUnit, Field, Schema, SchemaRef, TimeUnit},
+    error::{ArrowError, Result},
+    ffi::{ArrowArray, FFI_ArrowArray, FFI_ArrowSchema},
+    record_batch::RecordBatch,
+};
+
+use super::{export_array, import_raw, RawArray, RawSchema};
+use crate::Table;
+
+// Error codes returned by the callbacks, which are errno values
+const EIO: c_int = 5;
+const EINVAL: c_int = 22;
+
+// Flag of the fields that can have nulls
+const ARROW_FLAG_NULLABLE: i64 = 2;
+
+/// The ArrowArrayStream struct of the C stream interface. The producer sets
+/// the callbacks and the consumer calls release when it's done with the
+/// stream, which happens when the struct is dropped
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct FFI_ArrowArrayStream {
+    pub get_schema: Option<
+        unsafe extern "C" fn(stream: *mut FFI_ArrowArrayStream, out: *mut FFI_ArrowSchema) -> c_int,
+    >,
+    pub get_next: Option<
+        unsafe extern "C" fn(stream: *mut FFI_ArrowArrayStream, out: *mut FFI_ArrowArray) -> c_int,
+    >,
+    pub get_last_error:
+        Option<unsafe extern "C" fn(stream: *mut FFI_ArrowArrayStream) -> *const c_char>,
+    pub release: Option<unsafe extern "C" fn(stream: *mut FFI_ArrowArrayStream)>,
+    pub private_data: *mut c_void,
+}
+
+impl FFI_ArrowArrayStream {
+    /// A released stream, to be filled by a producer
+    pub fn empty() -> Self {
+        Self {
+            get_schema: None,
+            get_next: None,
+            get_last_error: None,
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+}
+
+impl Drop for FFI_ArrowArrayStream {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) };
+        }
+    }
+}
+
+// State of an exported stream
+struct StreamPrivate {
+    schema: SchemaRef,
+    batches: Box<dyn Iterator<Item = Result<RecordBatch>> + Send>,
+    last_error: Option<CString>,
+}
+
+impl StreamPrivate {
+    // Keeps the message for get_last_error and returns the code
+    fn fail(&mut self, error: ArrowError, code: c_int) -> c_int {
+        let message = error.to_string().replace('\0', " ");
+        self.last_error = CString::new(message).ok();
+        code
+    }
+}
+
+/// Exports the batches as a stream of the C stream interface. The batches
+/// are read when the consumer asks for them, and an error of the iterator
+/// is returned to the consumer. The columns can't have children, and the
+/// schema is checked here so unsupported types fail before the export
+pub fn export_stream<I>(schema: SchemaRef, batches: I) -> Result<FFI_ArrowArrayStream>
+where
+    I: IntoIterator<Item = Result<RecordBatch>>,
+    I::IntoIter: Send + 'static,
+{
+    for field in schema.fields() {
+        format_of(field.data_type())?;
+    }
+
+    let private = StreamPrivate {
+        schema,
+        batches: Box::new(batches.into_iter()),
+        last_error: None,
+    };
+    Ok(FFI_ArrowArrayStream {
+        get_schema: Some(get_schema),
+        get_next: Some(get_next),
+        get_last_error: Some(get_last_error),
+        release: Some(release_stream),
+        private_data: Box::into_raw(Box::new(private)) as *mut c_void,
+    })
+}
+
+/// Exports the batches of the table as a stream. Cloning the batches only
+/// clones the pointers to their columns, so the data is shared
+pub fn export_table(table: &Table) -> Result<FFI_ArrowArrayStream> {
+    let schema = Arc::new(table.schema().clone());
+    export_stream(schema, table.data().clone().into_iter().map(Ok))
+}
+
+unsafe extern "C" fn get_schema(
+    stream: *mut FFI_ArrowArrayStream,
+    out: *mut FFI_ArrowSchema,
+) -> c_int {
+    let private = &mut *((*stream).private_data as *mut StreamPrivate);
+    match export_schema(&private.schema) {
+        Ok(schema) => {
+            ptr::write(out as *mut RawSchema, schema);
+            0
+        }
+        Err(error) => private.fail(error, EINVAL),
+    }
+}
+
+unsafe extern "C" fn get_next(
+    stream: *mut FFI_ArrowArrayStream,
+    out: *mut FFI_ArrowArray,
+) -> c_int {
+    let private = &mut *((*stream).private_data as *mut StreamPrivate);
+    match private.batches.next() {
+        // A released array marks the end of the stream
+        None => {
+            ptr::write(out as *mut RawArray, RawArray::empty());
+            0
+        }
+        Some(Ok(batch)) => match export_batch(&private.schema, &batch) {
+            Ok(array) => {
+                ptr::write(out as *mut RawArray, array);
+                0
+            }
+            Err(error) => private.fail(error, EINVAL),
+        },
+        Some(Err(error)) => private.fail(error, EIO),
+    }
+}
+
+unsafe extern "C" fn get_last_error(stream: *mut FFI_ArrowArrayStream) -> *const c_char {
+    let private = &*((*stream).private_data as *const StreamPrivate);
+    match &private.last_error {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+unsafe extern "C" fn release_stream(stream: *mut FFI_ArrowArrayStream) {
+    let stream = &mut *stream;
+    drop(Box::from_raw(stream.private_data as *mut StreamPrivate));
+    stream.private_data = ptr::null_mut();
+    stream.release = None;
+}
+
+// Strings pointed by a schema built here, and its children
+struct SchemaPrivate {
+    format: CString,
+    name: Option<CString>,
+    children: Vec<*mut RawSchema>,
+}
+
+fn owned_schema(
+    format: &str,
+    name: Option<&str>,
+    flags: i64,
+    children: Vec<*mut RawSchema>,
+) -> Result<RawSchema> {
+    let cstring = |value: &str| {
+        CString::new(value).map_err(|_| {
+            ArrowError::CDataInterface(format!("The name {:?} has a null character", value))
+        })
+    };
+
+    let mut private = Box::new(SchemaPrivate {
+        format: cstring(format)?,
+        name: name.map(cstring).transpose()?,
+        children,
+    });
+    Ok(RawSchema {
+        format: private.format.as_ptr(),
+        name: private
+            .name
+            .as_ref()
+            .map_or(ptr::null(), |name| name.as_ptr()),
+        flags,
+        n_children: private.children.len() as i64,
+        children: private.children.as_mut_ptr(),
+        release: Some(release_schema),
+        private_data: Box::into_raw(private) as *mut c_void,
+        ..RawSchema::empty()
+    })
+}
+
+// Releases the children that the consumer didn't move and frees them
+unsafe extern "C" fn release_schema(schema: *mut RawSchema) {
+    let schema = &mut *schema;
+    let private = Box::from_raw(schema.private_data as *mut SchemaPrivate);
+    for &child in &private.children {
+        if let Some(release) = (*child).release {
+            release(child);
+        }
+        drop(Box::from_raw(child));
+    }
+    schema.private_data = ptr::null_mut();
+    schema.release = None;
+}
+
+// A batch is described as a struct with one child per column
+fn export_schema(schema: &Schema) -> Result<RawSchema> {
+    let mut children = Vec::with_capacity(schema.fields().len());
+    for field in schema.fields() {
+        let flags = if field.is_nullable() {
+            ARROW_FLAG_NULLABLE
+        } else {
+            0
+        };
+        let child = format_of(field.data_type())
+            .and_then(|format| owned_schema(format, Some(field.name()), flags, Vec::new()));
+        match child {
+            Ok(child) => children.push(Box::into_raw(Box::new(child))),
+            Err(error) => {
+                // There's no parent yet to free the children
+                for child in children {
+                    unsafe {
+                        release_schema(child);
+                        drop(Box::from_raw(child));
+                    }
+                }
+                return Err(error);
+            }
+        }
+    }
+    owned_schema("+s", None, 0, children)
+}
+
+// Buffers and children of an exported batch
+struct BatchPrivate {
+    buffers: [*const c_void; 1],
+    children: Vec<*mut RawArray>,
+}
+
+fn export_batch(schema: &Schema, batch: &RecordBatch) -> Result<RawArray> {
+    let types = schema.fields().iter().map(|field| field.data_type());
+    let batch_schema = batch.schema();
+    let batch_types = batch_schema.fields().iter().map(|field| field.data_type());
+    if !types.eq(batch_types) {
+        return Err(ArrowError::CDataInterface(
+            "The batch doesn't have the schema of the stream".to_string(),
+        ));
+    }
+
+    let mut children = Vec::with_capacity(batch.num_columns());
+    for column in batch.columns() {
+        match export_array(column) {
+            Ok((array, schema)) => unsafe {
+                // The types are described by the schema of the stream
+                drop(Arc::from_raw(schema));
+                children.push(array as *mut RawArray);
+            },
+            Err(error) => {
+                for child in children {
+                    unsafe { drop(Arc::from_raw(child as *const FFI_ArrowArray)) };
+                }
+                return Err(error);
+            }
+        }
+    }
+
+    // The struct has no validity bitmap, as a batch has no null rows
+    let mut private = Box::new(BatchPrivate {
+        buffers: [ptr::null()],
+        children,
+    });
+    Ok(RawArray {
+        length: batch.num_rows() as i64,
+        n_buffers: 1,
+        n_children: private.children.len() as i64,
+        buffers: private.buffers.as_mut_ptr(),
+        children: private.children.as_mut_ptr(),
+        release: Some(release_batch),
+        private_data: Box::into_raw(private) as *mut c_void,
+        ..RawArray::empty()
+    })
+}
+
+// The children were allocated by arrow, which releases them when they are
+// dropped unless the consumer moved them
+unsafe extern "C" fn release_batch(array: *mut RawArray) {
+    let array = &mut *array;
+    let private = Box::from_raw(array.private_data as *mut BatchPrivate);
+    for &child in &private.children {
+        drop(Arc::from_raw(child as *const FFI_ArrowArray));
+    }
+    array.private_data = ptr::null_mut();
+    array.release = None;
+}
+
+/// Reads the batches of a stream from the C stream interface. The columns
+/// are moved out of every batch, so they keep their memory after the rest
+/// of the batch is released, and they are validated before creating the
+/// batch
+pub struct ArrowArrayStreamReader {
+    stream: FFI_ArrowArrayStream,
+    schema: SchemaRef,
+}
+
+impl ArrowArrayStreamReader {
+    /// Takes the stream and reads its schema. The stream is released when
+    /// the reader is dropped
+    ///
+    /// # Safety
+    ///
+    /// The callbacks of the stream must follow the C stream interface
+    pub unsafe fn try_new(mut stream: FFI_ArrowArrayStream) -> Result<Self> {
+        if stream.release.is_none() {
+            return Err(ArrowError::CDataInterface(
+                "The stream is released".to_string(),
+            ));
+        }
+        let get_schema = callback(stream.get_schema)?;
+
+        let mut out = RawSchema::empty();
+        let code = get_schema(
+            &mut stream,
+            &mut out as *mut RawSchema as *mut FFI_ArrowSchema,
+        );
+        if code != 0 {
+            return Err(stream_error(&mut stream, code));
+        }
+        let schema = import_schema(&out);
+        if let Some(release) = out.release {
+            release(&mut out);
+        }
+
+        Ok(Self {
+            stream,
+            schema: Arc::new(schema?),
+        })
+    }
+
+    /// Schema of the batches
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    unsafe fn read_next(&mut self) -> Result<Option<RecordBatch>> {
+        let get_next = callback(self.stream.get_next)?;
+
+        let mut array = RawArray::empty();
+        let code = get_next(
+            &mut self.stream,
+            &mut array as *mut RawArray as *mut FFI_ArrowArray,
+        );
+        if code != 0 {
+            return Err(stream_error(&mut self.stream, code));
+        }
+        if array.release.is_none() {
+            return Ok(None);
+        }
+
+        let batch = self.import_batch(&mut array);
+        // The columns that were moved are already marked as released
+        if let Some(release) = array.release {
+            release(&mut array);
+        }
+        batch.map(Some)
+    }
+
+    unsafe fn import_batch(&self, array: &mut RawArray) -> Result<RecordBatch> {
+        let fields = self.schema.fields();
+        if array.n_children as usize != fields.len() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The batch has {} columns but the schema has {}",
+                array.n_children,
+                fields.len()
+            )));
+        }
+        if array.null_count > 0 {
+            return Err(ArrowError::CDataInterface(
+                "The batch has null rows".to_string(),
+            ));
+        }
+        let offset = array.offset as usize;
+        let length = array.length as usize;
+
+        let mut columns = Vec::with_capacity(fields.len());
+        for (i, field) in fields.iter().enumerate() {
+            let format = format_of(field.data_type())?;
+            let child = *array.children.add(i);
+
+            // The child is moved to structs allocated by arrow, as the
+            // spec allows, and the parent won't release it anymore
+            let (out_array, out_schema) = ArrowArray::into_raw(ArrowArray::empty());
+            ptr::copy_nonoverlapping(child, out_array as *mut RawArray, 1);
+            (*child).release = None;
+            ptr::write(
+                out_schema as *mut RawSchema,
+                owned_schema(format, None, 0, Vec::new())?,
+            );
+
+            let column = import_raw(out_array, out_schema)?;
+            if column.len() < offset + length {
+                return Err(ArrowError::CDataInterface(format!(
+                    "The column {} is shorter than the batch",
+                    field.name()
+                )));
+            }
+            columns.push(match offset == 0 && column.len() == length {
+                true => column,
+                false => column.slice(offset, length),
+            });
+        }
+
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+impl Iterator for ArrowArrayStreamReader {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe { self.read_next().transpose() }
+    }
+}
+
+/// Imports the stream filled by a producer, like a C function that takes
+/// the struct to write
+///
+/// # Safety
+///
+/// The producer must fill the struct following the C stream interface, or
+/// leave it untouched if it fails
+pub unsafe fn import_stream<F>(produce: F) -> Result<ArrowArrayStreamReader>
+where
+    F: FnOnce(*mut FFI_ArrowArrayStream),
+{
+    let mut stream = FFI_ArrowArrayStream::empty();
+    produce(&mut stream);
+    ArrowArrayStreamReader::try_new(stream)
+}
+
+/// Reads all the batches of the stream filled by a producer into a table
+///
+/// # Safety
+///
+/// The producer must fill the struct following the C stream interface, or
+/// leave it untouched if it fails
+pub unsafe fn import_table<F>(produce: F) -> Result<Table>
+where
+    F: FnOnce(*mut FFI_ArrowArrayStream),
+{
+    let reader = import_stream(produce)?;
+    let schema = reader.schema();
+    let batches = reader.collect::<Result<Vec<_>>>()?;
+    Ok(Table::new(schema.as_ref().clone(), batches))
+}
+
+fn callback<T>(callback: Option<T>) -> Result<T> {
+    callback.ok_or_else(|| {
+        ArrowError::CDataInterface("The stream doesn't have all the callbacks".to_string())
+    })
+}
+
+unsafe fn stream_error(stream: &mut FFI_ArrowArrayStream, code: c_int) -> ArrowError {
+    let message = match stream.get_last_error {
+        Some(get_last_error) => get_last_error(stream),
+        None => ptr::null(),
+    };
+    ArrowError::CDataInterface(match message.is_null() {
+        true => format!("The stream failed with error code {}", code),
+        false => CStr::from_ptr(message).to_string_lossy().into_owned(),
+    })
+}
+
+unsafe fn import_schema(schema: &RawSchema) -> Result<Schema> {
+    if schema.format.is_null() || CStr::from_ptr(schema.format).to_bytes() != b"+s" {
+        return Err(ArrowError::CDataInterface(
+            "The batches of the stream aren't structs".to_string(),
+        ));
+    }
+
+    let mut fields = Vec::with_capacity(schema.n_children as usize);
+    for i in 0..schema.n_children as usize {
+        let child = &**schema.children.add(i);
+        if child.format.is_null() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The column {} doesn't have a format",
+                i
+            )));
+        }
+        let format = CStr::from_ptr(child.format).to_string_lossy();
+        let name = match child.name.is_null() {
+            true => String::new(),
+            false => CStr::from_ptr(child.name).to_string_lossy().into_owned(),
+        };
+        let nullable = child.flags & ARROW_FLAG_NULLABLE != 0;
+        fields.push(Field::new(&name, type_of_format(&format)?, nullable));
+    }
+    Ok(Schema::new(fields))
+}
+
+// Format strings of the types that arrow can exchange
+fn format_of(data_type: &DataType) -> Result<&'static str> {
+    Ok(match data_type {
+        DataType::Null => "n",
+        DataType::Boolean => "b",
+        DataType::Int8 => "c",
+        DataType::UInt8 => "C",
+        DataType::Int16 => "s",
+        DataType::UInt16 => "S",
+        DataType::Int32 => "i",
+        DataType::UInt32 => "I",
+        DataType::Int64 => "l",
+        DataType::UInt64 => "L",
+        DataType::Float16 => "e",
+        DataType::Float32 => "f",
+        DataType::Float64 => "g",
+        DataType::Binary => "z",
+        DataType::LargeBinary => "Z",
+        DataType::Utf8 => "u",
+        DataType::LargeUtf8 => "U",
+        DataType::Date32(DateUnit::Day) => "tdD",
+        DataType::Date64(DateUnit::Millisecond) => "tdm",
+        DataType::Time32(TimeUnit::Second) => "tts",
+        DataType::Time32(TimeUnit::Millisecond) => "ttm",
+        DataType::Time64(TimeUnit::Microsecond) => "ttu",
+        DataType::Time64(TimeUnit::Nanosecond) => "ttn",
+        other => {
+            return Err(ArrowError::CDataInterface(format!(
+                "Columns of type {:?} can't be exchanged",
+                other
+            )))
+        }
+    })
+}
+
+fn type_of_format(format: &str) -> Result<DataType> {
+    Ok(match format {
+        "n" => DataType::Null,
+        "b" => DataType::Boolean,
+        "c" => DataType::Int8,
+        "C" => DataType::UInt8,
+        "s" => DataType::Int16,
+        "S" => DataType::UInt16,
+        "i" => DataType::Int32,
+        "I" => DataType::UInt32,
+        "l" => DataType::Int64,
+        "L" => DataType::UInt64,
+        "e" => DataType::Float16,
+        "f" => DataType::Float32,
+        "g" => DataType::Float64,
+        "z" => DataType::Binary,
+        "Z" => DataType::LargeBinary,
+        "u" => DataType::Utf8,
+        "U" => DataType::LargeUtf8,
+        "tdD" => DataType::Date32(DateUnit::Day),
+        "tdm" => DataType::Date64(DateUnit::Millisecond),
+        "tts" => DataType::Time32(TimeUnit::Second),
+        "ttm" => DataType::Time32(TimeUnit::Millisecond),
+        "ttu" => DataType::Time64(TimeUnit::Microsecond),
+        "ttn" => DataType::Time64(TimeUnit::Nanosecond),
+        other => {
+            return Err(ArrowError::CDataInterface(format!(
+                "Columns with format {:?} can't be exchanged",
+                other
+            )))
+        }
+    })
+}
@@ -0,0 +1,121 @@
+use arrow::{
+    datatypes::Schema,
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use arrow_flight::{
+    flight_service_client::FlightServiceClient, FlightDescriptor, HandshakeRequest, Ticket,
+};
+use tokio02::stream;
+use tonic::transport::Channel;
+
+use crate::ipc::decoder::{parse_message, StreamDecoder};
+use crate::Table;
+
+/// Client used to read Tables from a Flight server
+pub struct FlightClient {
+    client: FlightServiceClient<Channel>,
+}
+
+impl FlightClient {
+    /// Connects to a Flight server. The address has to include the scheme,
+    /// for example http://127.0.0.1:50051
+    pub async fn connect<D: Into<String>>(dst: D) -> Result<Self> {
+        let client = FlightServiceClient::connect(dst.into())
+            .await
+            .map_err(external_error)?;
+
+        Ok(Self { client })
+    }
+
+    /// Requests the flight described by the descriptor and reads all its
+    /// batches into a Table
+    pub async fn get_table(&mut self, descriptor: FlightDescriptor) -> Result<Table> {
+        self.handshake().await?;
+
+        let info = self
+            .client
+            .get_flight_info(descriptor)
+            .await
+            .map_err(external_error)?
+            .into_inner();
+
+        // Every endpoint holds a part of the flight. The endpoints are
+        // read from this server, ignoring their locations
+        let mut schema = None;
+        let mut data = Vec::new();
+        for endpoint in info.endpoint {
+            let ticket = endpoint.ticket.ok_or_else(|| {
+                ArrowError::IoError("The flight endpoint doesn't have a ticket".to_string())
+            })?;
+
+            let (endpoint_schema, batches) = self.read_ticket(ticket).await?;
+            schema.get_or_insert(endpoint_schema);
+            data.extend(batches);
+        }
+
+        let schema = schema.ok_or_else(|| {
+            ArrowError::IoError("The flight doesn't have any endpoint".to_string())
+        })?;
+
+        Ok(Table::new(schema, data))
+    }
+
+    // The server doesn't use authentication. The handshake is done to
+    // confirm the server answers before requesting any data
+    async fn handshake(&mut self) -> Result<()> {
+        let request = HandshakeRequest {
+            protocol_version: 0,
+            payload: vec![],
+        };
+
+        let mut responses = self
+            .client
+            .handshake(stream::iter(vec![request]))
+            .await
+            .map_err(external_error)?
+            .into_inner();
+
+        match responses.message().await.map_err(external_error)? {
+            Some(_) => Ok(()),
+            None => Err(ArrowError::IoError(
+                "The server didn't answer the handshake".to_string(),
+            )),
+        }
+    }
+
+    // The first message of the stream is the schema, followed by the
+    // dictionaries and the batches of the flight
+    async fn read_ticket(&mut self, ticket: Ticket) -> Result<(Schema, Vec<RecordBatch>)> {
+        let mut flight_data = self
+            .client
+            .do_get(ticket)
+            .await
+            .map_err(external_error)?
+            .into_inner();
+
+        let schema_data = flight_data
+            .message()
+            .await
+            .map_err(external_error)?
+            .ok_or_else(|| ArrowError::IoError("The flight stream is empty".to_string()))?;
+        let mut decoder = StreamDecoder::try_new(parse_message(&schema_data.data_header)?)?;
+
+        let mut batches = Vec::new();
+        while let Some(data) = flight_data.message().await.map_err(external_error)? {
+            let message = parse_message(&data.data_header)?;
+            if let Some(batch) = decoder.decode(message, &data.data_body)? {
+                batches.push(batch);
+            }
+        }
+
+        Ok((decoder.schema().as_ref().clone(), batches))
+    }
+}
+
+fn external_error<E>(err: E) -> ArrowError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    ArrowError::ExternalError(Box::new(err))
+}
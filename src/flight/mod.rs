@@ -0,0 +1,18 @@
+// Arrow Flight service and client used to share the Tables loaded from
+// parquet files using the Arrow RPC protocol
+mod client;
+mod server;
+
+use arrow_flight::{flight_descriptor::DescriptorType, FlightDescriptor};
+
+pub use client::FlightClient;
+pub use server::FlightServer;
+
+/// Descriptor used to request a Table by its name
+pub fn table_descriptor<T: Into<String>>(name: T) -> FlightDescriptor {
+    FlightDescriptor {
+        r#type: DescriptorType::Path as i32,
+        cmd: vec![],
+        path: vec![name.into()],
+    }
+}
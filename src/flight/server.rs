@@ -0,0 +1,217 @@
+// tonic::Status is the error returned by all the methods of the service
+#![allow(clippy::result_large_err)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use arrow::ipc::writer::IpcWriteOptions;
+use arrow_flight::{
+    flight_descriptor::DescriptorType,
+    flight_service_server::{FlightService, FlightServiceServer},
+    utils::{
+        flight_data_from_arrow_batch, flight_data_from_arrow_schema,
+        flight_schema_from_arrow_schema, ipc_message_from_arrow_schema,
+    },
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
+};
+use tokio02::stream::{self, Stream};
+use tonic::{transport::Server, Request, Response, Status, Streaming};
+
+use super::table_descriptor;
+use crate::Table;
+
+type FlightStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + Sync + 'static>>;
+
+/// Flight server that exposes a group of named Tables. A Table is
+/// requested using a path descriptor with its name and it is sent to the
+/// client with DoGet using the ticket returned by GetFlightInfo
+#[derive(Default)]
+pub struct FlightServer {
+    tables: HashMap<String, Arc<Table>>,
+}
+
+impl FlightServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a Table to the server. A Table registered with the same name
+    /// is replaced
+    pub fn add_table<T: Into<String>>(&mut self, name: T, table: Table) {
+        self.tables.insert(name.into(), Arc::new(table));
+    }
+
+    /// Names of the Tables available in the server
+    pub fn table_names(&self) -> impl Iterator<Item = &str> {
+        self.tables.keys().map(String::as_str)
+    }
+
+    /// Serves the Tables until the server fails. It has to be called from
+    /// a tokio 0.2 runtime, which is the version used by arrow-flight
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+        Server::builder()
+            .add_service(FlightServiceServer::new(self))
+            .serve(addr)
+            .await
+    }
+
+    // The name of the table is the only element in the descriptor path
+    fn table_from_descriptor<'a>(
+        &self,
+        descriptor: &'a FlightDescriptor,
+    ) -> Result<&'a str, Status> {
+        if descriptor.r#type != DescriptorType::Path as i32 || descriptor.path.len() != 1 {
+            return Err(Status::invalid_argument(
+                "Expected a path descriptor with the name of a table",
+            ));
+        }
+
+        let name = descriptor.path[0].as_str();
+        match self.tables.contains_key(name) {
+            true => Ok(name),
+            false => Err(Status::not_found(format!("Table {} not found", name))),
+        }
+    }
+
+    fn flight_info(&self, name: &str) -> Result<FlightInfo, Status> {
+        let table = &self.tables[name];
+        let options = IpcWriteOptions::default();
+
+        let schema = ipc_message_from_arrow_schema(table.schema(), &options)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        // The table is sent from this server so the endpoint doesn't
+        // include any location
+        let endpoint = FlightEndpoint {
+            ticket: Some(Ticket {
+                ticket: name.as_bytes().to_vec(),
+            }),
+            location: vec![],
+        };
+
+        Ok(FlightInfo {
+            schema,
+            flight_descriptor: Some(table_descriptor(name)),
+            endpoint: vec![endpoint],
+            total_records: table.rows() as i64,
+            total_bytes: -1,
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for FlightServer {
+    type HandshakeStream = FlightStream<HandshakeResponse>;
+    type ListFlightsStream = FlightStream<FlightInfo>;
+    type DoGetStream = FlightStream<FlightData>;
+    type DoPutStream = FlightStream<PutResult>;
+    type DoActionStream = FlightStream<arrow_flight::Result>;
+    type ListActionsStream = FlightStream<ActionType>;
+    type DoExchangeStream = FlightStream<FlightData>;
+
+    // The server doesn't use authentication. The handshake only confirms
+    // the client is able to reach the server
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        let response = HandshakeResponse {
+            protocol_version: 0,
+            payload: vec![],
+        };
+
+        Ok(Response::new(Box::pin(stream::iter(vec![Ok(response)]))))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        let flights = self
+            .table_names()
+            .map(|name| self.flight_info(name))
+            .collect::<Vec<Result<FlightInfo, Status>>>();
+
+        Ok(Response::new(Box::pin(stream::iter(flights))))
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let name = self.table_from_descriptor(request.get_ref())?;
+        Ok(Response::new(self.flight_info(name)?))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let name = self.table_from_descriptor(request.get_ref())?;
+        let options = IpcWriteOptions::default();
+
+        Ok(Response::new(flight_schema_from_arrow_schema(
+            self.tables[name].schema(),
+            &options,
+        )))
+    }
+
+    // The schema is sent first followed by the batches of the table. The
+    // dictionaries used by a batch are sent before it
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let name = String::from_utf8(request.into_inner().ticket)
+            .map_err(|_| Status::invalid_argument("The ticket is not a valid table name"))?;
+
+        let table = self
+            .tables
+            .get(&name)
+            .ok_or_else(|| Status::not_found(format!("Table {} not found", name)))?;
+
+        let options = IpcWriteOptions::default();
+        let mut flights = vec![Ok(flight_data_from_arrow_schema(table.schema(), &options))];
+
+        for batch in table.data() {
+            let (dictionaries, batch) = flight_data_from_arrow_batch(batch, &options);
+            flights.extend(dictionaries.into_iter().map(Ok));
+            flights.push(Ok(batch));
+        }
+
+        Ok(Response::new(Box::pin(stream::iter(flights))))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented(
+            "The server doesn't accept new tables",
+        ))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("The server doesn't have actions"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(Box::pin(stream::iter(vec![]))))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("The server doesn't exchange data"))
+    }
+}
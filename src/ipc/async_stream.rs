@@ -0,0 +1,168 @@
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+use super::compression::CompressionCodec;
+use super::decoder::{parse_message, StreamDecoder};
+use super::encoder::StreamEncoder;
+use super::framing::{body_len, metadata_len, DEFAULT_MAX_MESSAGE_SIZE};
+use super::CONTINUATION_MARKER;
+
+/// Reads an Arrow stream from any `AsyncRead`. It follows the same steps
+/// as arrow's `StreamReader`: the schema is read when the reader is created
+/// and each call to `next_batch` reads messages until a RecordBatch is found
+pub struct AsyncStreamReader<R: AsyncRead + Unpin> {
+    reader: BufReader<R>,
+    decoder: StreamDecoder,
+    finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncStreamReader<R> {
+    /// Creates the reader reading the schema message from the stream
+    pub async fn try_new(reader: R) -> Result<Self> {
+        let mut reader = BufReader::new(reader);
+
+        let meta_buffer = read_metadata(&mut reader).await?.ok_or_else(|| {
+            ArrowError::IoError("Stream ended before the schema was read".to_string())
+        })?;
+        let decoder = StreamDecoder::try_new(parse_message(&meta_buffer)?)?;
+
+        Ok(Self {
+            reader,
+            decoder,
+            finished: false,
+        })
+    }
+
+    /// Schema read from the first message in the stream
+    pub fn schema(&self) -> SchemaRef {
+        self.decoder.schema()
+    }
+
+    /// Returns true once the end of the stream has been reached
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Reads the next RecordBatch from the stream. Dictionary batches found
+    /// before the RecordBatch are stored to decode the dictionary columns.
+    /// Returns None when the stream has ended
+    pub async fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        while !self.finished {
+            let meta_buffer = match read_metadata(&mut self.reader).await? {
+                Some(meta_buffer) => meta_buffer,
+                None => {
+                    self.finished = true;
+                    break;
+                }
+            };
+
+            // The body of the message is read completely before decoding it
+            let message = parse_message(&meta_buffer)?;
+            let mut body = vec![0; body_len(&message, DEFAULT_MAX_MESSAGE_SIZE)?];
+            self.reader.read_exact(&mut body).await?;
+
+            if let Some(batch) = self.decoder.decode(message, &body)? {
+                return Ok(Some(batch));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+// Reads the metadata of the next message in the stream. None is returned
+// if the end of stream marker is found or the stream is closed
+async fn read_metadata<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+    let mut meta_size: [u8; 4] = [0; 4];
+    match reader.read_exact(&mut meta_size).await {
+        Ok(_) => (),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+
+    // Streams written with the legacy format don't have the continuation
+    // marker before the metadata length
+    if meta_size == CONTINUATION_MARKER {
+        reader.read_exact(&mut meta_size).await?;
+    }
+
+    let meta_len = match metadata_len(meta_size, DEFAULT_MAX_MESSAGE_SIZE)? {
+        Some(meta_len) => meta_len,
+        None => return Ok(None),
+    };
+
+    let mut meta_buffer = vec![0; meta_len];
+    reader.read_exact(&mut meta_buffer).await?;
+
+    Ok(Some(meta_buffer))
+}
+
+/// Writes an Arrow stream to any `AsyncWrite`. The messages are encoded in
+/// memory and then written to the writer
+pub struct AsyncStreamWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+    encoder: StreamEncoder,
+    finished: bool,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncStreamWriter<W> {
+    /// Creates the writer sending the schema message to the stream
+    pub async fn try_new(writer: W, schema: &Schema) -> Result<Self> {
+        Self::try_new_with_compression(writer, schema, None).await
+    }
+
+    /// Creates a writer that compresses the buffers of every batch with the
+    /// selected codec. The readers find the codec in each message
+    pub async fn try_new_with_compression(
+        mut writer: W,
+        schema: &Schema,
+        compression: Option<CompressionCodec>,
+    ) -> Result<Self> {
+        let encoder = StreamEncoder::try_new(compression)?;
+        writer.write_all(&encoder.encode_schema(schema)?).await?;
+
+        Ok(Self {
+            writer,
+            encoder,
+            finished: false,
+        })
+    }
+
+    /// Writes a RecordBatch to the stream, together with the dictionary
+    /// batches its dictionary columns require
+    pub async fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if self.finished {
+            return Err(ArrowError::IoError(
+                "Cannot write record batch to stream writer as it is closed".to_string(),
+            ));
+        }
+
+        let buffer = self.encoder.encode_batch(batch)?;
+        self.writer.write_all(&buffer).await?;
+        Ok(())
+    }
+
+    /// Writes the end of stream marker and flushes the writer. Since there
+    /// is no async drop, this has to be called before dropping the writer.
+    /// Calling it again once the stream is finished does nothing
+    pub async fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.writer.write_all(&self.encoder.encode_end()?).await?;
+        self.writer.flush().await?;
+
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -0,0 +1,226 @@
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use arrow::{datatypes::SchemaRef, error::Result, record_batch::RecordBatch};
+
+use super::transport::IpcTransport;
+use super::{IpcStreamReader, IpcStreamWriter};
+
+const DEFAULT_QUEUE_SIZE: usize = 16;
+
+/// What the broadcaster does when the queue of a subscriber is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Waits until the subscriber catches up. A slow subscriber slows down
+    /// all the others
+    Block,
+    /// Skips the batch for that subscriber only
+    Drop,
+}
+
+// Connection receiving the broadcast. Its batches are written in its own
+// thread from a bounded queue
+struct Subscriber {
+    id: usize,
+    sender: SyncSender<RecordBatch>,
+    policy: BackpressurePolicy,
+}
+
+// Unblocks a listener waiting for a new subscriber
+type Waker = Box<dyn Fn() -> io::Result<()> + Send>;
+
+#[derive(Default)]
+struct Subscribers {
+    subscribers: Vec<Subscriber>,
+    next_id: usize,
+    finished: bool,
+    wakers: Vec<Waker>,
+}
+
+/// Forwards the batches of one stream to many connections. Every
+/// subscriber gets its own stream starting with the schema, so a late
+/// subscriber starts receiving from the next batch sent. The broadcaster
+/// can be cloned to add subscribers from other threads
+#[derive(Clone)]
+pub struct Broadcaster {
+    schema: SchemaRef,
+    queue_size: usize,
+    subscribers: Arc<Mutex<Subscribers>>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl Broadcaster {
+    /// Creates the broadcaster for a stream with the given schema
+    pub fn new(schema: SchemaRef) -> Self {
+        Self {
+            schema,
+            queue_size: DEFAULT_QUEUE_SIZE,
+            subscribers: Arc::new(Mutex::new(Subscribers::default())),
+            dropped: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Number of batches queued for every subscriber before its policy is
+    /// applied. It only affects the subscribers added afterwards
+    pub fn with_queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size.max(1);
+        self
+    }
+
+    /// Adds a connection that receives the batches sent from now on
+    pub fn subscribe<W>(&self, writer: W, policy: BackpressurePolicy)
+    where
+        W: Write + Send + 'static,
+    {
+        let (sender, receiver) = sync_channel(self.queue_size);
+        let schema = self.schema.clone();
+        thread::spawn(move || write_subscriber(writer, &schema, receiver));
+
+        // Once the broadcast has finished the sender is dropped, and the
+        // subscriber only gets the schema and the end of stream marker
+        let mut state = self.subscribers.lock().unwrap();
+        if !state.finished {
+            let id = state.next_id;
+            state.next_id += 1;
+            state.subscribers.push(Subscriber { id, sender, policy });
+        }
+    }
+
+    /// Accepts subscribers from a transport in a background thread. All of
+    /// them use the same backpressure policy. The thread stops when the
+    /// broadcast finishes
+    pub fn listen<T: IpcTransport>(&self, transport: T, policy: BackpressurePolicy) {
+        let transport = Arc::new(transport);
+
+        // The transport is woken by finish, the same way a server is shut
+        // down
+        {
+            let mut state = self.subscribers.lock().unwrap();
+            if state.finished {
+                return;
+            }
+
+            let waker = transport.clone();
+            state.wakers.push(Box::new(move || waker.wake()));
+        }
+
+        let broadcaster = self.clone();
+        thread::spawn(move || loop {
+            let accepted = transport.accept();
+            if broadcaster.is_finished() {
+                break;
+            }
+
+            if let Ok((stream, _)) = accepted {
+                broadcaster.subscribe(stream, policy);
+            }
+        });
+    }
+
+    /// Returns true once the broadcast has finished
+    pub fn is_finished(&self) -> bool {
+        self.subscribers.lock().unwrap().finished
+    }
+
+    /// Number of connected subscribers
+    pub fn subscribers(&self) -> usize {
+        self.subscribers.lock().unwrap().subscribers.len()
+    }
+
+    /// Total number of batches skipped by subscribers with the Drop policy
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Sends a batch to all the subscribers. The subscribers whose
+    /// connection failed are removed
+    pub fn send(&self, batch: &RecordBatch) {
+        // The lock isn't held while sending, so blocking on a slow
+        // subscriber doesn't stop new subscribers from joining
+        let targets = self
+            .subscribers
+            .lock()
+            .unwrap()
+            .subscribers
+            .iter()
+            .map(|subscriber| (subscriber.id, subscriber.sender.clone(), subscriber.policy))
+            .collect::<Vec<_>>();
+
+        let mut disconnected = Vec::new();
+        for (id, sender, policy) in targets {
+            let result = match policy {
+                BackpressurePolicy::Block => sender.send(batch.clone()).is_ok(),
+                BackpressurePolicy::Drop => match sender.try_send(batch.clone()) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_)) => {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        true
+                    }
+                    Err(TrySendError::Disconnected(_)) => false,
+                },
+            };
+
+            if !result {
+                disconnected.push(id);
+            }
+        }
+
+        if !disconnected.is_empty() {
+            let mut state = self.subscribers.lock().unwrap();
+            state
+                .subscribers
+                .retain(|subscriber| !disconnected.contains(&subscriber.id));
+        }
+    }
+
+    /// Ends the stream of every subscriber once their queued batches are
+    /// written, and stops the threads listening for new subscribers
+    pub fn finish(&self) {
+        let wakers = {
+            let mut state = self.subscribers.lock().unwrap();
+            state.finished = true;
+            state.subscribers.clear();
+            std::mem::take(&mut state.wakers)
+        };
+
+        // A listener that can't be woken stops after its next connection
+        for wake in wakers {
+            let _ = wake();
+        }
+    }
+
+    /// Sends all the batches of a stream and finishes the broadcast, also
+    /// when reading the stream fails. Returns the number of batches read
+    pub fn forward<R: Read>(&self, reader: IpcStreamReader<R>) -> Result<usize> {
+        let result = reader.into_iter().try_fold(0, |batches, batch| {
+            self.send(&batch?);
+            Ok(batches + 1)
+        });
+
+        self.finish();
+        result
+    }
+}
+
+// Writes the queued batches to the connection until the broadcaster drops
+// the queue or the connection fails
+fn write_subscriber<W: Write>(
+    writer: W,
+    schema: &SchemaRef,
+    receiver: Receiver<RecordBatch>,
+) -> Result<()> {
+    let mut writer = IpcStreamWriter::try_new(writer, schema)?;
+    writer.get_mut().flush()?;
+
+    for batch in receiver {
+        writer.write(&batch)?;
+        writer.get_mut().flush()?;
+    }
+
+    writer.finish()?;
+    writer.get_mut().flush()?;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use arrow::{
+    error::Result,
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+
+use crate::Table;
+
+/// Amount of data collected from a stream, passed to the callback of
+/// `collect_stream` after every batch
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StreamProgress {
+    pub batches: usize,
+    pub rows: usize,
+    /// Size of the buffers of the arrays received so far
+    pub bytes: usize,
+}
+
+/// Reads all the batches from a stream into a Table. The callback is called
+/// after every batch with the batch and the progress including it, which
+/// can be used to report long loads from the network. Works with an
+/// IpcStreamReader or any of arrow's readers
+pub fn collect_stream<R, F>(reader: R, mut on_batch: F) -> Result<Table>
+where
+    R: RecordBatchReader,
+    F: FnMut(&RecordBatch, &StreamProgress),
+{
+    let schema = reader.schema();
+    let mut progress = StreamProgress::default();
+    let mut data = Vec::new();
+
+    for batch in reader {
+        let batch = batch?;
+
+        progress.batches += 1;
+        progress.rows += batch.num_rows();
+        progress.bytes += batch
+            .columns()
+            .iter()
+            .map(|column| column.get_buffer_memory_size())
+            .sum::<usize>();
+
+        on_batch(&batch, &progress);
+        data.push(batch);
+    }
+
+    Ok(Table::new(schema.as_ref().clone(), data))
+}
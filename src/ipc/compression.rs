@@ -0,0 +1,286 @@
+use arrow::{
+    error::{ArrowError, Result},
+    ipc::{self, writer::EncodedData},
+};
+use flatbuffers::FlatBufferBuilder;
+
+use std::convert::TryFrom;
+use std::ops::Range;
+
+use super::decoder::parse_message;
+
+/// Codecs defined by the Arrow IPC format to compress the buffers in the
+/// body of the RecordBatch and DictionaryBatch messages. The codecs are
+/// available with the `lz4` and `zstd` features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionCodec {
+    Lz4Frame,
+    Zstd,
+}
+
+impl CompressionCodec {
+    fn ipc_type(self) -> ipc::CompressionType {
+        match self {
+            CompressionCodec::Lz4Frame => ipc::CompressionType::LZ4_FRAME,
+            CompressionCodec::Zstd => ipc::CompressionType::ZSTD,
+        }
+    }
+
+    fn try_from_ipc(codec: ipc::CompressionType) -> Result<Self> {
+        match codec {
+            ipc::CompressionType::LZ4_FRAME => Ok(CompressionCodec::Lz4Frame),
+            ipc::CompressionType::ZSTD => Ok(CompressionCodec::Zstd),
+            other => Err(ArrowError::IoError(format!(
+                "Unknown compression codec {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[cfg_attr(not(all(feature = "lz4", feature = "zstd")), allow(unused_variables))]
+    fn compress(self, input: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            CompressionCodec::Lz4Frame => {
+                use std::io::Write;
+
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+                encoder.write_all(input)?;
+                encoder
+                    .finish()
+                    .map_err(|err| ArrowError::IoError(format!("LZ4 compression failed: {}", err)))
+            }
+            #[cfg(feature = "zstd")]
+            CompressionCodec::Zstd => Ok(zstd::stream::encode_all(input, 0)?),
+            #[allow(unreachable_patterns)]
+            codec => Err(codec_not_enabled(codec)),
+        }
+    }
+
+    #[cfg_attr(not(all(feature = "lz4", feature = "zstd")), allow(unused_variables))]
+    fn decompress(self, input: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            CompressionCodec::Lz4Frame => {
+                use std::io::Read;
+
+                let mut output = Vec::new();
+                lz4_flex::frame::FrameDecoder::new(input).read_to_end(&mut output)?;
+                Ok(output)
+            }
+            #[cfg(feature = "zstd")]
+            CompressionCodec::Zstd => Ok(zstd::stream::decode_all(input)?),
+            #[allow(unreachable_patterns)]
+            codec => Err(codec_not_enabled(codec)),
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn codec_not_enabled(codec: CompressionCodec) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "The {:?} codec is not enabled. Compile the crate with its feature",
+        codec
+    ))
+}
+
+// Length written before a buffer to indicate that it wasn't compressed
+const UNCOMPRESSED: i64 = -1;
+
+/// Compresses every buffer in the body of a RecordBatch or DictionaryBatch
+/// message. Each compressed buffer starts with its uncompressed length as
+/// a 64 bit integer and is padded to 8 bytes
+pub(crate) fn compress_message(
+    encoded: EncodedData,
+    codec: CompressionCodec,
+) -> Result<EncodedData> {
+    let message = parse_message(&encoded.ipc_message)?;
+    let record_batch = message_record_batch(&message)?;
+
+    let mut body = Vec::new();
+    let mut buffers = Vec::new();
+    for buffer in record_batch.buffers().unwrap_or(&[]) {
+        let data = encoded
+            .arrow_data
+            .get(buffer_range(buffer)?)
+            .ok_or_else(|| ArrowError::IoError("Buffer outside of message body".to_string()))?;
+
+        let offset = body.len();
+        if !data.is_empty() {
+            body.extend_from_slice(&(data.len() as i64).to_le_bytes());
+            body.extend_from_slice(&codec.compress(data)?);
+        }
+        buffers.push(ipc::Buffer::new(
+            offset as i64,
+            (body.len() - offset) as i64,
+        ));
+
+        pad_to_8(&mut body);
+    }
+
+    Ok(EncodedData {
+        ipc_message: rebuild_message(&message, &buffers, body.len(), Some(codec))?,
+        arrow_data: body,
+    })
+}
+
+/// Decompresses the body of a message if it was compressed. The returned
+/// message describes the uncompressed buffers in the new body
+pub(crate) fn decompress_message(
+    message: &ipc::Message,
+    body: &[u8],
+) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let record_batch = match message.header_type() {
+        ipc::MessageHeader::RecordBatch | ipc::MessageHeader::DictionaryBatch => {
+            message_record_batch(message)?
+        }
+        _ => return Ok(None),
+    };
+
+    let codec = match record_batch.compression() {
+        Some(compression) => CompressionCodec::try_from_ipc(compression.codec())?,
+        None => return Ok(None),
+    };
+
+    let mut new_body = Vec::new();
+    let mut buffers = Vec::new();
+    for buffer in record_batch.buffers().unwrap_or(&[]) {
+        let data = body
+            .get(buffer_range(buffer)?)
+            .ok_or_else(|| ArrowError::IoError("Buffer outside of message body".to_string()))?;
+
+        // Every buffer that isn't empty starts with its uncompressed length
+        let offset = new_body.len();
+        if !data.is_empty() {
+            if data.len() < 8 {
+                return Err(ArrowError::IoError(format!(
+                    "Compressed buffer of {} bytes is shorter than its length prefix",
+                    data.len()
+                )));
+            }
+
+            let mut length = [0u8; 8];
+            length.copy_from_slice(&data[..8]);
+
+            match i64::from_le_bytes(length) {
+                UNCOMPRESSED => new_body.extend_from_slice(&data[8..]),
+                length => {
+                    let decompressed = codec.decompress(&data[8..])?;
+                    if usize::try_from(length).ok() != Some(decompressed.len()) {
+                        return Err(ArrowError::IoError(format!(
+                            "Expected {} bytes after decompressing a buffer, found {}",
+                            length,
+                            decompressed.len()
+                        )));
+                    }
+                    new_body.extend_from_slice(&decompressed);
+                }
+            }
+        }
+        buffers.push(ipc::Buffer::new(
+            offset as i64,
+            (new_body.len() - offset) as i64,
+        ));
+
+        pad_to_8(&mut new_body);
+    }
+
+    let meta = rebuild_message(message, &buffers, new_body.len(), None)?;
+    Ok(Some((meta, new_body)))
+}
+
+// Range of a buffer in the body of a message. The offset and the length
+// come from the message, so they are checked before slicing the body
+fn buffer_range(buffer: &ipc::Buffer) -> Result<Range<usize>> {
+    let start = usize::try_from(buffer.offset()).ok();
+    let length = usize::try_from(buffer.length()).ok();
+
+    start
+        .zip(length)
+        .and_then(|(start, length)| Some(start..start.checked_add(length)?))
+        .ok_or_else(|| {
+            ArrowError::IoError(format!(
+                "Invalid buffer with offset {} and length {}",
+                buffer.offset(),
+                buffer.length()
+            ))
+        })
+}
+
+fn pad_to_8(body: &mut Vec<u8>) {
+    let padding = (8 - body.len() % 8) % 8;
+    body.resize(body.len() + padding, 0);
+}
+
+// The RecordBatch that describes the buffers in the body of the message
+fn message_record_batch<'a>(message: &ipc::Message<'a>) -> Result<ipc::RecordBatch<'a>> {
+    let record_batch = match message.header_type() {
+        ipc::MessageHeader::RecordBatch => message.header_as_record_batch(),
+        ipc::MessageHeader::DictionaryBatch => message
+            .header_as_dictionary_batch()
+            .and_then(|dictionary| dictionary.data()),
+        _ => None,
+    };
+
+    record_batch.ok_or_else(|| {
+        ArrowError::IoError("Unable to read the RecordBatch from the message".to_string())
+    })
+}
+
+// Creates a copy of a RecordBatch or DictionaryBatch message with new
+// buffers and body length
+fn rebuild_message(
+    message: &ipc::Message,
+    buffers: &[ipc::Buffer],
+    body_length: usize,
+    codec: Option<CompressionCodec>,
+) -> Result<Vec<u8>> {
+    let record_batch = message_record_batch(message)?;
+
+    let mut fbb = FlatBufferBuilder::new();
+    let nodes = fbb.create_vector(record_batch.nodes().unwrap_or(&[]));
+    let buffers = fbb.create_vector(buffers);
+    let compression = codec.map(|codec| {
+        ipc::BodyCompression::create(
+            &mut fbb,
+            &ipc::BodyCompressionArgs {
+                codec: codec.ipc_type(),
+                method: ipc::BodyCompressionMethod::BUFFER,
+            },
+        )
+    });
+
+    let data = {
+        let mut batch_builder = ipc::RecordBatchBuilder::new(&mut fbb);
+        batch_builder.add_length(record_batch.length());
+        batch_builder.add_nodes(nodes);
+        batch_builder.add_buffers(buffers);
+        if let Some(compression) = compression {
+            batch_builder.add_compression(compression);
+        }
+        batch_builder.finish()
+    };
+
+    let header = match message.header_as_dictionary_batch() {
+        Some(dictionary) => {
+            let mut dictionary_builder = ipc::DictionaryBatchBuilder::new(&mut fbb);
+            dictionary_builder.add_id(dictionary.id());
+            dictionary_builder.add_data(data);
+            dictionary_builder.add_isDelta(dictionary.isDelta());
+            dictionary_builder.finish().as_union_value()
+        }
+        None => data.as_union_value(),
+    };
+
+    let root = {
+        let mut message_builder = ipc::MessageBuilder::new(&mut fbb);
+        message_builder.add_version(message.version());
+        message_builder.add_header_type(message.header_type());
+        message_builder.add_bodyLength(body_length as i64);
+        message_builder.add_header(header);
+        message_builder.finish()
+    };
+    fbb.finish(root, None);
+
+    Ok(fbb.finished_data().to_vec())
+}
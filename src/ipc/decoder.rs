@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::ArrayRef,
+    compute::concat,
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::{ArrowError, Result},
+    ipc::{
+        self,
+        reader::{read_dictionary, read_record_batch},
+    },
+    record_batch::RecordBatch,
+};
+
+use super::compression::decompress_message;
+
+/// Parses the metadata of a message read from the stream
+pub(crate) fn parse_message(meta_buffer: &[u8]) -> Result<ipc::Message<'_>> {
+    ipc::root_as_message(meta_buffer)
+        .map_err(|err| ArrowError::IoError(format!("Unable to get root as message: {:?}", err)))
+}
+
+/// Decodes the messages of an Arrow stream once their metadata and body
+/// have been read. The dictionary batches, including delta dictionaries,
+/// are kept to decode the dictionary columns of the following batches
+pub(crate) struct StreamDecoder {
+    schema: SchemaRef,
+    // Dictionaries sent in the stream, one per field in the schema
+    dictionaries_by_field: Vec<Option<ArrayRef>>,
+}
+
+impl StreamDecoder {
+    /// Creates the decoder from the schema message that starts the stream
+    pub(crate) fn try_new(message: ipc::Message) -> Result<Self> {
+        let ipc_schema = message.header_as_schema().ok_or_else(|| {
+            ArrowError::IoError("Unable to read IPC message as schema".to_string())
+        })?;
+        let schema = ipc::convert::fb_to_schema(ipc_schema);
+        let dictionaries_by_field = vec![None; schema.fields().len()];
+
+        Ok(Self {
+            schema: Arc::new(schema),
+            dictionaries_by_field,
+        })
+    }
+
+    pub(crate) fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Decodes a message using its body. Dictionary batches are stored and
+    /// None is returned, since they don't produce a RecordBatch
+    pub(crate) fn decode(
+        &mut self,
+        message: ipc::Message,
+        body: &[u8],
+    ) -> Result<Option<RecordBatch>> {
+        // Compressed bodies are decompressed before reading the arrays
+        match decompress_message(&message, body)? {
+            Some((meta_buffer, body)) => {
+                self.decode_uncompressed(parse_message(&meta_buffer)?, &body)
+            }
+            None => self.decode_uncompressed(message, body),
+        }
+    }
+
+    fn decode_uncompressed(
+        &mut self,
+        message: ipc::Message,
+        body: &[u8],
+    ) -> Result<Option<RecordBatch>> {
+        match message.header_type() {
+            ipc::MessageHeader::RecordBatch => {
+                let batch = message.header_as_record_batch().ok_or_else(|| {
+                    ArrowError::IoError("Unable to read IPC message as record batch".to_string())
+                })?;
+
+                read_record_batch(body, batch, self.schema(), &self.dictionaries_by_field).map(Some)
+            }
+            ipc::MessageHeader::DictionaryBatch => {
+                let batch = message.header_as_dictionary_batch().ok_or_else(|| {
+                    ArrowError::IoError(
+                        "Unable to read IPC message as dictionary batch".to_string(),
+                    )
+                })?;
+
+                if batch.isDelta() {
+                    self.read_delta_dictionary(body, batch)?;
+                } else {
+                    read_dictionary(body, batch, &self.schema, &mut self.dictionaries_by_field)?;
+                }
+
+                Ok(None)
+            }
+            ipc::MessageHeader::Schema => Err(ArrowError::IoError(
+                "Not expecting a schema when messages are read".to_string(),
+            )),
+            other => Err(ArrowError::IoError(format!(
+                "Unable to read message of type {:?}",
+                other
+            ))),
+        }
+    }
+
+    // Arrow's reader doesn't support delta dictionaries. The new values
+    // are decoded and appended to the dictionary that was already received
+    fn read_delta_dictionary(&mut self, body: &[u8], batch: ipc::DictionaryBatch) -> Result<()> {
+        let id = batch.id();
+        let value_type = match self.schema.fields_with_dict_id(id).first() {
+            Some(field) => match field.data_type() {
+                DataType::Dictionary(_, value_type) => value_type.as_ref().clone(),
+                _ => unreachable!("Only dictionary fields have a dictionary id"),
+            },
+            None => {
+                return Err(ArrowError::InvalidArgumentError(
+                    "dictionary id not found in schema".to_string(),
+                ))
+            }
+        };
+
+        let data = batch.data().ok_or_else(|| {
+            ArrowError::IoError("Unable to read data from dictionary batch".to_string())
+        })?;
+        let values_schema = Schema::new(vec![Field::new("", value_type, true)]);
+        let values = read_record_batch(body, data, Arc::new(values_schema), &[])?;
+        let delta = values.column(0);
+
+        for (i, field) in self.schema.fields().iter().enumerate() {
+            if field.dict_id() != Some(id) {
+                continue;
+            }
+
+            let dictionary = match &self.dictionaries_by_field[i] {
+                Some(previous) => concat(&[previous.as_ref(), delta.as_ref()])?,
+                None => {
+                    return Err(ArrowError::IoError(format!(
+                        "Delta dictionary received for dictionary {} before the dictionary",
+                        id
+                    )))
+                }
+            };
+
+            self.dictionaries_by_field[i] = Some(dictionary);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::{Read, Write};
+
+use arrow::{
+    error::Result,
+    ipc::{self, MessageHeader},
+};
+
+use super::decoder::parse_message;
+use super::framing::MessageDeframer;
+
+// Bytes written before the metadata of every message: the continuation
+// marker and the length of the metadata
+const PREFIX_LEN: usize = 8;
+
+/// Writes a description of every message in an Arrow stream: the header
+/// type and body length of the Message flatbuffer, the fields of a Schema
+/// and the nodes and buffers of a RecordBatch or DictionaryBatch. It works
+/// with any stream, like the output of a StreamWriter or a recorded
+/// capture, and it is meant to learn the format and debug writers
+pub fn dissect_stream<R: Read, W: Write>(reader: R, mut out: W) -> Result<()> {
+    let mut deframer = MessageDeframer::new(reader);
+    let mut offset = 0;
+    let mut index = 0;
+
+    while let Some(framed) = deframer.read_message()? {
+        let message = parse_message(&framed.metadata)?;
+
+        writeln!(
+            out,
+            "Message {} at byte {}: {:?}, version {:?}, metadata {} bytes, body {} bytes",
+            index,
+            offset,
+            message.header_type(),
+            message.version(),
+            framed.metadata.len(),
+            message.bodyLength()
+        )?;
+
+        match message.header_type() {
+            MessageHeader::Schema => {
+                if let Some(schema) = message.header_as_schema() {
+                    dissect_schema(&schema, &mut out)?;
+                }
+            }
+            MessageHeader::RecordBatch => {
+                if let Some(batch) = message.header_as_record_batch() {
+                    dissect_batch(&batch, &mut out, 1)?;
+                }
+            }
+            MessageHeader::DictionaryBatch => {
+                if let Some(dictionary) = message.header_as_dictionary_batch() {
+                    writeln!(
+                        out,
+                        "  Dictionary id {}, delta: {}",
+                        dictionary.id(),
+                        dictionary.isDelta()
+                    )?;
+                    if let Some(batch) = dictionary.data() {
+                        dissect_batch(&batch, &mut out, 1)?;
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        offset += PREFIX_LEN + framed.metadata.len() + framed.body.len();
+        index += 1;
+    }
+
+    writeln!(out, "End of stream after {} messages", index)?;
+    Ok(())
+}
+
+fn dissect_schema<W: Write>(schema: &ipc::Schema, out: &mut W) -> Result<()> {
+    writeln!(out, "  Endianness {:?}", schema.endianness())?;
+
+    if let Some(fields) = schema.fields() {
+        for field in fields {
+            dissect_field(&field, out, 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn dissect_field<W: Write>(field: &ipc::Field, out: &mut W, depth: usize) -> Result<()> {
+    write!(
+        out,
+        "{}Field {:?}: {:?}, nullable: {}",
+        "  ".repeat(depth),
+        field.name().unwrap_or_default(),
+        field.type_type(),
+        field.nullable()
+    )?;
+    if let Some(dictionary) = field.dictionary() {
+        write!(out, ", dictionary id {}", dictionary.id())?;
+    }
+    writeln!(out)?;
+
+    if let Some(children) = field.children() {
+        for child in children {
+            dissect_field(&child, out, depth + 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn dissect_batch<W: Write>(batch: &ipc::RecordBatch, out: &mut W, depth: usize) -> Result<()> {
+    let indent = "  ".repeat(depth);
+    writeln!(out, "{}Length {} rows", indent, batch.length())?;
+
+    if let Some(compression) = batch.compression() {
+        writeln!(out, "{}Compression {:?}", indent, compression.codec())?;
+    }
+
+    // There is one node for every field, including the children of nested
+    // fields, in depth first order
+    for (i, node) in batch.nodes().unwrap_or_default().iter().enumerate() {
+        writeln!(
+            out,
+            "{}Node {}: length {}, null count {}",
+            indent,
+            i,
+            node.length(),
+            node.null_count()
+        )?;
+    }
+
+    // The offsets of the buffers are relative to the start of the body
+    for (i, buffer) in batch.buffers().unwrap_or_default().iter().enumerate() {
+        writeln!(
+            out,
+            "{}Buffer {}: offset {}, length {}",
+            indent,
+            i,
+            buffer.offset(),
+            buffer.length()
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use arrow::{
+    array::{make_array, ArrayRef, UInt32Array},
+    compute::take,
+    datatypes::{Field, Schema},
+    error::{ArrowError, Result},
+    ipc::{
+        self,
+        writer::{DictionaryTracker, EncodedData, IpcDataGenerator, IpcWriteOptions},
+    },
+    record_batch::RecordBatch,
+};
+use flatbuffers::FlatBufferBuilder;
+use std::sync::Arc;
+
+use super::compression::{compress_message, CompressionCodec};
+use super::framing::MessageFramer;
+
+/// Encodes the messages of an Arrow stream into bytes that can be written
+/// to any writer. Contrary to arrow's StreamWriter, a dictionary that only
+/// grows between batches is sent as a delta dictionary batch with the new
+/// values instead of sending the complete dictionary again
+pub(crate) struct StreamEncoder {
+    write_options: IpcWriteOptions,
+    data_gen: IpcDataGenerator,
+    // The dictionaries are registered in the tracker before a batch is
+    // encoded. This way the data generator never emits them and they are
+    // written by the encoder
+    dictionary_tracker: DictionaryTracker,
+    // Last dictionary values sent for each dictionary id
+    dictionaries: HashMap<i64, ArrayRef>,
+    // Codec used to compress the body of the messages
+    compression: Option<CompressionCodec>,
+}
+
+impl StreamEncoder {
+    pub(crate) fn try_new(compression: Option<CompressionCodec>) -> Result<Self> {
+        Ok(Self {
+            write_options: IpcWriteOptions::try_new(8, false, ipc::MetadataVersion::V5)?,
+            data_gen: IpcDataGenerator::default(),
+            dictionary_tracker: DictionaryTracker::new(false),
+            dictionaries: HashMap::new(),
+            compression,
+        })
+    }
+
+    /// Encodes the schema message that starts the stream
+    pub(crate) fn encode_schema(&self, schema: &Schema) -> Result<Vec<u8>> {
+        let encoded_message = self.data_gen.schema_to_bytes(schema, &self.write_options);
+
+        let mut framer = MessageFramer::new(Vec::new());
+        framer.write_message(&encoded_message.ipc_message, &encoded_message.arrow_data)?;
+
+        Ok(framer.into_inner())
+    }
+
+    /// Encodes the dictionary batches required by the batch followed by the
+    /// RecordBatch message
+    pub(crate) fn encode_batch(&mut self, batch: &RecordBatch) -> Result<Vec<u8>> {
+        Ok(self.encode_batch_messages(batch)?.concat())
+    }
+
+    /// Same as encode_batch but every message is returned in its own
+    /// buffer, for transports that frame each message separately
+    pub(crate) fn encode_batch_messages(&mut self, batch: &RecordBatch) -> Result<Vec<Vec<u8>>> {
+        let mut messages = Vec::new();
+
+        let schema = batch.schema();
+        for (i, field) in schema.fields().iter().enumerate() {
+            let column = batch.column(i);
+            if let Some(dict_id) = field.dict_id() {
+                if let Some(encoded) = self.encode_dictionary(dict_id, column)? {
+                    messages.push(self.write_message(encoded)?);
+                }
+                self.dictionary_tracker.insert(dict_id, column)?;
+            }
+        }
+
+        let (_, encoded_message) = self.data_gen.encoded_batch(
+            batch,
+            &mut self.dictionary_tracker,
+            &self.write_options,
+        )?;
+        messages.push(self.write_message(encoded_message)?);
+
+        Ok(messages)
+    }
+
+    // Encodes a RecordBatch or DictionaryBatch message, compressing its body
+    // if a codec was selected
+    fn write_message(&self, encoded: EncodedData) -> Result<Vec<u8>> {
+        let encoded = match self.compression {
+            Some(codec) => compress_message(encoded, codec)?,
+            None => encoded,
+        };
+
+        let mut framer = MessageFramer::new(Vec::new());
+        framer.write_message(&encoded.ipc_message, &encoded.arrow_data)?;
+        Ok(framer.into_inner())
+    }
+
+    /// Bytes that mark the end of the stream
+    pub(crate) fn encode_end(&self) -> Result<Vec<u8>> {
+        let mut framer = MessageFramer::new(Vec::new());
+        framer.write_end()?;
+        Ok(framer.into_inner())
+    }
+
+    // Decides which dictionary batch has to be sent for a dictionary column.
+    // Nothing is sent if the dictionary didn't change, a delta if the new
+    // dictionary starts with the values that were already sent, and the
+    // complete dictionary otherwise
+    fn encode_dictionary(
+        &mut self,
+        dict_id: i64,
+        column: &ArrayRef,
+    ) -> Result<Option<EncodedData>> {
+        let values = make_array(column.data().child_data()[0].clone());
+
+        let encoded = match self.dictionaries.get(&dict_id) {
+            Some(previous) if previous.data() == values.data() => None,
+            Some(previous)
+                if previous.len() < values.len()
+                    && previous.data() == values.slice(0, previous.len()).data() =>
+            {
+                let delta = previous.len()..values.len();
+                Some(self.dictionary_batch_to_bytes(dict_id, &values, delta, true)?)
+            }
+            _ => Some(self.dictionary_batch_to_bytes(dict_id, &values, 0..values.len(), false)?),
+        };
+
+        self.dictionaries.insert(dict_id, values);
+        Ok(encoded)
+    }
+
+    // The values in the range are encoded as a single column RecordBatch and
+    // its nodes and buffers are wrapped in a DictionaryBatch message
+    fn dictionary_batch_to_bytes(
+        &self,
+        dict_id: i64,
+        values: &ArrayRef,
+        range: Range<usize>,
+        is_delta: bool,
+    ) -> Result<EncodedData> {
+        // Arrow's writer doesn't take the offset of sliced arrays into
+        // account, so the values are copied into a new array
+        let indices = UInt32Array::from(range.map(|i| i as u32).collect::<Vec<u32>>());
+        let values = take(values.as_ref(), &indices, None)?;
+        let values_schema = Schema::new(vec![Field::new("", values.data_type().clone(), true)]);
+        let values_batch = RecordBatch::try_new(Arc::new(values_schema), vec![values])?;
+
+        let mut tracker = DictionaryTracker::new(false);
+        let (_, encoded) =
+            self.data_gen
+                .encoded_batch(&values_batch, &mut tracker, &self.write_options)?;
+
+        let message = ipc::root_as_message(&encoded.ipc_message).map_err(|err| {
+            ArrowError::IoError(format!("Unable to get root as message: {:?}", err))
+        })?;
+        let record_batch = message.header_as_record_batch().ok_or_else(|| {
+            ArrowError::IoError("Unable to read IPC message as record batch".to_string())
+        })?;
+
+        let mut fbb = FlatBufferBuilder::new();
+        let nodes = fbb.create_vector(record_batch.nodes().unwrap_or(&[]));
+        let buffers = fbb.create_vector(record_batch.buffers().unwrap_or(&[]));
+
+        let data = {
+            let mut batch_builder = ipc::RecordBatchBuilder::new(&mut fbb);
+            batch_builder.add_length(record_batch.length());
+            batch_builder.add_nodes(nodes);
+            batch_builder.add_buffers(buffers);
+            batch_builder.finish()
+        };
+
+        let header = {
+            let mut dictionary_builder = ipc::DictionaryBatchBuilder::new(&mut fbb);
+            dictionary_builder.add_id(dict_id);
+            dictionary_builder.add_data(data);
+            dictionary_builder.add_isDelta(is_delta);
+            dictionary_builder.finish().as_union_value()
+        };
+
+        let root = {
+            let mut message_builder = ipc::MessageBuilder::new(&mut fbb);
+            message_builder.add_version(message.version());
+            message_builder.add_header_type(ipc::MessageHeader::DictionaryBatch);
+            message_builder.add_bodyLength(encoded.arrow_data.len() as i64);
+            message_builder.add_header(header);
+            message_builder.finish()
+        };
+        fbb.finish(root, None);
+
+        Ok(EncodedData {
+            ipc_message: fbb.finished_data().to_vec(),
+            arrow_data: encoded.arrow_data,
+        })
+    }
+}
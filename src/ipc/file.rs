@@ -0,0 +1,81 @@
+use std::io::{Cursor, Read, Seek, Write};
+
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    error::{ArrowError, Result},
+    ipc::{reader::FileReader, writer::FileWriter},
+    record_batch::RecordBatch,
+};
+
+/// Writes the batches using the Arrow IPC file format. Contrary to the
+/// stream format, the file ends with a footer that stores the position of
+/// every batch, which makes it possible to read the batches in any order
+pub fn write_ipc_file<W: Write>(writer: W, schema: &Schema, batches: &[RecordBatch]) -> Result<()> {
+    let mut file_writer = FileWriter::try_new(writer, schema)?;
+
+    for batch in batches {
+        file_writer.write(batch)?;
+    }
+
+    file_writer.finish()
+}
+
+/// Encodes the batches as an Arrow IPC file in memory. The bytes can be
+/// sent through the network and read back with `IpcFileReader::from_bytes`
+pub fn ipc_file_to_bytes(schema: &Schema, batches: &[RecordBatch]) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    write_ipc_file(&mut bytes, schema, batches)?;
+
+    Ok(bytes)
+}
+
+/// Reader for the Arrow IPC file format with random access to its batches
+pub struct IpcFileReader<R: Read + Seek> {
+    reader: FileReader<R>,
+}
+
+impl IpcFileReader<Cursor<Vec<u8>>> {
+    /// Creates a reader from a complete IPC file kept in memory, for example
+    /// after receiving it from a socket
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        Self::try_new(Cursor::new(bytes))
+    }
+}
+
+impl<R: Read + Seek> IpcFileReader<R> {
+    /// Creates the reader reading the footer from the end of the file
+    pub fn try_new(reader: R) -> Result<Self> {
+        let reader = FileReader::try_new(reader)?;
+        Ok(Self { reader })
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        self.reader.schema()
+    }
+
+    /// Number of batches stored in the file's footer
+    pub fn num_batches(&self) -> usize {
+        self.reader.num_batches()
+    }
+
+    /// Reads the batch at position `index` using the block index from the
+    /// footer. Only the selected batch is read from the file
+    pub fn read_batch(&mut self, index: usize) -> Result<RecordBatch> {
+        self.reader.set_index(index)?;
+
+        match self.reader.next() {
+            Some(batch) => batch,
+            None => Err(ArrowError::IoError(format!(
+                "Unable to read batch {} from the file",
+                index
+            ))),
+        }
+    }
+
+    /// Reads all the batches in the file in order
+    pub fn read_all(&mut self) -> Result<Vec<RecordBatch>> {
+        (0..self.num_batches())
+            .map(|index| self.read_batch(index))
+            .collect()
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+
+use arrow::{
+    compute::filter_record_batch,
+    datatypes::{DataType, Schema},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+pub use crate::compute::Operator as FilterOp;
+use crate::compute::{compare_scalar, nulls_as_false};
+use crate::ScalarValue;
+
+/// Condition comparing a column with a literal, like `price > 10`. The
+/// literal is kept as text so it can be sent to a writer, and it is parsed
+/// with the type of the column when the filter is applied. Numeric and utf8
+/// columns are supported, and null values never match
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predicate {
+    pub column: String,
+    pub op: FilterOp,
+    pub literal: String,
+}
+
+impl Predicate {
+    pub fn new<C: Into<String>, L: ToString>(column: C, op: FilterOp, literal: L) -> Self {
+        Self {
+            column: column.into(),
+            op,
+            literal: literal.to_string(),
+        }
+    }
+
+    /// Checks that the column exists and the literal can be compared with
+    /// it. Returns the index of the column
+    pub fn validate(&self, schema: &Schema) -> Result<usize> {
+        let index = schema.index_of(&self.column)?;
+        self.literal_value(schema.field(index).data_type())?;
+        Ok(index)
+    }
+
+    // Parses the literal with the type of the column
+    fn literal_value(&self, data_type: &DataType) -> Result<ScalarValue> {
+        let literal = self.literal.as_str();
+        let value = match data_type {
+            DataType::Int8 => literal.parse().ok().map(|v| ScalarValue::Int8(Some(v))),
+            DataType::Int16 => literal.parse().ok().map(|v| ScalarValue::Int16(Some(v))),
+            DataType::Int32 => literal.parse().ok().map(|v| ScalarValue::Int32(Some(v))),
+            DataType::Int64 => literal.parse().ok().map(|v| ScalarValue::Int64(Some(v))),
+            DataType::UInt8 => literal.parse().ok().map(|v| ScalarValue::UInt8(Some(v))),
+            DataType::UInt16 => literal.parse().ok().map(|v| ScalarValue::UInt16(Some(v))),
+            DataType::UInt32 => literal.parse().ok().map(|v| ScalarValue::UInt32(Some(v))),
+            DataType::UInt64 => literal.parse().ok().map(|v| ScalarValue::UInt64(Some(v))),
+            DataType::Float32 => literal.parse().ok().map(|v| ScalarValue::Float32(Some(v))),
+            DataType::Float64 => literal.parse().ok().map(|v| ScalarValue::Float64(Some(v))),
+            DataType::Utf8 => Some(ScalarValue::Utf8(Some(self.literal.clone()))),
+            other => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Columns of type {:?} can't be filtered",
+                    other
+                )))
+            }
+        };
+
+        value.ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "{} can't be compared with the column {}",
+                self.literal, self.column
+            ))
+        })
+    }
+
+    /// Keeps the rows of the batch that match the predicate
+    pub fn filter(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let index = batch.schema().index_of(&self.column)?;
+        let column = batch.column(index);
+        let literal = self.literal_value(column.data_type())?;
+        let mask = compare_scalar(column, self.op, &literal)?;
+
+        filter_record_batch(batch, &nulls_as_false(&mask))
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.column, self.op, self.literal)
+    }
+}
@@ -0,0 +1,173 @@
+use std::io::{Read, Write};
+
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::{IpcStreamReader, IpcStreamWriter};
+
+// Every acknowledgement is the total number of batches consumed by the
+// reader, written as a little endian u64 in the opposite direction of the
+// stream
+type Ack = u64;
+
+/// Stream writer that waits for the reader before sending more batches.
+/// Once `window` batches haven't been acknowledged by the reader, the next
+/// write blocks until an acknowledgement arrives. This way a slow consumer
+/// can't make the producer buffer an unbounded amount of data
+pub struct AckStreamWriter<S: Read + Write> {
+    writer: IpcStreamWriter<S>,
+    window: usize,
+    sent: u64,
+    acknowledged: u64,
+}
+
+impl<S: Read + Write> AckStreamWriter<S> {
+    /// Creates the writer sending the schema message. The window has to be
+    /// larger or equal than the acknowledgement interval of the reader
+    pub fn try_new(stream: S, schema: &Schema, window: usize) -> Result<Self> {
+        if window == 0 {
+            return Err(ArrowError::InvalidArgumentError(
+                "The window of unacknowledged batches must be larger than zero".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            writer: IpcStreamWriter::try_new(stream, schema)?,
+            window,
+            sent: 0,
+            acknowledged: 0,
+        })
+    }
+
+    /// Writes a RecordBatch, blocking first if the window of
+    /// unacknowledged batches is full
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        while self.pending() >= self.window {
+            self.read_ack()?;
+        }
+
+        self.writer.write(batch)?;
+        self.writer.get_mut().flush()?;
+        self.sent += 1;
+
+        Ok(())
+    }
+
+    /// Number of batches sent that haven't been acknowledged
+    pub fn pending(&self) -> usize {
+        (self.sent - self.acknowledged) as usize
+    }
+
+    /// Writes the end of stream marker without waiting for the pending
+    /// acknowledgements
+    pub fn finish(&mut self) -> Result<()> {
+        self.writer.finish()
+    }
+
+    /// Writes the end of stream marker and waits until the reader has
+    /// acknowledged all the batches
+    pub fn finish_and_wait(&mut self) -> Result<()> {
+        self.writer.finish()?;
+
+        while self.pending() > 0 {
+            self.read_ack()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the underlying stream
+    pub fn into_inner(self) -> S {
+        self.writer.into_inner()
+    }
+
+    fn read_ack(&mut self) -> Result<()> {
+        let mut ack = [0; std::mem::size_of::<Ack>()];
+        self.writer.get_mut().read_exact(&mut ack)?;
+
+        let acknowledged = Ack::from_le_bytes(ack);
+        if acknowledged > self.sent {
+            return Err(ArrowError::IoError(format!(
+                "Acknowledged {} batches but only {} were sent",
+                acknowledged, self.sent
+            )));
+        }
+
+        self.acknowledged = acknowledged;
+        Ok(())
+    }
+}
+
+/// Stream reader that acknowledges the batches to an AckStreamWriter. An
+/// acknowledgement is sent every `ack_interval` batches returned by the
+/// reader and once the stream ends
+pub struct AckStreamReader<S: Read + Write> {
+    reader: IpcStreamReader<S>,
+    ack_interval: usize,
+    consumed: u64,
+    acknowledged: u64,
+}
+
+impl<S: Read + Write> AckStreamReader<S> {
+    /// Creates the reader reading the schema message from the stream
+    pub fn try_new(stream: S, ack_interval: usize) -> Result<Self> {
+        if ack_interval == 0 {
+            return Err(ArrowError::InvalidArgumentError(
+                "The acknowledgement interval must be larger than zero".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            reader: IpcStreamReader::try_new(stream)?,
+            ack_interval,
+            consumed: 0,
+            acknowledged: 0,
+        })
+    }
+
+    /// Schema read from the first message in the stream
+    pub fn schema(&self) -> SchemaRef {
+        self.reader.schema()
+    }
+
+    fn maybe_next(&mut self) -> Result<Option<RecordBatch>> {
+        match self.reader.next().transpose()? {
+            Some(batch) => {
+                self.consumed += 1;
+                if self.consumed - self.acknowledged >= self.ack_interval as u64 {
+                    self.send_ack()?;
+                }
+
+                Ok(Some(batch))
+            }
+            None => {
+                // The writer may be waiting for the last batches
+                if self.consumed > self.acknowledged {
+                    self.send_ack()?;
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    fn send_ack(&mut self) -> Result<()> {
+        let stream = self.reader.get_mut();
+        stream.write_all(&self.consumed.to_le_bytes())?;
+        stream.flush()?;
+
+        self.acknowledged = self.consumed;
+        Ok(())
+    }
+}
+
+impl<S: Read + Write> Iterator for AckStreamReader<S> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.maybe_next().transpose()
+    }
+}
@@ -0,0 +1,212 @@
+use std::convert::TryFrom;
+use std::io::{ErrorKind, Read, Write};
+
+use arrow::{
+    error::{ArrowError, Result},
+    ipc,
+};
+
+use super::decoder::parse_message;
+use super::CONTINUATION_MARKER;
+
+// The metadata and the body of every message are padded to a multiple of
+// 8 bytes so the buffers can be read without copying them
+const ALIGNMENT: usize = 8;
+
+/// Largest metadata or body a MessageDeframer accepts by default. The
+/// lengths come from the peer, so they are checked before allocating the
+/// buffers
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1 << 30;
+
+/// Message read from an Arrow stream. The metadata is the flatbuffer
+/// Message without the prefix and the body contains the buffers of the
+/// arrays
+#[derive(Debug, Clone, PartialEq)]
+pub struct FramedMessage {
+    pub metadata: Vec<u8>,
+    pub body: Vec<u8>,
+}
+
+/// Writes the encapsulated messages of an Arrow stream. Every message is
+/// written as:
+///
+/// - the continuation marker, 0xFFFFFFFF
+/// - the length of the metadata including its padding, as a little endian
+///   int32
+/// - the flatbuffer metadata, padded to a multiple of 8 bytes
+/// - the body, padded to a multiple of 8 bytes
+///
+/// The end of the stream is marked with the continuation marker followed
+/// by a length of zero
+pub struct MessageFramer<W: Write> {
+    writer: W,
+    finished: bool,
+}
+
+impl<W: Write> MessageFramer<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            finished: false,
+        }
+    }
+
+    /// Writes a message with its prefix and padding. Returns the number of
+    /// bytes written
+    pub fn write_message(&mut self, metadata: &[u8], body: &[u8]) -> Result<usize> {
+        // The prefix is included in the alignment of the metadata
+        let prefix_len = CONTINUATION_MARKER.len() + 4;
+        let metadata_len = padded_len(prefix_len + metadata.len()) - prefix_len;
+
+        self.writer.write_all(&CONTINUATION_MARKER)?;
+        self.writer
+            .write_all(&(metadata_len as i32).to_le_bytes())?;
+        self.writer.write_all(metadata)?;
+        self.write_padding(metadata_len - metadata.len())?;
+
+        self.writer.write_all(body)?;
+        self.write_padding(padded_len(body.len()) - body.len())?;
+
+        Ok(prefix_len + metadata_len + padded_len(body.len()))
+    }
+
+    /// Writes the end of stream marker. Calling it again once the marker
+    /// has been written does nothing
+    pub fn write_end(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.writer.write_all(&CONTINUATION_MARKER)?;
+        self.writer.write_all(&0i32.to_le_bytes())?;
+
+        self.finished = true;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_padding(&mut self, len: usize) -> Result<()> {
+        self.writer.write_all(&[0; ALIGNMENT][..len])?;
+        Ok(())
+    }
+}
+
+/// Reads the encapsulated messages of an Arrow stream written by a
+/// MessageFramer or any other Arrow writer. Streams written with the legacy
+/// format, without the continuation marker, are accepted as well
+pub struct MessageDeframer<R: Read> {
+    reader: R,
+    max_message_size: usize,
+}
+
+impl<R: Read> MessageDeframer<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+
+    /// Sets the largest metadata or body that is read. Longer messages are
+    /// rejected with an error
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Reads the next message. None is returned if the end of stream marker
+    /// is found or the reader is closed before a new message starts
+    pub fn read_message(&mut self) -> Result<Option<FramedMessage>> {
+        let metadata = match self.read_metadata()? {
+            Some(metadata) => metadata,
+            None => return Ok(None),
+        };
+
+        // The length of the body is only found in the metadata
+        let body_len = body_len(&parse_message(&metadata)?, self.max_message_size)?;
+        let mut body = vec![0; body_len];
+        self.reader.read_exact(&mut body)?;
+
+        Ok(Some(FramedMessage { metadata, body }))
+    }
+
+    /// Mutable reference to the underlying reader
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_metadata(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut meta_size: [u8; 4] = [0; 4];
+        match self.reader.read_exact(&mut meta_size) {
+            Ok(()) => (),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        // Streams written with the legacy format don't have the
+        // continuation marker before the metadata length
+        if meta_size == CONTINUATION_MARKER {
+            self.reader.read_exact(&mut meta_size)?;
+        }
+
+        let meta_len = match metadata_len(meta_size, self.max_message_size)? {
+            Some(meta_len) => meta_len,
+            None => return Ok(None),
+        };
+
+        let mut metadata = vec![0; meta_len];
+        self.reader.read_exact(&mut metadata)?;
+
+        Ok(Some(metadata))
+    }
+}
+
+/// Length of the metadata from the prefix of a message. None is returned
+/// for the end of stream marker. Negative lengths and lengths above the
+/// limit are rejected
+pub(crate) fn metadata_len(prefix: [u8; 4], max_message_size: usize) -> Result<Option<usize>> {
+    match i32::from_le_bytes(prefix) {
+        0 => Ok(None),
+        len => checked_len(len as i64, max_message_size, "metadata").map(Some),
+    }
+}
+
+/// Length of the body described by the metadata of a message
+pub(crate) fn body_len(message: &ipc::Message, max_message_size: usize) -> Result<usize> {
+    checked_len(message.bodyLength(), max_message_size, "body")
+}
+
+fn checked_len(len: i64, max_message_size: usize, part: &str) -> Result<usize> {
+    usize::try_from(len)
+        .ok()
+        .filter(|len| *len <= max_message_size)
+        .ok_or_else(|| {
+            ArrowError::IoError(format!(
+                "Invalid {} length {} in message, the limit is {} bytes",
+                part, len, max_message_size
+            ))
+        })
+}
+
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(ALIGNMENT) * ALIGNMENT
+}
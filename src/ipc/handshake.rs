@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::decoder::{parse_message, StreamDecoder};
+use super::filter::Predicate;
+use super::framing::MessageDeframer;
+use super::{IpcStreamReader, IpcStreamWriter};
+use crate::downcast::downcast_array;
+
+// The columns requested by the reader are sent as a small Arrow stream with
+// a single utf8 column, so the request uses the same framing as the batches.
+// An empty request asks for all the columns. The filter is sent in the
+// metadata of the request schema
+const REQUEST_COLUMN: &str = "column";
+const FILTER_COLUMN_KEY: &str = "filter_column";
+const FILTER_OP_KEY: &str = "filter_op";
+const FILTER_LITERAL_KEY: &str = "filter_literal";
+
+/// Opens a stream asking the writer to send only the selected columns. The
+/// request is written to the connection and the reader is created with the
+/// projected schema answered by a ProjectedStreamWriter. If the writer
+/// doesn't have one of the columns it closes the connection and an error is
+/// returned
+pub fn request_columns<S: Read + Write>(stream: S, columns: &[&str]) -> Result<IpcStreamReader<S>> {
+    send_request(stream, columns, None)
+}
+
+/// Same as request_columns, but the writer also filters the batches with
+/// the predicate before sending them. The filter can use a column that
+/// isn't requested
+pub fn request_filtered<S: Read + Write>(
+    stream: S,
+    columns: &[&str],
+    predicate: &Predicate,
+) -> Result<IpcStreamReader<S>> {
+    send_request(stream, columns, Some(predicate))
+}
+
+fn send_request<S: Read + Write>(
+    mut stream: S,
+    columns: &[&str],
+    predicate: Option<&Predicate>,
+) -> Result<IpcStreamReader<S>> {
+    let schema = request_schema(predicate);
+    let names = StringArray::from(columns.to_vec());
+    let request = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(names)])?;
+
+    let mut writer = IpcStreamWriter::try_new(&mut stream, &schema)?;
+    writer.write(&request)?;
+    writer.finish()?;
+    stream.flush()?;
+
+    IpcStreamReader::try_new(stream)
+}
+
+/// Stream writer that negotiates the columns with the reader before
+/// sending any batch. The reader sends the names of the columns it wants
+/// using `request_columns` and the writer answers with the projected schema.
+/// The batches written afterwards have the complete schema and the
+/// discarded columns are never sent. If the reader sent a filter, only the
+/// matching rows are sent
+pub struct ProjectedStreamWriter<S: Read + Write> {
+    writer: IpcStreamWriter<S>,
+    schema: SchemaRef,
+    projection: Vec<usize>,
+    num_columns: usize,
+    filter: Option<Predicate>,
+}
+
+impl<S: Read + Write> ProjectedStreamWriter<S> {
+    /// Reads the request of the reader and writes the projected schema
+    pub fn accept(mut stream: S, schema: &Schema) -> Result<Self> {
+        let (columns, filter) = read_request(&mut stream)?;
+        if let Some(predicate) = &filter {
+            predicate.validate(schema)?;
+        }
+
+        let projection = if columns.is_empty() {
+            (0..schema.fields().len()).collect()
+        } else {
+            columns
+                .iter()
+                .map(|name| schema.index_of(name))
+                .collect::<Result<Vec<usize>>>()?
+        };
+
+        let projected = Schema::new(
+            projection
+                .iter()
+                .map(|&index| schema.field(index).clone())
+                .collect(),
+        );
+
+        Ok(Self {
+            writer: IpcStreamWriter::try_new(stream, &projected)?,
+            schema: Arc::new(projected),
+            projection,
+            num_columns: schema.fields().len(),
+            filter,
+        })
+    }
+
+    /// Schema sent to the reader
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Indices of the columns requested by the reader
+    pub fn projection(&self) -> &[usize] {
+        &self.projection
+    }
+
+    /// Filter requested by the reader
+    pub fn filter(&self) -> Option<&Predicate> {
+        self.filter.as_ref()
+    }
+
+    /// Writes the requested columns of a RecordBatch with the complete
+    /// schema. Nothing is sent if none of the rows match the filter
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if batch.num_columns() != self.num_columns {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The batch has {} columns but the schema has {}",
+                batch.num_columns(),
+                self.num_columns
+            )));
+        }
+
+        let filtered;
+        let batch = match &self.filter {
+            Some(predicate) => {
+                filtered = predicate.filter(batch)?;
+                &filtered
+            }
+            None => batch,
+        };
+
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let columns = self
+            .projection
+            .iter()
+            .map(|&index| batch.column(index).clone())
+            .collect();
+
+        let projected = RecordBatch::try_new(self.schema.clone(), columns)?;
+        self.writer.write(&projected)
+    }
+
+    /// Writes the end of stream marker. Calling it again once the stream is
+    /// finished does nothing
+    pub fn finish(&mut self) -> Result<()> {
+        self.writer.finish()
+    }
+
+    /// Returns the underlying stream
+    pub fn into_inner(self) -> S {
+        self.writer.into_inner()
+    }
+}
+
+fn request_schema(predicate: Option<&Predicate>) -> Schema {
+    let mut metadata = HashMap::new();
+    if let Some(predicate) = predicate {
+        metadata.insert(FILTER_COLUMN_KEY.to_string(), predicate.column.clone());
+        metadata.insert(FILTER_OP_KEY.to_string(), predicate.op.to_string());
+        metadata.insert(FILTER_LITERAL_KEY.to_string(), predicate.literal.clone());
+    }
+
+    Schema::new_with_metadata(
+        vec![Field::new(REQUEST_COLUMN, DataType::Utf8, false)],
+        metadata,
+    )
+}
+
+fn read_filter(metadata: &HashMap<String, String>) -> Result<Option<Predicate>> {
+    let column = match metadata.get(FILTER_COLUMN_KEY) {
+        Some(column) => column,
+        None => return Ok(None),
+    };
+
+    let (op, literal) = match (
+        metadata.get(FILTER_OP_KEY),
+        metadata.get(FILTER_LITERAL_KEY),
+    ) {
+        (Some(op), Some(literal)) => (op, literal),
+        _ => {
+            return Err(ArrowError::IoError(
+                "The filter in the column request is incomplete".to_string(),
+            ))
+        }
+    };
+
+    Ok(Some(Predicate::new(column.as_str(), op.parse()?, literal)))
+}
+
+// The request is read without buffering so none of the bytes that follow
+// it are taken from the connection
+fn read_request<S: Read>(stream: &mut S) -> Result<(Vec<String>, Option<Predicate>)> {
+    let mut deframer = MessageDeframer::new(stream);
+
+    let schema_message = deframer.read_message()?.ok_or_else(|| {
+        ArrowError::IoError("Connection closed before the column request".to_string())
+    })?;
+    let mut decoder = StreamDecoder::try_new(parse_message(&schema_message.metadata)?)?;
+
+    let schema = decoder.schema();
+    if schema.fields() != request_schema(None).fields() {
+        return Err(ArrowError::IoError(
+            "The stream didn't start with a column request".to_string(),
+        ));
+    }
+    let filter = read_filter(schema.metadata())?;
+
+    let mut columns = Vec::new();
+    while let Some(message) = deframer.read_message()? {
+        let batch = match decoder.decode(parse_message(&message.metadata)?, &message.body)? {
+            Some(batch) => batch,
+            None => continue,
+        };
+
+        let names = downcast_array::<StringArray>(batch.column(0).as_ref())
+            .map_err(|_| ArrowError::IoError("Invalid column request".to_string()))?;
+
+        columns.extend((0..names.len()).map(|i| names.value(i).to_string()));
+    }
+
+    Ok((columns, filter))
+}
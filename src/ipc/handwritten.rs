@@ -0,0 +1,278 @@
+use std::io::Write;
+
+use arrow::{
+    array::{Array, ArrayRef, BooleanArray, StringArray},
+    datatypes::{DataType, Field, Schema},
+    error::{ArrowError, Result},
+    ipc::{self, MessageHeader, MetadataVersion},
+    record_batch::RecordBatch,
+};
+
+use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
+
+use super::framing::MessageFramer;
+use crate::downcast::downcast_array;
+
+// The buffers in the body of a message start at multiples of 8 bytes
+const ALIGNMENT: usize = 8;
+
+/// Writes an Arrow stream building every flatbuffer message by hand,
+/// without arrow's writers. It shows what a StreamWriter does: the schema
+/// message describes the type of every field, and every batch is sent as a
+/// RecordBatch message with one node per column and the buffers of the
+/// columns packed in the body.
+///
+/// Only flat schemas with boolean, integer, float and utf8 columns are
+/// supported
+pub fn write_stream_by_hand<W: Write>(
+    writer: W,
+    schema: &Schema,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    let mut framer = MessageFramer::new(writer);
+
+    let metadata = schema_message(schema)?;
+    framer.write_message(&metadata, &[])?;
+
+    for batch in batches {
+        let (metadata, body) = batch_message(batch)?;
+        framer.write_message(&metadata, &body)?;
+    }
+
+    framer.write_end()?;
+    framer.flush()
+}
+
+fn schema_message(schema: &Schema) -> Result<Vec<u8>> {
+    let mut fbb = FlatBufferBuilder::new();
+
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| schema_field(&mut fbb, field))
+        .collect::<Result<Vec<_>>>()?;
+    let fields = fbb.create_vector(&fields);
+
+    let ipc_schema = ipc::Schema::create(
+        &mut fbb,
+        &ipc::SchemaArgs {
+            endianness: ipc::Endianness::Little,
+            fields: Some(fields),
+            custom_metadata: None,
+            features: None,
+        },
+    );
+
+    Ok(finish_message(
+        fbb,
+        MessageHeader::Schema,
+        ipc_schema.as_union_value(),
+        0,
+    ))
+}
+
+fn schema_field<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    field: &Field,
+) -> Result<WIPOffset<ipc::Field<'a>>> {
+    let name = fbb.create_string(field.name());
+
+    let (type_type, type_) = match field.data_type() {
+        DataType::Boolean => (
+            ipc::Type::Bool,
+            ipc::Bool::create(fbb, &ipc::BoolArgs {}).as_union_value(),
+        ),
+        DataType::Int8 => int_type(fbb, 8, true),
+        DataType::Int16 => int_type(fbb, 16, true),
+        DataType::Int32 => int_type(fbb, 32, true),
+        DataType::Int64 => int_type(fbb, 64, true),
+        DataType::UInt8 => int_type(fbb, 8, false),
+        DataType::UInt16 => int_type(fbb, 16, false),
+        DataType::UInt32 => int_type(fbb, 32, false),
+        DataType::UInt64 => int_type(fbb, 64, false),
+        DataType::Float32 => float_type(fbb, ipc::Precision::SINGLE),
+        DataType::Float64 => float_type(fbb, ipc::Precision::DOUBLE),
+        DataType::Utf8 => (
+            ipc::Type::Utf8,
+            ipc::Utf8::create(fbb, &ipc::Utf8Args {}).as_union_value(),
+        ),
+        other => return Err(unsupported(other)),
+    };
+
+    let children = fbb.create_vector::<WIPOffset<ipc::Field>>(&[]);
+
+    Ok(ipc::Field::create(
+        fbb,
+        &ipc::FieldArgs {
+            name: Some(name),
+            nullable: field.is_nullable(),
+            type_type,
+            type_: Some(type_),
+            dictionary: None,
+            children: Some(children),
+            custom_metadata: None,
+        },
+    ))
+}
+
+fn int_type(
+    fbb: &mut FlatBufferBuilder,
+    bit_width: i32,
+    is_signed: bool,
+) -> (ipc::Type, WIPOffset<UnionWIPOffset>) {
+    let int = ipc::Int::create(
+        fbb,
+        &ipc::IntArgs {
+            bitWidth: bit_width,
+            is_signed,
+        },
+    );
+    (ipc::Type::Int, int.as_union_value())
+}
+
+fn float_type(
+    fbb: &mut FlatBufferBuilder,
+    precision: ipc::Precision,
+) -> (ipc::Type, WIPOffset<UnionWIPOffset>) {
+    let float = ipc::FloatingPoint::create(fbb, &ipc::FloatingPointArgs { precision });
+    (ipc::Type::FloatingPoint, float.as_union_value())
+}
+
+// Every column adds one node and the buffers of its layout to the body:
+// the validity bitmap first, followed by the values for fixed width types
+// or by the offsets and the values for utf8
+fn batch_message(batch: &RecordBatch) -> Result<(Vec<u8>, Vec<u8>)> {
+    let mut nodes = Vec::new();
+    let mut buffers = Vec::new();
+    let mut body = Vec::new();
+
+    for column in batch.columns() {
+        nodes.push(ipc::FieldNode::new(
+            column.len() as i64,
+            column.null_count() as i64,
+        ));
+
+        for buffer in column_buffers(column)? {
+            buffers.push(ipc::Buffer::new(body.len() as i64, buffer.len() as i64));
+            body.extend_from_slice(&buffer);
+
+            let padding = (ALIGNMENT - body.len() % ALIGNMENT) % ALIGNMENT;
+            body.extend(std::iter::repeat_n(0, padding));
+        }
+    }
+
+    let mut fbb = FlatBufferBuilder::new();
+    let nodes = fbb.create_vector(&nodes);
+    let buffers = fbb.create_vector(&buffers);
+
+    let ipc_batch = ipc::RecordBatch::create(
+        &mut fbb,
+        &ipc::RecordBatchArgs {
+            length: batch.num_rows() as i64,
+            nodes: Some(nodes),
+            buffers: Some(buffers),
+            compression: None,
+        },
+    );
+
+    let metadata = finish_message(
+        fbb,
+        MessageHeader::RecordBatch,
+        ipc_batch.as_union_value(),
+        body.len() as i64,
+    );
+    Ok((metadata, body))
+}
+
+// The buffers are rebuilt from the values, so sliced arrays start at
+// offset zero like the format expects
+fn column_buffers(column: &ArrayRef) -> Result<Vec<Vec<u8>>> {
+    // A column without nulls can omit the validity bitmap
+    let validity = if column.null_count() > 0 {
+        pack_bits((0..column.len()).map(|i| column.is_valid(i)))
+    } else {
+        Vec::new()
+    };
+
+    let buffers = match column.data_type() {
+        DataType::Boolean => {
+            let array = downcast_array::<BooleanArray>(column.as_ref())?;
+            let values = pack_bits((0..array.len()).map(|i| array.value(i)));
+            vec![validity, values]
+        }
+        DataType::Utf8 => {
+            let array = downcast_array::<StringArray>(column.as_ref())?;
+
+            let mut offsets = Vec::with_capacity((array.len() + 1) * 4);
+            let mut values = Vec::new();
+            offsets.extend_from_slice(&0i32.to_le_bytes());
+            for i in 0..array.len() {
+                if array.is_valid(i) {
+                    values.extend_from_slice(array.value(i).as_bytes());
+                }
+                offsets.extend_from_slice(&(values.len() as i32).to_le_bytes());
+            }
+
+            vec![validity, offsets, values]
+        }
+        data_type => {
+            let width = primitive_width(data_type)?;
+            let data = column.data();
+            let start = data.offset() * width;
+            let end = start + column.len() * width;
+
+            vec![validity, data.buffers()[0].as_slice()[start..end].to_vec()]
+        }
+    };
+
+    Ok(buffers)
+}
+
+fn primitive_width(data_type: &DataType) -> Result<usize> {
+    match data_type {
+        DataType::Int8 | DataType::UInt8 => Ok(1),
+        DataType::Int16 | DataType::UInt16 => Ok(2),
+        DataType::Int32 | DataType::UInt32 | DataType::Float32 => Ok(4),
+        DataType::Int64 | DataType::UInt64 | DataType::Float64 => Ok(8),
+        other => Err(unsupported(other)),
+    }
+}
+
+// Bitmaps use one bit per value, starting from the least significant bit
+fn pack_bits<I: Iterator<Item = bool>>(bits: I) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for (i, bit) in bits.enumerate() {
+        if i % 8 == 0 {
+            bytes.push(0);
+        }
+        if bit {
+            *bytes.last_mut().unwrap() |= 1 << (i % 8);
+        }
+    }
+    bytes
+}
+
+fn finish_message(
+    mut fbb: FlatBufferBuilder,
+    header_type: MessageHeader,
+    header: WIPOffset<UnionWIPOffset>,
+    body_length: i64,
+) -> Vec<u8> {
+    let message = ipc::Message::create(
+        &mut fbb,
+        &ipc::MessageArgs {
+            version: MetadataVersion::V5,
+            header_type,
+            header: Some(header),
+            bodyLength: body_length,
+            custom_metadata: None,
+        },
+    );
+
+    fbb.finish(message, None);
+    fbb.finished_data().to_vec()
+}
+
+fn unsupported(data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!("The type {:?} can't be written by hand", data_type))
+}
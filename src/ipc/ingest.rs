@@ -0,0 +1,226 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::net::{TcpListener, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use arrow::{
+    datatypes::SchemaRef,
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use parquet::arrow::ArrowWriter;
+
+use super::transport::{IpcTransport, PeerAddr};
+use super::IpcStreamReader;
+
+// Files are rotated once they reach 64MB unless a different size is set
+const DEFAULT_MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Parquet file written by the IngestServer. It is reported once the file
+/// is closed and can be read
+#[derive(Debug)]
+pub struct SpooledFile {
+    pub peer: PeerAddr,
+    pub path: PathBuf,
+    pub rows: usize,
+}
+
+/// Server that accepts Arrow stream connections and writes the received
+/// batches to parquet files. Every connection is written to its own files.
+/// A new file is started when the current one reaches the maximum size or
+/// when the client sends a new stream with a different schema
+pub struct IngestServer<T: IpcTransport = TcpListener> {
+    transport: T,
+    dir: PathBuf,
+    max_file_size: u64,
+}
+
+impl IngestServer<TcpListener> {
+    /// Binds the server to the given TCP address. The files are written
+    /// in the selected directory
+    pub fn bind<A: ToSocketAddrs, P: AsRef<Path>>(addr: A, dir: P) -> io::Result<Self> {
+        Ok(Self::new(TcpListener::bind(addr)?, dir))
+    }
+}
+
+#[cfg(unix)]
+impl IngestServer<UnixListener> {
+    /// Binds the server to a unix domain socket. The files are written in
+    /// the selected directory
+    pub fn bind_unix<S: AsRef<Path>, P: AsRef<Path>>(path: S, dir: P) -> io::Result<Self> {
+        Ok(Self::new(UnixListener::bind(path)?, dir))
+    }
+}
+
+impl<T: IpcTransport> IngestServer<T> {
+    /// Creates the server from a transport that is already listening
+    pub fn new<P: AsRef<Path>>(transport: T, dir: P) -> Self {
+        Self {
+            transport,
+            dir: dir.as_ref().to_path_buf(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+
+    /// Size in bytes after which a file is closed and a new one started.
+    /// The size is checked after every batch, so a file can be larger by
+    /// the size of a batch
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Starts accepting connections in a background thread. The returned
+    /// channel receives every parquet file once it has been closed
+    pub fn start(self) -> Result<Receiver<SpooledFile>> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let (sender, receiver) = channel();
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        thread::spawn(move || loop {
+            // A failed connection shouldn't stop the server from
+            // accepting new clients
+            let (stream, peer) = match self.transport.accept() {
+                Ok(connection) => connection,
+                Err(_) => continue,
+            };
+
+            let spool = Spool {
+                dir: self.dir.clone(),
+                connection: connections.fetch_add(1, Ordering::SeqCst),
+                max_file_size: self.max_file_size,
+                peer,
+                part: 0,
+                current: None,
+                sender: sender.clone(),
+            };
+
+            thread::spawn(move || handle_connection(stream, spool));
+        });
+
+        Ok(receiver)
+    }
+}
+
+// Writes all the streams sent through a connection. The last file is
+// closed even if the connection fails
+fn handle_connection<S: Read>(stream: S, mut spool: Spool) -> Result<()> {
+    let result = read_streams(stream, &mut spool);
+    spool.close_file()?;
+    result
+}
+
+fn read_streams<S: Read>(stream: S, spool: &mut Spool) -> Result<()> {
+    let mut ipc_reader = IpcStreamReader::try_new(stream)?;
+
+    loop {
+        for batch in &mut ipc_reader {
+            spool.write(&batch?)?;
+        }
+
+        if !ipc_reader.next_stream()? {
+            return Ok(());
+        }
+    }
+}
+
+// Parquet file that is being written
+struct SpoolFile {
+    writer: ArrowWriter<File>,
+    // Handle to the same file used to check its size while the writer
+    // owns the file
+    file: File,
+    path: PathBuf,
+    schema: SchemaRef,
+    rows: usize,
+}
+
+// Files written for a connection
+struct Spool {
+    dir: PathBuf,
+    connection: usize,
+    max_file_size: u64,
+    peer: PeerAddr,
+    part: usize,
+    current: Option<SpoolFile>,
+    sender: Sender<SpooledFile>,
+}
+
+impl Spool {
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let schema_changed = match &self.current {
+            Some(current) => current.schema != batch.schema(),
+            None => false,
+        };
+
+        if schema_changed {
+            self.close_file()?;
+        }
+
+        if self.current.is_none() {
+            self.current = Some(self.open_file(batch.schema())?);
+        }
+
+        let current = self.current.as_mut().unwrap();
+        current
+            .writer
+            .write(batch)
+            .map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+        current.rows += batch.num_rows();
+
+        if current.file.metadata()?.len() >= self.max_file_size {
+            self.close_file()?;
+        }
+
+        Ok(())
+    }
+
+    fn open_file(&mut self, schema: SchemaRef) -> Result<SpoolFile> {
+        let path = self.dir.join(format!(
+            "stream-{:04}-{:04}.parquet",
+            self.connection, self.part
+        ));
+        self.part += 1;
+
+        let file = File::create(&path)?;
+        let writer = ArrowWriter::try_new(file.try_clone()?, schema.clone(), None)
+            .map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+
+        Ok(SpoolFile {
+            writer,
+            file,
+            path,
+            schema,
+            rows: 0,
+        })
+    }
+
+    // Writes the footer of the current file and reports it to the owner
+    // of the server
+    fn close_file(&mut self) -> Result<()> {
+        if let Some(mut current) = self.current.take() {
+            current
+                .writer
+                .close()
+                .map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+
+            let spooled = SpooledFile {
+                peer: self.peer.clone(),
+                path: current.path,
+                rows: current.rows,
+            };
+
+            // The files are still written if nobody is listening
+            let _ = self.sender.send(spooled);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Receives the batches sent and received by the stream readers and
+/// writers, to export throughput metrics. The bytes are the size of the
+/// messages in the stream, including the dictionary batches sent for the
+/// batch, and the elapsed time is what it took to encode and write the
+/// batch, or to read and decode it
+pub trait StreamObserver: Send + Sync {
+    fn on_batch_sent(&self, _rows: usize, _bytes: usize, _elapsed: Duration) {}
+
+    fn on_batch_received(&self, _rows: usize, _bytes: usize, _elapsed: Duration) {}
+}
+
+/// Counters aggregated by StreamMetrics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub batches_sent: u64,
+    pub rows_sent: u64,
+    pub bytes_sent: u64,
+    pub send_time: Duration,
+    pub batches_received: u64,
+    pub rows_received: u64,
+    pub bytes_received: u64,
+    pub receive_time: Duration,
+}
+
+/// Observer that adds up the batches, rows, bytes and time of every batch.
+/// It can be shared between several readers and writers
+#[derive(Debug, Default)]
+pub struct StreamMetrics {
+    batches_sent: AtomicU64,
+    rows_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    send_nanos: AtomicU64,
+    batches_received: AtomicU64,
+    rows_received: AtomicU64,
+    bytes_received: AtomicU64,
+    receive_nanos: AtomicU64,
+}
+
+impl StreamMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current value of the counters
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            batches_sent: self.batches_sent.load(Ordering::Relaxed),
+            rows_sent: self.rows_sent.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            send_time: Duration::from_nanos(self.send_nanos.load(Ordering::Relaxed)),
+            batches_received: self.batches_received.load(Ordering::Relaxed),
+            rows_received: self.rows_received.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            receive_time: Duration::from_nanos(self.receive_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl StreamObserver for StreamMetrics {
+    fn on_batch_sent(&self, rows: usize, bytes: usize, elapsed: Duration) {
+        self.batches_sent.fetch_add(1, Ordering::Relaxed);
+        self.rows_sent.fetch_add(rows as u64, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.send_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    fn on_batch_received(&self, rows: usize, bytes: usize, elapsed: Duration) {
+        self.batches_received.fetch_add(1, Ordering::Relaxed);
+        self.rows_received.fetch_add(rows as u64, Ordering::Relaxed);
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.receive_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,68 @@
+// Helpers to share RecordBatches between processes using the Arrow IPC
+// streaming and file formats
+#[cfg(feature = "tokio")]
+mod async_stream;
+mod broadcast;
+mod collect;
+mod compression;
+pub(crate) mod decoder;
+mod dissect;
+mod encoder;
+mod file;
+mod filter;
+mod flow;
+mod framing;
+mod handshake;
+mod handwritten;
+#[cfg(feature = "parquet")]
+mod ingest;
+mod metrics;
+mod mux;
+#[cfg(feature = "redis")]
+mod queue;
+mod replay;
+mod resilient;
+mod server;
+mod split;
+mod stream;
+#[cfg(feature = "tls")]
+mod tls;
+mod transport;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+#[cfg(feature = "tokio")]
+pub use async_stream::{AsyncStreamReader, AsyncStreamWriter};
+pub use broadcast::{BackpressurePolicy, Broadcaster};
+pub use collect::{collect_stream, StreamProgress};
+pub use compression::CompressionCodec;
+pub use dissect::dissect_stream;
+pub use file::{ipc_file_to_bytes, write_ipc_file, IpcFileReader};
+pub use filter::{FilterOp, Predicate};
+pub use flow::{AckStreamReader, AckStreamWriter};
+pub use framing::{FramedMessage, MessageDeframer, MessageFramer, DEFAULT_MAX_MESSAGE_SIZE};
+pub use handshake::{request_columns, request_filtered, ProjectedStreamWriter};
+pub use handwritten::write_stream_by_hand;
+#[cfg(feature = "parquet")]
+pub use ingest::{IngestServer, SpooledFile};
+pub use metrics::{MetricsSnapshot, StreamMetrics, StreamObserver};
+pub use mux::{ChannelReader, MuxReader, MuxWriter};
+#[cfg(feature = "redis")]
+pub use queue::{RedisBatchSink, RedisBatchSource};
+pub use replay::{record_stream, replay_stream};
+pub use resilient::ResilientStreamWriter;
+pub use server::{ConnectionError, IpcServer, ReceivedBatch, ServerEvent, ShutdownHandle};
+pub use split::{split_batch, SplittingStreamWriter};
+pub use stream::{IpcStreamReader, IpcStreamWriter};
+#[cfg(feature = "tls")]
+pub use tls::{
+    connect_tls, load_certs, load_private_key, tls_client_config, tls_server_config,
+    TlsClientStream, TlsListener, TlsServerStream,
+};
+pub use transport::{IpcTransport, PeerAddr};
+#[cfg(feature = "websocket")]
+pub use websocket::{WebSocketReader, WebSocketStreamWriter};
+
+// Marker written before the metadata length of every message since
+// version 0.15.0 of the format
+pub(crate) const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
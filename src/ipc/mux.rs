@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::io::{BufReader, ErrorKind, Read, Write};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::decoder::{parse_message, StreamDecoder};
+use super::encoder::StreamEncoder;
+use super::framing::MessageDeframer;
+
+// Every message sent through a multiplexed connection is preceded by the id
+// of its channel as a little endian u32. The messages of a channel follow
+// the Arrow streaming format, starting with the schema and ending with the
+// end of stream marker
+type ChannelId = u32;
+
+/// Writes several Arrow streams through the same connection. Every stream
+/// is sent in its own channel and the messages of the channels can be
+/// interleaved
+pub struct MuxWriter<W: Write> {
+    writer: W,
+    channels: HashMap<ChannelId, StreamEncoder>,
+}
+
+impl<W: Write> MuxWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            channels: HashMap::new(),
+        }
+    }
+
+    /// Starts a new stream in the channel sending its schema. A channel id
+    /// can be used again once the channel is closed
+    pub fn open_channel(&mut self, channel: ChannelId, schema: &Schema) -> Result<()> {
+        if self.channels.contains_key(&channel) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Channel {} is already open",
+                channel
+            )));
+        }
+
+        let encoder = StreamEncoder::try_new(None)?;
+        let message = encoder.encode_schema(schema)?;
+        self.channels.insert(channel, encoder);
+
+        self.write_message(channel, &message)
+    }
+
+    /// Writes a RecordBatch to an open channel
+    pub fn write(&mut self, channel: ChannelId, batch: &RecordBatch) -> Result<()> {
+        let messages = self.encoder(channel)?.encode_batch_messages(batch)?;
+        for message in messages {
+            self.write_message(channel, &message)?;
+        }
+
+        Ok(())
+    }
+
+    /// Ends the stream of the channel writing the end of stream marker
+    pub fn close_channel(&mut self, channel: ChannelId) -> Result<()> {
+        let message = self.encoder(channel)?.encode_end()?;
+        self.channels.remove(&channel);
+
+        self.write_message(channel, &message)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn encoder(&mut self, channel: ChannelId) -> Result<&mut StreamEncoder> {
+        self.channels.get_mut(&channel).ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!("Channel {} isn't open", channel))
+        })
+    }
+
+    fn write_message(&mut self, channel: ChannelId, message: &[u8]) -> Result<()> {
+        self.writer.write_all(&channel.to_le_bytes())?;
+        self.writer.write_all(message)?;
+        Ok(())
+    }
+}
+
+/// Reader for one of the channels of a multiplexed connection. It returns
+/// the batches of the channel in the same way as an IpcStreamReader
+pub struct ChannelReader {
+    channel: ChannelId,
+    schema: SchemaRef,
+    batches: Receiver<Result<RecordBatch>>,
+}
+
+impl ChannelReader {
+    /// Id of the channel used by the writer
+    pub fn channel(&self) -> ChannelId {
+        self.channel
+    }
+
+    /// Schema sent when the channel was opened
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Iterator for ChannelReader {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.batches.recv().ok()
+    }
+}
+
+/// Reads a connection written by a MuxWriter. The connection is read in a
+/// background thread that sends the batches of each channel to its
+/// ChannelReader, so the channels can be consumed from different threads.
+/// The reader returns a ChannelReader every time a channel is opened
+pub struct MuxReader {
+    channels: Receiver<ChannelReader>,
+}
+
+impl MuxReader {
+    pub fn new<R: Read + Send + 'static>(reader: R) -> Self {
+        let (sender, channels) = channel();
+        thread::spawn(move || demultiplex(reader, sender));
+
+        Self { channels }
+    }
+}
+
+impl Iterator for MuxReader {
+    type Item = ChannelReader;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.channels.recv().ok()
+    }
+}
+
+// Channel that is being read
+struct OpenChannel {
+    decoder: StreamDecoder,
+    sender: Sender<Result<RecordBatch>>,
+}
+
+// Reads the messages of all the channels until the connection is closed.
+// A failure reading the connection is sent to every open channel
+fn demultiplex<R: Read>(reader: R, new_channels: Sender<ChannelReader>) {
+    let mut deframer = MessageDeframer::new(BufReader::new(reader));
+    let mut channels: HashMap<ChannelId, OpenChannel> = HashMap::new();
+
+    loop {
+        let result = read_channel_message(&mut deframer, &mut channels, &new_channels);
+        match result {
+            Ok(true) => (),
+            Ok(false) => return,
+            Err(err) => {
+                for (_, open) in channels.drain() {
+                    let _ = open.sender.send(Err(ArrowError::IoError(err.to_string())));
+                }
+                return;
+            }
+        }
+    }
+}
+
+// Reads the next message and forwards it to its channel. False is returned
+// once the connection has been closed
+fn read_channel_message<R: Read>(
+    deframer: &mut MessageDeframer<R>,
+    channels: &mut HashMap<ChannelId, OpenChannel>,
+    new_channels: &Sender<ChannelReader>,
+) -> Result<bool> {
+    let mut channel_id = [0; std::mem::size_of::<ChannelId>()];
+    match deframer.get_mut().read_exact(&mut channel_id) {
+        Ok(()) => (),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(false),
+        Err(e) => return Err(e.into()),
+    }
+    let channel_id = ChannelId::from_le_bytes(channel_id);
+
+    let message = match deframer.read_message()? {
+        Some(message) => message,
+        None => {
+            // The end of stream marker closes the channel, which ends the
+            // iterator of its reader
+            channels.remove(&channel_id);
+            return Ok(true);
+        }
+    };
+
+    match channels.get_mut(&channel_id) {
+        Some(open) => {
+            let ipc_message = parse_message(&message.metadata)?;
+            if let Some(batch) = open.decoder.decode(ipc_message, &message.body)? {
+                // The owner of the reader may have dropped it. The channel
+                // is still decoded to keep its dictionaries
+                let _ = open.sender.send(Ok(batch));
+            }
+        }
+        None => {
+            let decoder = StreamDecoder::try_new(parse_message(&message.metadata)?)?;
+            let (sender, batches) = channel();
+
+            let reader = ChannelReader {
+                channel: channel_id,
+                schema: decoder.schema(),
+                batches,
+            };
+            let _ = new_channels.send(reader);
+
+            channels.insert(channel_id, OpenChannel { decoder, sender });
+        }
+    }
+
+    Ok(true)
+}
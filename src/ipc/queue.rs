@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use arrow::{
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use redis::{Client, Connection};
+
+use super::compression::CompressionCodec;
+use super::{IpcStreamReader, IpcStreamWriter};
+use crate::Table;
+
+/// Producer of a queue of batches stored in a Redis list. Every batch is
+/// pushed as a complete Arrow stream with its schema, so each element of
+/// the list can be decoded on its own by any consumer
+pub struct RedisBatchSink {
+    connection: Connection,
+    key: String,
+    compression: Option<CompressionCodec>,
+}
+
+impl RedisBatchSink {
+    /// Connects to the Redis server of the url, like redis://127.0.0.1/,
+    /// to push the batches to the list with the key
+    pub fn connect(url: &str, key: &str) -> Result<Self> {
+        Ok(Self::new(open_connection(url)?, key))
+    }
+
+    /// Creates the sink from a connection that is already open
+    pub fn new(connection: Connection, key: &str) -> Self {
+        Self {
+            connection,
+            key: key.to_string(),
+            compression: None,
+        }
+    }
+
+    /// Compresses the buffers of every batch with the selected codec
+    pub fn with_compression(mut self, compression: CompressionCodec) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Pushes the batch at the end of the list and returns the number of
+    /// batches waiting in the queue
+    pub fn push(&mut self, batch: &RecordBatch) -> Result<usize> {
+        let mut writer = IpcStreamWriter::try_new_with_compression(
+            Vec::new(),
+            &batch.schema(),
+            self.compression,
+        )?;
+        writer.write(batch)?;
+        writer.finish()?;
+
+        redis::cmd("RPUSH")
+            .arg(&self.key)
+            .arg(writer.into_inner())
+            .query(&mut self.connection)
+            .map_err(redis_error)
+    }
+
+    /// Returns the underlying connection
+    pub fn into_inner(self) -> Connection {
+        self.connection
+    }
+}
+
+/// Consumer of the queue written by a RedisBatchSink. The batches are
+/// removed from the front of the list, so several consumers can share the
+/// same queue and every batch is read by only one of them
+pub struct RedisBatchSource {
+    connection: Connection,
+    key: String,
+}
+
+impl RedisBatchSource {
+    /// Connects to the Redis server of the url to pop the batches from the
+    /// list with the key
+    pub fn connect(url: &str, key: &str) -> Result<Self> {
+        Ok(Self::new(open_connection(url)?, key))
+    }
+
+    /// Creates the source from a connection that is already open
+    pub fn new(connection: Connection, key: &str) -> Self {
+        Self {
+            connection,
+            key: key.to_string(),
+        }
+    }
+
+    /// Number of batches waiting in the queue
+    pub fn len(&mut self) -> Result<usize> {
+        redis::cmd("LLEN")
+            .arg(&self.key)
+            .query(&mut self.connection)
+            .map_err(redis_error)
+    }
+
+    pub fn is_empty(&mut self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Pops the next batch without waiting. None is returned if the queue
+    /// is empty
+    pub fn pop(&mut self) -> Result<Option<RecordBatch>> {
+        let element: Option<Vec<u8>> = redis::cmd("LPOP")
+            .arg(&self.key)
+            .query(&mut self.connection)
+            .map_err(redis_error)?;
+
+        element.map(|bytes| decode_batch(&bytes)).transpose()
+    }
+
+    /// Pops the next batch waiting for it up to the timeout, which Redis
+    /// counts in whole seconds. None is returned if the timeout expires
+    pub fn pop_timeout(&mut self, timeout: Duration) -> Result<Option<RecordBatch>> {
+        // A timeout of zero would block forever
+        let seconds = (timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0)).max(1);
+        let element: Option<(String, Vec<u8>)> = redis::cmd("BLPOP")
+            .arg(&self.key)
+            .arg(seconds)
+            .query(&mut self.connection)
+            .map_err(redis_error)?;
+
+        element.map(|(_, bytes)| decode_batch(&bytes)).transpose()
+    }
+
+    /// Pops the batches that are in the queue and collects them in a Table.
+    /// None is returned if the queue is empty. All the batches need the
+    /// same schema. If a batch has a different one, or can't be decoded,
+    /// an error is returned and the queue is left untouched
+    pub fn drain(&mut self) -> Result<Option<Table>> {
+        let key = self.key.clone();
+
+        // The batches are only removed once they have been collected. The
+        // list is watched, so if other clients change it in the meantime
+        // EXEC returns nil and the batches are read again
+        redis::transaction(&mut self.connection, &[&key], |connection, pipe| {
+            let elements: Vec<Vec<u8>> = redis::cmd("LRANGE")
+                .arg(&key)
+                .arg(0)
+                .arg(-1)
+                .query(connection)?;
+
+            let table = match collect_table(&elements) {
+                Ok(Some(table)) => table,
+                result => return Ok(Some(result)),
+            };
+
+            let trimmed: Option<()> = pipe
+                .cmd("LTRIM")
+                .arg(&key)
+                .arg(elements.len())
+                .arg(-1)
+                .ignore()
+                .query(connection)?;
+            Ok(trimmed.map(|_| Ok(Some(table))))
+        })
+        .map_err(redis_error)?
+    }
+
+    /// Returns the underlying connection
+    pub fn into_inner(self) -> Connection {
+        self.connection
+    }
+}
+
+fn open_connection(url: &str) -> Result<Connection> {
+    Client::open(url)
+        .and_then(|client| client.get_connection())
+        .map_err(redis_error)
+}
+
+// Every element holds a stream with a single batch
+fn decode_batch(bytes: &[u8]) -> Result<RecordBatch> {
+    IpcStreamReader::try_new(bytes)?.next().unwrap_or_else(|| {
+        Err(ArrowError::IoError(
+            "The queued stream doesn't have a batch".to_string(),
+        ))
+    })
+}
+
+// Decodes the elements of the list into a Table, checking that all the
+// batches have the same schema
+fn collect_table(elements: &[Vec<u8>]) -> Result<Option<Table>> {
+    let batches = elements
+        .iter()
+        .map(|bytes| decode_batch(bytes))
+        .collect::<Result<Vec<_>>>()?;
+
+    let first = match batches.first() {
+        Some(first) => first,
+        None => return Ok(None),
+    };
+    for batch in &batches[1..] {
+        check_schema(first, batch)?;
+    }
+
+    let schema = first.schema().as_ref().clone();
+    Ok(Some(Table::new(schema, batches)))
+}
+
+fn check_schema(first: &RecordBatch, batch: &RecordBatch) -> Result<()> {
+    if first.schema() != batch.schema() {
+        return Err(ArrowError::SchemaError(format!(
+            "The queued batches have different schemas: {:?} and {:?}",
+            first.schema(),
+            batch.schema()
+        )));
+    }
+    Ok(())
+}
+
+fn redis_error(err: redis::RedisError) -> ArrowError {
+    ArrowError::IoError(format!("Redis error: {}", err))
+}
@@ -0,0 +1,82 @@
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use arrow::{
+    error::{ArrowError, Result},
+    ipc,
+};
+
+use super::decoder::parse_message;
+use super::framing::{MessageDeframer, MessageFramer};
+
+/// Copies a live stream verbatim until the connection is closed, so it can
+/// be replayed later without the original producer. Returns the number of
+/// bytes recorded
+pub fn record_stream<R: Read, W: Write>(mut reader: R, mut writer: W) -> io::Result<u64> {
+    let bytes = io::copy(&mut reader, &mut writer)?;
+    writer.flush()?;
+    Ok(bytes)
+}
+
+/// Sends a recorded stream to a writer. With a rate, the record batches are
+/// spaced to send at most that many batches per second; the schema and the
+/// dictionaries are sent as soon as they are read. Only the first stream of
+/// the recording is replayed. The rate has to be a positive number. Returns
+/// the number of record batches sent
+pub fn replay_stream<R: Read, W: Write>(
+    reader: R,
+    writer: W,
+    batches_per_second: Option<f64>,
+) -> Result<usize> {
+    let interval = batches_per_second.map(batch_interval).transpose()?;
+
+    let mut deframer = MessageDeframer::new(reader);
+    let mut framer = MessageFramer::new(writer);
+
+    let start = Instant::now();
+    let mut batches = 0;
+
+    while let Some(message) = deframer.read_message()? {
+        let is_batch =
+            parse_message(&message.metadata)?.header_type() == ipc::MessageHeader::RecordBatch;
+
+        if is_batch {
+            // The deadline is measured from the start so the time spent
+            // writing doesn't slow down the replay
+            if let Some(interval) = interval {
+                let deadline = start + interval * batches as u32;
+                if let Some(wait) = deadline.checked_duration_since(Instant::now()) {
+                    thread::sleep(wait);
+                }
+            }
+            batches += 1;
+        }
+
+        framer.write_message(&message.metadata, &message.body)?;
+        if is_batch {
+            framer.flush()?;
+        }
+    }
+
+    framer.write_end()?;
+    framer.flush()?;
+
+    Ok(batches)
+}
+
+// Time between two record batches sent at the selected rate
+fn batch_interval(batches_per_second: f64) -> Result<Duration> {
+    let invalid_rate = || {
+        ArrowError::InvalidArgumentError(format!(
+            "Invalid replay rate {}, expected a positive number of batches per second",
+            batches_per_second
+        ))
+    };
+
+    if !batches_per_second.is_finite() || batches_per_second <= 0.0 {
+        return Err(invalid_rate());
+    }
+
+    Duration::try_from_secs_f64(1.0 / batches_per_second).map_err(|_| invalid_rate())
+}
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+use std::io;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+use arrow::{
+    datatypes::Schema,
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::IpcStreamWriter;
+
+const DEFAULT_MAX_BUFFERED_BATCHES: usize = 1024;
+const DEFAULT_MAX_RETRIES: usize = 8;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// TCP stream writer that survives dropped connections. The batches are
+/// buffered until they are written to the connection, and when the
+/// connection fails the writer reconnects waiting an exponential backoff
+/// between attempts. Every new connection starts a new stream, so the schema
+/// and the dictionaries are sent again.
+///
+/// A batch is removed from the buffer once it is written to the socket.
+/// Batches that were written just before the connection dropped can still
+/// be lost if the reader didn't receive them
+pub struct ResilientStreamWriter {
+    addrs: Vec<SocketAddr>,
+    schema: Schema,
+    writer: Option<IpcStreamWriter<TcpStream>>,
+    buffer: VecDeque<RecordBatch>,
+    max_buffered_batches: usize,
+    max_retries: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl ResilientStreamWriter {
+    /// Creates the writer. The connection is opened when the first batch
+    /// is written
+    pub fn new<A: ToSocketAddrs>(addr: A, schema: &Schema) -> io::Result<Self> {
+        Ok(Self {
+            addrs: addr.to_socket_addrs()?.collect(),
+            schema: schema.clone(),
+            writer: None,
+            buffer: VecDeque::new(),
+            max_buffered_batches: DEFAULT_MAX_BUFFERED_BATCHES,
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        })
+    }
+
+    /// Maximum number of unsent batches kept by the writer. Writing a batch
+    /// when the buffer is full returns an error
+    pub fn with_max_buffered_batches(mut self, max_buffered_batches: usize) -> Self {
+        self.max_buffered_batches = max_buffered_batches;
+        self
+    }
+
+    /// Number of times the writer tries to reconnect before returning an
+    /// error. The unsent batches are kept and sent with the next write
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Time waited before the first reconnection. It is doubled after every
+    /// failed attempt up to the maximum backoff
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Number of batches that haven't been written to the connection
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Buffers the batch and sends all the unsent batches, reconnecting if
+    /// the connection was dropped. If the writer can't reconnect the batch
+    /// stays in the buffer and it is sent with the next write
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if self.buffer.len() >= self.max_buffered_batches {
+            return Err(ArrowError::IoError(format!(
+                "The writer buffer is full with {} unsent batches",
+                self.buffer.len()
+            )));
+        }
+
+        self.buffer.push_back(batch.clone());
+        self.flush()
+    }
+
+    /// Sends all the unsent batches
+    pub fn flush(&mut self) -> Result<()> {
+        while !self.buffer.is_empty() {
+            self.with_retries(Self::send_front)?;
+            self.buffer.pop_front();
+        }
+
+        Ok(())
+    }
+
+    /// Sends the unsent batches followed by the end of stream marker
+    pub fn finish(&mut self) -> Result<()> {
+        self.flush()?;
+        self.with_retries(|writer| writer.connected()?.finish())
+    }
+
+    fn send_front(&mut self) -> Result<()> {
+        let batch = self.buffer.front().cloned();
+        match batch {
+            Some(batch) => self.connected()?.write(&batch),
+            None => Ok(()),
+        }
+    }
+
+    // Returns the writer of the current connection, opening a new
+    // connection that starts a new stream if there isn't one
+    fn connected(&mut self) -> Result<&mut IpcStreamWriter<TcpStream>> {
+        if self.writer.is_none() {
+            let stream = TcpStream::connect(&self.addrs[..])?;
+            self.writer = Some(IpcStreamWriter::try_new(stream, &self.schema)?);
+        }
+
+        Ok(self.writer.as_mut().unwrap())
+    }
+
+    // Runs the operation until it succeeds. The connection is dropped after
+    // every failure and the writer waits before trying again
+    fn with_retries<F>(&mut self, mut operation: F) -> Result<()>
+    where
+        F: FnMut(&mut Self) -> Result<()>,
+    {
+        let mut attempt = 0;
+        loop {
+            match operation(self) {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    self.writer = None;
+
+                    if attempt >= self.max_retries {
+                        return Err(err);
+                    }
+
+                    thread::sleep(self.backoff(attempt));
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    fn backoff(&self, attempt: usize) -> Duration {
+        let factor = 2u32.saturating_pow(attempt as u32);
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
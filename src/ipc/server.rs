@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use arrow::{error::Result, record_batch::RecordBatch};
+#[cfg(feature = "tls")]
+use rustls::ServerConfig;
+
+#[cfg(feature = "tls")]
+use super::tls::TlsListener;
+
+use super::transport::{IpcTransport, PeerAddr};
+use super::IpcStreamReader;
+
+// Pause after a failed accept before waiting for the next client
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// A RecordBatch received by the server together with the address of the
+/// client that sent it
+#[derive(Debug)]
+pub struct ReceivedBatch {
+    pub peer: PeerAddr,
+    pub batch: RecordBatch,
+}
+
+/// Reason a client connection ended before its stream was complete
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionError {
+    /// The connection failed or was closed in the middle of the stream
+    Reset(String),
+    /// The client sent a message that couldn't be decoded
+    Malformed(String),
+}
+
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectionError::Reset(reason) => write!(f, "Connection reset: {}", reason),
+            ConnectionError::Malformed(reason) => write!(f, "Malformed message: {}", reason),
+        }
+    }
+}
+
+impl Error for ConnectionError {}
+
+/// Everything that happens in a running server
+#[derive(Debug)]
+pub enum ServerEvent {
+    Batch(ReceivedBatch),
+    /// A client connection was closed. The result is Ok if the stream ended
+    /// cleanly
+    Closed {
+        peer: PeerAddr,
+        result: std::result::Result<(), ConnectionError>,
+    },
+    /// A client couldn't be accepted. The server keeps waiting for new
+    /// clients after a short pause
+    AcceptFailed(ConnectionError),
+}
+
+/// Stops a running server from accepting new clients. The connections
+/// that are open are read until they finish
+pub struct ShutdownHandle<T: IpcTransport = TcpListener> {
+    transport: Arc<T>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl<T: IpcTransport> ShutdownHandle<T> {
+    pub fn shutdown(&self) -> io::Result<()> {
+        self.shutdown.store(true, Ordering::SeqCst);
+        self.transport.wake()
+    }
+}
+
+impl<T: IpcTransport> Clone for ShutdownHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            transport: self.transport.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
+}
+
+/// Server that reads Arrow streams from several clients at the same
+/// time. Every connection is read in its own thread and the received
+/// batches are sent through a channel to the owner of the server
+pub struct IpcServer<T: IpcTransport = TcpListener> {
+    transport: Arc<T>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl IpcServer<TcpListener> {
+    /// Binds the server to the given TCP address. The connections are not
+    /// accepted until the server is started
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self::new(TcpListener::bind(addr)?))
+    }
+
+    /// Address the server is listening to. Useful when binding to port 0
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.transport.local_addr()
+    }
+}
+
+#[cfg(unix)]
+impl IpcServer<UnixListener> {
+    /// Binds the server to a unix domain socket. The socket file is created
+    /// by the server and binding fails if the file already exists
+    pub fn bind_unix<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(UnixListener::bind(path)?))
+    }
+}
+
+#[cfg(feature = "tls")]
+impl IpcServer<TlsListener> {
+    /// Binds the server to the given TCP address encrypting the
+    /// connections with TLS
+    pub fn bind_tls<A: ToSocketAddrs>(addr: A, config: Arc<ServerConfig>) -> io::Result<Self> {
+        Ok(Self::new(TlsListener::bind(addr, config)?))
+    }
+}
+
+impl<T: IpcTransport> IpcServer<T> {
+    /// Creates the server from a transport that is already listening
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            shutdown: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Handle to stop the server once it is started
+    pub fn shutdown_handle(&self) -> ShutdownHandle<T> {
+        ShutdownHandle {
+            transport: self.transport.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
+
+    /// Starts accepting connections in a background thread. The returned
+    /// channel receives the batches from all the connected clients in the
+    /// order they are read. The channel is closed once the server is shut
+    /// down and all the open connections finish
+    pub fn start(self) -> Receiver<ReceivedBatch> {
+        let (sender, receiver) = channel();
+
+        self.spawn(move |event| match event {
+            ServerEvent::Batch(received) => sender.send(received).is_ok(),
+            ServerEvent::Closed { .. } | ServerEvent::AcceptFailed(_) => true,
+        });
+
+        receiver
+    }
+
+    /// Same as start, but the channel also receives how every connection
+    /// ended
+    pub fn start_with_events(self) -> Receiver<ServerEvent> {
+        let (sender, receiver) = channel();
+        self.spawn(move |event| sender.send(event).is_ok());
+
+        receiver
+    }
+
+    // Accepts the clients until the server is shut down. The events of
+    // every connection are passed to the handler, and a connection stops
+    // being read if the handler returns false
+    fn spawn<F>(self, handler: F)
+    where
+        F: Fn(ServerEvent) -> bool + Clone + Send + 'static,
+    {
+        thread::spawn(move || loop {
+            let accepted = self.transport.accept();
+            if self.shutdown.load(Ordering::SeqCst) {
+                break;
+            }
+
+            // A failed connection shouldn't stop the server from
+            // accepting new clients. Errors like running out of file
+            // descriptors fail again right away, so the loop waits a bit
+            // before retrying
+            let (stream, peer) = match accepted {
+                Ok(connection) => connection,
+                Err(err) => {
+                    handler(ServerEvent::AcceptFailed(ConnectionError::Reset(
+                        err.to_string(),
+                    )));
+                    thread::sleep(ACCEPT_RETRY_DELAY);
+                    continue;
+                }
+            };
+
+            let handler = handler.clone();
+            thread::spawn(move || {
+                let result = handle_connection(stream, &peer, &handler);
+                handler(ServerEvent::Closed { peer, result });
+            });
+        });
+    }
+}
+
+// Reader that remembers why the connection stopped giving data, which tells
+// a dropped connection apart from a malformed message when decoding fails
+struct ConnectionReader<R: Read> {
+    reader: R,
+    closed: Option<String>,
+}
+
+impl<R: Read> Read for ConnectionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.reader.read(buf) {
+            Ok(0) if !buf.is_empty() => {
+                self.closed = Some("Connection closed before the end of the stream".to_string());
+                Ok(0)
+            }
+            Err(err) if err.kind() != io::ErrorKind::Interrupted => {
+                self.closed = Some(err.to_string());
+                Err(err)
+            }
+            result => result,
+        }
+    }
+}
+
+// Reads all the batches sent by a client and passes them to the handler.
+// A stream that ends with the end of stream marker, or at the end of a
+// message, is a clean close
+fn handle_connection<S, F>(
+    stream: S,
+    peer: &PeerAddr,
+    handler: &F,
+) -> std::result::Result<(), ConnectionError>
+where
+    S: Read,
+    F: Fn(ServerEvent) -> bool,
+{
+    let mut connection = ConnectionReader {
+        reader: stream,
+        closed: None,
+    };
+
+    read_batches(&mut connection, peer, handler).map_err(|err| match connection.closed.take() {
+        Some(reason) => ConnectionError::Reset(reason),
+        None => ConnectionError::Malformed(err.to_string()),
+    })
+}
+
+fn read_batches<R, F>(reader: R, peer: &PeerAddr, handler: &F) -> Result<()>
+where
+    R: Read,
+    F: Fn(ServerEvent) -> bool,
+{
+    let ipc_reader = IpcStreamReader::try_new(reader)?;
+
+    for batch in ipc_reader {
+        let received = ReceivedBatch {
+            peer: peer.clone(),
+            batch: batch?,
+        };
+
+        if !handler(ServerEvent::Batch(received)) {
+            break;
+        }
+    }
+
+    Ok(())
+}
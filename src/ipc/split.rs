@@ -0,0 +1,133 @@
+use std::io::Write;
+
+use arrow::{
+    array::UInt32Array,
+    compute::take,
+    datatypes::Schema,
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+
+use super::IpcStreamWriter;
+
+/// Splits a RecordBatch into batches with at most `max_rows` rows. The
+/// rows are copied into the new batches
+pub fn split_batch(batch: &RecordBatch, max_rows: usize) -> Result<Vec<RecordBatch>> {
+    if max_rows == 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "The batches must have at least one row".to_string(),
+        ));
+    }
+
+    if batch.num_rows() <= max_rows {
+        return Ok(vec![batch.clone()]);
+    }
+
+    (0..batch.num_rows())
+        .step_by(max_rows)
+        .map(|start| {
+            let end = (start + max_rows).min(batch.num_rows());
+            let indices = UInt32Array::from((start as u32..end as u32).collect::<Vec<u32>>());
+
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|column| take(column.as_ref(), &indices, None))
+                .collect::<Result<Vec<_>>>()?;
+
+            RecordBatch::try_new(batch.schema(), columns)
+        })
+        .collect()
+}
+
+/// Stream writer that splits large batches before sending them. Huge
+/// batches make the reader wait for the whole batch and allocate it at
+/// once, so they are sent as several smaller batches that don't go over
+/// the row or byte limits
+pub struct SplittingStreamWriter<W: Write> {
+    writer: IpcStreamWriter<W>,
+    max_rows: Option<usize>,
+    max_bytes: Option<usize>,
+}
+
+impl<W: Write> SplittingStreamWriter<W> {
+    /// Creates the writer sending the schema message. Without limits the
+    /// batches are written unchanged
+    pub fn try_new(writer: W, schema: &Schema) -> Result<Self> {
+        Ok(Self {
+            writer: IpcStreamWriter::try_new(writer, schema)?,
+            max_rows: None,
+            max_bytes: None,
+        })
+    }
+
+    /// Maximum number of rows in every batch sent
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows.max(1));
+        self
+    }
+
+    /// Maximum size of the buffers of every batch sent. The size of a row
+    /// is estimated from the whole batch, and a batch always has at least
+    /// one row even if it is larger than the limit
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Writes the batch, split if it goes over the limits. Returns the
+    /// number of batches sent
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<usize> {
+        let max_rows = self.rows_per_batch(batch);
+        let batches = split_batch(batch, max_rows)?;
+
+        for batch in &batches {
+            self.writer.write(batch)?;
+        }
+
+        Ok(batches.len())
+    }
+
+    /// Writes the end of stream marker. Calling it again once the stream is
+    /// finished does nothing
+    pub fn finish(&mut self) -> Result<()> {
+        self.writer.finish()
+    }
+
+    /// Mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        self.writer.get_mut()
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    fn rows_per_batch(&self, batch: &RecordBatch) -> usize {
+        let mut max_rows = self.max_rows.unwrap_or(usize::MAX);
+
+        if let (Some(max_bytes), true) = (self.max_bytes, batch.num_rows() > 0) {
+            let bytes = batch
+                .columns()
+                .iter()
+                .map(|column| column.get_buffer_memory_size())
+                .sum::<usize>();
+            let row_bytes = bytes.div_ceil(batch.num_rows()).max(1);
+
+            max_rows = max_rows.min(max_bytes / row_bytes);
+        }
+
+        max_rows.max(1)
+    }
+}
+
+impl<W: Write> From<IpcStreamWriter<W>> for SplittingStreamWriter<W> {
+    fn from(writer: IpcStreamWriter<W>) -> Self {
+        Self {
+            writer,
+            max_rows: None,
+            max_bytes: None,
+        }
+    }
+}
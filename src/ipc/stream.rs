@@ -0,0 +1,256 @@
+use std::io::{BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+
+#[cfg(feature = "tls")]
+use rustls::ClientConfig;
+
+use super::compression::CompressionCodec;
+use super::decoder::{parse_message, StreamDecoder};
+use super::encoder::StreamEncoder;
+use super::framing::MessageDeframer;
+use super::metrics::StreamObserver;
+#[cfg(feature = "tls")]
+use super::tls::{connect_tls, TlsClientStream};
+
+/// Reader for the Arrow streaming format. It works like arrow's
+/// `StreamReader` but it also accepts delta dictionary batches, which are
+/// appended to the dictionaries received before
+pub struct IpcStreamReader<R: Read> {
+    deframer: MessageDeframer<BufReader<R>>,
+    decoder: StreamDecoder,
+    finished: bool,
+    observer: Option<Arc<dyn StreamObserver>>,
+}
+
+impl<R: Read> IpcStreamReader<R> {
+    /// Creates the reader reading the schema message from the stream
+    pub fn try_new(reader: R) -> Result<Self> {
+        let mut deframer = MessageDeframer::new(BufReader::new(reader));
+
+        let schema_message = deframer.read_message()?.ok_or_else(|| {
+            ArrowError::IoError("Stream ended before the schema was read".to_string())
+        })?;
+        let decoder = StreamDecoder::try_new(parse_message(&schema_message.metadata)?)?;
+
+        Ok(Self {
+            deframer,
+            decoder,
+            finished: false,
+            observer: None,
+        })
+    }
+
+    /// Reports every batch read to the observer
+    pub fn with_observer(mut self, observer: Arc<dyn StreamObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Schema read from the first message in the stream
+    pub fn schema(&self) -> SchemaRef {
+        self.decoder.schema()
+    }
+
+    /// Returns true once the end of the stream has been reached
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Mutable reference to the underlying reader. Reading from it would
+    /// corrupt the stream, but it can be used to write to a connection
+    pub fn get_mut(&mut self) -> &mut R {
+        self.deframer.get_mut().get_mut()
+    }
+
+    /// Starts reading the next stream once the current one has finished.
+    /// A writer can send several streams through the same connection, each
+    /// one starting with its schema. False is returned if the connection was
+    /// closed instead
+    pub fn next_stream(&mut self) -> Result<bool> {
+        if !self.finished {
+            return Err(ArrowError::IoError(
+                "The current stream hasn't been read completely".to_string(),
+            ));
+        }
+
+        match self.deframer.read_message()? {
+            Some(schema_message) => {
+                self.decoder = StreamDecoder::try_new(parse_message(&schema_message.metadata)?)?;
+                self.finished = false;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn maybe_next(&mut self) -> Result<Option<RecordBatch>> {
+        // The clock is only read for the observer, as Instant::now panics
+        // in wasm32-unknown-unknown
+        let start = self.observer.as_ref().map(|_| Instant::now());
+        let mut bytes = 0;
+
+        while !self.finished {
+            let framed = match self.deframer.read_message()? {
+                Some(framed) => framed,
+                None => {
+                    self.finished = true;
+                    break;
+                }
+            };
+
+            // The prefix of the message is the marker and the length
+            bytes += 8 + framed.metadata.len() + framed.body.len();
+
+            let message = parse_message(&framed.metadata)?;
+            if let Some(batch) = self.decoder.decode(message, &framed.body)? {
+                if let (Some(observer), Some(start)) = (&self.observer, start) {
+                    observer.on_batch_received(batch.num_rows(), bytes, start.elapsed());
+                }
+                return Ok(Some(batch));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<R: Read> Iterator for IpcStreamReader<R> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.maybe_next().transpose()
+    }
+}
+
+impl<R: Read> RecordBatchReader for IpcStreamReader<R> {
+    fn schema(&self) -> SchemaRef {
+        self.decoder.schema()
+    }
+}
+
+/// Writer for the Arrow streaming format. Dictionary columns are sent as
+/// delta dictionary batches when the new dictionary only adds values at
+/// the end of the one that was sent before
+pub struct IpcStreamWriter<W: Write> {
+    writer: W,
+    encoder: StreamEncoder,
+    finished: bool,
+    observer: Option<Arc<dyn StreamObserver>>,
+}
+
+impl<W: Write> IpcStreamWriter<W> {
+    /// Creates the writer sending the schema message to the stream
+    pub fn try_new(writer: W, schema: &Schema) -> Result<Self> {
+        Self::try_new_with_compression(writer, schema, None)
+    }
+
+    /// Creates a writer that compresses the buffers of every batch with the
+    /// selected codec. The readers find the codec in each message
+    pub fn try_new_with_compression(
+        mut writer: W,
+        schema: &Schema,
+        compression: Option<CompressionCodec>,
+    ) -> Result<Self> {
+        let encoder = StreamEncoder::try_new(compression)?;
+        writer.write_all(&encoder.encode_schema(schema)?)?;
+
+        Ok(Self {
+            writer,
+            encoder,
+            finished: false,
+            observer: None,
+        })
+    }
+
+    /// Reports every batch written to the observer
+    pub fn with_observer(mut self, observer: Arc<dyn StreamObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Writes a RecordBatch to the stream, together with the dictionary
+    /// batches its dictionary columns require
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if self.finished {
+            return Err(ArrowError::IoError(
+                "Cannot write record batch to stream writer as it is closed".to_string(),
+            ));
+        }
+
+        let start = self.observer.as_ref().map(|_| Instant::now());
+        let encoded = self.encoder.encode_batch(batch)?;
+        self.writer.write_all(&encoded)?;
+
+        if let (Some(observer), Some(start)) = (&self.observer, start) {
+            observer.on_batch_sent(batch.num_rows(), encoded.len(), start.elapsed());
+        }
+        Ok(())
+    }
+
+    /// Writes the end of stream marker and flushes the writer. Calling it
+    /// again once the stream is finished does nothing
+    pub fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.writer.write_all(&self.encoder.encode_end()?)?;
+        self.writer.flush()?;
+
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl IpcStreamWriter<TcpStream> {
+    /// Connects to a server listening in a TCP address and sends the
+    /// schema message
+    pub fn connect<A: ToSocketAddrs>(addr: A, schema: &Schema) -> Result<Self> {
+        Self::try_new(TcpStream::connect(addr)?, schema)
+    }
+}
+
+#[cfg(unix)]
+impl IpcStreamWriter<UnixStream> {
+    /// Connects to a server listening in a unix domain socket and sends the
+    /// schema message. Useful when both processes run in the same host
+    pub fn connect_unix<P: AsRef<Path>>(path: P, schema: &Schema) -> Result<Self> {
+        Self::try_new(UnixStream::connect(path)?, schema)
+    }
+}
+
+#[cfg(feature = "tls")]
+impl IpcStreamWriter<TlsClientStream> {
+    /// Connects to a server using TLS and sends the schema message. The
+    /// domain is the name checked against the certificate of the server
+    pub fn connect_tls<A: ToSocketAddrs>(
+        addr: A,
+        domain: &str,
+        config: &Arc<ClientConfig>,
+        schema: &Schema,
+    ) -> Result<Self> {
+        Self::try_new(connect_tls(addr, domain, config)?, schema)
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::{
+    internal::pemfile, Certificate, ClientConfig, ClientSession, NoClientAuth, PrivateKey,
+    ServerConfig, ServerSession, StreamOwned,
+};
+
+use super::transport::{wake_tcp, IpcTransport, PeerAddr};
+
+/// Encrypted connection accepted by a TlsListener
+pub type TlsServerStream = StreamOwned<ServerSession, TcpStream>;
+
+/// Encrypted connection opened by a writer
+pub type TlsClientStream = StreamOwned<ClientSession, TcpStream>;
+
+/// Reads all the certificates from a PEM file. The file should contain the
+/// certificate of the server followed by the intermediate certificates
+pub fn load_certs<P: AsRef<Path>>(path: P) -> io::Result<Vec<Certificate>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    pemfile::certs(&mut reader).map_err(|_| invalid_pem("certificates"))
+}
+
+/// Reads the first private key from a PEM file. Both PKCS8 and RSA keys
+/// are accepted
+pub fn load_private_key<P: AsRef<Path>>(path: P) -> io::Result<PrivateKey> {
+    let mut reader = BufReader::new(File::open(&path)?);
+    let mut keys = pemfile::pkcs8_private_keys(&mut reader).map_err(|_| invalid_pem("key"))?;
+
+    if keys.is_empty() {
+        let mut reader = BufReader::new(File::open(&path)?);
+        keys = pemfile::rsa_private_keys(&mut reader).map_err(|_| invalid_pem("key"))?;
+    }
+
+    keys.into_iter().next().ok_or_else(|| invalid_pem("key"))
+}
+
+/// Configuration for a server using the certificate chain and private key
+/// stored in PEM files. The clients aren't authenticated
+pub fn tls_server_config<C, K>(cert_path: C, key_path: K) -> io::Result<Arc<ServerConfig>>
+where
+    C: AsRef<Path>,
+    K: AsRef<Path>,
+{
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config
+        .set_single_cert(load_certs(cert_path)?, load_private_key(key_path)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+    Ok(Arc::new(config))
+}
+
+/// Configuration for a client that trusts the certificate authorities
+/// stored in a PEM file
+pub fn tls_client_config<P: AsRef<Path>>(ca_path: P) -> io::Result<Arc<ClientConfig>> {
+    let mut config = ClientConfig::new();
+    let mut reader = BufReader::new(File::open(ca_path)?);
+    config
+        .root_store
+        .add_pem_file(&mut reader)
+        .map_err(|_| invalid_pem("certificate authorities"))?;
+
+    Ok(Arc::new(config))
+}
+
+/// TCP listener that encrypts the accepted connections. The TLS handshake
+/// is done in the thread that reads the connection, so a slow client
+/// doesn't block the server from accepting new clients
+pub struct TlsListener {
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+}
+
+impl TlsListener {
+    pub fn bind<A: ToSocketAddrs>(addr: A, config: Arc<ServerConfig>) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            config,
+        })
+    }
+
+    /// Address the listener is bound to
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+}
+
+impl IpcTransport for TlsListener {
+    type Stream = TlsServerStream;
+
+    fn accept(&self) -> io::Result<(Self::Stream, PeerAddr)> {
+        let (stream, addr) = self.listener.accept()?;
+        let session = ServerSession::new(&self.config);
+        Ok((StreamOwned::new(session, stream), PeerAddr::Tcp(addr)))
+    }
+
+    fn wake(&self) -> io::Result<()> {
+        wake_tcp(&self.listener)
+    }
+}
+
+/// Opens an encrypted connection to a server. The domain is the name
+/// checked against the certificate of the server
+pub fn connect_tls<A: ToSocketAddrs>(
+    addr: A,
+    domain: &str,
+    config: &Arc<ClientConfig>,
+) -> io::Result<TlsClientStream> {
+    let dns_name = webpki::DNSNameRef::try_from_ascii_str(domain)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+
+    let session = ClientSession::new(config, dns_name);
+    Ok(StreamOwned::new(session, TcpStream::connect(addr)?))
+}
+
+fn invalid_pem(content: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Unable to read the {} from the PEM file", content),
+    )
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+
+/// Address of the client that opened a connection
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeerAddr {
+    Tcp(SocketAddr),
+    // Unix clients usually connect from an unnamed socket, so the path
+    // is only available if the client bound its socket to a file
+    Unix(Option<PathBuf>),
+}
+
+impl fmt::Display for PeerAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeerAddr::Tcp(addr) => write!(f, "{}", addr),
+            PeerAddr::Unix(Some(path)) => write!(f, "{}", path.display()),
+            PeerAddr::Unix(None) => write!(f, "unnamed unix socket"),
+        }
+    }
+}
+
+/// Listening socket used by the IpcServer to accept the clients that send
+/// Arrow streams. Every accepted connection is read in its own thread
+pub trait IpcTransport: Send + Sync + 'static {
+    type Stream: Read + Write + Send + 'static;
+
+    /// Blocks until a new client connects to the transport
+    fn accept(&self) -> io::Result<(Self::Stream, PeerAddr)>;
+
+    /// Unblocks a thread waiting in `accept`, used when a server is shut
+    /// down. Transports that can't do it stop after the next connection
+    fn wake(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl IpcTransport for TcpListener {
+    type Stream = TcpStream;
+
+    fn accept(&self) -> io::Result<(Self::Stream, PeerAddr)> {
+        let (stream, addr) = TcpListener::accept(self)?;
+        Ok((stream, PeerAddr::Tcp(addr)))
+    }
+
+    // Connecting to the listener makes accept return
+    fn wake(&self) -> io::Result<()> {
+        wake_tcp(self)
+    }
+}
+
+#[cfg(unix)]
+impl IpcTransport for UnixListener {
+    type Stream = UnixStream;
+
+    fn accept(&self) -> io::Result<(Self::Stream, PeerAddr)> {
+        let (stream, addr) = UnixListener::accept(self)?;
+        let path = addr.as_pathname().map(PathBuf::from);
+        Ok((stream, PeerAddr::Unix(path)))
+    }
+
+    fn wake(&self) -> io::Result<()> {
+        let addr = self.local_addr()?;
+        match addr.as_pathname() {
+            Some(path) => UnixStream::connect(path).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Opens and closes a connection to a TCP listener. A listener bound to all
+/// the interfaces is reached through the loopback address
+pub(crate) fn wake_tcp(listener: &TcpListener) -> io::Result<()> {
+    let mut addr = listener.local_addr()?;
+    if addr.ip().is_unspecified() {
+        match addr {
+            SocketAddr::V4(_) => addr.set_ip(Ipv4Addr::LOCALHOST.into()),
+            SocketAddr::V6(_) => addr.set_ip(Ipv6Addr::LOCALHOST.into()),
+        }
+    }
+
+    TcpStream::connect(addr).map(|_| ())
+}
@@ -0,0 +1,161 @@
+use std::io::{self, Read, Write};
+
+use arrow::{
+    datatypes::Schema,
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use tungstenite::{Message, WebSocket};
+
+use super::compression::CompressionCodec;
+use super::encoder::StreamEncoder;
+
+/// Writer for the Arrow streaming format over a WebSocket. Every message of
+/// the stream is sent in its own binary frame, so a browser can pass the
+/// frames to Arrow JS as they arrive
+pub struct WebSocketStreamWriter<S: Read + Write> {
+    socket: WebSocket<S>,
+    encoder: StreamEncoder,
+    finished: bool,
+}
+
+impl<S: Read + Write> WebSocketStreamWriter<S> {
+    /// Creates the writer sending the schema message. The WebSocket
+    /// handshake has to be completed before creating the writer
+    pub fn try_new(socket: WebSocket<S>, schema: &Schema) -> Result<Self> {
+        Self::try_new_with_compression(socket, schema, None)
+    }
+
+    /// Creates a writer that compresses the buffers of every batch with the
+    /// selected codec
+    pub fn try_new_with_compression(
+        mut socket: WebSocket<S>,
+        schema: &Schema,
+        compression: Option<CompressionCodec>,
+    ) -> Result<Self> {
+        let encoder = StreamEncoder::try_new(compression)?;
+        send_frame(&mut socket, encoder.encode_schema(schema)?)?;
+
+        Ok(Self {
+            socket,
+            encoder,
+            finished: false,
+        })
+    }
+
+    /// Writes a RecordBatch, sending first the dictionary batches its
+    /// dictionary columns require
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if self.finished {
+            return Err(ArrowError::IoError(
+                "Cannot write record batch to stream writer as it is closed".to_string(),
+            ));
+        }
+
+        for message in self.encoder.encode_batch_messages(batch)? {
+            send_frame(&mut self.socket, message)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the end of stream marker and closes the WebSocket. Calling it
+    /// again once the stream is finished does nothing
+    pub fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+
+        send_frame(&mut self.socket, self.encoder.encode_end()?)?;
+        self.socket.close(None).map_err(websocket_error)?;
+
+        // The close frame is only sent once the socket is flushed
+        match self.socket.write_pending() {
+            Ok(()) | Err(tungstenite::Error::ConnectionClosed) => (),
+            Err(err) => return Err(websocket_error(err)),
+        }
+
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Returns the underlying WebSocket
+    pub fn into_inner(self) -> WebSocket<S> {
+        self.socket
+    }
+}
+
+/// Reads the payload of the binary frames of a WebSocket as a continuous
+/// stream of bytes. This way the frames sent by any Arrow writer can be
+/// read using an IpcStreamReader
+pub struct WebSocketReader<S: Read + Write> {
+    socket: WebSocket<S>,
+    frame: Vec<u8>,
+    position: usize,
+}
+
+impl<S: Read + Write> WebSocketReader<S> {
+    pub fn new(socket: WebSocket<S>) -> Self {
+        Self {
+            socket,
+            frame: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns the underlying WebSocket
+    pub fn into_inner(self) -> WebSocket<S> {
+        self.socket
+    }
+
+    // Reads frames until a binary frame is found. False is returned once
+    // the connection is closed
+    fn next_frame(&mut self) -> io::Result<bool> {
+        loop {
+            let message = match self.socket.read_message() {
+                Ok(message) => message,
+                Err(tungstenite::Error::ConnectionClosed) => return Ok(false),
+                Err(tungstenite::Error::Io(err)) => return Err(err),
+                Err(err) => return Err(io::Error::other(err)),
+            };
+
+            match message {
+                Message::Binary(frame) => {
+                    self.frame = frame;
+                    self.position = 0;
+                    return Ok(true);
+                }
+                Message::Close(_) => return Ok(false),
+                // Text and control frames aren't part of the Arrow stream
+                _ => continue,
+            }
+        }
+    }
+}
+
+impl<S: Read + Write> Read for WebSocketReader<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.frame.len() {
+            if !self.next_frame()? {
+                return Ok(0);
+            }
+        }
+
+        let available = &self.frame[self.position..];
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.position += len;
+
+        Ok(len)
+    }
+}
+
+fn send_frame<S: Read + Write>(socket: &mut WebSocket<S>, data: Vec<u8>) -> Result<()> {
+    socket
+        .write_message(Message::Binary(data))
+        .map_err(websocket_error)
+}
+
+fn websocket_error(err: tungstenite::Error) -> ArrowError {
+    ArrowError::IoError(format!("WebSocket error: {}", err))
+}
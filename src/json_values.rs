@@ -0,0 +1,331 @@
+// Converts the rows of a Table to and from serde_json objects, the shape
+// web frameworks and template engines work with. Every row is an object
+// with a key for every column, structs are nested objects, lists are
+// arrays and nulls are null. The dates and times are strings, so they
+// read the same in JSON and in the CSV files of `csv_records`
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        make_array, Array, ArrayData, ArrayRef, FixedSizeListArray, LargeListArray, ListArray,
+        NullArray, StructArray,
+    },
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use serde_json::{Map, Number, Value};
+
+use crate::bitmap;
+use crate::builders::{make_column, OffsetsBuilder};
+use crate::csv_records::default_parser;
+use crate::downcast::downcast_array;
+use crate::ScalarValue;
+
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+
+/// Converts every row of the batch to an object with the names of the
+/// columns as keys
+pub(crate) fn batch_to_values(batch: &RecordBatch) -> Result<Vec<Map<String, Value>>> {
+    let schema = batch.schema();
+    (0..batch.num_rows())
+        .map(|row| {
+            schema
+                .fields()
+                .iter()
+                .zip(batch.columns())
+                .map(|(field, column)| Ok((field.name().clone(), to_value(column, row)?)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Builds a batch with the schema from the objects. The keys that aren't
+/// columns are ignored and the missing ones are null
+pub(crate) fn values_to_batch(
+    schema: &Schema,
+    values: &[Map<String, Value>],
+) -> Result<RecordBatch> {
+    let rows = values.iter().map(Some).collect::<Vec<_>>();
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let values = fields_of(&rows, field.name());
+            check_nullable(&values, &vec![true; values.len()], field)?;
+            build_array(&values, field)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    RecordBatch::try_new(Arc::new(schema.clone()), columns)
+}
+
+// The value at `index`. The children of structs and lists aren't sliced
+// with their parent, so they are read at the index of the parent data
+fn to_value(array: &ArrayRef, index: usize) -> Result<Value> {
+    if array.is_null(index) {
+        return Ok(Value::Null);
+    }
+
+    match array.data_type() {
+        DataType::Null => Ok(Value::Null),
+        DataType::Struct(fields) => {
+            let array = downcast_array::<StructArray>(array.as_ref())
+                .map_err(|e| ArrowError::InvalidArgumentError(e.to_string()))?;
+            let child_index = array.offset() + index;
+            fields
+                .iter()
+                .zip(array.columns())
+                .map(|(field, child)| Ok((field.name().clone(), to_value(child, child_index)?)))
+                .collect::<Result<Map<_, _>>>()
+                .map(Value::Object)
+        }
+        DataType::List(_) => {
+            let array = downcast_array::<ListArray>(array.as_ref())
+                .map_err(|e| ArrowError::InvalidArgumentError(e.to_string()))?;
+            let start = array.value_offset(index) as usize;
+            let end = start + array.value_length(index) as usize;
+            to_values(&array.values(), start..end)
+        }
+        DataType::LargeList(_) => {
+            let array = downcast_array::<LargeListArray>(array.as_ref())
+                .map_err(|e| ArrowError::InvalidArgumentError(e.to_string()))?;
+            let start = array.value_offset(index) as usize;
+            let end = start + array.value_length(index) as usize;
+            to_values(&array.values(), start..end)
+        }
+        DataType::FixedSizeList(_, _) => {
+            let array = downcast_array::<FixedSizeListArray>(array.as_ref())
+                .map_err(|e| ArrowError::InvalidArgumentError(e.to_string()))?;
+            let start = array.value_offset(index) as usize;
+            let end = start + array.value_length() as usize;
+            to_values(&array.values(), start..end)
+        }
+        _ => {
+            let scalar = ScalarValue::try_from_array(array, index)
+                .map_err(ArrowError::InvalidArgumentError)?;
+            scalar_to_value(&scalar)
+        }
+    }
+}
+
+fn to_values(array: &ArrayRef, range: std::ops::Range<usize>) -> Result<Value> {
+    range
+        .map(|index| to_value(array, index))
+        .collect::<Result<Vec<_>>>()
+        .map(Value::Array)
+}
+
+fn scalar_to_value(scalar: &ScalarValue) -> Result<Value> {
+    let value = match scalar {
+        ScalarValue::Boolean(Some(value)) => Value::Bool(*value),
+        ScalarValue::Int8(Some(value)) => Value::from(*value),
+        ScalarValue::Int16(Some(value)) => Value::from(*value),
+        ScalarValue::Int32(Some(value)) => Value::from(*value),
+        ScalarValue::Int64(Some(value)) => Value::from(*value),
+        ScalarValue::UInt8(Some(value)) => Value::from(*value),
+        ScalarValue::UInt16(Some(value)) => Value::from(*value),
+        ScalarValue::UInt32(Some(value)) => Value::from(*value),
+        ScalarValue::UInt64(Some(value)) => Value::from(*value),
+        // JSON doesn't have NaN or infinity, they become null
+        ScalarValue::Float32(Some(value)) => {
+            Number::from_f64(f64::from(*value)).map_or(Value::Null, Value::Number)
+        }
+        ScalarValue::Float64(Some(value)) => {
+            Number::from_f64(*value).map_or(Value::Null, Value::Number)
+        }
+        ScalarValue::Utf8(Some(value)) | ScalarValue::LargeUtf8(Some(value)) => {
+            Value::String(value.clone())
+        }
+        ScalarValue::Date32(Some(days)) => {
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1)
+                .and_then(|epoch| {
+                    epoch.checked_add_signed(chrono::Duration::days(i64::from(*days)))
+                })
+                .ok_or_else(|| out_of_range(scalar))?;
+            Value::String(date.to_string())
+        }
+        ScalarValue::TimeMicrosecond(Some(value)) => {
+            Value::String(time_of_day(*value, 1_000_000).ok_or_else(|| out_of_range(scalar))?)
+        }
+        ScalarValue::TimeNanosecond(Some(value)) => {
+            Value::String(time_of_day(*value, 1_000_000_000).ok_or_else(|| out_of_range(scalar))?)
+        }
+        ScalarValue::Duration(Some(value), _) => Value::from(*value),
+        ScalarValue::List(Some(values), _) => Value::Array(
+            values
+                .iter()
+                .map(scalar_to_value)
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        _ => Value::Null,
+    };
+    Ok(value)
+}
+
+fn time_of_day(value: i64, per_second: i64) -> Option<String> {
+    let seconds = u32::try_from(value.div_euclid(per_second)).ok()?;
+    let nanos = value.rem_euclid(per_second) * (1_000_000_000 / per_second);
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos as u32)
+        .map(|time| time.format(TIME_FORMAT).to_string())
+}
+
+fn out_of_range(scalar: &ScalarValue) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!("{:?} is out of range", scalar))
+}
+
+// The values of the key in every object, None if the object is null or
+// the key is missing
+fn fields_of<'a>(rows: &[Option<&'a Map<String, Value>>], name: &str) -> Vec<Option<&'a Value>> {
+    rows.iter()
+        .map(|row| row.and_then(|row| row.get(name)))
+        .map(|value| value.filter(|value| !value.is_null()))
+        .collect()
+}
+
+// Nulls in a field that isn't nullable are an error, except below a null
+// struct, where the children have to be null too
+fn check_nullable(values: &[Option<&Value>], parents: &[bool], field: &Field) -> Result<()> {
+    let missing = values
+        .iter()
+        .zip(parents)
+        .any(|(value, parent)| *parent && value.is_none());
+    match !field.is_nullable() && missing {
+        true => Err(ArrowError::InvalidArgumentError(format!(
+            "The field {} isn't nullable, but a value is null or missing",
+            field.name()
+        ))),
+        false => Ok(()),
+    }
+}
+
+fn build_array(values: &[Option<&Value>], field: &Field) -> Result<ArrayRef> {
+    let validity = values.iter().map(Option::is_some).collect::<Vec<_>>();
+    let builder = match field.data_type() {
+        DataType::Null => return Ok(Arc::new(NullArray::new(values.len()))),
+        DataType::Struct(fields) => {
+            let objects = values
+                .iter()
+                .map(|value| match value {
+                    None => Ok(None),
+                    Some(Value::Object(object)) => Ok(Some(object)),
+                    Some(other) => Err(mismatch(other, field)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut builder = ArrayData::builder(field.data_type().clone()).len(values.len());
+            let parents = objects.iter().map(Option::is_some).collect::<Vec<_>>();
+            for child in fields {
+                let values = fields_of(&objects, child.name());
+                check_nullable(&values, &parents, child)?;
+                let array = build_array(&values, child)?;
+                builder = builder.add_child_data(array.data());
+            }
+            builder
+        }
+        DataType::List(item) => {
+            let mut offsets = OffsetsBuilder::<i32>::new(values.len());
+            let mut items = Vec::new();
+            for value in values {
+                match value {
+                    None => offsets.append_length(0)?,
+                    Some(Value::Array(list)) => {
+                        offsets.append_length(list.len())?;
+                        items.extend(list.iter().map(|item| Some(item).filter(|v| !v.is_null())));
+                    }
+                    Some(other) => return Err(mismatch(other, field)),
+                }
+            }
+            check_nullable(&items, &vec![true; items.len()], item)?;
+            let items = build_array(&items, item)?;
+            ArrayData::builder(field.data_type().clone())
+                .len(values.len())
+                .add_buffer(offsets.finish())
+                .add_child_data(items.data())
+        }
+        data_type => {
+            let mut column = make_column(data_type, values.len())?;
+            for value in values {
+                match value {
+                    None => column.append_null()?,
+                    Some(value) => column.append_scalar(&to_scalar(value, field)?)?,
+                }
+            }
+            return Ok(column.finish());
+        }
+    };
+
+    let builder = match validity.contains(&false) {
+        true => builder.null_bit_buffer(bitmap::from_bools(&validity)),
+        false => builder,
+    };
+    Ok(make_array(builder.build()))
+}
+
+fn to_scalar(value: &Value, field: &Field) -> Result<ScalarValue> {
+    macro_rules! integer {
+        ($variant:ident, $as_native:ident) => {
+            value
+                .$as_native()
+                .and_then(|number| TryFrom::try_from(number).ok())
+                .map(|number| ScalarValue::$variant(Some(number)))
+        };
+    }
+
+    let scalar = match field.data_type() {
+        DataType::Boolean => value
+            .as_bool()
+            .map(|value| ScalarValue::Boolean(Some(value))),
+        DataType::Int8 => integer!(Int8, as_i64),
+        DataType::Int16 => integer!(Int16, as_i64),
+        DataType::Int32 => integer!(Int32, as_i64),
+        DataType::Int64 => integer!(Int64, as_i64),
+        DataType::UInt8 => integer!(UInt8, as_u64),
+        DataType::UInt16 => integer!(UInt16, as_u64),
+        DataType::UInt32 => integer!(UInt32, as_u64),
+        DataType::UInt64 => integer!(UInt64, as_u64),
+        DataType::Float32 => value
+            .as_f64()
+            .map(|value| ScalarValue::Float32(Some(value as f32))),
+        DataType::Float64 => value
+            .as_f64()
+            .map(|value| ScalarValue::Float64(Some(value))),
+        DataType::Utf8 => value
+            .as_str()
+            .map(|value| ScalarValue::Utf8(Some(value.to_string()))),
+        DataType::LargeUtf8 => value
+            .as_str()
+            .map(|value| ScalarValue::LargeUtf8(Some(value.to_string()))),
+        DataType::Date32(_) => match value.as_str() {
+            Some(text) => Some(default_parser(field.data_type())?(text)?),
+            None => None,
+        },
+        DataType::Time64(unit) => value
+            .as_str()
+            .and_then(|text| NaiveTime::parse_from_str(text, TIME_FORMAT).ok())
+            .map(|time| {
+                let nanos = i64::from(time.num_seconds_from_midnight()) * 1_000_000_000
+                    + i64::from(time.nanosecond());
+                match unit {
+                    TimeUnit::Nanosecond => ScalarValue::TimeNanosecond(Some(nanos)),
+                    _ => ScalarValue::TimeMicrosecond(Some(nanos / 1_000)),
+                }
+            }),
+        DataType::Duration(unit) => value
+            .as_i64()
+            .map(|value| ScalarValue::Duration(Some(value), unit.clone())),
+        _ => None,
+    };
+    scalar.ok_or_else(|| mismatch(value, field))
+}
+
+fn mismatch(value: &Value, field: &Field) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Can't convert {} to {:?} for the field {}",
+        value,
+        field.data_type(),
+        field.name()
+    ))
+}
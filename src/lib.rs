@@ -5,5 +5,47 @@ mod guide {
     doc_comment::doctest!("../guide/src/arrays_primitive.md");
     doc_comment::doctest!("../guide/src/arrays_nested.md");
     doc_comment::doctest!("../guide/src/arrays_operations.md");
+    #[cfg(feature = "parquet")]
     doc_comment::doctest!("../guide/src/reading_parquet.md");
 }
+
+#[doc(hidden)]
+pub mod macros;
+
+pub mod bitmap;
+pub mod buffer;
+pub mod builders;
+mod chunked;
+pub mod compute;
+pub mod csv_records;
+#[cfg(feature = "parquet")]
+mod delta;
+pub mod downcast;
+mod feather;
+pub mod ffi;
+#[cfg(feature = "flight")]
+pub mod flight;
+pub mod ipc;
+mod json_values;
+pub mod pandas;
+#[cfg(feature = "parquet")]
+pub mod parquet_schema;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+#[cfg(feature = "python")]
+pub mod python;
+mod scalar;
+pub mod serde_to_arrow;
+mod table;
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use chunked::ChunkedColumn;
+pub use scalar::ScalarValue;
+pub use table::{ColumnIterator, Table};
+
+// Used by the exported macros, so the crates calling them don't need to
+// import the same version of arrow
+#[doc(hidden)]
+pub use arrow;
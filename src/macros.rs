@@ -0,0 +1,178 @@
+// Macros that build arrays and batches from literals. They are exported at
+// the root of the crate, and the arrow types are used through `$crate` so
+// the crates using them only need to import the macro. The functions of
+// this module are called by the macros and aren't meant to be used directly
+use arrow::{
+    array::{ArrayData, ArrayRef, ListArray, StructArray},
+    buffer::Buffer,
+    datatypes::{DataType, Field, ToByteSlice},
+    error::{ArrowError, Result},
+};
+
+use crate::bitmap;
+
+/// Builds a `RecordBatch` from a list of `name => type => [values]`
+/// columns. The type is the name of a `DataType` variant without
+/// parameters, and the values are anything the array of that type can be
+/// built from, so a column with nulls is written with `Some` and `None`.
+/// A field is nullable if its column has nulls. An error is returned if the
+/// columns don't have the same length
+#[macro_export]
+macro_rules! record_batch {
+    ($($name:expr => $data_type:ident => [$($value:expr),* $(,)?]),* $(,)?) => {{
+        let columns: Vec<$crate::arrow::array::ArrayRef> = vec![$(
+            std::sync::Arc::new(<$crate::__array_type!($data_type)>::from(vec![$($value),*]))
+        ),*];
+        let fields = vec![$(($name, $crate::arrow::datatypes::DataType::$data_type)),*]
+            .into_iter()
+            .zip(&columns)
+            .map(|((name, data_type), column)| {
+                $crate::arrow::datatypes::Field::new(name, data_type, column.null_count() > 0)
+            })
+            .collect::<Vec<_>>();
+        let schema = $crate::arrow::datatypes::Schema::new(fields);
+        $crate::arrow::record_batch::RecordBatch::try_new(std::sync::Arc::new(schema), columns)
+    }};
+}
+
+/// Builds a `ListArray` from a list of lists written as `[values]`, or
+/// `None` for a null list. The type is the `DataType` variant of the
+/// values, which can be written with `Some` and `None` if some of them are
+/// null. The item field is nullable if any of the values is null
+///
+/// `list_array!(Int32, [[1, 2], [3], [], None])`
+#[macro_export]
+macro_rules! list_array {
+    ($data_type:ident, [$($list:tt),* $(,)?]) => {{
+        let mut values = Vec::new();
+        let mut offsets = vec![0];
+        let mut validity = Vec::new();
+        $($crate::__list_item!(values, offsets, validity, $list);)*
+        $crate::macros::build_list(
+            std::sync::Arc::new(<$crate::__array_type!($data_type)>::from(values)),
+            offsets,
+            validity,
+        )
+    }};
+}
+
+/// Builds a `StructArray` from a list of `name => type => [values]` fields,
+/// written like the columns of `record_batch!`. A field is nullable if its
+/// values have nulls. An error is returned if the fields don't have the
+/// same length
+#[macro_export]
+macro_rules! struct_array {
+    ($($name:expr => $data_type:ident => [$($value:expr),* $(,)?]),* $(,)?) => {{
+        let fields: Vec<(&str, $crate::arrow::array::ArrayRef)> = vec![$((
+            $name,
+            std::sync::Arc::new(<$crate::__array_type!($data_type)>::from(vec![$($value),*])),
+        )),*];
+        $crate::macros::build_struct(fields)
+    }};
+}
+
+// Adds a list of list_array! to the values, offsets and validity
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __list_item {
+    ($values:ident, $offsets:ident, $validity:ident, None) => {
+        $offsets.push($values.len() as i32);
+        $validity.push(false);
+    };
+    ($values:ident, $offsets:ident, $validity:ident, [$($value:expr),* $(,)?]) => {
+        $($values.push($value);)*
+        $offsets.push($values.len() as i32);
+        $validity.push(true);
+    };
+}
+
+// Array type used for the values of a DataType variant
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __array_type {
+    (Boolean) => {
+        $crate::arrow::array::BooleanArray
+    };
+    (Int8) => {
+        $crate::arrow::array::Int8Array
+    };
+    (Int16) => {
+        $crate::arrow::array::Int16Array
+    };
+    (Int32) => {
+        $crate::arrow::array::Int32Array
+    };
+    (Int64) => {
+        $crate::arrow::array::Int64Array
+    };
+    (UInt8) => {
+        $crate::arrow::array::UInt8Array
+    };
+    (UInt16) => {
+        $crate::arrow::array::UInt16Array
+    };
+    (UInt32) => {
+        $crate::arrow::array::UInt32Array
+    };
+    (UInt64) => {
+        $crate::arrow::array::UInt64Array
+    };
+    (Float32) => {
+        $crate::arrow::array::Float32Array
+    };
+    (Float64) => {
+        $crate::arrow::array::Float64Array
+    };
+    (Utf8) => {
+        $crate::arrow::array::StringArray
+    };
+    (LargeUtf8) => {
+        $crate::arrow::array::LargeStringArray
+    };
+    (Binary) => {
+        $crate::arrow::array::BinaryArray
+    };
+    (LargeBinary) => {
+        $crate::arrow::array::LargeBinaryArray
+    };
+}
+
+#[doc(hidden)]
+pub fn build_list(values: ArrayRef, offsets: Vec<i32>, validity: Vec<bool>) -> ListArray {
+    let item = Field::new("item", values.data_type().clone(), values.null_count() > 0);
+    let mut builder = ArrayData::builder(DataType::List(Box::new(item)))
+        .len(validity.len())
+        .add_buffer(Buffer::from(offsets.to_byte_slice()))
+        .add_child_data(values.data());
+    // Lists without nulls don't need a validity bitmap
+    if validity.contains(&false) {
+        builder = builder.null_bit_buffer(bitmap::from_bools(&validity));
+    }
+    ListArray::from(builder.build())
+}
+
+#[doc(hidden)]
+pub fn build_struct(fields: Vec<(&str, ArrayRef)>) -> Result<StructArray> {
+    if let Some((_, first)) = fields.first() {
+        for (name, values) in &fields {
+            if values.len() != first.len() {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "The field {} has {} values but the first field has {}",
+                    name,
+                    values.len(),
+                    first.len()
+                )));
+            }
+        }
+    }
+
+    Ok(StructArray::from(
+        fields
+            .into_iter()
+            .map(|(name, values)| {
+                let field = Field::new(name, values.data_type().clone(), values.null_count() > 0);
+                (field, values)
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
@@ -0,0 +1,334 @@
+// The metadata pandas keeps with a DataFrame written through pyarrow. It's
+// a JSON document under the "pandas" key of the schema metadata that says
+// which columns are the index and the dtype of every column, because the
+// arrow types alone don't tell an int64 from a nullable Int64 or a
+// datetime64[ns] from a date. Files written with it open in pandas with
+// the same index and dtypes, and files written by pandas can be read with
+// their index
+use arrow::{
+    datatypes::{DataType, Schema},
+    error::{ArrowError, Result},
+};
+use serde_json::{json, Value};
+
+/// Key of the schema metadata where pandas stores its metadata
+pub const PANDAS_METADATA_KEY: &str = "pandas";
+
+// The oldest pandas with the nullable dtypes, like Int64 and boolean,
+// used for the columns that can have nulls
+const PANDAS_VERSION: &str = "1.0.0";
+
+/// How pandas rebuilds a column of the DataFrame, or of its index
+#[derive(Debug, Clone, PartialEq)]
+pub struct PandasColumn {
+    /// Name of the column in the DataFrame, None for an unnamed index
+    pub name: Option<String>,
+    /// Name of the field that stores the column
+    pub field_name: String,
+    /// Logical type of the column, like "int64", "unicode", "datetimetz"
+    /// or "categorical"
+    pub pandas_type: String,
+    /// dtype of the column, like "int64", "Int64" or "datetime64[ns]"
+    pub numpy_type: String,
+    /// Details of the type, like the time zone of a datetimetz
+    pub metadata: Option<Value>,
+}
+
+/// A level of the index of the DataFrame
+#[derive(Debug, Clone, PartialEq)]
+pub enum PandasIndex {
+    /// The index is stored in the field with this name
+    Column(String),
+    /// A RangeIndex, which isn't stored in the file
+    Range {
+        name: Option<String>,
+        start: i64,
+        stop: i64,
+        step: i64,
+    },
+}
+
+/// Metadata pandas uses to rebuild a DataFrame from a schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct PandasMetadata {
+    pub index_columns: Vec<PandasIndex>,
+    pub columns: Vec<PandasColumn>,
+    /// Version of pandas the metadata was written for, if it says
+    pub pandas_version: Option<String>,
+}
+
+impl PandasMetadata {
+    /// Describes the fields of the schema, with the fields in
+    /// `index_columns` as the index of the DataFrame. Without index columns
+    /// pandas creates a RangeIndex. The columns that can have nulls get the
+    /// nullable dtypes, like Int64 instead of int64, so the integers don't
+    /// become floats
+    pub fn try_new(schema: &Schema, index_columns: &[&str]) -> Result<Self> {
+        for name in index_columns {
+            schema.field_with_name(name).map_err(|_| {
+                ArrowError::InvalidArgumentError(format!(
+                    "The index column {} isn't in the schema",
+                    name
+                ))
+            })?;
+        }
+
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let index = index_columns.contains(&field.name().as_str());
+                // pandas doesn't support indexes with the nullable dtypes
+                let nullable = field.is_nullable() && !index;
+                let (pandas_type, numpy_type, metadata) = pandas_type(field.data_type(), nullable);
+                PandasColumn {
+                    name: Some(field.name().clone()),
+                    field_name: field.name().clone(),
+                    pandas_type,
+                    numpy_type,
+                    metadata,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            index_columns: index_columns
+                .iter()
+                .map(|name| PandasIndex::Column(name.to_string()))
+                .collect(),
+            columns,
+            pandas_version: Some(PANDAS_VERSION.to_string()),
+        })
+    }
+
+    /// Reads the metadata from the schema. It's None if the schema doesn't
+    /// have the pandas key
+    pub fn from_schema(schema: &Schema) -> Result<Option<Self>> {
+        schema
+            .metadata()
+            .get(PANDAS_METADATA_KEY)
+            .map(|json| Self::parse(json))
+            .transpose()
+    }
+
+    /// Parses the JSON document of the metadata
+    pub fn parse(json: &str) -> Result<Self> {
+        let document: Value =
+            serde_json::from_str(json).map_err(|e| pandas_error(e.to_string()))?;
+
+        let index_columns = array_field(&document, "index_columns")?
+            .iter()
+            .map(parse_index)
+            .collect::<Result<Vec<_>>>()?;
+        let columns = array_field(&document, "columns")?
+            .iter()
+            .map(parse_column)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            index_columns,
+            columns,
+            pandas_version: document
+                .get("pandas_version")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        })
+    }
+
+    /// Writes the metadata as the JSON document pandas reads
+    pub fn to_json(&self) -> String {
+        let index_columns = self
+            .index_columns
+            .iter()
+            .map(|index| match index {
+                PandasIndex::Column(name) => json!(name),
+                PandasIndex::Range {
+                    name,
+                    start,
+                    stop,
+                    step,
+                } => json!({
+                    "kind": "range",
+                    "name": name,
+                    "start": start,
+                    "stop": stop,
+                    "step": step,
+                }),
+            })
+            .collect::<Vec<_>>();
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                json!({
+                    "name": column.name,
+                    "field_name": column.field_name,
+                    "pandas_type": column.pandas_type,
+                    "numpy_type": column.numpy_type,
+                    "metadata": column.metadata,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "index_columns": index_columns,
+            // The names of the columns are strings
+            "column_indexes": [{
+                "name": null,
+                "field_name": null,
+                "pandas_type": "unicode",
+                "numpy_type": "object",
+                "metadata": {"encoding": "UTF-8"},
+            }],
+            "columns": columns,
+            "creator": {"library": "arrow_guide", "version": env!("CARGO_PKG_VERSION")},
+            "pandas_version": self.pandas_version,
+        })
+        .to_string()
+    }
+
+    /// Copy of the schema with the metadata under the pandas key
+    pub fn add_to_schema(&self, schema: &Schema) -> Schema {
+        let mut metadata = schema.metadata().clone();
+        metadata.insert(PANDAS_METADATA_KEY.to_string(), self.to_json());
+        Schema::new_with_metadata(schema.fields().clone(), metadata)
+    }
+
+    /// Names of the fields that store the index. A RangeIndex isn't stored
+    /// in a field
+    pub fn index_fields(&self) -> Vec<&str> {
+        self.index_columns
+            .iter()
+            .filter_map(|index| match index {
+                PandasIndex::Column(name) => Some(name.as_str()),
+                PandasIndex::Range { .. } => None,
+            })
+            .collect()
+    }
+
+    /// The column stored in the field
+    pub fn column(&self, field_name: &str) -> Option<&PandasColumn> {
+        self.columns
+            .iter()
+            .find(|column| column.field_name == field_name)
+    }
+}
+
+// The pandas type, the dtype and the type metadata of a field, as pyarrow
+// writes them. pandas stores the datetimes in nanoseconds whatever the unit
+fn pandas_type(data_type: &DataType, nullable: bool) -> (String, String, Option<Value>) {
+    let (pandas_type, numpy_type, metadata) = match data_type {
+        DataType::Null => ("empty", "object".to_string(), None),
+        DataType::Boolean => ("bool", nullable_dtype("bool", "boolean", nullable), None),
+        DataType::Int8 => ("int8", nullable_dtype("int8", "Int8", nullable), None),
+        DataType::Int16 => ("int16", nullable_dtype("int16", "Int16", nullable), None),
+        DataType::Int32 => ("int32", nullable_dtype("int32", "Int32", nullable), None),
+        DataType::Int64 => ("int64", nullable_dtype("int64", "Int64", nullable), None),
+        DataType::UInt8 => ("uint8", nullable_dtype("uint8", "UInt8", nullable), None),
+        DataType::UInt16 => ("uint16", nullable_dtype("uint16", "UInt16", nullable), None),
+        DataType::UInt32 => ("uint32", nullable_dtype("uint32", "UInt32", nullable), None),
+        DataType::UInt64 => ("uint64", nullable_dtype("uint64", "UInt64", nullable), None),
+        DataType::Float16 => ("float16", "float16".to_string(), None),
+        DataType::Float32 => ("float32", "float32".to_string(), None),
+        DataType::Float64 => ("float64", "float64".to_string(), None),
+        DataType::Utf8 | DataType::LargeUtf8 => ("unicode", "object".to_string(), None),
+        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
+            ("bytes", "object".to_string(), None)
+        }
+        DataType::Date32(_) | DataType::Date64(_) => ("date", "object".to_string(), None),
+        DataType::Time32(_) | DataType::Time64(_) => ("time", "object".to_string(), None),
+        DataType::Timestamp(_, None) => ("datetime", "datetime64[ns]".to_string(), None),
+        DataType::Timestamp(_, Some(timezone)) => (
+            "datetimetz",
+            format!("datetime64[ns, {}]", timezone),
+            Some(json!({ "timezone": timezone })),
+        ),
+        DataType::Duration(_) => ("timedelta", "timedelta64[ns]".to_string(), None),
+        DataType::Decimal(precision, scale) => (
+            "decimal",
+            "object".to_string(),
+            Some(json!({"precision": precision, "scale": scale})),
+        ),
+        // The dtype of a categorical is the one of its codes
+        DataType::Dictionary(key_type, _) => {
+            let (_, codes, _) = pandas_type(key_type, false);
+            let metadata = json!({"num_categories": null, "ordered": false});
+            return ("categorical".to_string(), codes, Some(metadata));
+        }
+        DataType::List(item) | DataType::LargeList(item) | DataType::FixedSizeList(item, _) => {
+            let (item_type, _, _) = pandas_type(item.data_type(), true);
+            return (format!("list[{}]", item_type), "object".to_string(), None);
+        }
+        _ => ("object", "object".to_string(), None),
+    };
+    (pandas_type.to_string(), numpy_type, metadata)
+}
+
+fn nullable_dtype(dtype: &str, nullable_dtype: &str, nullable: bool) -> String {
+    match nullable {
+        true => nullable_dtype.to_string(),
+        false => dtype.to_string(),
+    }
+}
+
+fn parse_index(index: &Value) -> Result<PandasIndex> {
+    match index {
+        Value::String(name) => Ok(PandasIndex::Column(name.clone())),
+        Value::Object(range) if range.get("kind").and_then(Value::as_str) == Some("range") => {
+            let integer = |name: &str| {
+                range.get(name).and_then(Value::as_i64).ok_or_else(|| {
+                    pandas_error(format!("The range index {} has no {}", index, name))
+                })
+            };
+            Ok(PandasIndex::Range {
+                name: range.get("name").and_then(name_of),
+                start: integer("start")?,
+                stop: integer("stop")?,
+                step: integer("step")?,
+            })
+        }
+        other => Err(pandas_error(format!("Unknown index {}", other))),
+    }
+}
+
+fn parse_column(column: &Value) -> Result<PandasColumn> {
+    let string = |name: &str| column.get(name).and_then(Value::as_str).map(str::to_string);
+    let name = column.get("name").and_then(name_of);
+    // Files written before pandas 0.23 don't have field names, the field is
+    // named after the column
+    let field_name = string("field_name")
+        .or_else(|| name.clone())
+        .ok_or_else(|| pandas_error(format!("The column {} has no field name", column)))?;
+
+    Ok(PandasColumn {
+        name,
+        field_name,
+        pandas_type: string("pandas_type").unwrap_or_default(),
+        numpy_type: string("numpy_type").unwrap_or_default(),
+        metadata: column
+            .get("metadata")
+            .filter(|value| !value.is_null())
+            .cloned(),
+    })
+}
+
+// The names of the columns can be numbers in pandas, they are kept as
+// text
+fn name_of(name: &Value) -> Option<String> {
+    match name {
+        Value::Null => None,
+        Value::String(name) => Some(name.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn array_field<'a>(document: &'a Value, name: &str) -> Result<&'a Vec<Value>> {
+    document
+        .get(name)
+        .and_then(Value::as_array)
+        .ok_or_else(|| pandas_error(format!("The metadata doesn't have {}", name)))
+}
+
+fn pandas_error(message: String) -> ArrowError {
+    ArrowError::ParseError(format!("pandas metadata: {}", message))
+}
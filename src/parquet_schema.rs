@@ -0,0 +1,220 @@
+// Explains how the schema of a parquet file becomes the schema of a Table.
+// Every leaf column of the file has a physical type, how the values are
+// stored, and can have a logical type, how they should be read. The arrow
+// type comes from both, unless the file stores the arrow schema it was
+// written with. Some combinations lose information on the way, and those
+// are the ones that make a Table look different from what was expected
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+
+use arrow::{
+    datatypes::{DataType, IntervalUnit, Schema},
+    error::{ArrowError, Result},
+};
+use parquet::{
+    arrow::{parquet_to_arrow_schema, schema::parquet_to_arrow_field, ARROW_SCHEMA_META_KEY},
+    basic::{LogicalType, Type as PhysicalType},
+    file::reader::{FileReader, SerializedFileReader},
+    schema::types::ColumnDescriptor,
+};
+
+/// How a leaf column of a parquet file is read
+#[derive(Debug)]
+pub struct ColumnReport {
+    /// Path of the column, with the names of its parents for nested columns
+    pub path: String,
+    pub physical_type: PhysicalType,
+    pub logical_type: LogicalType,
+    /// Arrow type of the values, None if the column can't be read
+    pub arrow_type: Option<DataType>,
+    /// Information lost in the conversion or other reasons why the arrow
+    /// type may not be the expected one
+    pub notes: Vec<String>,
+}
+
+/// Report of the conversion of every leaf column of a parquet file
+#[derive(Debug)]
+pub struct SchemaReport {
+    pub columns: Vec<ColumnReport>,
+    /// True if the file stores the arrow schema it was written with, which
+    /// has priority over the parquet types
+    pub has_arrow_schema: bool,
+    /// Why the file can't be read as a Table, if one of its columns can't
+    /// be converted
+    pub table_error: Option<String>,
+}
+
+/// Reads the schema of the parquet file and explains the conversion of its
+/// columns to arrow, see `SchemaReport`
+pub fn explain_schema<P: AsRef<Path>>(path: P) -> Result<SchemaReport> {
+    let file = File::open(path)?;
+    let reader =
+        SerializedFileReader::new(file).map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+    let file_metadata = reader.metadata().file_metadata();
+    let schema_descr = file_metadata.schema_descr();
+    let key_value_metadata = file_metadata.key_value_metadata();
+
+    let has_arrow_schema = key_value_metadata
+        .iter()
+        .flatten()
+        .any(|pair| pair.key == ARROW_SCHEMA_META_KEY);
+
+    // The schema a Table gets, which can fail as a whole because of a
+    // single column
+    let table_schema =
+        parquet_to_arrow_schema(schema_descr, key_value_metadata).map_err(|e| e.to_string());
+
+    let columns = (0..schema_descr.num_columns())
+        .map(|index| {
+            let top_level = schema_descr.get_column_root(index).is_primitive();
+            explain_column(&schema_descr.column(index), top_level, &table_schema)
+        })
+        .collect();
+
+    Ok(SchemaReport {
+        columns,
+        has_arrow_schema,
+        table_error: table_schema.err(),
+    })
+}
+
+fn explain_column(
+    column: &ColumnDescriptor,
+    top_level: bool,
+    table_schema: &std::result::Result<Schema, String>,
+) -> ColumnReport {
+    let physical_type = column.physical_type();
+    let logical_type = column.logical_type();
+    let mut notes = Vec::new();
+
+    let converted = parquet_to_arrow_field(column).map(|field| field.data_type().clone());
+    let mut arrow_type = match converted {
+        Ok(data_type) => Some(data_type),
+        Err(err) => {
+            notes.push(format!("Can't be read: {}", err));
+            None
+        }
+    };
+
+    // The type of a top level column is the one of the Table, which comes
+    // from the arrow schema if the file has it
+    if let (true, Ok(schema)) = (top_level, table_schema) {
+        if let Ok(field) = schema.field_with_name(column.name()) {
+            if arrow_type.as_ref() != Some(field.data_type()) {
+                notes.push(format!(
+                    "The stored arrow schema has {:?} instead",
+                    field.data_type()
+                ));
+            }
+            arrow_type = Some(field.data_type().clone());
+        }
+    }
+
+    notes.extend(lossy_notes(
+        physical_type,
+        logical_type,
+        arrow_type.as_ref(),
+    ));
+
+    ColumnReport {
+        path: column.path().string(),
+        physical_type,
+        logical_type,
+        arrow_type,
+        notes,
+    }
+}
+
+// Conversions that lose information or that other readers may do
+// differently
+fn lossy_notes(
+    physical_type: PhysicalType,
+    logical_type: LogicalType,
+    arrow_type: Option<&DataType>,
+) -> Vec<String> {
+    let mut notes = Vec::new();
+
+    if physical_type == PhysicalType::INT96 {
+        notes.push(
+            "INT96 is a legacy timestamp, read as nanoseconds. Dates outside of the years \
+             1677 to 2262 overflow"
+                .to_string(),
+        );
+    }
+
+    match logical_type {
+        LogicalType::UINT_8
+        | LogicalType::UINT_16
+        | LogicalType::UINT_32
+        | LogicalType::UINT_64 => notes.push(format!(
+            "Unsigned values are stored as signed {}, readers that ignore the logical \
+                 type see the large values as negative",
+            physical_type
+        )),
+        LogicalType::INTERVAL => {
+            if let Some(DataType::Interval(IntervalUnit::DayTime)) = arrow_type {
+                notes.push("Read as days and milliseconds, the months are lost".to_string())
+            }
+        }
+        _ => (),
+    }
+
+    if let Some(DataType::Timestamp(_, None)) = arrow_type {
+        notes.push("The time zone isn't stored, the values are read as UTC".to_string());
+    }
+
+    notes
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .columns
+            .iter()
+            .map(|column| {
+                [
+                    column.path.clone(),
+                    column.physical_type.to_string(),
+                    column.logical_type.to_string(),
+                    column
+                        .arrow_type
+                        .as_ref()
+                        .map_or("-".to_string(), |data_type| format!("{:?}", data_type)),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let header = ["column", "physical", "logical", "arrow"];
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let header = header.map(str::to_string);
+        for row in std::iter::once(&header).chain(&rows) {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        for column in &self.columns {
+            for note in &column.notes {
+                writeln!(f, "{}: {}", column.path, note)?;
+            }
+        }
+        if let Some(err) = &self.table_error {
+            writeln!(f, "The file can't be read as a Table: {}", err)?;
+        }
+        if self.has_arrow_schema {
+            writeln!(f, "The file stores the arrow schema it was written with")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,701 @@
+// Converts protobuf messages into RecordBatches using the descriptors of
+// their types, so any message generated by prost, or received as bytes by
+// a gRPC service, can be stored in arrow without writing its builders. The
+// schema follows the descriptor: scalar fields become primitive columns,
+// enums become the names of their values, nested messages become structs
+// and repeated fields become lists. A map field is a repeated message with
+// a key and a value, so it becomes a list of structs
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::{
+    array::{make_array, ArrayData, ArrayRef},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use prost::Message;
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, EnumDescriptorProto, FileDescriptorSet,
+};
+
+use crate::bitmap;
+use crate::builders::{make_column, MutableColumn, OffsetsBuilder};
+use crate::ScalarValue;
+
+/// Builds RecordBatches from encoded protobuf messages of one type. The
+/// messages are decoded with the descriptor of the type, found by its full
+/// name in the descriptors of the proto files, like the set written by
+/// `protoc --descriptor_set_out` or prost-build. A batch is returned every
+/// time the batch size is reached
+pub struct ProtobufBatchBuilder {
+    schema: SchemaRef,
+    layout: MessageLayout,
+    columns: Vec<Column>,
+    batch_size: usize,
+    len: usize,
+}
+
+impl ProtobufBatchBuilder {
+    /// Creates the builder for the message with the full name, like
+    /// "package.Message". Recursive messages can't be converted, their
+    /// schema would never end
+    pub fn try_new(
+        descriptors: &FileDescriptorSet,
+        message: &str,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let types = Types::new(descriptors);
+        let name = format!(".{}", message.trim_start_matches('.'));
+        let layout = types.layout(&name, &mut Vec::new())?;
+
+        let fields = layout
+            .fields
+            .iter()
+            .map(FieldLayout::to_field)
+            .collect::<Vec<_>>();
+        let batch_size = batch_size.max(1);
+        let columns = fields
+            .iter()
+            .map(|field| Column::new(field.data_type(), batch_size))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            schema: Arc::new(Schema::new(fields)),
+            layout,
+            columns,
+            batch_size,
+            len: 0,
+        })
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Number of messages appended since the last batch
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Decodes the message and appends it as a row. The batch is returned
+    /// once it has batch_size rows. A message that fails to decode doesn't
+    /// leave values in the columns
+    pub fn append(&mut self, bytes: &[u8]) -> Result<Option<RecordBatch>> {
+        let values = decode_message(&self.layout, bytes)?;
+        for ((column, field), values) in self
+            .columns
+            .iter_mut()
+            .zip(&self.layout.fields)
+            .zip(&values)
+        {
+            column.append_field(field, values)?;
+        }
+        self.len += 1;
+
+        if self.len >= self.batch_size {
+            self.finish()
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Encodes a message generated by prost and appends it, see `append`
+    pub fn append_message<M: Message>(&mut self, message: &M) -> Result<Option<RecordBatch>> {
+        let mut bytes = Vec::with_capacity(message.encoded_len());
+        message
+            .encode(&mut bytes)
+            .map_err(|err| ArrowError::InvalidArgumentError(err.to_string()))?;
+        self.append(&bytes)
+    }
+
+    /// Returns the rows appended since the last batch, or None if there
+    /// aren't any, and resets the columns
+    pub fn finish(&mut self) -> Result<Option<RecordBatch>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        let columns = self
+            .columns
+            .iter_mut()
+            .zip(self.schema.fields())
+            .map(|(column, field)| column.finish(field.data_type()))
+            .collect::<Result<Vec<_>>>()?;
+        self.len = 0;
+        RecordBatch::try_new(self.schema.clone(), columns).map(Some)
+    }
+
+    /// Converts every message of the iterator and returns the batches. The
+    /// last batch has the remaining rows
+    pub fn read<I, B>(mut self, messages: I) -> impl Iterator<Item = Result<RecordBatch>>
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        let mut messages = messages.into_iter();
+        let mut done = false;
+        std::iter::from_fn(move || {
+            while !done {
+                let batch = match messages.next() {
+                    Some(bytes) => self.append(bytes.as_ref()),
+                    None => {
+                        done = true;
+                        self.finish()
+                    }
+                };
+                match batch {
+                    Ok(None) => continue,
+                    Ok(Some(batch)) => return Some(Ok(batch)),
+                    Err(error) => {
+                        done = true;
+                        return Some(Err(error));
+                    }
+                }
+            }
+            None
+        })
+    }
+}
+
+// Field of a message as needed to decode it and build its column
+struct FieldLayout {
+    name: String,
+    number: u32,
+    kind: FieldKind,
+    repeated: bool,
+    // Fields with presence are null when they aren't in the message, the
+    // rest take the default value of their type
+    presence: bool,
+}
+
+enum FieldKind {
+    Scalar(Type),
+    // Names of the values of the enum by number
+    Enum(HashMap<i32, String>),
+    Message(MessageLayout),
+}
+
+struct MessageLayout {
+    fields: Vec<FieldLayout>,
+}
+
+impl FieldLayout {
+    fn to_field(&self) -> Field {
+        let data_type = match &self.kind {
+            FieldKind::Scalar(field_type) => scalar_type(*field_type),
+            FieldKind::Enum(_) => DataType::Utf8,
+            FieldKind::Message(layout) => {
+                DataType::Struct(layout.fields.iter().map(FieldLayout::to_field).collect())
+            }
+        };
+
+        // The items of a repeated field can't be null, but the lists built
+        // by the other builders have nullable items
+        match self.repeated {
+            true => Field::new(
+                &self.name,
+                DataType::List(Box::new(Field::new("item", data_type, true))),
+                false,
+            ),
+            false => Field::new(&self.name, data_type, self.presence),
+        }
+    }
+
+    // Value of a field without presence that isn't in the message
+    fn default_value(&self) -> Option<ScalarValue> {
+        if self.presence {
+            return None;
+        }
+
+        let value = match &self.kind {
+            FieldKind::Scalar(field_type) => match field_type {
+                Type::Double => ScalarValue::Float64(Some(0.0)),
+                Type::Float => ScalarValue::Float32(Some(0.0)),
+                Type::Int64 | Type::Sint64 | Type::Sfixed64 => ScalarValue::Int64(Some(0)),
+                Type::Uint64 | Type::Fixed64 => ScalarValue::UInt64(Some(0)),
+                Type::Int32 | Type::Sint32 | Type::Sfixed32 => ScalarValue::Int32(Some(0)),
+                Type::Uint32 | Type::Fixed32 => ScalarValue::UInt32(Some(0)),
+                Type::Bool => ScalarValue::Boolean(Some(false)),
+                Type::Bytes => ScalarValue::List(Some(Vec::new()), DataType::UInt8),
+                _ => ScalarValue::Utf8(Some(String::new())),
+            },
+            FieldKind::Enum(names) => ScalarValue::Utf8(Some(enum_name(names, 0))),
+            FieldKind::Message(_) => return None,
+        };
+        Some(value)
+    }
+}
+
+fn scalar_type(field_type: Type) -> DataType {
+    match field_type {
+        Type::Double => DataType::Float64,
+        Type::Float => DataType::Float32,
+        Type::Int64 | Type::Sint64 | Type::Sfixed64 => DataType::Int64,
+        Type::Uint64 | Type::Fixed64 => DataType::UInt64,
+        Type::Int32 | Type::Sint32 | Type::Sfixed32 => DataType::Int32,
+        Type::Uint32 | Type::Fixed32 => DataType::UInt32,
+        Type::Bool => DataType::Boolean,
+        // Bytes are a list of u8, as in serde_to_arrow
+        Type::Bytes => DataType::List(Box::new(Field::new("item", DataType::UInt8, true))),
+        _ => DataType::Utf8,
+    }
+}
+
+fn enum_name(names: &HashMap<i32, String>, number: i32) -> String {
+    names
+        .get(&number)
+        .cloned()
+        .unwrap_or_else(|| number.to_string())
+}
+
+// Messages and enums of the proto files by their full names, which start
+// with a dot as in the type names of the fields
+struct Types<'a> {
+    messages: HashMap<String, (&'a DescriptorProto, bool)>,
+    enums: HashMap<String, &'a EnumDescriptorProto>,
+}
+
+impl<'a> Types<'a> {
+    fn new(descriptors: &'a FileDescriptorSet) -> Self {
+        let mut types = Types {
+            messages: HashMap::new(),
+            enums: HashMap::new(),
+        };
+        for file in &descriptors.file {
+            let prefix = match file.package() {
+                "" => String::new(),
+                package => format!(".{}", package),
+            };
+            // Proto2 fields have presence, proto3 fields only if they are
+            // messages or in a oneof
+            let proto2 = matches!(file.syntax(), "" | "proto2");
+            for message in &file.message_type {
+                types.add_message(&prefix, message, proto2);
+            }
+            for enum_type in &file.enum_type {
+                types
+                    .enums
+                    .insert(format!("{}.{}", prefix, enum_type.name()), enum_type);
+            }
+        }
+        types
+    }
+
+    fn add_message(&mut self, prefix: &str, message: &'a DescriptorProto, proto2: bool) {
+        let name = format!("{}.{}", prefix, message.name());
+        for nested in &message.nested_type {
+            self.add_message(&name, nested, proto2);
+        }
+        for enum_type in &message.enum_type {
+            self.enums
+                .insert(format!("{}.{}", name, enum_type.name()), enum_type);
+        }
+        self.messages.insert(name, (message, proto2));
+    }
+
+    // The names of the messages being resolved are kept to find recursive
+    // messages
+    fn layout(&self, name: &str, parents: &mut Vec<String>) -> Result<MessageLayout> {
+        let (message, proto2) = self.messages.get(name).ok_or_else(|| {
+            protobuf_error(format!("The message {} isn't in the descriptors", name))
+        })?;
+        if parents.iter().any(|parent| parent == name) {
+            return Err(protobuf_error(format!(
+                "The message {} is recursive and can't be converted",
+                name
+            )));
+        }
+        parents.push(name.to_string());
+
+        let fields = message
+            .field
+            .iter()
+            .map(|field| {
+                let kind = match field.r#type() {
+                    Type::Message => FieldKind::Message(self.layout(field.type_name(), parents)?),
+                    Type::Enum => {
+                        let enum_type = self.enums.get(field.type_name()).ok_or_else(|| {
+                            protobuf_error(format!(
+                                "The enum {} isn't in the descriptors",
+                                field.type_name()
+                            ))
+                        })?;
+                        FieldKind::Enum(
+                            enum_type
+                                .value
+                                .iter()
+                                .map(|value| (value.number(), value.name().to_string()))
+                                .collect(),
+                        )
+                    }
+                    Type::Group => {
+                        return Err(protobuf_error(format!(
+                            "The field {} is a group, which isn't supported",
+                            field.name()
+                        )))
+                    }
+                    scalar => FieldKind::Scalar(scalar),
+                };
+
+                let presence =
+                    *proto2 || field.oneof_index.is_some() || matches!(kind, FieldKind::Message(_));
+                Ok(FieldLayout {
+                    name: field.name().to_string(),
+                    number: field.number() as u32,
+                    kind,
+                    repeated: field.label() == Label::Repeated,
+                    presence,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        parents.pop();
+        Ok(MessageLayout { fields })
+    }
+}
+
+// Value of a field read from the wire
+enum Decoded {
+    Scalar(ScalarValue),
+    // Values of every field of the message, in the order of the layout
+    Message(Vec<Vec<Decoded>>),
+}
+
+// Decodes the values of every field. A field can appear several times:
+// repeated fields keep all the values and the rest keep the last one
+fn decode_message(layout: &MessageLayout, mut bytes: &[u8]) -> Result<Vec<Vec<Decoded>>> {
+    let mut values = layout.fields.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+
+    while !bytes.is_empty() {
+        let key = read_varint(&mut bytes)?;
+        let (number, wire_type) = ((key >> 3) as u32, (key & 0x7) as u8);
+
+        let position = match layout
+            .fields
+            .iter()
+            .position(|field| field.number == number)
+        {
+            Some(position) => position,
+            None => {
+                // Fields that aren't in the descriptor are skipped
+                skip_value(&mut bytes, wire_type)?;
+                continue;
+            }
+        };
+        let field = &layout.fields[position];
+
+        match (&field.kind, wire_type) {
+            (FieldKind::Message(message), 2) => {
+                let data = read_bytes(&mut bytes)?;
+                values[position].push(Decoded::Message(decode_message(message, data)?));
+            }
+            // Packed repeated numbers come in a single length delimited value
+            (FieldKind::Enum(_), 2) | (FieldKind::Scalar(_), 2) if is_packable(field) => {
+                let mut data = read_bytes(&mut bytes)?;
+                let wire_type = match &field.kind {
+                    FieldKind::Scalar(field_type) => numeric_wire_type(*field_type),
+                    _ => 0,
+                };
+                while !data.is_empty() {
+                    let value = read_scalar(field, wire_type, &mut data)?;
+                    values[position].push(Decoded::Scalar(value));
+                }
+            }
+            _ => {
+                let value = read_scalar(field, wire_type, &mut bytes)?;
+                values[position].push(Decoded::Scalar(value));
+            }
+        }
+    }
+
+    for (field, values) in layout.fields.iter().zip(values.iter_mut()) {
+        if !field.repeated && values.len() > 1 {
+            values.drain(..values.len() - 1);
+        }
+    }
+    Ok(values)
+}
+
+fn is_packable(field: &FieldLayout) -> bool {
+    match &field.kind {
+        FieldKind::Scalar(Type::String) | FieldKind::Scalar(Type::Bytes) => false,
+        FieldKind::Scalar(_) | FieldKind::Enum(_) => field.repeated,
+        FieldKind::Message(_) => false,
+    }
+}
+
+fn numeric_wire_type(field_type: Type) -> u8 {
+    match field_type {
+        Type::Double | Type::Fixed64 | Type::Sfixed64 => 1,
+        Type::Float | Type::Fixed32 | Type::Sfixed32 => 5,
+        _ => 0,
+    }
+}
+
+fn read_scalar(field: &FieldLayout, wire_type: u8, bytes: &mut &[u8]) -> Result<ScalarValue> {
+    let field_type = match &field.kind {
+        FieldKind::Scalar(field_type) => *field_type,
+        FieldKind::Enum(names) => {
+            check_wire_type(field, wire_type, 0)?;
+            let number = read_varint(bytes)? as i32;
+            return Ok(ScalarValue::Utf8(Some(enum_name(names, number))));
+        }
+        FieldKind::Message(_) => return Err(wrong_wire_type(field, wire_type)),
+    };
+
+    let value = match field_type {
+        Type::String | Type::Bytes => {
+            check_wire_type(field, wire_type, 2)?;
+            let data = read_bytes(bytes)?;
+            match field_type {
+                Type::String => {
+                    ScalarValue::Utf8(Some(String::from_utf8(data.to_vec()).map_err(|_| {
+                        protobuf_error(format!("The field {} isn't UTF-8", field.name))
+                    })?))
+                }
+                _ => ScalarValue::List(
+                    Some(
+                        data.iter()
+                            .map(|&byte| ScalarValue::UInt8(Some(byte)))
+                            .collect(),
+                    ),
+                    DataType::UInt8,
+                ),
+            }
+        }
+        Type::Double => {
+            check_wire_type(field, wire_type, 1)?;
+            ScalarValue::Float64(Some(f64::from_bits(read_fixed64(bytes)?)))
+        }
+        Type::Fixed64 => {
+            check_wire_type(field, wire_type, 1)?;
+            ScalarValue::UInt64(Some(read_fixed64(bytes)?))
+        }
+        Type::Sfixed64 => {
+            check_wire_type(field, wire_type, 1)?;
+            ScalarValue::Int64(Some(read_fixed64(bytes)? as i64))
+        }
+        Type::Float => {
+            check_wire_type(field, wire_type, 5)?;
+            ScalarValue::Float32(Some(f32::from_bits(read_fixed32(bytes)?)))
+        }
+        Type::Fixed32 => {
+            check_wire_type(field, wire_type, 5)?;
+            ScalarValue::UInt32(Some(read_fixed32(bytes)?))
+        }
+        Type::Sfixed32 => {
+            check_wire_type(field, wire_type, 5)?;
+            ScalarValue::Int32(Some(read_fixed32(bytes)? as i32))
+        }
+        varint_type => {
+            check_wire_type(field, wire_type, 0)?;
+            let value = read_varint(bytes)?;
+            match varint_type {
+                Type::Int64 => ScalarValue::Int64(Some(value as i64)),
+                Type::Uint64 => ScalarValue::UInt64(Some(value)),
+                Type::Int32 => ScalarValue::Int32(Some(value as i32)),
+                Type::Uint32 => ScalarValue::UInt32(Some(value as u32)),
+                Type::Bool => ScalarValue::Boolean(Some(value != 0)),
+                // Zigzag encoding of the signed types
+                Type::Sint64 => {
+                    ScalarValue::Int64(Some((value >> 1) as i64 ^ -((value & 1) as i64)))
+                }
+                Type::Sint32 => {
+                    ScalarValue::Int32(Some((value >> 1) as i32 ^ -((value & 1) as i32)))
+                }
+                _ => return Err(wrong_wire_type(field, wire_type)),
+            }
+        }
+    };
+    Ok(value)
+}
+
+fn check_wire_type(field: &FieldLayout, wire_type: u8, expected: u8) -> Result<()> {
+    match wire_type == expected {
+        true => Ok(()),
+        false => Err(wrong_wire_type(field, wire_type)),
+    }
+}
+
+fn wrong_wire_type(field: &FieldLayout, wire_type: u8) -> ArrowError {
+    protobuf_error(format!(
+        "The field {} can't have wire type {}",
+        field.name, wire_type
+    ))
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes
+            .split_first()
+            .ok_or_else(|| protobuf_error("The message ends inside a varint".to_string()))?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(protobuf_error(
+        "A varint is longer than 10 bytes".to_string(),
+    ))
+}
+
+fn read_fixed64(bytes: &mut &[u8]) -> Result<u64> {
+    let mut value = [0; 8];
+    value.copy_from_slice(take(bytes, 8)?);
+    Ok(u64::from_le_bytes(value))
+}
+
+fn read_fixed32(bytes: &mut &[u8]) -> Result<u32> {
+    let mut value = [0; 4];
+    value.copy_from_slice(take(bytes, 4)?);
+    Ok(u32::from_le_bytes(value))
+}
+
+fn read_bytes<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let len = read_varint(bytes)? as usize;
+    take(bytes, len)
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(protobuf_error("The message is truncated".to_string()));
+    }
+    let (value, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(value)
+}
+
+fn skip_value(bytes: &mut &[u8], wire_type: u8) -> Result<()> {
+    match wire_type {
+        0 => read_varint(bytes).map(|_| ()),
+        1 => take(bytes, 8).map(|_| ()),
+        2 => read_bytes(bytes).map(|_| ()),
+        5 => take(bytes, 4).map(|_| ()),
+        other => Err(protobuf_error(format!(
+            "The wire type {} isn't supported",
+            other
+        ))),
+    }
+}
+
+// Builder of the column of a field. Structs and lists can't be appended as
+// ScalarValues, so they keep the columns of their children
+enum Column {
+    Scalar(Box<dyn MutableColumn>),
+    List(OffsetsBuilder<i32>, Box<Column>),
+    Struct(Vec<bool>, Vec<Column>),
+}
+
+impl Column {
+    fn new(data_type: &DataType, capacity: usize) -> Result<Self> {
+        Ok(match data_type {
+            // Bytes are appended as a ScalarValue list
+            DataType::List(item) if item.data_type() == &DataType::UInt8 => {
+                Column::Scalar(make_column(data_type, capacity)?)
+            }
+            DataType::List(item) => Column::List(
+                OffsetsBuilder::new(capacity),
+                Box::new(Column::new(item.data_type(), capacity)?),
+            ),
+            DataType::Struct(fields) => Column::Struct(
+                Vec::with_capacity(capacity),
+                fields
+                    .iter()
+                    .map(|field| Column::new(field.data_type(), capacity))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            data_type => Column::Scalar(make_column(data_type, capacity)?),
+        })
+    }
+
+    fn append_field(&mut self, field: &FieldLayout, values: &[Decoded]) -> Result<()> {
+        match (self, field.repeated) {
+            (Column::List(offsets, items), true) => {
+                offsets.append_length(values.len())?;
+                values
+                    .iter()
+                    .try_for_each(|value| items.append_value(field, Some(value)))
+            }
+            (column, _) => column.append_value(field, values.last()),
+        }
+    }
+
+    fn append_value(&mut self, field: &FieldLayout, value: Option<&Decoded>) -> Result<()> {
+        match (self, value) {
+            (Column::Scalar(column), Some(Decoded::Scalar(scalar))) => column.append_scalar(scalar),
+            (Column::Scalar(column), None) => match field.default_value() {
+                Some(scalar) => column.append_scalar(&scalar),
+                None => column.append_null(),
+            },
+            (Column::Struct(validity, children), value) => {
+                let layout = match &field.kind {
+                    FieldKind::Message(layout) => layout,
+                    _ => return Err(protobuf_error(format!("{} isn't a message", field.name))),
+                };
+                // A missing message still appends to its children, their
+                // values are hidden by the validity
+                let values = match value {
+                    Some(Decoded::Message(values)) => Some(values),
+                    _ => None,
+                };
+                validity.push(values.is_some());
+                for (index, (child, child_field)) in
+                    children.iter_mut().zip(&layout.fields).enumerate()
+                {
+                    let child_values = values.map_or(&[][..], |values| &values[index]);
+                    child.append_field(child_field, child_values)?;
+                }
+                Ok(())
+            }
+            _ => Err(protobuf_error(format!(
+                "The value of {} doesn't match its column",
+                field.name
+            ))),
+        }
+    }
+
+    fn finish(&mut self, data_type: &DataType) -> Result<ArrayRef> {
+        let data = match (self, data_type) {
+            (Column::Scalar(column), _) => return Ok(column.finish()),
+            (Column::List(offsets, items), DataType::List(item)) => {
+                let len = offsets.len();
+                let items = items.finish(item.data_type())?;
+                ArrayData::builder(data_type.clone())
+                    .len(len)
+                    .add_buffer(offsets.finish())
+                    .add_child_data(items.data())
+                    .build()
+            }
+            (Column::Struct(validity, children), DataType::Struct(fields)) => {
+                let mut builder = ArrayData::builder(data_type.clone()).len(validity.len());
+                for (child, field) in children.iter_mut().zip(fields) {
+                    builder = builder.add_child_data(child.finish(field.data_type())?.data());
+                }
+                if validity.contains(&false) {
+                    builder = builder.null_bit_buffer(bitmap::from_bools(validity));
+                }
+                validity.clear();
+                builder.build()
+            }
+            _ => {
+                return Err(protobuf_error(format!(
+                    "The column doesn't match the type {:?}",
+                    data_type
+                )))
+            }
+        };
+        Ok(make_array(data))
+    }
+}
+
+fn protobuf_error(message: String) -> ArrowError {
+    ArrowError::ParseError(format!("Protobuf: {}", message))
+}
@@ -0,0 +1,158 @@
+// Python bindings for the Table, built with the python feature. The data
+// is handed to pyarrow through the C stream interface, so pyarrow reads the
+// same buffers instead of a copy. An extension module built with maturin
+// is used from a notebook like this:
+//
+//     import arrow_guide
+//     table = arrow_guide.Table.read_parquet("data.parquet")
+//     table["name"]          # pyarrow.ChunkedArray
+//     table.to_pyarrow()     # pyarrow.Table
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use pyo3::{
+    exceptions::{PyIOError, PyIndexError, PyKeyError, PyValueError},
+    prelude::*,
+};
+
+use crate::ffi::{export_stream, FFI_ArrowArrayStream};
+use crate::Table;
+
+/// Table that can be used from Python. The columns are returned as pyarrow
+/// objects, which need pyarrow to be installed
+#[pyclass(name = "Table")]
+pub struct PyTable {
+    table: Table,
+}
+
+impl PyTable {
+    /// The wrapped table
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+}
+
+impl From<Table> for PyTable {
+    fn from(table: Table) -> Self {
+        Self { table }
+    }
+}
+
+#[pymethods]
+impl PyTable {
+    /// Reads the parquet file in batches of chunk_size rows
+    #[staticmethod]
+    #[pyo3(signature = (path, chunk_size = 2048))]
+    fn read_parquet(path: PathBuf, chunk_size: usize) -> PyResult<Self> {
+        // The reader panics if the file can't be opened, so the error is
+        // raised here as an OSError
+        File::open(&path).map_err(|error| PyIOError::new_err(error.to_string()))?;
+        Ok(Table::read_parquet(path, chunk_size).into())
+    }
+
+    /// Number of rows of the table
+    #[getter]
+    fn num_rows(&self) -> usize {
+        self.table.rows()
+    }
+
+    /// Names of the columns, in order
+    #[getter]
+    fn column_names(&self) -> Vec<String> {
+        let fields = self.table.schema().fields();
+        fields.iter().map(|field| field.name().clone()).collect()
+    }
+
+    /// Converts the table to a pyarrow.Table without copying the buffers
+    fn to_pyarrow(&self, py: Python) -> PyResult<PyObject> {
+        let schema = Arc::new(self.table.schema().clone());
+        let batches = self.table.data().clone();
+        to_pyarrow_table(py, schema, batches)
+    }
+
+    /// Returns the column with the index or the name as a
+    /// pyarrow.ChunkedArray
+    fn __getitem__(&self, py: Python, key: &PyAny) -> PyResult<PyObject> {
+        let fields = self.table.schema().fields();
+        let column = match key.extract::<isize>() {
+            Ok(index) => {
+                // Negative indices count from the end, as in Python
+                let position = match index < 0 {
+                    true => index + fields.len() as isize,
+                    false => index,
+                };
+                if position < 0 || position as usize >= fields.len() {
+                    return Err(PyIndexError::new_err(format!(
+                        "The table doesn't have column {}",
+                        index
+                    )));
+                }
+                position as usize
+            }
+            Err(_) => {
+                let name = key.extract::<&str>()?;
+                self.table
+                    .schema()
+                    .index_of(name)
+                    .map_err(|_| PyKeyError::new_err(name.to_string()))?
+            }
+        };
+
+        let schema = Arc::new(Schema::new(vec![fields[column].clone()]));
+        let batches = self
+            .table
+            .data()
+            .iter()
+            .map(|batch| RecordBatch::try_new(schema.clone(), vec![batch.column(column).clone()]))
+            .collect::<Result<Vec<_>>>()
+            .map_err(py_error)?;
+        let table = to_pyarrow_table(py, schema, batches)?;
+        table.call_method1(py, "column", (0,))
+    }
+
+    fn __len__(&self) -> usize {
+        self.table.rows()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Table({} rows, columns {:?})",
+            self.table.rows(),
+            self.column_names()
+        )
+    }
+}
+
+// The batches are exported as a stream that pyarrow moves to its own
+// struct, so the struct here is already released when it's dropped
+fn to_pyarrow_table(
+    py: Python,
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+) -> PyResult<PyObject> {
+    let stream = Box::new(export_stream(schema, batches.into_iter().map(Ok)).map_err(py_error)?);
+    let address = &*stream as *const FFI_ArrowArrayStream as usize;
+
+    let pyarrow = py.import("pyarrow")?;
+    let reader = pyarrow
+        .getattr("RecordBatchReader")?
+        .call_method1("_import_from_c", (address,))?;
+    Ok(reader.call_method0("read_all")?.into())
+}
+
+fn py_error(error: ArrowError) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// Module of the extension, which makes the Table class available as
+/// `arrow_guide.Table`
+#[pymodule]
+fn arrow_guide(_py: Python, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyTable>()
+}
@@ -0,0 +1,260 @@
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Date32Array, DurationMicrosecondArray,
+        DurationMillisecondArray, DurationNanosecondArray, DurationSecondArray, Float32Array,
+        Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, LargeStringArray, ListArray,
+        StringArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    },
+    datatypes::{DataType, DateUnit, Field, TimeUnit},
+};
+
+use std::hash::{Hash, Hasher};
+
+use crate::downcast::downcast_array;
+
+/// Taken from DataFusion
+/// Represents a dynamically typed, nullable single value.
+/// This is the single-valued counter-part of arrow’s `Array`.
+#[derive(Debug, Clone)]
+pub enum ScalarValue {
+    Boolean(Option<bool>),
+    Float32(Option<f32>),
+    Float64(Option<f64>),
+    Int8(Option<i8>),
+    Int16(Option<i16>),
+    Int32(Option<i32>),
+    Int64(Option<i64>),
+    UInt8(Option<u8>),
+    UInt16(Option<u16>),
+    UInt32(Option<u32>),
+    UInt64(Option<u64>),
+    Utf8(Option<String>),
+    LargeUtf8(Option<String>),
+    List(Option<Vec<ScalarValue>>, DataType),
+    Date32(Option<i32>),
+    TimeMicrosecond(Option<i64>),
+    TimeNanosecond(Option<i64>),
+    Duration(Option<i64>, TimeUnit),
+}
+
+// Float values don't implement Eq and Hash. To be able to store a
+// ScalarValue in a HashSet the floats are compared and hashed using their
+// bits, with a single NaN and -0.0 folded into 0.0 so both traits agree.
+// The row hashes of the compute kernels use the same bits
+pub(crate) fn f32_bits(value: f32) -> u32 {
+    if value.is_nan() {
+        f32::NAN.to_bits()
+    } else if value == 0.0 {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
+pub(crate) fn f64_bits(value: f64) -> u64 {
+    if value.is_nan() {
+        f64::NAN.to_bits()
+    } else if value == 0.0 {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
+impl PartialEq for ScalarValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ScalarValue::Boolean(a), ScalarValue::Boolean(b)) => a == b,
+            (ScalarValue::Float32(a), ScalarValue::Float32(b)) => {
+                a.map(f32_bits) == b.map(f32_bits)
+            }
+            (ScalarValue::Float64(a), ScalarValue::Float64(b)) => {
+                a.map(f64_bits) == b.map(f64_bits)
+            }
+            (ScalarValue::Int8(a), ScalarValue::Int8(b)) => a == b,
+            (ScalarValue::Int16(a), ScalarValue::Int16(b)) => a == b,
+            (ScalarValue::Int32(a), ScalarValue::Int32(b)) => a == b,
+            (ScalarValue::Int64(a), ScalarValue::Int64(b)) => a == b,
+            (ScalarValue::UInt8(a), ScalarValue::UInt8(b)) => a == b,
+            (ScalarValue::UInt16(a), ScalarValue::UInt16(b)) => a == b,
+            (ScalarValue::UInt32(a), ScalarValue::UInt32(b)) => a == b,
+            (ScalarValue::UInt64(a), ScalarValue::UInt64(b)) => a == b,
+            (ScalarValue::Utf8(a), ScalarValue::Utf8(b)) => a == b,
+            (ScalarValue::LargeUtf8(a), ScalarValue::LargeUtf8(b)) => a == b,
+            (ScalarValue::List(a, a_type), ScalarValue::List(b, b_type)) => {
+                a == b && a_type == b_type
+            }
+            (ScalarValue::Date32(a), ScalarValue::Date32(b)) => a == b,
+            (ScalarValue::TimeMicrosecond(a), ScalarValue::TimeMicrosecond(b)) => a == b,
+            (ScalarValue::TimeNanosecond(a), ScalarValue::TimeNanosecond(b)) => a == b,
+            (ScalarValue::Duration(a, a_unit), ScalarValue::Duration(b, b_unit)) => {
+                a == b && a_unit == b_unit
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ScalarValue {}
+
+impl Hash for ScalarValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ScalarValue::Boolean(v) => v.hash(state),
+            ScalarValue::Float32(v) => v.map(f32_bits).hash(state),
+            ScalarValue::Float64(v) => v.map(f64_bits).hash(state),
+            ScalarValue::Int8(v) => v.hash(state),
+            ScalarValue::Int16(v) => v.hash(state),
+            ScalarValue::Int32(v) => v.hash(state),
+            ScalarValue::Int64(v) => v.hash(state),
+            ScalarValue::UInt8(v) => v.hash(state),
+            ScalarValue::UInt16(v) => v.hash(state),
+            ScalarValue::UInt32(v) => v.hash(state),
+            ScalarValue::UInt64(v) => v.hash(state),
+            ScalarValue::Utf8(v) => v.hash(state),
+            ScalarValue::LargeUtf8(v) => v.hash(state),
+            ScalarValue::List(v, data_type) => {
+                v.hash(state);
+                data_type.hash(state);
+            }
+            ScalarValue::Date32(v) => v.hash(state),
+            ScalarValue::TimeMicrosecond(v) => v.hash(state),
+            ScalarValue::TimeNanosecond(v) => v.hash(state),
+            ScalarValue::Duration(v, unit) => {
+                v.hash(state);
+                unit.hash(state);
+            }
+        }
+    }
+}
+
+// Macro used to extract data from an specific array
+macro_rules! typed_cast {
+    ($array:expr, $index:expr, $ARRAYTYPE:ident, $SCALAR:ident) => {{
+        let array =
+            downcast_array::<$ARRAYTYPE>($array.as_ref()).map_err(|error| error.to_string())?;
+        ScalarValue::$SCALAR(match array.is_null($index) {
+            true => None,
+            false => Some(array.value($index).into()),
+        })
+    }};
+}
+
+// Durations also keep the unit of the array
+macro_rules! typed_duration {
+    ($array:expr, $index:expr, $ARRAYTYPE:ident, $UNIT:ident) => {{
+        let array =
+            downcast_array::<$ARRAYTYPE>($array.as_ref()).map_err(|error| error.to_string())?;
+        let value = match array.is_null($index) {
+            true => None,
+            false => Some(array.value($index)),
+        };
+        ScalarValue::Duration(value, TimeUnit::$UNIT)
+    }};
+}
+
+impl ScalarValue {
+    /// Converts a value in `array` at `index` into a ScalarValue
+    pub fn try_from_array(array: &ArrayRef, index: usize) -> Result<Self, String> {
+        Ok(match array.data_type() {
+            DataType::Boolean => typed_cast!(array, index, BooleanArray, Boolean),
+            DataType::Float64 => typed_cast!(array, index, Float64Array, Float64),
+            DataType::Float32 => typed_cast!(array, index, Float32Array, Float32),
+            DataType::UInt64 => typed_cast!(array, index, UInt64Array, UInt64),
+            DataType::UInt32 => typed_cast!(array, index, UInt32Array, UInt32),
+            DataType::UInt16 => typed_cast!(array, index, UInt16Array, UInt16),
+            DataType::UInt8 => typed_cast!(array, index, UInt8Array, UInt8),
+            DataType::Int64 => typed_cast!(array, index, Int64Array, Int64),
+            DataType::Int32 => typed_cast!(array, index, Int32Array, Int32),
+            DataType::Int16 => typed_cast!(array, index, Int16Array, Int16),
+            DataType::Int8 => typed_cast!(array, index, Int8Array, Int8),
+            DataType::Utf8 => typed_cast!(array, index, StringArray, Utf8),
+            DataType::LargeUtf8 => typed_cast!(array, index, LargeStringArray, LargeUtf8),
+            DataType::List(nested_type) => {
+                let list_array = downcast_array::<ListArray>(array.as_ref())
+                    .map_err(|error| error.to_string())?;
+                let value = match list_array.is_null(index) {
+                    true => None,
+                    false => {
+                        let nested_array = list_array.value(index);
+                        let scalar_vec = (0..nested_array.len())
+                            .map(|i| ScalarValue::try_from_array(&nested_array, i))
+                            .collect::<Result<Vec<ScalarValue>, String>>()?;
+                        Some(scalar_vec)
+                    }
+                };
+                ScalarValue::List(value, nested_type.data_type().clone())
+            }
+            DataType::Date32(DateUnit::Day) => {
+                typed_cast!(array, index, Date32Array, Date32)
+            }
+            DataType::Duration(TimeUnit::Second) => {
+                typed_duration!(array, index, DurationSecondArray, Second)
+            }
+            DataType::Duration(TimeUnit::Millisecond) => {
+                typed_duration!(array, index, DurationMillisecondArray, Millisecond)
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                typed_duration!(array, index, DurationMicrosecondArray, Microsecond)
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                typed_duration!(array, index, DurationNanosecondArray, Nanosecond)
+            }
+            other => {
+                return Err(format!("Downcast not available for type: {}", other));
+            }
+        })
+    }
+
+    /// DataType of the array the value belongs to
+    pub fn get_datatype(&self) -> DataType {
+        match self {
+            ScalarValue::Boolean(_) => DataType::Boolean,
+            ScalarValue::Float32(_) => DataType::Float32,
+            ScalarValue::Float64(_) => DataType::Float64,
+            ScalarValue::Int8(_) => DataType::Int8,
+            ScalarValue::Int16(_) => DataType::Int16,
+            ScalarValue::Int32(_) => DataType::Int32,
+            ScalarValue::Int64(_) => DataType::Int64,
+            ScalarValue::UInt8(_) => DataType::UInt8,
+            ScalarValue::UInt16(_) => DataType::UInt16,
+            ScalarValue::UInt32(_) => DataType::UInt32,
+            ScalarValue::UInt64(_) => DataType::UInt64,
+            ScalarValue::Utf8(_) => DataType::Utf8,
+            ScalarValue::LargeUtf8(_) => DataType::LargeUtf8,
+            ScalarValue::List(_, data_type) => {
+                DataType::List(Box::new(Field::new("item", data_type.clone(), true)))
+            }
+            ScalarValue::Date32(_) => DataType::Date32(DateUnit::Day),
+            ScalarValue::TimeMicrosecond(_) => DataType::Time64(TimeUnit::Microsecond),
+            ScalarValue::TimeNanosecond(_) => DataType::Time64(TimeUnit::Nanosecond),
+            ScalarValue::Duration(_, unit) => DataType::Duration(unit.clone()),
+        }
+    }
+
+    /// Returns true if the ScalarValue doesn't hold a value
+    pub fn is_null(&self) -> bool {
+        matches!(
+            self,
+            ScalarValue::Boolean(None)
+                | ScalarValue::Float32(None)
+                | ScalarValue::Float64(None)
+                | ScalarValue::Int8(None)
+                | ScalarValue::Int16(None)
+                | ScalarValue::Int32(None)
+                | ScalarValue::Int64(None)
+                | ScalarValue::UInt8(None)
+                | ScalarValue::UInt16(None)
+                | ScalarValue::UInt32(None)
+                | ScalarValue::UInt64(None)
+                | ScalarValue::Utf8(None)
+                | ScalarValue::LargeUtf8(None)
+                | ScalarValue::List(None, _)
+                | ScalarValue::Date32(None)
+                | ScalarValue::TimeMicrosecond(None)
+                | ScalarValue::TimeNanosecond(None)
+                | ScalarValue::Duration(None, _)
+        )
+    }
+}
@@ -0,0 +1,516 @@
+// Converts values of any type that implements Serialize into a
+// RecordBatch. Every row is serialized into a tree of values first, the
+// schema is inferred from the trees of all the rows, and then the columns
+// are built from them. The fields of a struct become the columns, nested
+// structs become StructArrays and sequences become ListArrays
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::{
+    array::{make_array, ArrayData, ArrayRef, NullArray},
+    datatypes::{DataType, Field, Schema},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::bitmap;
+use crate::builders::{make_column, OffsetsBuilder};
+use crate::ScalarValue;
+
+/// Serializes the rows into a batch with a column for every field of T,
+/// which must be a struct. The schema is inferred from the values: fields
+/// that are None or missing in some row are nullable, and a field that is
+/// always None has type Null. Maps and enum variants with data aren't
+/// supported
+pub fn to_record_batch<T: Serialize>(rows: &[T]) -> Result<RecordBatch> {
+    if rows.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "The schema can't be inferred without rows".to_string(),
+        ));
+    }
+
+    let values = rows
+        .iter()
+        .map(|row| row.serialize(ValueSerializer))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut inferred = Inferred::default();
+    for value in &values {
+        if !matches!(value, Value::Struct(_)) {
+            return Err(ArrowError::InvalidArgumentError(
+                "The rows must be structs".to_string(),
+            ));
+        }
+        inferred.update(value, "")?;
+    }
+
+    let fields = match inferred.to_field("").data_type() {
+        DataType::Struct(fields) => fields.clone(),
+        _ => unreachable!("The rows are structs"),
+    };
+    let values = values.iter().collect::<Vec<_>>();
+    let columns = fields
+        .iter()
+        .map(|field| build_array(&children(&values, field.name()), field))
+        .collect::<Result<Vec<_>>>()?;
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
+// A row, or one of its fields, once serialized
+#[derive(Debug, Clone)]
+enum Value {
+    Null,
+    Scalar(ScalarValue),
+    List(Vec<Value>),
+    Struct(Vec<(&'static str, Value)>),
+}
+
+#[derive(Debug)]
+struct SerializeError(String);
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerializeError(msg.to_string())
+    }
+}
+
+impl From<SerializeError> for ArrowError {
+    fn from(error: SerializeError) -> Self {
+        ArrowError::InvalidArgumentError(error.to_string())
+    }
+}
+
+fn unsupported(what: &str) -> SerializeError {
+    SerializeError(format!("{} can't be converted to arrow", what))
+}
+
+struct ValueSerializer;
+
+macro_rules! serialize_scalar {
+    ($method:ident, $native:ty, $variant:ident) => {
+        fn $method(self, v: $native) -> std::result::Result<Value, SerializeError> {
+            Ok(Value::Scalar(ScalarValue::$variant(Some(v))))
+        }
+    };
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerializeError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = Impossible<Value, SerializeError>;
+    type SerializeMap = Impossible<Value, SerializeError>;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = Impossible<Value, SerializeError>;
+
+    serialize_scalar!(serialize_bool, bool, Boolean);
+    serialize_scalar!(serialize_i8, i8, Int8);
+    serialize_scalar!(serialize_i16, i16, Int16);
+    serialize_scalar!(serialize_i32, i32, Int32);
+    serialize_scalar!(serialize_i64, i64, Int64);
+    serialize_scalar!(serialize_u8, u8, UInt8);
+    serialize_scalar!(serialize_u16, u16, UInt16);
+    serialize_scalar!(serialize_u32, u32, UInt32);
+    serialize_scalar!(serialize_u64, u64, UInt64);
+    serialize_scalar!(serialize_f32, f32, Float32);
+    serialize_scalar!(serialize_f64, f64, Float64);
+
+    fn serialize_char(self, v: char) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::Scalar(ScalarValue::Utf8(Some(v.to_string()))))
+    }
+
+    fn serialize_str(self, v: &str) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::Scalar(ScalarValue::Utf8(Some(v.to_string()))))
+    }
+
+    // Bytes become a list of u8, as a Vec<u8> is serialized
+    fn serialize_bytes(self, v: &[u8]) -> std::result::Result<Value, SerializeError> {
+        let bytes = v
+            .iter()
+            .map(|&byte| Value::Scalar(ScalarValue::UInt8(Some(byte))));
+        Ok(Value::List(bytes.collect()))
+    }
+
+    fn serialize_none(self) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(
+        self,
+        value: &T,
+    ) -> std::result::Result<Value, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _name: &'static str,
+    ) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::Null)
+    }
+
+    // Enums without data are stored as the name of the variant
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::Scalar(ScalarValue::Utf8(Some(variant.to_string()))))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> std::result::Result<Value, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _value: &T,
+    ) -> std::result::Result<Value, SerializeError> {
+        Err(unsupported(&format!("The variant {}::{}", name, variant)))
+    }
+
+    fn serialize_seq(
+        self,
+        len: Option<usize>,
+    ) -> std::result::Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> std::result::Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> std::result::Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTupleVariant, SerializeError> {
+        Err(unsupported(&format!("The variant {}::{}", name, variant)))
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeMap, SerializeError> {
+        Err(unsupported("A map"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> std::result::Result<StructSerializer, SerializeError> {
+        Ok(StructSerializer {
+            fields: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeStructVariant, SerializeError> {
+        Err(unsupported(&format!("The variant {}::{}", name, variant)))
+    }
+}
+
+struct SeqSerializer {
+    items: Vec<Value>,
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), SerializeError> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::List(self.items))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), SerializeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> std::result::Result<Value, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), SerializeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> std::result::Result<Value, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct StructSerializer {
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl ser::SerializeStruct for StructSerializer {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> std::result::Result<(), SerializeError> {
+        self.fields.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> std::result::Result<Value, SerializeError> {
+        Ok(Value::Struct(self.fields))
+    }
+}
+
+// Type found for a field in the values seen so far
+#[derive(Debug, Default)]
+struct Inferred {
+    kind: Option<Kind>,
+    nullable: bool,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Scalar(DataType),
+    List(Box<Inferred>),
+    Struct(Vec<(&'static str, Inferred)>),
+}
+
+impl Inferred {
+    fn update(&mut self, value: &Value, path: &str) -> Result<()> {
+        match value {
+            Value::Null => self.nullable = true,
+            Value::Scalar(scalar) => {
+                let data_type = scalar.get_datatype();
+                match &self.kind {
+                    None => self.kind = Some(Kind::Scalar(data_type)),
+                    Some(Kind::Scalar(existing)) if *existing == data_type => {}
+                    kind => return Err(mismatch(path, kind, &format!("{:?}", data_type))),
+                }
+            }
+            Value::List(items) => {
+                if self.kind.is_none() {
+                    self.kind = Some(Kind::List(Box::default()));
+                }
+                match &mut self.kind {
+                    Some(Kind::List(item)) => {
+                        let item_path = format!("{}.item", path);
+                        for value in items {
+                            item.update(value, &item_path)?;
+                        }
+                    }
+                    kind => return Err(mismatch(path, kind, "list")),
+                }
+            }
+            Value::Struct(values) => {
+                let first = self.kind.is_none();
+                if first {
+                    self.kind = Some(Kind::Struct(Vec::new()));
+                }
+                match &mut self.kind {
+                    Some(Kind::Struct(fields)) => update_fields(fields, values, first, path)?,
+                    kind => return Err(mismatch(path, kind, "struct")),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn to_field(&self, name: &str) -> Field {
+        let data_type = match &self.kind {
+            None => DataType::Null,
+            Some(Kind::Scalar(data_type)) => data_type.clone(),
+            Some(Kind::List(item)) => DataType::List(Box::new(item.to_field("item"))),
+            Some(Kind::Struct(fields)) => DataType::Struct(
+                fields
+                    .iter()
+                    .map(|(name, inferred)| inferred.to_field(name))
+                    .collect(),
+            ),
+        };
+        let nullable = self.nullable || self.kind.is_none();
+        Field::new(name, data_type, nullable)
+    }
+}
+
+// Fields found after the first struct, or missing in this one, are
+// nullable
+fn update_fields(
+    fields: &mut Vec<(&'static str, Inferred)>,
+    values: &[(&'static str, Value)],
+    first: bool,
+    path: &str,
+) -> Result<()> {
+    for (name, inferred) in fields.iter_mut() {
+        if !values.iter().any(|(key, _)| key == name) {
+            inferred.nullable = true;
+        }
+    }
+
+    for (key, value) in values {
+        let position = match fields.iter().position(|(name, _)| name == key) {
+            Some(position) => position,
+            None => {
+                let inferred = Inferred {
+                    kind: None,
+                    nullable: !first,
+                };
+                fields.push((key, inferred));
+                fields.len() - 1
+            }
+        };
+        let field_path = match path.is_empty() {
+            true => key.to_string(),
+            false => format!("{}.{}", path, key),
+        };
+        fields[position].1.update(value, &field_path)?;
+    }
+    Ok(())
+}
+
+fn mismatch(path: &str, kind: &Option<Kind>, found: &str) -> ArrowError {
+    let expected = match kind {
+        None => "null".to_string(),
+        Some(Kind::Scalar(data_type)) => format!("{:?}", data_type),
+        Some(Kind::List(_)) => "list".to_string(),
+        Some(Kind::Struct(_)) => "struct".to_string(),
+    };
+    ArrowError::InvalidArgumentError(format!(
+        "The field {} has values of type {} and {}",
+        path, expected, found
+    ))
+}
+
+// Values of a field in every struct, which are null if the struct is null
+// or doesn't have the field
+fn children<'a>(values: &[&'a Value], name: &str) -> Vec<&'a Value> {
+    values
+        .iter()
+        .map(|value| match value {
+            Value::Struct(fields) => fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map_or(&Value::Null, |(_, value)| value),
+            _ => &Value::Null,
+        })
+        .collect()
+}
+
+fn build_array(values: &[&Value], field: &Field) -> Result<ArrayRef> {
+    let validity = values
+        .iter()
+        .map(|value| !matches!(value, Value::Null))
+        .collect::<Vec<_>>();
+
+    let builder = match field.data_type() {
+        DataType::Null => return Ok(Arc::new(NullArray::new(values.len()))),
+        DataType::Struct(fields) => {
+            let mut builder = ArrayData::builder(field.data_type().clone()).len(values.len());
+            for child in fields {
+                let array = build_array(&children(values, child.name()), child)?;
+                builder = builder.add_child_data(array.data());
+            }
+            builder
+        }
+        DataType::List(item) => {
+            let mut offsets = OffsetsBuilder::<i32>::new(values.len());
+            let mut items = Vec::new();
+            for value in values {
+                match value {
+                    Value::List(list) => {
+                        offsets.append_length(list.len())?;
+                        items.extend(list.iter());
+                    }
+                    _ => offsets.append_length(0)?,
+                }
+            }
+            let items = build_array(&items, item)?;
+            ArrayData::builder(field.data_type().clone())
+                .len(values.len())
+                .add_buffer(offsets.finish())
+                .add_child_data(items.data())
+        }
+        data_type => {
+            let mut column = make_column(data_type, values.len())?;
+            for value in values {
+                match value {
+                    Value::Scalar(scalar) => column.append_scalar(scalar)?,
+                    _ => column.append_null()?,
+                }
+            }
+            return Ok(column.finish());
+        }
+    };
+
+    let builder = match validity.contains(&false) {
+        true => builder.null_bit_buffer(bitmap::from_bools(&validity)),
+        false => builder,
+    };
+    Ok(make_array(builder.build()))
+}
@@ -0,0 +1,544 @@
+use arrow::{
+    array::ArrayRef,
+    compute::{take, SortOptions},
+    datatypes::{Field, Schema},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use serde_json::{Map, Value};
+
+#[cfg(feature = "parquet")]
+use parquet::{
+    arrow::{ArrowReader, ArrowWriter, ParquetFileArrowReader},
+    file::{metadata::KeyValue, properties::WriterProperties, reader::SerializedFileReader},
+};
+
+use std::collections::{HashSet, VecDeque};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::compute::{self, Aggregate, GroupByHash, NullTreatment, RankMethod};
+use crate::json_values;
+use crate::pandas::PandasMetadata;
+use crate::{ChunkedColumn, ScalarValue};
+
+// Number of records decoded at a time when streaming a column
+// directly from a parquet file, also the chunk size of Delta tables
+#[cfg(feature = "parquet")]
+const STREAM_CHUNK_SIZE: usize = 2048;
+
+/// The Table object will be used to store all the information collected
+/// from the parquet file
+pub struct Table {
+    schema: Schema,
+    data: Vec<RecordBatch>,
+    rows: usize,
+    chunk_size: usize,
+    // Row of the table where every batch starts, the batches can have
+    // different number of rows
+    offsets: Vec<usize>,
+}
+
+impl Table {
+    /// Reads the parquet file and stores the chunks in a vector
+    /// This will keep the data in memory
+    #[cfg(feature = "parquet")]
+    pub fn read_parquet<T: AsRef<Path>>(path: T, chunk_size: usize) -> Self {
+        let file = File::open(path).unwrap();
+        let file_reader = SerializedFileReader::new(file).unwrap();
+        let mut arrow_reader = ParquetFileArrowReader::new(Arc::new(file_reader));
+
+        let schema = arrow_reader.get_schema().unwrap();
+        let record_batch_reader = arrow_reader.get_record_reader(chunk_size).unwrap();
+        let mut data: Vec<RecordBatch> = Vec::new();
+
+        let mut rows = 0;
+        for maybe_batch in record_batch_reader {
+            let record_batch = maybe_batch.unwrap();
+            rows += record_batch.num_rows();
+
+            data.push(record_batch);
+        }
+
+        Self {
+            offsets: batch_offsets(&data),
+            schema,
+            data,
+            rows,
+            chunk_size,
+        }
+    }
+
+    /// Reads a Delta Lake table, a directory of parquet files with a
+    /// _delta_log, at the selected version or at the latest one. The log
+    /// gives the active files, which are read and concatenated with their
+    /// partition columns added after the columns of the files
+    #[cfg(feature = "parquet")]
+    pub fn read_delta<T: AsRef<Path>>(path: T, version: Option<u64>) -> Result<Self> {
+        let (schema, data) = crate::delta::read_delta(path.as_ref(), version, STREAM_CHUNK_SIZE)?;
+        Ok(Self::new(schema, data))
+    }
+
+    /// Reads a file of the first version of Feather, written by R and pandas
+    /// before Feather files became Arrow IPC files. Categories, or factors,
+    /// become dictionaries. The files of the second version are read with
+    /// `ipc::IpcFileReader`
+    pub fn read_feather_v1<T: AsRef<Path>>(path: T) -> Result<Self> {
+        let (schema, data) = crate::feather::read_feather_v1(path.as_ref())?;
+        Ok(Self::new(schema, data))
+    }
+
+    /// Creates a Table from batches that were already loaded, for example
+    /// the batches received from a stream. The batches can have any number
+    /// of rows
+    pub fn new(schema: Schema, data: Vec<RecordBatch>) -> Self {
+        let rows = data.iter().map(RecordBatch::num_rows).sum();
+        let chunk_size = data.first().map_or(1, RecordBatch::num_rows).max(1);
+
+        Self {
+            offsets: batch_offsets(&data),
+            schema,
+            data,
+            rows,
+            chunk_size,
+        }
+    }
+
+    /// Creates a Table from rows as JSON objects, like the body of a request.
+    /// The keys are the names of the fields, structs are nested objects and
+    /// lists are arrays. Keys that aren't in the schema are ignored
+    pub fn from_json_values(schema: Schema, values: &[Map<String, Value>]) -> Result<Self> {
+        let batch = json_values::values_to_batch(&schema, values)?;
+        Ok(Self::new(schema, vec![batch]))
+    }
+
+    /// Reads the values of a single column from the parquet file without
+    /// creating a Table. Only the selected column is decoded and the row
+    /// groups are read from the file as the values are consumed. Errors
+    /// decoding a row group or converting a value are returned as items
+    #[cfg(feature = "parquet")]
+    pub fn stream_column_from_parquet<T: AsRef<Path>>(
+        path: T,
+        column: usize,
+    ) -> Result<impl Iterator<Item = Result<ScalarValue>>> {
+        let file = File::open(path)?;
+        let file_reader =
+            SerializedFileReader::new(file).map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+        let mut arrow_reader = ParquetFileArrowReader::new(Arc::new(file_reader));
+
+        let record_batch_reader = arrow_reader
+            .get_record_reader_by_columns(vec![column], STREAM_CHUNK_SIZE)
+            .map_err(|e| ArrowError::ParquetError(e.to_string()))?;
+
+        Ok(record_batch_reader.flat_map(|maybe_batch| {
+            let values: Box<dyn Iterator<Item = Result<ScalarValue>>> = match maybe_batch {
+                Ok(batch) => {
+                    // The projected batches only contain the selected column
+                    let array = batch.column(0).clone();
+                    Box::new((0..array.len()).map(move |index| {
+                        ScalarValue::try_from_array(&array, index)
+                            .map_err(ArrowError::InvalidArgumentError)
+                    }))
+                }
+                Err(error) => Box::new(std::iter::once(Err(error))),
+            };
+            values
+        }))
+    }
+
+    /// Simple writer to store the table data into a parquet file
+    #[cfg(feature = "parquet")]
+    pub fn to_parquet<T: AsRef<Path>>(&self, path: T) {
+        // The schema metadata is also written as metadata of the file, where
+        // readers like pandas look for it
+        let mut key_value_metadata = self
+            .schema
+            .metadata()
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.clone(), value.clone()))
+            .collect::<Vec<_>>();
+        key_value_metadata.sort_by(|a, b| a.key.cmp(&b.key));
+        let properties = WriterProperties::builder()
+            .set_key_value_metadata(Some(key_value_metadata).filter(|pairs| !pairs.is_empty()))
+            .build();
+
+        let file = File::create(path).unwrap();
+        let mut writer =
+            ArrowWriter::try_new(file, Arc::new(self.schema.clone()), Some(properties)).unwrap();
+
+        for batch in self.data.iter() {
+            writer.write(batch).unwrap();
+        }
+
+        writer.close().unwrap();
+    }
+
+    /// Converts every row to a JSON object with the names of the columns as
+    /// keys, for example to render it in a template. Dates and times are
+    /// strings and NaN floats are null
+    pub fn to_json_values(&self) -> Result<Vec<Map<String, Value>>> {
+        let mut values = Vec::with_capacity(self.rows);
+        for batch in &self.data {
+            values.extend(json_values::batch_to_values(batch)?);
+        }
+        Ok(values)
+    }
+
+    /// Writes the table as an Arrow IPC file, the format of Feather v2
+    /// files, with the metadata of the schema
+    pub fn to_ipc_file<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        crate::ipc::write_ipc_file(File::create(path)?, &self.schema, &self.data)
+    }
+
+    /// Adds the metadata pandas uses to rebuild a DataFrame to the schema,
+    /// with the columns in `index_columns` as its index. The files written
+    /// afterwards with `to_parquet` or `to_ipc_file` open in pandas with
+    /// that index and the dtypes of `PandasMetadata::try_new`
+    pub fn with_pandas_metadata(self, index_columns: &[&str]) -> Result<Self> {
+        let metadata = PandasMetadata::try_new(&self.schema, index_columns)?;
+        let schema = metadata.add_to_schema(&self.schema);
+
+        // The batches need the same schema as the table to be written
+        let schema_ref = Arc::new(schema.clone());
+        let data = self
+            .data
+            .iter()
+            .map(|batch| RecordBatch::try_new(schema_ref.clone(), batch.columns().to_vec()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            schema,
+            data,
+            ..self
+        })
+    }
+
+    /// Reads the metadata pandas stores in the schema, for example to find
+    /// the columns of the index of a file written by pandas. It's None if
+    /// the schema doesn't have it
+    pub fn pandas_metadata(&self) -> Result<Option<PandasMetadata>> {
+        PandasMetadata::from_schema(&self.schema)
+    }
+
+    /// From the schema we can extract all the information regarding
+    /// the data extracted from the parquet file. The schema contains
+    /// the name of the fields and the types of each column.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn data(&self) -> &Vec<RecordBatch> {
+        &self.data
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Extracts the value from the selected column and index
+    pub fn value(&self, column: usize, index: usize) -> Option<ScalarValue> {
+        // If the selected column is larger than the available columns
+        // in the schema then there is no value to collect
+        if column >= self.schema.fields().len() {
+            return None;
+        }
+
+        // If the index is larger than all the rows in the table, then
+        // there is no value to collect, thus None
+        if index >= self.rows {
+            return None;
+        }
+
+        // The row is in the last batch that starts before it. Empty batches
+        // start at the same row as the next one, so they are skipped
+        let batch = self.offsets.partition_point(|&start| start <= index) - 1;
+
+        // Selecting the array from the RecordBatch stored in the
+        // data vector
+        let array = self.data[batch].column(column);
+
+        // The index argument refers to the position of the value within
+        // all the rows in the table. A relative index in the batch is
+        // required to access the data stored in the batch
+        let index_in_batch = index - self.offsets[batch];
+
+        ScalarValue::try_from_array(array, index_in_batch).ok()
+    }
+
+    pub fn column_iterator(&self, column: usize) -> ColumnIterator<'_> {
+        ColumnIterator::new(column, &self.data)
+    }
+
+    /// Returns the unique values from the selected column in the order
+    /// in which they are first found
+    pub fn distinct_values(&self, column: usize) -> impl Iterator<Item = Result<ScalarValue>> + '_ {
+        let mut seen: HashSet<ScalarValue> = HashSet::new();
+        self.column_iterator(column)
+            .filter(move |value| match value {
+                Ok(value) => seen.insert(value.clone()),
+                Err(_) => true,
+            })
+    }
+
+    /// Returns the selected column with all its chunks. None is returned
+    /// if the column doesn't exist
+    pub fn column(&self, column: usize) -> Option<ChunkedColumn> {
+        let field = self.schema.fields().get(column)?.clone();
+        ChunkedColumn::try_new(field, self.column_chunks(column).collect()).ok()
+    }
+
+    /// Returns the selected column with every value moved `n` rows down,
+    /// so each row holds the value of the column `n` rows before. The
+    /// values cross the boundaries between the batches and the first `n`
+    /// rows are null
+    pub fn lag(&self, column: usize, n: usize) -> Result<ChunkedColumn> {
+        self.chunked_column(column)?.shift(n as i64)
+    }
+
+    /// Returns the selected column with every value moved `n` rows up, so
+    /// each row holds the value of the column `n` rows after. The last `n`
+    /// rows are null
+    pub fn lead(&self, column: usize, n: usize) -> Result<ChunkedColumn> {
+        self.chunked_column(column)?.shift(-(n as i64))
+    }
+
+    /// Returns the selected column with its nulls filled with the last
+    /// value before them, which can be in a previous batch
+    pub fn fill_forward(&self, column: usize) -> Result<ChunkedColumn> {
+        self.chunked_column(column)?.fill_forward()
+    }
+
+    /// Returns the selected column with its nulls filled with the first
+    /// value after them
+    pub fn fill_backward(&self, column: usize) -> Result<ChunkedColumn> {
+        self.chunked_column(column)?.fill_backward()
+    }
+
+    /// Returns the selected column as Float64 with the gaps between two
+    /// values filled by linear interpolation
+    pub fn interpolate_linear(&self, column: usize) -> Result<ChunkedColumn> {
+        self.chunked_column(column)?.interpolate_linear()
+    }
+
+    /// Returns the cumulative sum of the selected column. The sum continues
+    /// from one batch to the next
+    pub fn cumsum(&self, column: usize, nulls: NullTreatment) -> Result<ChunkedColumn> {
+        self.chunked_column(column)?.cumsum(nulls)
+    }
+
+    /// Groups the rows of the table by the key columns and calculates the
+    /// aggregates of every group. The result has one row per group with
+    /// the keys followed by the aggregates, see compute::GroupByHash
+    pub fn group_by(&self, key_columns: &[usize], aggregates: &[Aggregate]) -> Result<Table> {
+        let mut group_by =
+            GroupByHash::new(Arc::new(self.schema.clone()), key_columns, aggregates)?;
+        for batch in &self.data {
+            group_by.update(batch)?;
+        }
+
+        let schema = group_by.schema().as_ref().clone();
+        Ok(Table::new(schema, vec![group_by.finish()?]))
+    }
+
+    /// Returns a copy of the table with the rows sorted by the key columns,
+    /// see compute::sort_indices. The sorted rows are split in batches of
+    /// the chunk size of the table
+    pub fn sort_by(&self, columns: &[(usize, SortOptions)]) -> Result<Table> {
+        let keys = columns
+            .iter()
+            .map(|(column, _)| self.chunked_column(*column)?.flatten())
+            .collect::<Result<Vec<_>>>()?;
+        let keys = keys
+            .iter()
+            .zip(columns)
+            .map(|(key, (_, options))| (key, *options))
+            .collect::<Vec<_>>();
+        let indices = compute::sort_indices(&keys)?;
+
+        let sorted = (0..self.schema.fields().len())
+            .map(|column| {
+                take(
+                    self.chunked_column(column)?.flatten()?.as_ref(),
+                    &indices,
+                    None,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let schema = Arc::new(self.schema.clone());
+        let mut data = Vec::new();
+        let mut offset = 0;
+        while offset < self.rows {
+            let len = self.chunk_size.min(self.rows - offset);
+            let columns = sorted
+                .iter()
+                .map(|column| column.slice(offset, len))
+                .collect();
+            data.push(RecordBatch::try_new(schema.clone(), columns)?);
+            offset += len;
+        }
+
+        Ok(Table {
+            schema: self.schema.clone(),
+            offsets: batch_offsets(&data),
+            data,
+            rows: self.rows,
+            chunk_size: self.chunk_size,
+        })
+    }
+
+    /// Returns a copy of the table with a new column holding the rank of
+    /// every row in the selected column. The rows are ranked across all the
+    /// batches, see compute::rank
+    pub fn with_rank(
+        &self,
+        column: usize,
+        name: &str,
+        descending: bool,
+        method: RankMethod,
+    ) -> Result<Table> {
+        let ranks = compute::rank(&self.chunked_column(column)?.flatten()?, descending, method)?;
+
+        let mut fields = self.schema.fields().clone();
+        fields.push(Field::new(name, ranks.data_type().clone(), true));
+        let schema = Schema::new_with_metadata(fields, self.schema.metadata().clone());
+
+        let mut offset = 0;
+        let mut data = Vec::with_capacity(self.data.len());
+        for batch in &self.data {
+            let mut columns = batch.columns().to_vec();
+            columns.push(ranks.slice(offset, batch.num_rows()));
+            offset += batch.num_rows();
+
+            data.push(RecordBatch::try_new(Arc::new(schema.clone()), columns)?);
+        }
+
+        Ok(Table {
+            schema,
+            data,
+            rows: self.rows,
+            chunk_size: self.chunk_size,
+            offsets: self.offsets.clone(),
+        })
+    }
+
+    /// Returns the arrays that form the selected column, one per
+    /// RecordBatch stored in the table. Working with whole arrays lets
+    /// the caller use the compute kernels batch by batch instead of
+    /// going through every value as a ScalarValue
+    pub fn column_chunks(&self, column: usize) -> impl Iterator<Item = ArrayRef> + '_ {
+        self.data
+            .iter()
+            .filter_map(move |batch| batch.columns().get(column).cloned())
+    }
+
+    fn chunked_column(&self, column: usize) -> Result<ChunkedColumn> {
+        self.column(column).ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!("The table doesn't have column {}", column))
+        })
+    }
+}
+
+// Row of the table where each batch starts
+fn batch_offsets(data: &[RecordBatch]) -> Vec<usize> {
+    data.iter()
+        .scan(0, |start, batch| {
+            let offset = *start;
+            *start += batch.num_rows();
+            Some(offset)
+        })
+        .collect()
+}
+
+pub struct ColumnIterator<'iter> {
+    column: usize,
+    data: &'iter [RecordBatch],
+    index: usize,
+    batch: usize,
+    // Position of the next value within all the rows in the table
+    row: usize,
+}
+
+impl<'iter> ColumnIterator<'iter> {
+    pub fn new(column: usize, data: &'iter [RecordBatch]) -> Self {
+        Self {
+            column,
+            data,
+            index: 0,
+            batch: 0,
+            row: 0,
+        }
+    }
+
+    /// Consumes the iterator skipping all the null values found
+    /// in the column
+    pub fn non_null(self) -> impl Iterator<Item = Result<ScalarValue>> + 'iter {
+        self.filter(|value| !matches!(value, Ok(value) if value.is_null()))
+    }
+
+    /// Consumes the iterator returning each value together with its
+    /// index within all the rows in the table. This index is the same
+    /// one that can be used with Table::value
+    pub fn enumerate_rows(mut self) -> impl Iterator<Item = (usize, Result<ScalarValue>)> + 'iter {
+        std::iter::from_fn(move || {
+            let row = self.row;
+            self.next().map(|value| (row, value))
+        })
+    }
+
+    /// Consumes the iterator returning overlapping windows of n values.
+    /// The values are kept in a buffer so a window can contain values
+    /// from two or more batches. A value that can't be converted is
+    /// returned as an error in place of the window
+    pub fn windows(mut self, n: usize) -> impl Iterator<Item = Result<Vec<ScalarValue>>> + 'iter {
+        assert!(n > 0, "The window size must be larger than zero");
+
+        let mut window: VecDeque<ScalarValue> = VecDeque::with_capacity(n);
+        std::iter::from_fn(move || {
+            if window.len() == n {
+                window.pop_front();
+            }
+
+            while window.len() < n {
+                match self.next()? {
+                    Ok(value) => window.push_back(value),
+                    Err(error) => return Some(Err(error)),
+                }
+            }
+
+            Some(Ok(window.iter().cloned().collect()))
+        })
+    }
+}
+
+// Values of types without a ScalarValue are returned as errors instead
+// of ending the iteration
+impl<'iter> Iterator for ColumnIterator<'iter> {
+    type Item = Result<ScalarValue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Moving to the next batch once all the records from the
+        // current batch have been returned
+        while self.batch < self.data.len()
+            && self.index >= self.data[self.batch].column(self.column).len()
+        {
+            self.index = 0;
+            self.batch += 1;
+        }
+
+        if self.batch >= self.data.len() {
+            return None;
+        }
+
+        let array = self.data[self.batch].column(self.column);
+
+        let value = ScalarValue::try_from_array(array, self.index)
+            .map_err(ArrowError::InvalidArgumentError);
+
+        self.index += 1;
+        self.row += 1;
+
+        Some(value)
+    }
+}
@@ -0,0 +1,399 @@
+// Checks the invariants that the arrays assume about their ArrayData. The
+// builder of ArrayData accepts any buffers, and an offset that points past
+// the end of a buffer is only found when the value is read, reading memory
+// that doesn't belong to the array. Validating the data after building it
+// by hand turns those mistakes into an error
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt;
+
+use arrow::{
+    array::ArrayData,
+    buffer::Buffer,
+    datatypes::{DataType, IntervalUnit},
+    error::ArrowError,
+    util::bit_util,
+};
+
+/// Error returned when an ArrayData breaks one of the invariants of its
+/// type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// Path from the validated array to the child with the error, like
+    /// `owner.name`. It's empty if the error is in the validated array
+    pub path: String,
+    /// What is wrong with the data
+    pub reason: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "Invalid array data: {}", self.reason),
+            false => write!(f, "Invalid array data in {}: {}", self.path, self.reason),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+impl From<ValidationError> for ArrowError {
+    fn from(error: ValidationError) -> Self {
+        ArrowError::InvalidArgumentError(error.to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, ValidationError>;
+
+/// Checks that the buffers of the data are large enough for its length,
+/// offset and type, that the offsets of variable-size types never decrease
+/// and stay inside their values, that the null bitmap covers all the rows
+/// and agrees with the null count, and that the children have the types
+/// and lengths of the parent. The children are validated recursively
+pub fn validate_array_data(data: &ArrayData) -> Result<()> {
+    validate(data, "")
+}
+
+fn validate(data: &ArrayData, path: &str) -> Result<()> {
+    let error = |reason: String| ValidationError {
+        path: path.to_string(),
+        reason,
+    };
+    let end = data
+        .offset()
+        .checked_add(data.len())
+        .ok_or_else(|| error("The offset plus the length overflows".to_string()))?;
+
+    validate_nulls(data, end).map_err(error)?;
+
+    match data.data_type() {
+        DataType::Null => {
+            expect_layout(data, 0, 0).map_err(error)?;
+        }
+        DataType::Boolean => {
+            expect_layout(data, 1, 0).map_err(error)?;
+            expect_size(&data.buffers()[0], bit_util::ceil(end, 8), "values").map_err(error)?;
+        }
+        DataType::Utf8 | DataType::Binary => {
+            expect_layout(data, 2, 0).map_err(error)?;
+            let offsets = read_offsets(&data.buffers()[0], 4, data.offset(), end).map_err(error)?;
+            validate_values(data, &offsets).map_err(error)?;
+        }
+        DataType::LargeUtf8 | DataType::LargeBinary => {
+            expect_layout(data, 2, 0).map_err(error)?;
+            let offsets = read_offsets(&data.buffers()[0], 8, data.offset(), end).map_err(error)?;
+            validate_values(data, &offsets).map_err(error)?;
+        }
+        DataType::List(field) | DataType::LargeList(field) => {
+            expect_layout(data, 1, 1).map_err(error)?;
+            let width = match data.data_type() {
+                DataType::List(_) => 4,
+                _ => 8,
+            };
+            let offsets =
+                read_offsets(&data.buffers()[0], width, data.offset(), end).map_err(error)?;
+            let child = &data.child_data()[0];
+            expect_child(child, field.data_type(), field.name()).map_err(error)?;
+            if let Some(&last) = offsets.last() {
+                expect_child_len(child, last, field.name()).map_err(error)?;
+            }
+            validate(child, &child_path(path, field.name()))?;
+        }
+        DataType::FixedSizeList(field, size) => {
+            expect_layout(data, 0, 1).map_err(error)?;
+            if *size < 0 {
+                return Err(error(format!("The size of the lists is {}", size)));
+            }
+            let child = &data.child_data()[0];
+            expect_child(child, field.data_type(), field.name()).map_err(error)?;
+            let needed = end
+                .checked_mul(*size as usize)
+                .ok_or_else(|| error("The length of the values overflows".to_string()))?;
+            expect_child_len(child, needed, field.name()).map_err(error)?;
+            validate(child, &child_path(path, field.name()))?;
+        }
+        DataType::Struct(fields) => {
+            expect_layout(data, 0, fields.len()).map_err(error)?;
+            for (field, child) in fields.iter().zip(data.child_data()) {
+                expect_child(child, field.data_type(), field.name()).map_err(error)?;
+                expect_child_len(child, end, field.name()).map_err(error)?;
+                validate(child, &child_path(path, field.name()))?;
+            }
+        }
+        DataType::Union(fields) => {
+            validate_union(data, fields.len(), end).map_err(error)?;
+            for (field, child) in fields.iter().zip(data.child_data()) {
+                expect_child(child, field.data_type(), field.name()).map_err(error)?;
+                validate(child, &child_path(path, field.name()))?;
+            }
+        }
+        DataType::Dictionary(key_type, value_type) => {
+            expect_layout(data, 1, 1).map_err(error)?;
+            let width = byte_width(key_type)
+                .ok_or_else(|| error(format!("The keys have type {:?}", key_type)))?;
+            expect_size(&data.buffers()[0], end * width, "keys").map_err(error)?;
+            let values = &data.child_data()[0];
+            expect_child(values, value_type, "values").map_err(error)?;
+            validate_keys(data, key_type, width, values.len()).map_err(error)?;
+            validate(values, &child_path(path, "values"))?;
+        }
+        data_type => {
+            let width = byte_width(data_type).ok_or_else(|| {
+                error(format!("Arrays of type {:?} can't be validated", data_type))
+            })?;
+            expect_layout(data, 1, 0).map_err(error)?;
+            expect_size(&data.buffers()[0], end * width, "values").map_err(error)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Arrays may skip the bitmap only if none of their rows is null. Null
+// arrays don't have a bitmap, and arrow gives them a null count of 0
+fn validate_nulls(data: &ArrayData, end: usize) -> std::result::Result<(), String> {
+    let nulls = match (data.data_type(), data.null_buffer()) {
+        (DataType::Null, _) => return Ok(()),
+        (_, Some(bitmap)) => {
+            expect_size(bitmap, bit_util::ceil(end, 8), "null bitmap")?;
+            data.len() - bitmap.count_set_bits_offset(data.offset(), data.len())
+        }
+        (_, None) => 0,
+    };
+
+    match nulls == data.null_count() {
+        true => Ok(()),
+        false => Err(format!(
+            "The null count is {} but {} rows are null",
+            data.null_count(),
+            nulls
+        )),
+    }
+}
+
+fn expect_layout(
+    data: &ArrayData,
+    buffers: usize,
+    children: usize,
+) -> std::result::Result<(), String> {
+    if data.buffers().len() != buffers {
+        return Err(format!(
+            "Arrays of type {:?} have {} buffers but there are {}",
+            data.data_type(),
+            buffers,
+            data.buffers().len()
+        ));
+    }
+    if data.child_data().len() != children {
+        return Err(format!(
+            "Arrays of type {:?} have {} children but there are {}",
+            data.data_type(),
+            children,
+            data.child_data().len()
+        ));
+    }
+    Ok(())
+}
+
+fn expect_size(buffer: &Buffer, size: usize, name: &str) -> std::result::Result<(), String> {
+    match buffer.len() >= size {
+        true => Ok(()),
+        false => Err(format!(
+            "The {} buffer has {} bytes but it needs {}",
+            name,
+            buffer.len(),
+            size
+        )),
+    }
+}
+
+fn expect_child(
+    child: &ArrayData,
+    data_type: &DataType,
+    name: &str,
+) -> std::result::Result<(), String> {
+    match child.data_type() == data_type {
+        true => Ok(()),
+        false => Err(format!(
+            "The child {} has type {:?} but the field has type {:?}",
+            name,
+            child.data_type(),
+            data_type
+        )),
+    }
+}
+
+fn expect_child_len(child: &ArrayData, len: usize, name: &str) -> std::result::Result<(), String> {
+    match child.len() >= len {
+        true => Ok(()),
+        false => Err(format!(
+            "The child {} has {} rows but it needs {}",
+            name,
+            child.len(),
+            len
+        )),
+    }
+}
+
+// Reads the offsets of the rows from `start` to `end`, which need one
+// offset more than rows. The offsets can't be negative or decrease
+fn read_offsets(
+    buffer: &Buffer,
+    width: usize,
+    start: usize,
+    end: usize,
+) -> std::result::Result<Vec<usize>, String> {
+    expect_size(buffer, (end + 1) * width, "offsets")?;
+
+    let bytes = buffer.as_slice();
+    let mut offsets = Vec::with_capacity(end - start + 1);
+    for i in start..=end {
+        let bytes = &bytes[i * width..(i + 1) * width];
+        let offset = match width {
+            4 => i32::from_le_bytes(bytes.try_into().unwrap()) as i64,
+            _ => i64::from_le_bytes(bytes.try_into().unwrap()),
+        };
+        if offset < 0 {
+            return Err(format!("The offset of row {} is negative", i));
+        }
+        let offset = offset as usize;
+        if offsets.last().is_some_and(|&previous| offset < previous) {
+            return Err(format!(
+                "The offset of row {} is smaller than the previous",
+                i
+            ));
+        }
+        offsets.push(offset);
+    }
+    Ok(offsets)
+}
+
+// The offsets must stay inside the values buffer, and the values of the
+// string types must be valid UTF-8
+fn validate_values(data: &ArrayData, offsets: &[usize]) -> std::result::Result<(), String> {
+    let values = &data.buffers()[1];
+    if let Some(&last) = offsets.last() {
+        expect_size(values, last, "values")?;
+    }
+
+    if matches!(data.data_type(), DataType::Utf8 | DataType::LargeUtf8) {
+        for (i, pair) in offsets.windows(2).enumerate() {
+            if std::str::from_utf8(&values.as_slice()[pair[0]..pair[1]]).is_err() {
+                return Err(format!(
+                    "The value of row {} isn't valid UTF-8",
+                    data.offset() + i
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Unions have a buffer of type ids and, if they are dense, a buffer of
+// offsets into the child of every row. A type id is the index of the child
+fn validate_union(data: &ArrayData, fields: usize, end: usize) -> std::result::Result<(), String> {
+    let dense = data.buffers().len() == 2;
+    expect_layout(data, if dense { 2 } else { 1 }, fields)?;
+    expect_size(&data.buffers()[0], end, "type ids")?;
+    if dense {
+        expect_size(&data.buffers()[1], end * 4, "offsets")?;
+    }
+
+    for i in data.offset()..end {
+        let type_id = data.buffers()[0].as_slice()[i] as i8;
+        let child = match usize::try_from(type_id)
+            .ok()
+            .and_then(|id| data.child_data().get(id))
+        {
+            Some(child) => child,
+            None => return Err(format!("The type id of row {} is {}", i, type_id)),
+        };
+
+        let (position, needed) = match dense {
+            true => {
+                let bytes = &data.buffers()[1].as_slice()[i * 4..(i + 1) * 4];
+                let offset = i32::from_le_bytes(bytes.try_into().unwrap());
+                (offset as i64, offset as i64 + 1)
+            }
+            false => (i as i64, end as i64),
+        };
+        if position < 0 || (child.len() as i64) < needed {
+            return Err(format!(
+                "Row {} points to row {} of a child with {} rows",
+                i,
+                position,
+                child.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
+// The keys of the valid rows must point to one of the values
+fn validate_keys(
+    data: &ArrayData,
+    key_type: &DataType,
+    width: usize,
+    values: usize,
+) -> std::result::Result<(), String> {
+    let bytes = data.buffers()[0].as_slice();
+    for i in data.offset()..data.offset() + data.len() {
+        let valid = data
+            .null_buffer()
+            .is_none_or(|bitmap| bit_util::get_bit(bitmap.as_slice(), i));
+        if !valid {
+            continue;
+        }
+
+        let key = &bytes[i * width..(i + 1) * width];
+        let key = match key_type {
+            DataType::Int8 => i8::from_le_bytes(key.try_into().unwrap()) as i128,
+            DataType::Int16 => i16::from_le_bytes(key.try_into().unwrap()) as i128,
+            DataType::Int32 => i32::from_le_bytes(key.try_into().unwrap()) as i128,
+            DataType::Int64 => i64::from_le_bytes(key.try_into().unwrap()) as i128,
+            DataType::UInt8 => key[0] as i128,
+            DataType::UInt16 => u16::from_le_bytes(key.try_into().unwrap()) as i128,
+            DataType::UInt32 => u32::from_le_bytes(key.try_into().unwrap()) as i128,
+            DataType::UInt64 => u64::from_le_bytes(key.try_into().unwrap()) as i128,
+            other => return Err(format!("The keys have type {:?}", other)),
+        };
+        if key < 0 || key >= values as i128 {
+            return Err(format!(
+                "The key of row {} is {} but there are {} values",
+                i, key, values
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn byte_width(data_type: &DataType) -> Option<usize> {
+    Some(match data_type {
+        DataType::Int8 | DataType::UInt8 => 1,
+        DataType::Int16 | DataType::UInt16 | DataType::Float16 => 2,
+        DataType::Int32
+        | DataType::UInt32
+        | DataType::Float32
+        | DataType::Date32(_)
+        | DataType::Time32(_)
+        | DataType::Interval(IntervalUnit::YearMonth) => 4,
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Float64
+        | DataType::Date64(_)
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_)
+        | DataType::Interval(IntervalUnit::DayTime) => 8,
+        DataType::Decimal(_, _) => 16,
+        DataType::FixedSizeBinary(size) if *size >= 0 => *size as usize,
+        _ => return None,
+    })
+}
+
+fn child_path(path: &str, name: &str) -> String {
+    match path.is_empty() {
+        true => name.to_string(),
+        false => format!("{}.{}", path, name),
+    }
+}
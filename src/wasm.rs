@@ -0,0 +1,91 @@
+// Helpers to exchange bytes with JavaScript when the crate is compiled to
+// wasm32-unknown-unknown, for example the IPC streams read and written by
+// Arrow JS. The functions of a WebAssembly module only take and return
+// numbers, so the bytes are passed as pointers to the memory of the module:
+// JavaScript allocates the input with arrow_guide_alloc and copies the
+// bytes to it, and every output starts with its length as a little endian
+// u32 so JavaScript knows how many bytes to read before freeing them
+use std::cell::RefCell;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+use arrow::error::Result;
+
+// Bytes of the length written before every output
+const LEN_PREFIX: usize = 4;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Allocates len bytes for JavaScript to write the input of a function.
+/// The bytes are freed with `arrow_guide_free` after the call
+#[no_mangle]
+pub extern "C" fn arrow_guide_alloc(len: usize) -> *mut u8 {
+    // The capacity of a Vec created with_capacity is exactly len, so the
+    // same len frees it
+    let mut bytes = Vec::<u8>::with_capacity(len);
+    let ptr = bytes.as_mut_ptr();
+    mem::forget(bytes);
+    ptr
+}
+
+/// Frees the bytes allocated by `arrow_guide_alloc`, or an output returned
+/// by `to_js`, whose len includes the 4 bytes of its length
+///
+/// # Safety
+///
+/// The pointer and the length must be the ones of an allocation made here,
+/// and the bytes can't be used after this call
+#[no_mangle]
+pub unsafe extern "C" fn arrow_guide_free(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        drop(Vec::from_raw_parts(ptr, 0, len));
+    }
+}
+
+/// Returns the message of the last error as an output of `to_js`, or null
+/// if there wasn't an error since the last call
+#[no_mangle]
+pub extern "C" fn arrow_guide_last_error() -> *mut u8 {
+    match LAST_ERROR.with(|error| error.borrow_mut().take()) {
+        Some(message) => with_len(message.into_bytes()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Reads the input written by JavaScript in the memory returned by
+/// `arrow_guide_alloc`
+///
+/// # Safety
+///
+/// The pointer must hold len bytes, which aren't freed while the slice is
+/// used
+pub unsafe fn from_js<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    match len {
+        0 => &[],
+        len => slice::from_raw_parts(ptr, len),
+    }
+}
+
+/// Hands the bytes to JavaScript, prefixed by their length. An error
+/// returns null and keeps its message for `arrow_guide_last_error`
+pub fn to_js(result: Result<Vec<u8>>) -> *mut u8 {
+    match result {
+        Ok(bytes) => with_len(bytes),
+        Err(error) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(error.to_string()));
+            ptr::null_mut()
+        }
+    }
+}
+
+// Copies the bytes after their length into an allocation that has no
+// spare capacity, so JavaScript frees it with the length it reads
+fn with_len(bytes: Vec<u8>) -> *mut u8 {
+    let mut output = Vec::with_capacity(LEN_PREFIX + bytes.len());
+    output.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    output.extend_from_slice(&bytes);
+    Box::into_raw(output.into_boxed_slice()) as *mut u8
+}